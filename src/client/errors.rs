@@ -4,7 +4,10 @@ use reqwest::{Error as ClientError, Response};
 use reqwest_middleware::Error as ReqError;
 use thiserror::Error;
 
-use crate::{nodes::models::S3ErrorResponse, utils::FromResponse};
+use crate::{
+    config::PasswordPolicyViolation, models::OrphanedRoom, nodes::models::S3ErrorResponse,
+    utils::FromResponse,
+};
 
 use super::models::{DracoonAuthErrorResponse, DracoonErrorResponse};
 
@@ -40,6 +43,34 @@ pub enum DracoonClientError {
     MissingEncryptionSecret,
     #[error("Missing argument")]
     MissingArgument,
+    #[error("Integrity check of encrypted content failed (modified content or wrong file key)")]
+    IntegrityCheckFailed,
+    #[error("Invalid expiration: {0}")]
+    InvalidExpiration(String),
+    #[error("Password policy violation: {0}")]
+    PasswordPolicyViolation(PasswordPolicyViolation),
+    #[error("Transfer cancelled")]
+    Cancelled,
+    #[error("Upload cannot be resumed (only unencrypted S3 uploads are resumable)")]
+    UploadNotResumable,
+    #[error("Uploads with unknown size require S3 storage")]
+    UnknownSizeNotSupported,
+    #[error("S3 upload not completed in time")]
+    S3UploadTimedOut,
+    #[error("Checksum of S3 upload part {0} does not match its ETag")]
+    S3ChecksumMismatch(u32),
+    #[error("No presigned S3 url returned for upload part {0}")]
+    MissingS3Url(u32),
+    #[error("Virus scan not completed in time")]
+    VirusScanTimedOut,
+    #[error("Invalid upload options: {0}")]
+    InvalidUploadOptions(String),
+    #[error("Invalid chunk size: {0}")]
+    InvalidChunkSize(String),
+    #[error("Invalid share options: {0}")]
+    InvalidShareOptions(String),
+    #[error("Deletion would leave {} room(s) without an administrator", .0.len())]
+    WouldOrphanRooms(Vec<OrphanedRoom>),
 }
 
 impl From<ReqError> for DracoonClientError {
@@ -106,6 +137,62 @@ impl DracoonClientError {
         matches!(self, DracoonClientError::Auth(_))
     }
 
+    /// Check if the error is a failed integrity check of encrypted content
+    /// (the decrypted content must be discarded)
+    pub fn is_integrity_check_failed(&self) -> bool {
+        matches!(self, DracoonClientError::IntegrityCheckFailed)
+    }
+
+    /// Check if the error is an invalid expiration
+    pub fn is_invalid_expiration(&self) -> bool {
+        matches!(self, DracoonClientError::InvalidExpiration(_))
+    }
+
+    /// Check if the error is a password policy violation
+    pub fn is_password_policy_violation(&self) -> bool {
+        matches!(self, DracoonClientError::PasswordPolicyViolation(_))
+    }
+
+    /// Check if the error is caused by a deletion that would leave rooms without an administrator
+    pub fn is_would_orphan_rooms(&self) -> bool {
+        matches!(self, DracoonClientError::WouldOrphanRooms(_))
+    }
+
+    /// Check if the error is caused by an S3 upload not completed in time
+    pub fn is_s3_upload_timed_out(&self) -> bool {
+        matches!(self, DracoonClientError::S3UploadTimedOut)
+    }
+
+    /// Check if the error is caused by an S3 upload part with a mismatching ETag (corrupted part)
+    pub fn is_s3_checksum_mismatch(&self) -> bool {
+        matches!(self, DracoonClientError::S3ChecksumMismatch(_))
+    }
+
+    /// Check if the error is caused by a virus scan not completed in time
+    pub fn is_virus_scan_timed_out(&self) -> bool {
+        matches!(self, DracoonClientError::VirusScanTimedOut)
+    }
+
+    /// Check if the error is caused by upload options violating the policies of the instance
+    pub fn is_invalid_upload_options(&self) -> bool {
+        matches!(self, DracoonClientError::InvalidUploadOptions(_))
+    }
+
+    /// Check if the error is caused by a chunk size exceeding the S3 part size or part count limits
+    pub fn is_invalid_chunk_size(&self) -> bool {
+        matches!(self, DracoonClientError::InvalidChunkSize(_))
+    }
+
+    /// Check if the error is caused by share options that cannot be applied (e.g. a file key for multiple nodes)
+    pub fn is_invalid_share_options(&self) -> bool {
+        matches!(self, DracoonClientError::InvalidShareOptions(_))
+    }
+
+    /// Check if the error is caused by a cancelled transfer
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, DracoonClientError::Cancelled)
+    }
+
     /// Check if the error is an HTTP error
     pub fn is_http_error(&self) -> bool {
         matches!(self, DracoonClientError::Http(_))
@@ -6,7 +6,8 @@ use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
 use retry_policies::Jitter;
 use secrecy::{ExposeSecret, Secret};
-use std::{marker::PhantomData, time::Duration};
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tracing::{debug, error};
 
 use base64::{
@@ -17,19 +18,22 @@ use base64::{
 
 pub mod errors;
 pub mod models;
+pub(crate) mod retry;
+pub(crate) mod throttle;
 
 pub use models::*;
 
 use crate::{
     constants::{
         DRACOON_TOKEN_REVOKE_URL, DRACOON_TOKEN_URL, MAX_RETRIES, MAX_RETRY_DELAY, MAX_TOKEN_COUNT,
-        MIN_RETRY_DELAY, MIN_TOKEN_COUNT, TOKEN_TYPE_HINT_ACCESS_TOKEN,
+        MIN_RETRY_DELAY, MIN_TOKEN_COUNT, POLLING_MAX_DELAY, POLLING_START_DELAY, POLLING_TIMEOUT,
+        TOKEN_TYPE_HINT_ACCESS_TOKEN,
     },
     models::Container,
     ConnectedClient,
 };
 
-use self::errors::DracoonClientError;
+use self::{errors::DracoonClientError, throttle::RateLimiter};
 use super::constants::{APP_USER_AGENT, TOKEN_TYPE_HINT_REFRESH_TOKEN};
 
 /// represents the possible `OAuth2` flows
@@ -145,6 +149,10 @@ pub struct DracoonClient<State = Disconnected> {
     client_secret: Secret<String>,
     pub http: ClientWithMiddleware,
     pub stream_http: Client,
+    retry_policy: ExponentialBackoff,
+    transfer_limit: Option<Arc<Semaphore>>,
+    upload_rate_limit: Option<RateLimiter>,
+    upload_polling: UploadPolling,
     connection: Container<Connection>,
     token_rotation: Option<u8>,
     additional_connections: Container<Vec<Connection>>,
@@ -153,6 +161,13 @@ pub struct DracoonClient<State = Disconnected> {
     provisioning_token: Option<Secret<String>>,
 }
 
+/// limits for polling the status of finalized S3 uploads
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct UploadPolling {
+    pub max_delay: Duration,
+    pub timeout: Duration,
+}
+
 /// Builder for the [DracoonClient] struct.
 #[derive(Default)]
 pub struct DracoonClientBuilder {
@@ -164,6 +179,10 @@ pub struct DracoonClientBuilder {
     max_retries: Option<u32>,
     min_retry_delay: Option<u64>,
     max_retry_delay: Option<u64>,
+    max_concurrent_transfers: Option<usize>,
+    max_upload_rate: Option<u64>,
+    max_upload_polling_delay: Option<u64>,
+    upload_polling_timeout: Option<u64>,
     token_rotation: Option<u8>,
     provisioning_token: Option<String>,
 }
@@ -180,6 +199,10 @@ impl DracoonClientBuilder {
             max_retries: None,
             min_retry_delay: None,
             max_retry_delay: None,
+            max_concurrent_transfers: None,
+            max_upload_rate: None,
+            max_upload_polling_delay: None,
+            upload_polling_timeout: None,
             provisioning_token: None,
             token_rotation: None,
         }
@@ -233,6 +256,30 @@ impl DracoonClientBuilder {
         self
     }
 
+    /// Sets the max. number of simultaneous transfers (uploads and downloads) of the client
+    pub fn with_max_concurrent_transfers(mut self, max_concurrent_transfers: usize) -> Self {
+        self.max_concurrent_transfers = Some(max_concurrent_transfers);
+        self
+    }
+
+    /// Sets the max. upload rate of the client (in bytes per second) shared by all uploads
+    pub fn with_max_upload_rate(mut self, max_upload_rate: u64) -> Self {
+        self.max_upload_rate = Some(max_upload_rate);
+        self
+    }
+
+    /// Sets the max. delay between polling the status of a finalized S3 upload (in ms)
+    pub fn with_max_upload_polling_delay(mut self, max_upload_polling_delay: u64) -> Self {
+        self.max_upload_polling_delay = Some(max_upload_polling_delay);
+        self
+    }
+
+    /// Sets the max. time to wait for a finalized S3 upload to be completed (in ms)
+    pub fn with_upload_polling_timeout(mut self, upload_polling_timeout: u64) -> Self {
+        self.upload_polling_timeout = Some(upload_polling_timeout);
+        self
+    }
+
     /// Sets the provisioning token for the provisioning API
     pub fn with_provisioning_token(mut self, token: impl Into<String>) -> Self {
         self.provisioning_token = Some(token.into());
@@ -245,8 +292,37 @@ impl DracoonClientBuilder {
         self
     }
 
+    /// Builds the semaphore limiting simultaneous transfers (if configured)
+    fn build_transfer_limit(&self) -> Option<Arc<Semaphore>> {
+        self.max_concurrent_transfers
+            .map(|max_concurrent_transfers| {
+                Arc::new(Semaphore::new(max_concurrent_transfers.max(1)))
+            })
+    }
+
+    /// Builds the rate limit shared by all uploads (if configured)
+    fn build_upload_rate_limit(&self) -> Option<RateLimiter> {
+        self.max_upload_rate.map(RateLimiter::new)
+    }
+
+    /// Builds the limits for polling the status of S3 uploads
+    fn build_upload_polling(&self) -> UploadPolling {
+        let max_delay = self
+            .max_upload_polling_delay
+            .unwrap_or(POLLING_MAX_DELAY)
+            .max(POLLING_START_DELAY);
+        let timeout = self.upload_polling_timeout.unwrap_or(POLLING_TIMEOUT);
+
+        UploadPolling {
+            max_delay: Duration::from_millis(max_delay),
+            timeout: Duration::from_millis(timeout),
+        }
+    }
+
     /// Builds reqwest clients with configured middleware
-    fn build_clients(&self) -> Result<(ClientWithMiddleware, Client), DracoonClientError> {
+    fn build_clients(
+        &self,
+    ) -> Result<(ClientWithMiddleware, Client, ExponentialBackoff), DracoonClientError> {
         let max_retries = self
             .max_retries
             .unwrap_or(MAX_RETRIES)
@@ -283,7 +359,7 @@ impl DracoonClientBuilder {
             ))
             .build();
 
-        Ok((http, upload_http))
+        Ok((http, upload_http, retry_policy))
     }
     /// Builds the [DracoonClient] struct for the provisioning API
     pub fn build_provisioning(self) -> Result<DracoonClient<Provisioning>, DracoonClientError> {
@@ -298,7 +374,7 @@ impl DracoonClientBuilder {
 
         let base_url = Url::parse(&base_url)?;
 
-        let (http, upload_http) = self.build_clients()?;
+        let (http, upload_http, retry_policy) = self.build_clients()?;
 
         Ok(DracoonClient {
             base_url,
@@ -307,6 +383,10 @@ impl DracoonClientBuilder {
             client_secret: Secret::new(String::new()),
             http,
             stream_http: upload_http,
+            retry_policy,
+            transfer_limit: self.build_transfer_limit(),
+            upload_rate_limit: self.build_upload_rate_limit(),
+            upload_polling: self.build_upload_polling(),
             state: PhantomData,
             connection: Container::new(),
             additional_connections: Container::new(),
@@ -318,7 +398,10 @@ impl DracoonClientBuilder {
 
     /// Builds the [DracoonClient] struct - returns an error if any of the required fields are missing
     pub fn build(self) -> Result<DracoonClient<Disconnected>, DracoonClientError> {
-        let (http, upload_http) = self.build_clients()?;
+        let (http, upload_http, retry_policy) = self.build_clients()?;
+        let transfer_limit = self.build_transfer_limit();
+        let upload_rate_limit = self.build_upload_rate_limit();
+        let upload_polling = self.build_upload_polling();
 
         let Some(base_url) = self.base_url.clone() else {
             error!("Missing base url");
@@ -368,6 +451,10 @@ impl DracoonClientBuilder {
             http,
             curr_connection: Container::new_from(CurrentConnection::Main),
             stream_http: upload_http,
+            retry_policy,
+            transfer_limit,
+            upload_rate_limit,
+            upload_polling,
             provisioning_token: None,
         })
     }
@@ -426,6 +513,10 @@ impl DracoonClient<Disconnected> {
             state: PhantomData,
             http: self.http.clone(),
             stream_http: self.stream_http.clone(),
+            retry_policy: self.retry_policy,
+            transfer_limit: self.transfer_limit.clone(),
+            upload_rate_limit: self.upload_rate_limit.clone(),
+            upload_polling: self.upload_polling,
             provisioning_token: None,
         })
     }
@@ -589,6 +680,10 @@ impl DracoonClient<Connected> {
             state: PhantomData,
             http: self.http,
             stream_http: self.stream_http,
+            retry_policy: self.retry_policy,
+            transfer_limit: self.transfer_limit,
+            upload_rate_limit: self.upload_rate_limit,
+            upload_polling: self.upload_polling,
             provisioning_token: None,
         })
     }
@@ -816,6 +911,29 @@ impl<S> DracoonClient<S> {
             .join(url_part)
             .expect("Invalid base url or url part")
     }
+
+    /// Returns the configured retry policy (e.g. to retry failed chunks of a transfer)
+    pub(crate) fn retry_policy(&self) -> &ExponentialBackoff {
+        &self.retry_policy
+    }
+
+    /// Returns the limits for polling the status of S3 uploads
+    pub(crate) fn upload_polling(&self) -> &UploadPolling {
+        &self.upload_polling
+    }
+
+    /// Returns the rate limit shared by all uploads (if configured)
+    pub(crate) fn upload_rate_limit(&self) -> Option<&RateLimiter> {
+        self.upload_rate_limit.as_ref()
+    }
+
+    /// Waits for a free transfer slot if the number of simultaneous transfers is limited.
+    /// The slot is released when the returned permit is dropped.
+    pub(crate) async fn acquire_transfer_permit(&self) -> Option<OwnedSemaphorePermit> {
+        let transfer_limit = self.transfer_limit.clone()?;
+
+        transfer_limit.acquire_owned().await.ok()
+    }
 }
 
 #[cfg(test)]
@@ -1315,6 +1433,43 @@ mod tests {
         assert!(dracoon.is_err());
     }
 
+    #[tokio::test]
+    async fn test_transfer_limit() {
+        let dracoon = DracoonClient::builder()
+            .with_base_url("https://dracoon.team")
+            .with_client_id("client_id")
+            .with_client_secret("client_secret")
+            .with_max_concurrent_transfers(1)
+            .build()
+            .unwrap();
+
+        let permit = dracoon.acquire_transfer_permit().await;
+        assert!(permit.is_some());
+
+        // second transfer waits until the first permit is released
+        let second =
+            tokio::time::timeout(Duration::from_millis(50), dracoon.acquire_transfer_permit())
+                .await;
+        assert!(second.is_err());
+
+        drop(permit);
+
+        let second = dracoon.acquire_transfer_permit().await;
+        assert!(second.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_limit_unlimited() {
+        let dracoon = DracoonClient::builder()
+            .with_base_url("https://dracoon.team")
+            .with_client_id("client_id")
+            .with_client_secret("client_secret")
+            .build()
+            .unwrap();
+
+        assert!(dracoon.acquire_transfer_permit().await.is_none());
+    }
+
     #[tokio::test]
     async fn test_token_refresh() {
         let mut mock_server = mockito::Server::new_async().await;
@@ -0,0 +1,84 @@
+use std::time::{Duration, SystemTime};
+
+use reqwest_retry::policies::ExponentialBackoff;
+use retry_policies::{RetryDecision, RetryPolicy};
+use tracing::{debug, error};
+
+use super::errors::DracoonClientError;
+
+/// Checks if a transfer failed with a transient error (connection or server error)
+pub(crate) fn is_transient(err: &DracoonClientError) -> bool {
+    match err {
+        DracoonClientError::ConnectionFailed(_) => true,
+        DracoonClientError::S3Error(s3_err) => s3_err.status.is_server_error(),
+        _ => err.is_server_error(),
+    }
+}
+
+/// Returns the delay until the next retry if the retry policy allows another retry.
+/// Returns the error otherwise.
+pub(crate) fn next_retry_delay(
+    retry_policy: &ExponentialBackoff,
+    err: DracoonClientError,
+    retries: &mut u32,
+) -> Result<Duration, DracoonClientError> {
+    match retry_policy.should_retry(SystemTime::now(), *retries) {
+        RetryDecision::Retry { execute_after } => {
+            let delay = execute_after
+                .duration_since(SystemTime::now())
+                .unwrap_or_default();
+            *retries += 1;
+            debug!("Retrying in {:?} (retry {}): {}", delay, retries, err);
+            Ok(delay)
+        }
+        RetryDecision::DoNotRetry => {
+            error!("Transfer failed (no retries left): {}", err);
+            Err(err)
+        }
+    }
+}
+
+/// Waits for the next retry if the retry policy allows another retry.
+/// Returns the error otherwise.
+pub(crate) async fn wait_for_retry(
+    retry_policy: &ExponentialBackoff,
+    err: DracoonClientError,
+    retries: &mut u32,
+) -> Result<(), DracoonClientError> {
+    let delay = next_retry_delay(retry_policy, err, retries)?;
+    tokio::time::sleep(delay).await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient(&DracoonClientError::ConnectionFailed(
+            "Timeout".into()
+        )));
+        assert!(!is_transient(&DracoonClientError::Cancelled));
+        assert!(!is_transient(&DracoonClientError::IoError));
+    }
+
+    #[test]
+    fn test_next_retry_delay() {
+        let retry_policy = ExponentialBackoff::builder()
+            .retry_bounds(Duration::from_millis(1), Duration::from_millis(2))
+            .build_with_max_retries(1);
+        let mut retries = 0;
+
+        let delay = next_retry_delay(&retry_policy, DracoonClientError::IoError, &mut retries)
+            .expect("should retry");
+
+        assert!(delay <= Duration::from_millis(2));
+        assert_eq!(retries, 1);
+
+        let res = next_retry_delay(&retry_policy, DracoonClientError::IoError, &mut retries);
+
+        assert_eq!(res.unwrap_err(), DracoonClientError::IoError);
+        assert_eq!(retries, 1);
+    }
+}
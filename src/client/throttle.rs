@@ -0,0 +1,142 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures_util::{Stream, StreamExt};
+use tokio::{sync::watch, time::Instant};
+
+/// Limits the rate of transferred bytes (bytes per second) - clones share the same limit
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    bytes_per_second: u64,
+    next_free: Arc<Mutex<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second: bytes_per_second.max(1),
+            next_free: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Waits until the given amount of bytes may be transferred
+    pub async fn acquire(&self, bytes: u64) {
+        let start = {
+            let mut next_free = self.next_free.lock().expect("rate limiter lock poisoned");
+            // unused capacity is not accumulated (no bursts after idle periods)
+            let start = (*next_free).max(Instant::now());
+            *next_free =
+                start + Duration::from_secs_f64(bytes as f64 / self.bytes_per_second as f64);
+            start
+        };
+
+        tokio::time::sleep_until(start).await;
+    }
+}
+
+/// Pause state of a managed transfer - transfers wait at the next part or chunk boundary
+/// (no request in flight) while paused
+#[derive(Debug, Clone)]
+pub(crate) struct PauseSignal(watch::Receiver<bool>);
+
+impl PauseSignal {
+    pub fn new(paused: watch::Receiver<bool>) -> Self {
+        Self(paused)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Waits until the transfer is resumed (returns immediately if not paused)
+    pub async fn wait_while_paused(&self) {
+        let mut paused = self.0.clone();
+        // the sender is only dropped with the transfer
+        let _ = paused.wait_for(|paused| !*paused).await;
+    }
+}
+
+/// Throttles a byte stream by the global rate limit and the rate limit of the transfer
+pub(crate) fn throttle_stream<S, E>(
+    mut stream: S,
+    global_rate_limit: Option<RateLimiter>,
+    transfer_rate_limit: Option<RateLimiter>,
+) -> impl Stream<Item = Result<bytes::Bytes, E>> + Send + Sync + 'static
+where
+    S: Stream<Item = Result<bytes::Bytes, E>> + Send + Sync + Unpin + 'static,
+    E: Send + Sync + 'static,
+{
+    let limiters = global_rate_limit
+        .into_iter()
+        .chain(transfer_rate_limit)
+        .collect::<Vec<_>>();
+
+    async_stream::stream! {
+        while let Some(item) = stream.next().await {
+            if let Ok(bytes) = &item {
+                for limiter in &limiters {
+                    limiter.acquire(bytes.len() as u64).await;
+                }
+            }
+            yield item;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter_delays_transfer() {
+        let limiter = RateLimiter::new(10_000);
+        let started = Instant::now();
+
+        limiter.acquire(1000).await;
+        assert_eq!(started.elapsed(), Duration::ZERO);
+
+        limiter.acquire(1000).await;
+        assert_eq!(started.elapsed(), Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_throttle_stream_uses_transfer_rate_limit() {
+        let started = Instant::now();
+
+        let chunks = (0..3).map(|_| Ok::<_, std::io::Error>(bytes::Bytes::from(vec![0; 500])));
+        let stream = throttle_stream(
+            futures_util::stream::iter(chunks),
+            None,
+            Some(RateLimiter::new(10_000)),
+        );
+
+        let received = stream
+            .map(|chunk| chunk.expect("no error").len())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(received, vec![500, 500, 500]);
+        // the first chunk passes immediately, the others wait 50 ms each
+        assert_eq!(started.elapsed(), Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_throttle_stream_uses_global_and_transfer_rate_limit() {
+        let started = Instant::now();
+
+        let chunks = (0..2).map(|_| Ok::<_, std::io::Error>(bytes::Bytes::from(vec![0; 500])));
+        let stream = throttle_stream(
+            futures_util::stream::iter(chunks),
+            Some(RateLimiter::new(5_000)),
+            Some(RateLimiter::new(10_000)),
+        );
+
+        let received = stream.collect::<Vec<_>>().await;
+
+        assert_eq!(received.len(), 2);
+        // the lower (global) rate limit applies
+        assert_eq!(started.elapsed(), Duration::from_millis(100));
+    }
+}
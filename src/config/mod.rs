@@ -9,7 +9,7 @@ use crate::constants::{
     CONFIG_PRODUCT_PACKAGES, CONFIG_PRODUCT_PACKAGES_CURRENT, CONFIG_S3_TAGS, DRACOON_API_PREFIX,
 };
 use crate::utils::FromResponse;
-use crate::{client::Connected, DracoonClientError};
+use crate::{client::Connected, models::ObjectExpiration, DracoonClientError};
 
 pub use self::models::*;
 
@@ -38,6 +38,88 @@ pub trait Config {
     ) -> Result<ProductPackageResponseList, DracoonClientError>;
 
     async fn get_s3_tags(&self) -> Result<S3TagList, DracoonClientError>;
+
+    /// Returns the default expiration of the given target (files, download shares or upload shares)
+    /// based on the configured default expiration period - `None` if no default is configured.
+    /// Note: the period is a default, not a maximum - longer expirations are accepted.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, Config, config::ExpirationTarget, shares::CreateDownloadShareRequest};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let share = CreateDownloadShareRequest::builder(123);
+    /// let share = match dracoon.config().get_default_expiration(ExpirationTarget::DownloadShare).await.unwrap() {
+    ///     Some(expiration) => share.with_expiration(expiration),
+    ///     None => share,
+    /// };
+    /// # }
+    /// ```
+    async fn get_default_expiration(
+        &self,
+        target: ExpirationTarget,
+    ) -> Result<Option<ObjectExpiration>, DracoonClientError> {
+        Ok(self.get_defaults().await?.default_expiration(target))
+    }
+
+    /// Generates a random share password compliant with the configured share password policies.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, Config};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let password = dracoon.config().generate_share_password().await.unwrap();
+    /// # }
+    /// ```
+    async fn generate_share_password(&self) -> Result<String, DracoonClientError> {
+        self.get_password_policies()
+            .await?
+            .shares_password_policies
+            .unwrap_or_default()
+            .generate_password()
+    }
+
+    /// Validates a share password against the configured share password policies
+    /// (minimum length and character rules).
+    /// Returns `DracoonClientError::PasswordPolicyViolation` if the password is not compliant.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, Config};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// dracoon.config().validate_share_password("VerySecret123!").await.unwrap();
+    /// # }
+    /// ```
+    async fn validate_share_password(&self, password: &str) -> Result<(), DracoonClientError> {
+        self.get_password_policies()
+            .await?
+            .shares_password_policies
+            .unwrap_or_default()
+            .validate_password(password)
+    }
 }
 
 #[async_trait]
@@ -227,10 +309,13 @@ mod tests {
     use chrono::DateTime;
 
     use crate::{
-        config::{AlgorithmStatus, CharacterRule, MinimumClassification},
+        config::{
+            AlgorithmStatus, CharacterRule, ExpirationTarget, MinimumClassification,
+            PasswordPolicyViolation, SharesPasswordPolicies, SystemDefaults,
+        },
         nodes::UserType,
         tests::dracoon::get_connected_client,
-        Config,
+        Config, DracoonClientError,
     };
 
     #[tokio::test]
@@ -620,4 +705,163 @@ mod tests {
         assert_eq!(s3_tag.value, Some("string".into()));
         assert_eq!(s3_tag.is_mandatory, Some(false));
     }
+
+    #[test]
+    fn test_default_expiration_not_configured() {
+        let defaults: SystemDefaults =
+            serde_json::from_str(include_str!("../tests/responses/config/defaults_ok.json"))
+                .unwrap();
+
+        assert_eq!(
+            defaults.default_expiration_period(ExpirationTarget::DownloadShare),
+            None
+        );
+        assert!(defaults
+            .default_expiration(ExpirationTarget::DownloadShare)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_default_expiration() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let mut defaults_res: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/responses/config/defaults_ok.json"))
+                .unwrap();
+        defaults_res["downloadShareDefaultExpirationPeriod"] = serde_json::json!(7);
+
+        let defaults_mock = mock_server
+            .mock("GET", "/api/v4/config/info/defaults")
+            .with_status(200)
+            .with_body(defaults_res.to_string())
+            .with_header("content-type", "application/json")
+            .expect(2)
+            .create();
+
+        let expiration = client
+            .config()
+            .get_default_expiration(ExpirationTarget::DownloadShare)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(expiration.enable_expiration);
+        let expire_at =
+            chrono::DateTime::parse_from_rfc3339(expiration.expire_at.as_ref().unwrap()).unwrap();
+        let days = (expire_at.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_days();
+        assert!((6..=7).contains(&days));
+
+        // other targets have no default
+        let expiration = client
+            .config()
+            .get_default_expiration(ExpirationTarget::UploadShare)
+            .await
+            .unwrap();
+        assert!(expiration.is_none());
+
+        defaults_mock.assert();
+    }
+
+    #[test]
+    fn test_validate_share_password() {
+        let policies: SharesPasswordPolicies = serde_json::from_value(serde_json::json!({
+            "characterRules": {
+                "mustContainCharacters": ["all"],
+                "numberOfCharacteristicsToEnforce": 4
+            },
+            "minLength": 12
+        }))
+        .unwrap();
+
+        assert!(policies.validate_password("VerySecret123!").is_ok());
+
+        assert_eq!(
+            policies.validate_password("Secret1!").unwrap_err(),
+            DracoonClientError::PasswordPolicyViolation(PasswordPolicyViolation::TooShort(12))
+        );
+
+        let err = policies.validate_password("verysecret1234").unwrap_err();
+        assert!(err.is_password_policy_violation());
+        assert_eq!(
+            err,
+            DracoonClientError::PasswordPolicyViolation(
+                PasswordPolicyViolation::MissingCharacters {
+                    required: 4,
+                    missing: vec![CharacterRule::UpperCase, CharacterRule::Special]
+                }
+            )
+        );
+
+        // only 2 of 4 character types enforced
+        let policies: SharesPasswordPolicies = serde_json::from_value(serde_json::json!({
+            "characterRules": {
+                "mustContainCharacters": ["uppercase", "lowercase", "numeric", "special"],
+                "numberOfCharacteristicsToEnforce": 2
+            }
+        }))
+        .unwrap();
+
+        assert!(policies.validate_password("verysecret1234").is_ok());
+        assert!(policies.validate_password("VERYSECRET!").is_ok());
+
+        let err = policies.validate_password("verysecret").unwrap_err();
+        assert_eq!(
+            err,
+            DracoonClientError::PasswordPolicyViolation(
+                PasswordPolicyViolation::MissingCharacters {
+                    required: 2,
+                    missing: vec![
+                        CharacterRule::UpperCase,
+                        CharacterRule::Numeric,
+                        CharacterRule::Special
+                    ]
+                }
+            )
+        );
+
+        // no policies configured
+        assert!(SharesPasswordPolicies::default()
+            .validate_password("secret")
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_generate_share_password() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let password_policies_res =
+            include_str!("../tests/responses/config/password_policies_ok.json");
+
+        let password_policies_mock = mock_server
+            .mock("GET", "/api/v4/config/info/policies/passwords")
+            .with_status(200)
+            .with_body(password_policies_res)
+            .with_header("content-type", "application/json")
+            .expect(2)
+            .create();
+
+        let password = client.config().generate_share_password().await.unwrap();
+
+        // min length is 1024
+        assert_eq!(password.len(), 1024);
+
+        client
+            .config()
+            .validate_share_password(&password)
+            .await
+            .unwrap();
+
+        password_policies_mock.assert();
+
+        let policies = SharesPasswordPolicies::default();
+        let password = policies.generate_password().unwrap();
+        let other_password = policies.generate_password().unwrap();
+
+        assert_eq!(password.len(), 16);
+        assert_ne!(password, other_password);
+        assert!(password.chars().any(|c| c.is_uppercase()));
+        assert!(password.chars().any(|c| c.is_lowercase()));
+        assert!(password.chars().any(|c| c.is_numeric()));
+        assert!(password.chars().any(|c| !c.is_alphanumeric()));
+    }
 }
@@ -1,10 +1,14 @@
 use std::sync::Arc;
 
-use chrono::{DateTime, Utc};
+use chrono::{Duration, Utc};
 use dco3_derive::FromResponse;
 use serde::Deserialize;
+use thiserror::Error;
 
-use crate::{client::DracoonClient, nodes::UserInfo};
+use crate::{
+    client::DracoonClient, constants::DEFAULT_SHARE_PASSWORD_LENGTH, models::ObjectExpiration,
+    nodes::UserInfo, utils::random_index, DracoonClientError,
+};
 
 #[derive(Clone)]
 pub struct ConfigEndpoint<S> {
@@ -59,6 +63,33 @@ pub struct SystemDefaults {
     pub hide_login_input_fields: Option<bool>,
 }
 
+/// Object types with a configured default expiration period
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExpirationTarget {
+    File,
+    DownloadShare,
+    UploadShare,
+}
+
+impl SystemDefaults {
+    /// Returns the configured default expiration period (in days) - `None` if no default is set (0 or not set)
+    pub fn default_expiration_period(&self, target: ExpirationTarget) -> Option<u32> {
+        let period = match target {
+            ExpirationTarget::File => self.file_default_expiration_period,
+            ExpirationTarget::DownloadShare => self.download_share_default_expiration_period,
+            ExpirationTarget::UploadShare => self.upload_share_default_expiration_period,
+        };
+
+        period.filter(|period| *period > 0)
+    }
+
+    /// Returns the default expiration (now + default expiration period) - `None` if no default is set
+    pub fn default_expiration(&self, target: ExpirationTarget) -> Option<ObjectExpiration> {
+        self.default_expiration_period(target)
+            .map(|period| ObjectExpiration::new(Utc::now() + Duration::days(i64::from(period))))
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, FromResponse)]
 #[serde(rename_all = "camelCase")]
 pub struct InfrastructureProperties {
@@ -182,7 +213,7 @@ pub struct LoginPasswordPolicies {
     pub updated_by: UserInfo,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SharesPasswordPolicies {
     pub character_rules: Option<CharacterRules>,
@@ -194,6 +225,144 @@ pub struct SharesPasswordPolicies {
     pub updated_by: Option<UserInfo>,
 }
 
+/// Violation of a password policy (checked client-side)
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum PasswordPolicyViolation {
+    #[error("password must contain at least {0} characters")]
+    TooShort(u32),
+    #[error("password must contain at least {required} character types - missing: {missing:?}")]
+    MissingCharacters {
+        required: u32,
+        missing: Vec<CharacterRule>,
+    },
+}
+
+const UPPERCASE_CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const LOWERCASE_CHARS: &[u8] = b"abcdefghijkmnopqrstuvwxyz";
+const NUMERIC_CHARS: &[u8] = b"23456789";
+const SPECIAL_CHARS: &[u8] = b"!#$%&()*+-.:;=?@[]_{}";
+
+impl CharacterRule {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CharacterRule::Alpha => c.is_alphabetic(),
+            CharacterRule::UpperCase => c.is_uppercase(),
+            CharacterRule::LowerCase => c.is_lowercase(),
+            CharacterRule::Numeric => c.is_numeric(),
+            CharacterRule::Special => !c.is_alphanumeric() && !c.is_whitespace(),
+            CharacterRule::All | CharacterRule::None => true,
+        }
+    }
+}
+
+impl CharacterRules {
+    /// Returns the character rules a password can fulfill (`all` is expanded)
+    pub fn rules(&self) -> Vec<CharacterRule> {
+        if self.must_contain_characters.contains(&CharacterRule::All) {
+            return vec![
+                CharacterRule::UpperCase,
+                CharacterRule::LowerCase,
+                CharacterRule::Numeric,
+                CharacterRule::Special,
+            ];
+        }
+
+        self.must_contain_characters
+            .iter()
+            .filter(|rule| !matches!(rule, CharacterRule::All | CharacterRule::None))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns how many of the character rules a password must fulfill
+    /// (`number_of_characteristics_to_enforce`, capped at the number of rules)
+    pub fn required_count(&self) -> usize {
+        let enforced = self.number_of_characteristics_to_enforce.max(0) as usize;
+        enforced.min(self.rules().len())
+    }
+}
+
+impl SharesPasswordPolicies {
+    /// Returns the minimum password length - `None` if unrestricted (0 or not set)
+    pub fn min_length(&self) -> Option<u32> {
+        self.min_length
+            .filter(|min_length| *min_length > 0)
+            .map(|min_length| min_length as u32)
+    }
+
+    /// Validates a share password against the configured length and character rules.
+    /// Dictionary words, keyboard patterns and user info are only checked by DRACOON.
+    pub fn validate_password(&self, password: &str) -> Result<(), DracoonClientError> {
+        if let Some(min_length) = self.min_length() {
+            if (password.chars().count() as u32) < min_length {
+                return Err(DracoonClientError::PasswordPolicyViolation(
+                    PasswordPolicyViolation::TooShort(min_length),
+                ));
+            }
+        }
+
+        if let Some(character_rules) = &self.character_rules {
+            let required = character_rules.required_count();
+            let (fulfilled, missing): (Vec<_>, Vec<_>) = character_rules
+                .rules()
+                .into_iter()
+                .partition(|rule| password.chars().any(|c| rule.matches(c)));
+
+            if fulfilled.len() < required {
+                return Err(DracoonClientError::PasswordPolicyViolation(
+                    PasswordPolicyViolation::MissingCharacters {
+                        required: required as u32,
+                        missing,
+                    },
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Generates a random share password compliant with the configured policies
+    /// (at least `DEFAULT_SHARE_PASSWORD_LENGTH` characters, containing all character types).
+    pub fn generate_password(&self) -> Result<String, DracoonClientError> {
+        let length = self
+            .min_length()
+            .map_or(DEFAULT_SHARE_PASSWORD_LENGTH, |min_length| {
+                (min_length as usize).max(DEFAULT_SHARE_PASSWORD_LENGTH)
+            });
+
+        let charsets = [
+            UPPERCASE_CHARS,
+            LOWERCASE_CHARS,
+            NUMERIC_CHARS,
+            SPECIAL_CHARS,
+        ];
+        let all_chars = charsets.concat();
+
+        // one character of every type fulfills all character rules - the rest is random
+        let mut password = charsets
+            .iter()
+            .map(|charset| random_char(charset))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        while password.len() < length {
+            password.push(random_char(&all_chars)?);
+        }
+
+        // shuffle (Fisher-Yates) to avoid predictable positions of the character types
+        for idx in (1..password.len()).rev() {
+            let swap_idx = random_index(idx + 1)?;
+            password.swap(idx, swap_idx);
+        }
+
+        Ok(password.into_iter().map(char::from).collect())
+    }
+}
+
+/// returns a random character of the given (non-empty) charset
+fn random_char(charset: &[u8]) -> Result<u8, DracoonClientError> {
+    Ok(charset[random_index(charset.len())?])
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct EncryptionPasswordPolicies {
@@ -30,7 +30,10 @@ pub const FILES_S3_URLS: &str = "s3_urls";
 pub const FILES_S3_COMPLETE: &str = "s3";
 pub const FOLDERS_BASE: &str = "folders";
 pub const NODES_DOWNLOAD_URL: &str = "downloads";
+pub const NODES_ZIP: &str = "zip";
 pub const NODES_SEARCH: &str = "search";
+pub const NODES_DELETED_NODES: &str = "deleted_nodes";
+pub const DELETED_NODES_VERSIONS: &str = "versions";
 pub const MISSING_FILE_KEYS: &str = "missingFileKeys";
 pub const FILES_KEYS: &str = "keys";
 pub const ROOMS_BASE: &str = "rooms";
@@ -39,6 +42,10 @@ pub const ROOMS_ENCRYPT: &str = "encrypt";
 pub const ROOMS_USERS: &str = "users";
 pub const ROOMS_GROUPS: &str = "groups";
 pub const ROOMS_POLICIES: &str = "policies";
+pub const ROOMS_WEBHOOKS: &str = "webhooks";
+pub const ROOMS_KEYPAIR: &str = "keypair";
+pub const ROOMS_S3_TAGS: &str = "s3_tags";
+pub const ROOMS_EVENTS: &str = "events";
 // note: needed for NFS upload (DRACOON Server)
 pub const UPLOADS_BASE: &str = "uploads";
 
@@ -54,10 +61,44 @@ pub const SHARES_DOWNLOAD: &str = "downloads";
 
 // DEFAULTS
 pub const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024 * 32; // 32 MB
+pub const DOWNLOAD_BUFFER_SIZE: usize = 1024 * 1024; // 1 MB
+pub const UPLOAD_WRITER_BUFFER_SIZE: usize = 1024 * 1024; // 1 MB (buffered content of an upload writer)
 pub const DEFAULT_UPLOAD_GRANULARITY: usize = 1024; // default upload stream granularity
+pub const DEFAULT_UPLOAD_PARALLELISM: usize = 1; // default number of parallel S3 part uploads
+
+// defines how many presigned S3 urls are requested at once
+pub const PRESIGNED_URLS_BATCH: u32 = 20;
 pub const POLLING_START_DELAY: u64 = 300;
+pub const POLLING_MAX_DELAY: u64 = 10 * 1000; // in milliseconds (10 seconds)
+pub const POLLING_TIMEOUT: u64 = 60 * 60 * 1000; // in milliseconds (1 hour)
+
 // defines how many keys (users) distributed per file on upload
 pub const MISSING_KEYS_BATCH: usize = 50;
+pub const DEFAULT_NODES_CONCURRENCY: usize = 5;
+// defines how many items are requested per page when listing all items
+pub const LIST_ALL_PAGE_LIMIT: u64 = 500;
+// defines how many files are updated per bulk update request
+pub const FILES_UPDATE_BATCH: usize = 100;
+// defines how many deleted nodes are removed per request
+pub const DELETED_NODES_BATCH: usize = 100;
+// defines how many shares are deleted per request
+pub const DELETED_SHARES_BATCH: usize = 100;
+// defines how many users are added to / removed from a group per request
+pub const GROUP_MEMBERS_BATCH: usize = 100;
+// defines how many shares are created in parallel (bulk creation)
+pub const DEFAULT_SHARES_CONCURRENCY: usize = 5;
+// minimum length of generated share passwords
+pub const DEFAULT_SHARE_PASSWORD_LENGTH: usize = 16;
+// S3 multipart upload limits (all parts except the last one need the min. size)
+pub const S3_MIN_PART_SIZE: usize = 1024 * 1024 * 5; // 5 MB
+pub const S3_MAX_PART_SIZE: u64 = 1024 * 1024 * 1024 * 5; // 5 GB
+pub const S3_MAX_PARTS: u64 = 10_000;
+// max. length of node names (characters)
+pub const MAX_NODE_NAME_LENGTH: usize = 150;
+// defines how many transfers of a transfer manager run at once (default)
+pub const DEFAULT_MANAGED_TRANSFERS: usize = 3;
+// defines how many transfer updates are buffered for slow subscribers
+pub const TRANSFER_UPDATES_CAPACITY: usize = 1024;
 
 // EVENTLOG
 pub const EVENTLOG_BASE: &str = "eventlog";
@@ -72,15 +113,27 @@ pub const USER_BASE: &str = "user";
 pub const USER_ACCOUNT: &str = "account";
 pub const USER_ACCOUNT_KEYPAIR: &str = "keypair";
 pub const USER_ACCOUNT_CUSTOMER: &str = "customer";
+pub const USER_ACCOUNT_AVATAR: &str = "avatar";
+pub const USER_SUBSCRIPTIONS: &str = "subscriptions";
+pub const USER_SUBSCRIPTIONS_NODES: &str = "nodes";
+
+// DOWNLOADS (public avatar downloads)
+pub const DOWNLOADS_BASE: &str = "downloads";
+pub const DOWNLOADS_AVATAR: &str = "avatar";
 
 // GROUPS
 pub const GROUPS_BASE: &str = "groups";
 pub const GROUPS_USERS: &str = "users";
 pub const GROUPS_LAST_ADMIN_ROOMS: &str = "last_admin_rooms";
+pub const GROUPS_ROLES: &str = "roles";
 
 // USERS
 pub const USERS_BASE: &str = "users";
 pub const USERS_LAST_ADMIN_ROOMS: &str = "last_admin_rooms";
+pub const USERS_ROLES: &str = "roles";
+pub const USERS_GROUPS: &str = "groups";
+pub const USERS_ATTRIBUTES: &str = "userAttributes";
+pub const USERS_AVATAR: &str = "avatar";
 
 // PROVISIONING
 pub const PROVISIONING_BASE: &str = "provisioning";
@@ -129,6 +182,10 @@ pub const PUBLIC_SHARES_BASE: &str = "shares";
 pub const PUBLIC_DOWNLOAD_SHARES: &str = "downloads";
 pub const PUBLIC_UPLOAD_SHARES: &str = "uploads";
 
+// PUBLIC - WEB (share links)
+pub const PUBLIC_DOWNLOAD_SHARES_WEB: &str = "download-shares";
+pub const PUBLIC_UPLOAD_SHARES_WEB: &str = "upload-shares";
+
 /// user agent header
 pub const APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "|", env!("CARGO_PKG_VERSION"));
 
@@ -1,10 +1,11 @@
 use async_trait::async_trait;
 pub use models::{
     AuditNodeList, AuditNodeResponse, AuditNodesFilter, AuditNodesSortBy, AuditUserPermission,
-    EventStatus, EventlogEndpoint, EventlogParams, EventlogSortBy, LogEvent, LogEventList,
-    LogOperation, LogOperationList,
+    EventExportFormat, EventStatus, EventlogEndpoint, EventlogParams, EventlogSortBy, LogEvent,
+    LogEventList, LogOperation, LogOperationList,
 };
 use reqwest::header;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::utils::FromResponse;
 use crate::ListAllParams;
@@ -14,7 +15,7 @@ mod models;
 
 use crate::constants::{
     AUDITS_BASE, AUDITS_NODES, DRACOON_API_PREFIX, EVENTLOG_BASE, EVENTLOG_EVENTS,
-    EVENTLOG_OPERATIONS,
+    EVENTLOG_OPERATIONS, LIST_ALL_PAGE_LIMIT, NODES_BASE, ROOMS_BASE, ROOMS_EVENTS,
 };
 
 #[async_trait]
@@ -69,6 +70,65 @@ pub trait Eventlog {
     /// ```
     async fn get_event_operations(&self) -> Result<LogOperationList, DracoonClientError>;
 
+    /// Get a list of events of a room (room activities log).
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Eventlog, eventlog::EventlogParams};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let params = EventlogParams::builder().with_limit(10).build();
+    /// let room_events = dracoon.eventlog().get_room_events(123, params).await.unwrap();
+    /// # }
+    /// ```
+    async fn get_room_events(
+        &self,
+        room_id: u64,
+        params: EventlogParams,
+    ) -> Result<LogEventList, DracoonClientError>;
+
+    /// Exports all events matching the params into a writer (CSV or JSON Lines).
+    /// If a room id is passed, the room events are exported - otherwise the eventlog events.
+    /// Events are fetched page by page (starting at the offset of the params if set).
+    /// The limit of the params is used as page size (default: 500).
+    /// Returns the amount of exported events.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Eventlog, eventlog::{EventlogParams, EventExportFormat}};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let mut file = tokio::fs::File::create("room_events.csv").await.unwrap();
+    ///
+    /// let exported = dracoon
+    ///     .eventlog()
+    ///     .export_events(Some(123), EventlogParams::default(), EventExportFormat::Csv, &mut file)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    async fn export_events<'w>(
+        &'w self,
+        room_id: Option<u64>,
+        params: EventlogParams,
+        format: EventExportFormat,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+    ) -> Result<u64, DracoonClientError>;
+
     #[deprecated = "DRACOON Cloud no longer supports sync permissions - use a permissions report instead"]
     async fn get_node_permissions(
         &self,
@@ -80,45 +140,8 @@ pub trait Eventlog {
 impl Eventlog for EventlogEndpoint<Connected> {
     async fn get_events(&self, params: EventlogParams) -> Result<LogEventList, DracoonClientError> {
         let url_part = format!("{DRACOON_API_PREFIX}/{EVENTLOG_BASE}/{EVENTLOG_EVENTS}");
-        let mut api_url = self.client().build_api_url(&url_part);
-
-        if !params.is_empty() {
-            api_url
-                .query_pairs_mut()
-                .extend_pairs(params.limit.map(|v| ("limit", v.to_string())))
-                .extend_pairs(params.offset.map(|v| ("offset", v.to_string())))
-                .extend_pairs(params.sort.map(|v| ("sort", v.to_sort_string())))
-                .extend_pairs(params.user_id.map(|v| ("user_id", v.to_string())))
-                .extend_pairs(params.operation_type.map(|v| ("type", v.to_string())))
-                .extend_pairs(params.status.map(|v| ("status", (v as i64).to_string())))
-                .extend_pairs(params.date_start.map(|v| {
-                    (
-                        "date_start",
-                        v.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
-                    )
-                }))
-                .extend_pairs(params.date_end.map(|v| {
-                    (
-                        "date_end",
-                        v.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
-                    )
-                }))
-                .finish();
-        };
-
-        let response = self
-            .client()
-            .http
-            .get(api_url)
-            .header(
-                header::AUTHORIZATION,
-                self.client().get_auth_header().await?,
-            )
-            .header(header::CONTENT_TYPE, "application/json")
-            .send()
-            .await?;
 
-        LogEventList::from_response(response).await
+        self.fetch_events(&url_part, &params).await
     }
 
     async fn get_event_operations(&self) -> Result<LogOperationList, DracoonClientError> {
@@ -173,6 +196,143 @@ impl Eventlog for EventlogEndpoint<Connected> {
 
         AuditNodeList::from_response(response).await
     }
+
+    async fn get_room_events(
+        &self,
+        room_id: u64,
+        params: EventlogParams,
+    ) -> Result<LogEventList, DracoonClientError> {
+        let url_part =
+            format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{ROOMS_BASE}/{room_id}/{ROOMS_EVENTS}");
+
+        self.fetch_events(&url_part, &params).await
+    }
+
+    async fn export_events<'w>(
+        &'w self,
+        room_id: Option<u64>,
+        mut params: EventlogParams,
+        format: EventExportFormat,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+    ) -> Result<u64, DracoonClientError> {
+        let url_part = match room_id {
+            Some(room_id) => {
+                format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{ROOMS_BASE}/{room_id}/{ROOMS_EVENTS}")
+            }
+            None => format!("{DRACOON_API_PREFIX}/{EVENTLOG_BASE}/{EVENTLOG_EVENTS}"),
+        };
+
+        if format == EventExportFormat::Csv {
+            writer
+                .write_all(format!("{}\n", LogEvent::CSV_HEADER).as_bytes())
+                .await
+                .or(Err(DracoonClientError::IoError))?;
+        }
+
+        let mut offset = params.offset.unwrap_or(0);
+        let limit = params
+            .limit
+            .filter(|limit| *limit > 0)
+            .unwrap_or(LIST_ALL_PAGE_LIMIT);
+        let mut exported = 0;
+
+        loop {
+            params.offset = Some(offset);
+            params.limit = Some(limit);
+
+            let events = self.fetch_events(&url_part, &params).await?;
+            let total = events.range.total;
+
+            for event in events.items {
+                let line = match format {
+                    EventExportFormat::Csv => event.to_csv_row(),
+                    EventExportFormat::JsonLines => {
+                        serde_json::to_string(&event).or(Err(DracoonClientError::Internal))?
+                    }
+                };
+
+                writer
+                    .write_all(format!("{line}\n").as_bytes())
+                    .await
+                    .or(Err(DracoonClientError::IoError))?;
+
+                exported += 1;
+            }
+
+            offset += limit;
+
+            if offset >= total {
+                break;
+            }
+        }
+
+        writer.flush().await.or(Err(DracoonClientError::IoError))?;
+
+        Ok(exported)
+    }
+}
+
+#[async_trait]
+trait EventlogInternal {
+    async fn fetch_events(
+        &self,
+        url_part: &str,
+        params: &EventlogParams,
+    ) -> Result<LogEventList, DracoonClientError>;
+}
+
+#[async_trait]
+impl EventlogInternal for EventlogEndpoint<Connected> {
+    async fn fetch_events(
+        &self,
+        url_part: &str,
+        params: &EventlogParams,
+    ) -> Result<LogEventList, DracoonClientError> {
+        let mut api_url = self.client().build_api_url(url_part);
+
+        if !params.is_empty() {
+            api_url
+                .query_pairs_mut()
+                .extend_pairs(params.limit.map(|v| ("limit", v.to_string())))
+                .extend_pairs(params.offset.map(|v| ("offset", v.to_string())))
+                .extend_pairs(params.sort.as_ref().map(|v| ("sort", v.to_sort_string())))
+                .extend_pairs(params.user_id.map(|v| ("user_id", v.to_string())))
+                .extend_pairs(params.operation_type.map(|v| ("type", v.to_string())))
+                .extend_pairs(
+                    params
+                        .status
+                        .clone()
+                        .map(|v| ("status", i64::from(v).to_string())),
+                )
+                .extend_pairs(params.date_start.map(|v| {
+                    (
+                        "date_start",
+                        v.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                    )
+                }))
+                .extend_pairs(params.date_end.map(|v| {
+                    (
+                        "date_end",
+                        v.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                    )
+                }))
+                .finish();
+        };
+
+        let response = self
+            .client()
+            .http
+            .get(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        LogEventList::from_response(response).await
+    }
 }
 
 #[cfg(test)]
@@ -184,7 +344,7 @@ mod tests {
     use crate::{
         eventlog::{
             models::{AuditNodesFilter, AuditNodesSortBy, EventStatus, EventlogSortBy},
-            Eventlog, EventlogParams,
+            EventExportFormat, Eventlog, EventlogParams,
         },
         nodes::UserType,
         tests::dracoon::get_connected_client,
@@ -733,4 +893,155 @@ mod tests {
 
         assert_eq!(node_permissions.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_get_room_events() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let response = include_str!("../tests/responses/eventlog/events_ok.json");
+
+        let events_mock = mock_server
+            .mock("GET", "/api/v4/nodes/rooms/123/events")
+            .with_status(200)
+            .with_body(response)
+            .with_header("content-type", "application/json")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let events = client
+            .eventlog()
+            .get_room_events(123, EventlogParams::default())
+            .await
+            .unwrap();
+
+        events_mock.assert();
+
+        assert_eq!(events.items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_events_csv() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let mut response: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/responses/eventlog/events_ok.json"))
+                .unwrap();
+        response["items"][0]["message"] = serde_json::json!("Renamed \"a\", \"b\"");
+
+        let events_mock = mock_server
+            .mock("GET", "/api/v4/eventlog/events?limit=500&offset=0")
+            .with_status(200)
+            .with_body(response.to_string())
+            .with_header("content-type", "application/json")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let mut buf = Vec::new();
+
+        let exported = client
+            .eventlog()
+            .export_events(
+                None,
+                EventlogParams::default(),
+                EventExportFormat::Csv,
+                &mut buf,
+            )
+            .await
+            .unwrap();
+
+        events_mock.assert();
+
+        assert_eq!(exported, 1);
+
+        let csv = String::from_utf8(buf).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("id,time,userId,"));
+        assert!(lines[1].starts_with("1,2023-01-01T00:00:00+00:00,2,string,"));
+        assert!(lines[1].contains(",2,\"Renamed \"\"a\"\", \"\"b\"\"\",5,"));
+    }
+
+    #[tokio::test]
+    async fn test_export_events_with_limit() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let response = include_str!("../tests/responses/eventlog/events_paged_ok.json");
+
+        let first_page_mock = mock_server
+            .mock("GET", "/api/v4/eventlog/events?limit=1&offset=0")
+            .with_status(200)
+            .with_body(response)
+            .with_header("content-type", "application/json")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let second_page_mock = mock_server
+            .mock("GET", "/api/v4/eventlog/events?limit=1&offset=1")
+            .with_status(200)
+            .with_body(response)
+            .with_header("content-type", "application/json")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let mut buf = Vec::new();
+
+        let params = EventlogParams::builder().with_limit(1).build();
+
+        let exported = client
+            .eventlog()
+            .export_events(None, params, EventExportFormat::JsonLines, &mut buf)
+            .await
+            .unwrap();
+
+        first_page_mock.assert();
+        second_page_mock.assert();
+
+        assert_eq!(exported, 2);
+    }
+
+    #[tokio::test]
+    async fn test_export_room_events_json_lines() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let response = include_str!("../tests/responses/eventlog/events_ok.json");
+
+        let events_mock = mock_server
+            .mock("GET", "/api/v4/nodes/rooms/123/events?limit=500&offset=0")
+            .with_status(200)
+            .with_body(response)
+            .with_header("content-type", "application/json")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let mut buf = Vec::new();
+
+        let exported = client
+            .eventlog()
+            .export_events(
+                Some(123),
+                EventlogParams::default(),
+                EventExportFormat::JsonLines,
+                &mut buf,
+            )
+            .await
+            .unwrap();
+
+        events_mock.assert();
+
+        assert_eq!(exported, 1);
+
+        let output = String::from_utf8(buf).unwrap();
+        let event: serde_json::Value =
+            serde_json::from_str(output.lines().next().unwrap()).unwrap();
+
+        assert_eq!(event["id"], 1);
+        assert_eq!(event["status"], 2);
+        assert_eq!(event["objectName1"], "string");
+    }
 }
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use dco3_derive::FromResponse;
 use reqwest::Response;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     client::{DracoonClient, DracoonErrorResponse},
@@ -32,8 +32,8 @@ impl<S> EventlogEndpoint<S> {
     }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
-#[serde(try_from = "i64")]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "i64", into = "i64")]
 pub enum EventStatus {
     Success = 0,
     Failure = 2,
@@ -57,7 +57,7 @@ impl TryFrom<i64> for EventStatus {
     }
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct LogEvent {
     pub id: i64,
@@ -93,6 +93,60 @@ impl FromResponse for LogEventList {
     }
 }
 
+/// Output format of an event export
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventExportFormat {
+    /// Comma separated values (including a header row)
+    Csv,
+    /// One JSON object per line
+    JsonLines,
+}
+
+impl LogEvent {
+    pub(crate) const CSV_HEADER: &'static str = "id,time,userId,userName,userIp,userClient,customerId,operationId,operationName,status,message,objectId1,objectType1,objectName1,objectId2,objectType2,objectName2,attribute1,attribute2,attribute3,authParentSource,authParentTarget";
+
+    pub(crate) fn to_csv_row(&self) -> String {
+        let status = self.status.clone().map(i64::from);
+
+        [
+            Some(self.id.to_string()),
+            Some(self.time.to_rfc3339()),
+            Some(self.user_id.to_string()),
+            self.user_name.clone(),
+            self.user_ip.clone(),
+            self.user_client.clone(),
+            self.customer_id.map(|v| v.to_string()),
+            self.operation_id.map(|v| v.to_string()),
+            self.operation_name.clone(),
+            status.map(|v| v.to_string()),
+            Some(self.message.clone()),
+            self.object_id1.map(|v| v.to_string()),
+            self.object_type1.map(|v| v.to_string()),
+            self.object_name1.clone(),
+            self.object_id2.map(|v| v.to_string()),
+            self.object_type2.map(|v| v.to_string()),
+            self.object_name2.clone(),
+            self.attribute1.clone(),
+            self.attribute2.clone(),
+            self.attribute3.clone(),
+            self.auth_parent_source.clone(),
+            self.auth_parent_target.clone(),
+        ]
+        .iter()
+        .map(|value| escape_csv_value(value.as_deref().unwrap_or_default()))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+fn escape_csv_value(value: &str) -> String {
+    if value.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct LogOperation {
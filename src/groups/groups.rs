@@ -1,16 +1,22 @@
+use std::collections::HashSet;
+
 use async_trait::async_trait;
 use reqwest::header;
 
 use crate::{
     client::Connected,
-    constants::{DRACOON_API_PREFIX, GROUPS_BASE, GROUPS_LAST_ADMIN_ROOMS, GROUPS_USERS},
+    constants::{
+        DRACOON_API_PREFIX, GROUPS_BASE, GROUPS_LAST_ADMIN_ROOMS, GROUPS_ROLES, GROUPS_USERS,
+        ROLES_BASE, ROLES_GROUPS,
+    },
     models::ListAllParams,
-    utils::FromResponse,
+    roles::{AssignRoleBatchRequest, RevokeRoleBatchRequest, RoleGroupList, RoleList, RoleType},
+    utils::{list_all, FromResponse},
     DracoonClientError,
 };
 
 use super::models::*;
-use super::Groups;
+use super::{GroupRoles, Groups};
 
 #[async_trait]
 impl Groups for GroupsEndpoint<Connected> {
@@ -180,69 +186,202 @@ impl Groups for GroupsEndpoint<Connected> {
     ) -> Result<Group, DracoonClientError> {
         let url_part = format!("/{DRACOON_API_PREFIX}/{GROUPS_BASE}/{group_id}/{GROUPS_USERS}");
 
+        let mut group = None;
+        for batch in user_ids.batches() {
+            let api_url = self.client().build_api_url(&url_part);
+
+            let response = self
+                .client()
+                .http
+                .post(api_url)
+                .header(
+                    header::AUTHORIZATION,
+                    self.client().get_auth_header().await?,
+                )
+                .header(header::CONTENT_TYPE, "application/json")
+                .json(&batch)
+                .send()
+                .await?;
+
+            group = Some(Group::from_response(response).await?);
+        }
+
+        Ok(group.expect("at least one batch is sent"))
+    }
+
+    async fn remove_group_users(
+        &self,
+        group_id: u64,
+        user_ids: ChangeGroupMembersRequest,
+    ) -> Result<Group, DracoonClientError> {
+        let url_part = format!("/{DRACOON_API_PREFIX}/{GROUPS_BASE}/{group_id}/{GROUPS_USERS}");
+
+        let mut group = None;
+        for batch in user_ids.batches() {
+            let api_url = self.client().build_api_url(&url_part);
+
+            let response = self
+                .client()
+                .http
+                .delete(api_url)
+                .header(
+                    header::AUTHORIZATION,
+                    self.client().get_auth_header().await?,
+                )
+                .header(header::CONTENT_TYPE, "application/json")
+                .json(&batch)
+                .send()
+                .await?;
+
+            group = Some(Group::from_response(response).await?);
+        }
+
+        Ok(group.expect("at least one batch is sent"))
+    }
+
+    async fn set_group_members(
+        &self,
+        group_id: u64,
+        user_ids: Vec<u64>,
+    ) -> Result<GroupMembershipChanges, DracoonClientError> {
+        let current = self.get_all_group_members(group_id).await?;
+        let desired = user_ids.into_iter().collect::<HashSet<_>>();
+
+        let mut changes = GroupMembershipChanges {
+            added: desired.difference(&current).copied().collect(),
+            removed: current.difference(&desired).copied().collect(),
+        };
+        changes.added.sort_unstable();
+        changes.removed.sort_unstable();
+
+        if !changes.added.is_empty() {
+            self.add_group_users(group_id, changes.added.clone().into())
+                .await?;
+        }
+
+        if !changes.removed.is_empty() {
+            self.remove_group_users(group_id, changes.removed.clone().into())
+                .await?;
+        }
+
+        Ok(changes)
+    }
+
+    async fn get_group_last_admin_rooms(
+        &self,
+        group_id: u64,
+    ) -> Result<LastAdminGroupRoomList, DracoonClientError> {
+        let url_part =
+            format!("/{DRACOON_API_PREFIX}/{GROUPS_BASE}/{group_id}/{GROUPS_LAST_ADMIN_ROOMS}");
+
         let api_url = self.client().build_api_url(&url_part);
 
         let response = self
             .client()
             .http
-            .post(api_url)
+            .get(api_url)
             .header(
                 header::AUTHORIZATION,
                 self.client().get_auth_header().await?,
             )
-            .header(header::CONTENT_TYPE, "application/json")
-            .json(&user_ids)
             .send()
             .await?;
 
-        Group::from_response(response).await
+        LastAdminGroupRoomList::from_response(response).await
     }
+}
 
-    async fn remove_group_users(
+impl GroupsEndpoint<Connected> {
+    /// lists the ids of all members of a group
+    async fn get_all_group_members(
         &self,
         group_id: u64,
-        user_ids: ChangeGroupMembersRequest,
-    ) -> Result<Group, DracoonClientError> {
-        let url_part = format!("/{DRACOON_API_PREFIX}/{GROUPS_BASE}/{group_id}/{GROUPS_USERS}");
+    ) -> Result<HashSet<u64>, DracoonClientError> {
+        let members = list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_offset(offset)
+                .with_limit(limit)
+                .with_filter(GroupUsersFilter::is_member(true))
+                .build();
+
+            self.get_group_users(group_id, Some(params))
+        })
+        .await?;
+
+        Ok(members
+            .iter()
+            .map(|user| user.user_info.id as u64)
+            .collect())
+    }
+}
 
+#[async_trait]
+impl GroupRoles for GroupsEndpoint<Connected> {
+    async fn get_group_roles(&self, group_id: u64) -> Result<RoleList, DracoonClientError> {
+        let url_part = format!("/{DRACOON_API_PREFIX}/{GROUPS_BASE}/{group_id}/{GROUPS_ROLES}");
         let api_url = self.client().build_api_url(&url_part);
 
         let response = self
             .client()
             .http
-            .delete(api_url)
+            .get(api_url)
             .header(
                 header::AUTHORIZATION,
                 self.client().get_auth_header().await?,
             )
-            .header(header::CONTENT_TYPE, "application/json")
-            .json(&user_ids)
             .send()
             .await?;
 
-        Group::from_response(response).await
+        RoleList::from_response(response).await
     }
 
-    async fn get_group_last_admin_rooms(
+    async fn assign_group_role(
         &self,
         group_id: u64,
-    ) -> Result<LastAdminGroupRoomList, DracoonClientError> {
-        let url_part =
-            format!("/{DRACOON_API_PREFIX}/{GROUPS_BASE}/{group_id}/{GROUPS_LAST_ADMIN_ROOMS}");
+        role: RoleType,
+    ) -> Result<RoleGroupList, DracoonClientError> {
+        let role_id = role.id();
+        let url_part = format!("/{DRACOON_API_PREFIX}/{ROLES_BASE}/{role_id}/{ROLES_GROUPS}");
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .post(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&AssignRoleBatchRequest::from(vec![group_id]))
+            .send()
+            .await?;
+
+        RoleGroupList::from_response(response).await
+    }
 
+    async fn revoke_group_role(
+        &self,
+        group_id: u64,
+        role: RoleType,
+    ) -> Result<RoleGroupList, DracoonClientError> {
+        let role_id = role.id();
+        let url_part = format!("/{DRACOON_API_PREFIX}/{ROLES_BASE}/{role_id}/{ROLES_GROUPS}");
         let api_url = self.client().build_api_url(&url_part);
 
         let response = self
             .client()
             .http
-            .get(api_url)
+            .delete(api_url)
             .header(
                 header::AUTHORIZATION,
                 self.client().get_auth_header().await?,
             )
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&RevokeRoleBatchRequest::from(vec![group_id]))
             .send()
             .await?;
 
-        LastAdminGroupRoomList::from_response(response).await
+        RoleGroupList::from_response(response).await
     }
 }
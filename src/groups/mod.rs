@@ -6,7 +6,11 @@ mod models;
 
 pub use models::*;
 
-use crate::{models::ListAllParams, DracoonClientError};
+use crate::{
+    models::ListAllParams,
+    roles::{RoleGroupList, RoleList, RoleType},
+    DracoonClientError,
+};
 
 /// This trait provides all methods to manage groups.
 /// All sorting and filtering is implemented and can be found using respective '*Filter` or
@@ -151,6 +155,8 @@ pub trait Groups {
         params: Option<ListAllParams>,
     ) -> Result<GroupUserList, DracoonClientError>;
     /// Add users to a group.
+    /// Large lists of users are split into multiple requests - the group is returned after
+    /// all users are added (stops at the first failed request).
     /// ```no_run
     /// # use dco3::{Dracoon, auth::OAuth2Flow, Groups, groups::{ChangeGroupMembersRequest}};
     /// # #[tokio::main]
@@ -178,6 +184,8 @@ pub trait Groups {
         user_ids: ChangeGroupMembersRequest,
     ) -> Result<Group, DracoonClientError>;
     /// Remove users from a group.
+    /// Large lists of users are split into multiple requests - the group is returned after
+    /// all users are removed (stops at the first failed request).
     /// ```no_run
     /// # use dco3::{Dracoon, auth::OAuth2Flow, Groups, groups::{ChangeGroupMembersRequest}};
     /// # #[tokio::main]
@@ -204,6 +212,31 @@ pub trait Groups {
         group_id: u64,
         user_ids: ChangeGroupMembersRequest,
     ) -> Result<Group, DracoonClientError>;
+    /// Sets the members of a group - users not in the list are removed and missing users are added.
+    /// Returns the ids of the added and removed users.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Groups};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let changes = dracoon.groups().set_group_members(123, vec![1, 2, 3]).await.unwrap();
+    ///
+    /// println!("Added: {:?}, removed: {:?}", changes.added, changes.removed);
+    /// # }
+    /// ```
+    async fn set_group_members(
+        &self,
+        group_id: u64,
+        user_ids: Vec<u64>,
+    ) -> Result<GroupMembershipChanges, DracoonClientError>;
     /// Get group last admin rooms
     /// ```no_run
     /// # use dco3::{Dracoon, auth::OAuth2Flow, Groups};
@@ -225,4 +258,105 @@ pub trait Groups {
         &self,
         group_id: u64,
     ) -> Result<LastAdminGroupRoomList, DracoonClientError>;
+    /// Checks if a group can be deleted without leaving rooms without an administrator.
+    /// Returns a [DracoonClientError::WouldOrphanRooms] error with the affected rooms if
+    /// the group is the last admin of any room.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Groups};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// match dracoon.groups().validate_group_deletion(123).await {
+    ///     Ok(()) => dracoon.groups().delete_group(123).await.unwrap(),
+    ///     Err(err) if err.is_would_orphan_rooms() => println!("Group is last admin: {}", err),
+    ///     Err(err) => panic!("{}", err),
+    /// }
+    /// # }
+    /// ```
+    async fn validate_group_deletion(&self, group_id: u64) -> Result<(), DracoonClientError> {
+        let rooms = self.get_group_last_admin_rooms(group_id).await?;
+
+        if rooms.is_empty() {
+            return Ok(());
+        }
+
+        Err(rooms.into())
+    }
+}
+
+/// This trait provides the management of global roles of a group.
+/// Roles of a group apply to all its members.
+#[async_trait]
+pub trait GroupRoles: Groups {
+    /// Get the roles of a group.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, GroupRoles, roles::RoleType};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let roles = dracoon.groups().get_group_roles(123).await.unwrap();
+    /// let is_room_manager = roles.contains(RoleType::RoomManager);
+    /// # }
+    /// ```
+    async fn get_group_roles(&self, group_id: u64) -> Result<RoleList, DracoonClientError>;
+    /// Assign a role to a group.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, GroupRoles, roles::RoleType};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// dracoon.groups().assign_group_role(123, RoleType::RoomManager).await.unwrap();
+    /// # }
+    /// ```
+    async fn assign_group_role(
+        &self,
+        group_id: u64,
+        role: RoleType,
+    ) -> Result<RoleGroupList, DracoonClientError>;
+    /// Revoke a role from a group.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, GroupRoles, roles::RoleType};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// dracoon.groups().revoke_group_role(123, RoleType::RoomManager).await.unwrap();
+    /// # }
+    /// ```
+    async fn revoke_group_role(
+        &self,
+        group_id: u64,
+        role: RoleType,
+    ) -> Result<RoleGroupList, DracoonClientError>;
 }
@@ -1,14 +1,18 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use dco3_derive::FromResponse;
 use reqwest::Response;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     client::{DracoonClient, DracoonErrorResponse},
-    models::{FilterOperator, FilterQuery, ObjectExpiration, RangedItems, SortOrder, SortQuery},
+    constants::GROUP_MEMBERS_BATCH,
+    models::{
+        FilterOperator, FilterQuery, ObjectExpiration, OrphanedRoom, RangedItems, SortOrder,
+        SortQuery,
+    },
     nodes::models::UserInfo,
     roles::RoleList,
     utils::{parse_body, FromResponse},
@@ -122,6 +126,34 @@ impl ChangeGroupMembersRequest {
     pub fn new(ids: Vec<u64>) -> Self {
         Self { ids }
     }
+
+    /// splits the request into batches of the max. size accepted by the API
+    pub(crate) fn batches(&self) -> Vec<Self> {
+        if self.ids.is_empty() {
+            return vec![self.clone()];
+        }
+
+        self.ids
+            .chunks(GROUP_MEMBERS_BATCH)
+            .map(|ids| Self::new(ids.to_vec()))
+            .collect()
+    }
+}
+
+/// Changes applied to the members of a group (see [crate::groups::Groups::set_group_members])
+/// - added: ids of users added to the group
+/// - removed: ids of users removed from the group
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GroupMembershipChanges {
+    pub added: Vec<u64>,
+    pub removed: Vec<u64>,
+}
+
+impl GroupMembershipChanges {
+    /// Returns true if the members of the group were already up to date
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, FromResponse)]
@@ -139,6 +171,29 @@ pub struct LastAdminGroupRoom {
     pub parent_id: Option<u64>,
 }
 
+impl LastAdminGroupRoomList {
+    /// Returns true if the group is not the last admin of any room
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl From<LastAdminGroupRoom> for OrphanedRoom {
+    fn from(room: LastAdminGroupRoom) -> Self {
+        Self {
+            id: room.id,
+            name: room.name,
+            parent_path: room.parent_path,
+        }
+    }
+}
+
+impl From<LastAdminGroupRoomList> for DracoonClientError {
+    fn from(rooms: LastAdminGroupRoomList) -> Self {
+        DracoonClientError::WouldOrphanRooms(rooms.items.into_iter().map(Into::into).collect())
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupUser {
@@ -159,6 +214,7 @@ impl FromResponse for GroupUserList {
 pub enum GroupsFilter {
     Name(FilterOperator, String),
     HasRole(FilterOperator, String),
+    CreatedAt(FilterOperator, DateTime<Utc>),
 }
 
 impl FilterQuery for GroupsFilter {
@@ -172,6 +228,11 @@ impl FilterQuery for GroupsFilter {
                 let op: String = op.into();
                 format!("hasRole:{}:{}", op, val)
             }
+            GroupsFilter::CreatedAt(op, val) => {
+                let op: String = op.into();
+                let val = val.to_rfc3339_opts(SecondsFormat::Secs, true);
+                format!("createdAt:{}:{}", op, val)
+            }
         }
     }
 }
@@ -184,6 +245,18 @@ impl GroupsFilter {
     pub fn has_role(val: impl Into<String>) -> Self {
         Self::HasRole(FilterOperator::Eq, val.into())
     }
+
+    pub fn name_equals(val: impl Into<String>) -> Self {
+        Self::Name(FilterOperator::Eq, val.into())
+    }
+
+    pub fn created_at_before(val: DateTime<Utc>) -> Self {
+        Self::CreatedAt(FilterOperator::Le, val)
+    }
+
+    pub fn created_at_after(val: DateTime<Utc>) -> Self {
+        Self::CreatedAt(FilterOperator::Ge, val)
+    }
 }
 
 #[derive(Debug, Clone)]
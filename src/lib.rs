@@ -17,15 +17,22 @@
 //!
 //! * [User] - for user account management
 //! * [UserAccountKeyPairs] - for user keypair management
+//! * [NodeSubscriptions] - for node subscription management
+//! * [UserAvatar] - for user avatar retrieval
 //! * [Nodes] - for node operations (folders, rooms, upload and download are excluded)
 //! * [Download] - for downloading files
 //! * [Upload] - for uploading files
+//! * [UploadMany] - for uploading multiple local files
+//! * [OpenUpload] - for uploading content written to a writer
+//! * [SyncDirectory] - for syncing a local directory to a room or folder
 //! * [Folders] - for folder operations
 //! * [Rooms] - for room operations
+//! * [RoomInvitations] - for inviting (guest) users to rooms
 //! * [DownloadShares] - for download share operations
 //! * [UploadShares] - for upload share operations
 //! * [Groups] - for group operations
 //! * [Users] - for user management operations
+//! * [UserDeletion] - for deleting users without leaving rooms without an administrator
 //! * [CustomerProvisioning] - for customer provisioning operations
 //! * [MissingFileKeys] - for distributing missing keys using the user keypair
 //! * [RescueKeyPair] - for distributing missing keys using the rescue key
@@ -455,17 +462,20 @@ pub use self::{
     client::OAuth2Flow,
     config::Config,
     eventlog::Eventlog,
-    groups::Groups,
+    groups::{GroupRoles, Groups},
     models::*,
-    nodes::{Download, Folders, MissingFileKeys, Nodes, Rooms, Upload},
+    nodes::{
+        Download, Folders, MissingFileKeys, Nodes, OpenUpload, RoomInvitations, Rooms,
+        SyncDirectory, Upload, UploadMany,
+    },
     provisioning::CustomerProvisioning,
     public::{Public, PublicDownload, PublicUpload},
     roles::Roles,
     settings::RescueKeyPair,
-    shares::{DownloadShares, UploadShares},
+    shares::{DownloadShares, EncryptedDownloadShares, ShareManagement, UploadShares},
     system::AuthenticationMethods,
-    user::{User, UserAccountKeyPairs},
-    users::Users,
+    user::{NodeSubscriptions, User, UserAccountKeyPairs, UserAvatar},
+    users::{UserDataExport, UserDeletion, UserRoles, Users},
 };
 
 pub mod client;
@@ -581,6 +591,39 @@ impl DracoonBuilder {
         self
     }
 
+    /// Sets the max. number of simultaneous transfers (uploads and downloads) - unlimited by default.
+    /// Additional transfers wait until a running transfer is finished.
+    pub fn with_max_concurrent_transfers(mut self, max_concurrent_transfers: usize) -> Self {
+        self.client_builder = self
+            .client_builder
+            .with_max_concurrent_transfers(max_concurrent_transfers);
+        self
+    }
+
+    /// Sets the max. upload rate (in bytes per second) shared by all uploads - unlimited by default.
+    /// Single uploads can be limited additionally via `UploadOptions`.
+    pub fn with_max_upload_rate(mut self, max_upload_rate: u64) -> Self {
+        self.client_builder = self.client_builder.with_max_upload_rate(max_upload_rate);
+        self
+    }
+
+    /// Sets the max. delay between polling the status of a finalized S3 upload (in ms)
+    pub fn with_max_upload_polling_delay(mut self, max_upload_polling_delay: u64) -> Self {
+        self.client_builder = self
+            .client_builder
+            .with_max_upload_polling_delay(max_upload_polling_delay);
+        self
+    }
+
+    /// Sets the max. time to wait for a finalized S3 upload to be completed (in ms) - 1 hour by default.
+    /// Uploads not completed in time fail with `DracoonClientError::S3UploadTimedOut`.
+    pub fn with_upload_polling_timeout(mut self, upload_polling_timeout: u64) -> Self {
+        self.client_builder = self
+            .client_builder
+            .with_upload_polling_timeout(upload_polling_timeout);
+        self
+    }
+
     /// Sets X-SDS-Service-token for DRACOON customer provisioning
     pub fn with_provisioning_token(mut self, provisioning_token: impl Into<String>) -> Self {
         self.client_builder = self
@@ -247,6 +247,32 @@ impl ObjectExpiration {
             expire_at: Some(expire_at.to_rfc3339()),
         }
     }
+
+    /// Validates an enabled expiration: the date must be set, a valid RFC 3339 date and in the future.
+    /// Returns `DracoonClientError::InvalidExpiration` otherwise.
+    pub fn validate(&self) -> Result<(), DracoonClientError> {
+        if !self.enable_expiration {
+            return Ok(());
+        }
+
+        let Some(expire_at) = self.expire_at.as_ref() else {
+            return Err(DracoonClientError::InvalidExpiration(
+                "expiration enabled without a date".into(),
+            ));
+        };
+
+        let expire_at = DateTime::parse_from_rfc3339(expire_at).map_err(|_| {
+            DracoonClientError::InvalidExpiration(format!("invalid date '{expire_at}'"))
+        })?;
+
+        if expire_at <= Utc::now() {
+            return Err(DracoonClientError::InvalidExpiration(format!(
+                "date '{expire_at}' is in the past"
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 impl AsRef<ObjectExpiration> for ObjectExpiration {
@@ -261,6 +287,15 @@ impl From<DateTime<Utc>> for ObjectExpiration {
     }
 }
 
+/// Room that would be left without an administrator by deleting a user or group
+/// (see [DracoonClientError::WouldOrphanRooms])
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrphanedRoom {
+    pub id: u64,
+    pub name: String,
+    pub parent_path: String,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct RangedItems<T> {
     pub range: Range,
@@ -466,6 +501,32 @@ pub struct KeyValueEntry {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_expiration() {
+        let expiration = ObjectExpiration::new(Utc::now() + chrono::Duration::days(30));
+        assert!(expiration.validate().is_ok());
+
+        assert!(ObjectExpiration::default().validate().is_ok());
+
+        let past = ObjectExpiration::new(Utc::now() - chrono::Duration::days(1));
+        assert!(past.validate().unwrap_err().is_invalid_expiration());
+
+        let invalid = ObjectExpiration {
+            enable_expiration: true,
+            expire_at: Some("not a date".into()),
+        };
+        assert_eq!(
+            invalid.validate().unwrap_err(),
+            DracoonClientError::InvalidExpiration("invalid date 'not a date'".into())
+        );
+
+        let missing = ObjectExpiration {
+            enable_expiration: true,
+            expire_at: None,
+        };
+        assert!(missing.validate().unwrap_err().is_invalid_expiration());
+    }
+
     #[test]
     fn test_filter_query_builder() {
         let query = FilterQueryBuilder::new()
@@ -0,0 +1,486 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use base64::{engine::general_purpose, Engine};
+use dco3_crypto::{
+    DracoonCrypto, DracoonRSACrypto, FileKey, FileKeyVersion, PlainFileKey,
+    PlainUserKeyPairContainer,
+};
+use openssl::{
+    error::ErrorStack,
+    symm::{Cipher, Crypter, Mode},
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::error;
+
+use crate::{constants::DOWNLOAD_BUFFER_SIZE, utils::random_bytes, DracoonClientError};
+
+/// Decrypts file content (AES-256-GCM) chunk by chunk without buffering the whole file.
+/// The authentication tag is verified in [ChunkedDecrypter::finalize] - decrypted chunks
+/// are returned *before* the content is authenticated and must be discarded if finalizing
+/// fails with `DracoonClientError::IntegrityCheckFailed`.
+/// (the decrypter of dco3_crypto writes into a single buffer and requires the whole file in memory)
+pub(crate) struct ChunkedDecrypter {
+    crypter: Crypter,
+    block_size: usize,
+}
+
+impl ChunkedDecrypter {
+    pub fn new(plain_file_key: &PlainFileKey) -> Result<Self, DracoonClientError> {
+        let cipher = Cipher::aes_256_gcm();
+
+        let key = decode_base64(&plain_file_key.key)?;
+        let iv = decode_base64(&plain_file_key.iv)?;
+        let tag = plain_file_key
+            .tag
+            .as_deref()
+            .ok_or(DracoonClientError::MissingArgument)
+            .and_then(decode_base64)?;
+
+        let mut crypter =
+            Crypter::new(cipher, Mode::Decrypt, &key, Some(&iv)).map_err(crypter_error)?;
+        crypter.set_tag(&tag).map_err(crypter_error)?;
+
+        Ok(Self {
+            crypter,
+            block_size: cipher.block_size(),
+        })
+    }
+
+    /// Decrypts a chunk and returns the plain bytes
+    pub fn update(&mut self, chunk: &[u8]) -> Result<Vec<u8>, DracoonClientError> {
+        let mut buffer = vec![0u8; chunk.len() + self.block_size];
+        let count = self
+            .crypter
+            .update(chunk, &mut buffer)
+            .map_err(crypter_error)?;
+        buffer.truncate(count);
+
+        Ok(buffer)
+    }
+
+    /// Finalizes the decryption (verifies the authentication tag) and returns remaining plain bytes
+    /// Returns `DracoonClientError::IntegrityCheckFailed` if the tag does not match the content.
+    pub fn finalize(&mut self) -> Result<Vec<u8>, DracoonClientError> {
+        let mut buffer = vec![0u8; self.block_size];
+        let count = self.crypter.finalize(&mut buffer).map_err(|err| {
+            error!("Authentication tag verification failed: {}", err);
+            DracoonClientError::IntegrityCheckFailed
+        })?;
+        buffer.truncate(count);
+
+        Ok(buffer)
+    }
+}
+
+/// length of the AES-256-GCM key (in bytes)
+const KEY_LENGTH: usize = 32;
+/// length of the AES-256-GCM initialization vector (in bytes)
+const IV_LENGTH: usize = 12;
+/// length of the AES-256-GCM authentication tag (in bytes)
+const TAG_LENGTH: usize = 16;
+
+/// Encrypts file content (AES-256-GCM) chunk by chunk with a generated file key
+/// without buffering the whole file. The encrypted content has the same size as the plain content.
+/// The plain file key (including the authentication tag) is available after [ChunkedEncrypter::finalize].
+pub(crate) struct ChunkedEncrypter {
+    crypter: Crypter,
+    key: Vec<u8>,
+    iv: Vec<u8>,
+    tag: Option<Vec<u8>>,
+    block_size: usize,
+}
+
+impl ChunkedEncrypter {
+    pub fn new() -> Result<Self, DracoonClientError> {
+        let mut key = vec![0u8; KEY_LENGTH];
+        let mut iv = vec![0u8; IV_LENGTH];
+        random_bytes(&mut key)?;
+        random_bytes(&mut iv)?;
+
+        Self::with_key(key, iv)
+    }
+
+    /// Creates an encrypter with a pre-generated file key (the tag of the key is ignored)
+    pub fn with_file_key(plain_file_key: &PlainFileKey) -> Result<Self, DracoonClientError> {
+        let key = decode_base64(&plain_file_key.key)?;
+        let iv = decode_base64(&plain_file_key.iv)?;
+
+        if key.len() != KEY_LENGTH || iv.len() != IV_LENGTH {
+            error!("Invalid file key (key or iv length)");
+            return Err(DracoonClientError::InvalidUploadOptions(
+                "invalid file key".into(),
+            ));
+        }
+
+        Self::with_key(key, iv)
+    }
+
+    fn with_key(key: Vec<u8>, iv: Vec<u8>) -> Result<Self, DracoonClientError> {
+        let cipher = Cipher::aes_256_gcm();
+
+        let crypter =
+            Crypter::new(cipher, Mode::Encrypt, &key, Some(&iv)).map_err(crypter_error)?;
+
+        Ok(Self {
+            crypter,
+            key,
+            iv,
+            tag: None,
+            block_size: cipher.block_size(),
+        })
+    }
+
+    /// Encrypts a chunk and returns the encrypted bytes
+    pub fn update(&mut self, chunk: &[u8]) -> Result<Vec<u8>, DracoonClientError> {
+        let mut buffer = vec![0u8; chunk.len() + self.block_size];
+        let count = self
+            .crypter
+            .update(chunk, &mut buffer)
+            .map_err(crypter_error)?;
+        buffer.truncate(count);
+
+        Ok(buffer)
+    }
+
+    /// Finalizes the encryption (computes the authentication tag) and returns remaining encrypted bytes
+    pub fn finalize(&mut self) -> Result<Vec<u8>, DracoonClientError> {
+        let mut buffer = vec![0u8; self.block_size];
+        let count = self.crypter.finalize(&mut buffer).map_err(crypter_error)?;
+        buffer.truncate(count);
+
+        let mut tag = vec![0u8; TAG_LENGTH];
+        self.crypter.get_tag(&mut tag).map_err(crypter_error)?;
+        self.tag = Some(tag);
+
+        Ok(buffer)
+    }
+
+    /// Returns the plain file key - fails if the encryption is not finalized
+    pub fn plain_file_key(&self) -> Result<PlainFileKey, DracoonClientError> {
+        let Some(tag) = &self.tag else {
+            error!("Encryption not finalized - missing authentication tag");
+            return Err(DracoonClientError::Internal);
+        };
+
+        Ok(PlainFileKey {
+            key: general_purpose::STANDARD.encode(&self.key),
+            iv: general_purpose::STANDARD.encode(&self.iv),
+            tag: Some(general_purpose::STANDARD.encode(tag)),
+            version: FileKeyVersion::AES256GCM,
+        })
+    }
+}
+
+/// Encrypts chunks with a shared [ChunkedEncrypter] on blocking threads - encrypting large chunks
+/// on the worker threads of the async runtime would stall other tasks.
+/// Chunks must be encrypted in order (await each chunk before encrypting the next one).
+#[derive(Clone)]
+pub(crate) struct BlockingEncrypter {
+    encrypter: Arc<Mutex<ChunkedEncrypter>>,
+}
+
+impl BlockingEncrypter {
+    /// Creates an encrypter with the given file key or a generated one
+    pub fn new(plain_file_key: Option<&PlainFileKey>) -> Result<Self, DracoonClientError> {
+        let encrypter = match plain_file_key {
+            Some(plain_file_key) => ChunkedEncrypter::with_file_key(plain_file_key)?,
+            None => ChunkedEncrypter::new()?,
+        };
+
+        Ok(Self {
+            encrypter: Arc::new(Mutex::new(encrypter)),
+        })
+    }
+
+    /// Encrypts a chunk (and finalizes the encryption if it is the last chunk)
+    pub fn encrypt(
+        &self,
+        chunk: Vec<u8>,
+        is_last: bool,
+    ) -> impl Future<Output = Result<Vec<u8>, DracoonClientError>> + Send + 'static {
+        let encrypter = self.encrypter.clone();
+
+        async move {
+            tokio::task::spawn_blocking(move || {
+                let mut encrypter = encrypter.lock().expect("encrypter lock poisoned");
+                let mut encrypted = encrypter.update(&chunk)?;
+                if is_last {
+                    encrypted.extend(encrypter.finalize()?);
+                }
+                Ok(encrypted)
+            })
+            .await
+            .map_err(|err| {
+                error!("Error running encryption task: {}", err);
+                DracoonClientError::Internal
+            })?
+        }
+    }
+
+    /// Returns the plain file key - fails if the encryption is not finalized
+    pub fn plain_file_key(&self) -> Result<PlainFileKey, DracoonClientError> {
+        self.encrypter
+            .lock()
+            .expect("encrypter lock poisoned")
+            .plain_file_key()
+    }
+}
+
+/// Decrypts already downloaded encrypted file content from a reader to a writer with the given
+/// plain file key - e.g. if transfer and decryption are separated (see [crate::nodes::Download::get_download_url]).
+/// Returns the number of decrypted bytes.
+/// The authentication tag is verified after all content is written - if this fails,
+/// `DracoonClientError::IntegrityCheckFailed` is returned and the written content must be discarded.
+/// Example
+/// ```no_run
+/// use dco3::nodes::decrypt_reader;
+/// # use dco3_crypto::PlainFileKey;
+/// # fn get_plain_file_key() -> PlainFileKey { unimplemented!() }
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let plain_file_key = get_plain_file_key();
+///   let mut reader = tokio::fs::File::open("encrypted.bin").await.unwrap();
+///   let mut writer = tokio::fs::File::create("plain.txt").await.unwrap();
+///
+///   let bytes = decrypt_reader(&mut reader, &mut writer, &plain_file_key).await.unwrap();
+/// }
+/// ```
+pub async fn decrypt_reader<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    plain_file_key: &PlainFileKey,
+) -> Result<u64, DracoonClientError>
+where
+    R: AsyncRead + Unpin + ?Sized,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let mut decrypter = ChunkedDecrypter::new(plain_file_key)?;
+    let mut buffer = vec![0u8; DOWNLOAD_BUFFER_SIZE];
+    let mut decrypted_bytes = 0u64;
+
+    loop {
+        let count = reader
+            .read(&mut buffer)
+            .await
+            .or(Err(DracoonClientError::IoError))?;
+
+        if count == 0 {
+            break;
+        }
+
+        let plain = decrypter.update(&buffer[..count])?;
+        write_all(writer, &plain).await?;
+        decrypted_bytes += plain.len() as u64;
+    }
+
+    let plain = decrypter.finalize()?;
+    write_all(writer, &plain).await?;
+    decrypted_bytes += plain.len() as u64;
+
+    writer.flush().await.or(Err(DracoonClientError::IoError))?;
+
+    Ok(decrypted_bytes)
+}
+
+/// Decrypts already downloaded encrypted file content from a reader to a writer with the
+/// (encrypted) file key of the user and the plain user keypair (see [decrypt_reader]).
+pub async fn decrypt_reader_with_keypair<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    file_key: FileKey,
+    keypair: PlainUserKeyPairContainer,
+) -> Result<u64, DracoonClientError>
+where
+    R: AsyncRead + Unpin + ?Sized,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let plain_file_key = DracoonCrypto::decrypt_file_key(file_key, keypair)?;
+
+    decrypt_reader(reader, writer, &plain_file_key).await
+}
+
+async fn write_all<W>(writer: &mut W, plain: &[u8]) -> Result<(), DracoonClientError>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    writer
+        .write_all(plain)
+        .await
+        .or(Err(DracoonClientError::IoError))
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, DracoonClientError> {
+    general_purpose::STANDARD.decode(value).map_err(|err| {
+        error!("Invalid file key encoding: {}", err);
+        DracoonClientError::Internal
+    })
+}
+
+fn crypter_error(err: ErrorStack) -> DracoonClientError {
+    error!("Crypter operation failed: {}", err);
+    DracoonClientError::Internal
+}
+
+#[cfg(test)]
+mod tests {
+    use dco3_crypto::{DracoonCrypto, Encrypt};
+
+    use super::*;
+
+    #[test]
+    fn test_chunked_decryption() {
+        let plain = b"some plain text that is decrypted in chunks".to_vec();
+        let (encrypted, plain_file_key) = DracoonCrypto::encrypt(plain.clone()).unwrap();
+
+        let mut decrypter = ChunkedDecrypter::new(&plain_file_key).unwrap();
+
+        let mut decrypted = Vec::new();
+        for chunk in encrypted.chunks(7) {
+            decrypted.extend(decrypter.update(chunk).unwrap());
+        }
+        decrypted.extend(decrypter.finalize().unwrap());
+
+        assert_eq!(decrypted, plain);
+    }
+
+    #[test]
+    fn test_chunked_decryption_invalid_tag() {
+        let plain = b"some plain text".to_vec();
+        let (mut encrypted, plain_file_key) = DracoonCrypto::encrypt(plain).unwrap();
+        encrypted[0] ^= 0xff;
+
+        let mut decrypter = ChunkedDecrypter::new(&plain_file_key).unwrap();
+        decrypter.update(&encrypted).unwrap();
+
+        let err = decrypter.finalize().unwrap_err();
+        assert!(err.is_integrity_check_failed());
+    }
+
+    #[test]
+    fn test_chunked_encryption() {
+        let plain = b"some plain text that is encrypted in chunks".to_vec();
+
+        let mut encrypter = ChunkedEncrypter::new().unwrap();
+
+        let mut encrypted = Vec::new();
+        for chunk in plain.chunks(7) {
+            encrypted.extend(encrypter.update(chunk).unwrap());
+        }
+        encrypted.extend(encrypter.finalize().unwrap());
+
+        assert_eq!(encrypted.len(), plain.len());
+
+        let plain_file_key = encrypter.plain_file_key().unwrap();
+
+        let mut decrypter = ChunkedDecrypter::new(&plain_file_key).unwrap();
+        let mut decrypted = decrypter.update(&encrypted).unwrap();
+        decrypted.extend(decrypter.finalize().unwrap());
+
+        assert_eq!(decrypted, plain);
+    }
+
+    #[test]
+    fn test_chunked_encryption_not_finalized() {
+        let mut encrypter = ChunkedEncrypter::new().unwrap();
+        encrypter.update(b"some plain text").unwrap();
+
+        assert!(encrypter.plain_file_key().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_blocking_encryption() {
+        let plain = b"some plain text that is encrypted on blocking threads".to_vec();
+
+        let encrypter = BlockingEncrypter::new(None).unwrap();
+
+        let chunks = plain.chunks(7).collect::<Vec<_>>();
+        let mut encrypted = Vec::new();
+        for (idx, chunk) in chunks.iter().enumerate() {
+            let is_last = idx == chunks.len() - 1;
+            encrypted.extend(encrypter.encrypt(chunk.to_vec(), is_last).await.unwrap());
+        }
+
+        assert_eq!(encrypted.len(), plain.len());
+
+        let plain_file_key = encrypter.plain_file_key().unwrap();
+
+        let mut decrypter = ChunkedDecrypter::new(&plain_file_key).unwrap();
+        let mut decrypted = decrypter.update(&encrypted).unwrap();
+        decrypted.extend(decrypter.finalize().unwrap());
+
+        assert_eq!(decrypted, plain);
+    }
+
+    #[tokio::test]
+    async fn test_blocking_encryption_with_file_key() {
+        let plain = b"some plain text that is encrypted with a given file key".to_vec();
+        let (_, file_key) = DracoonCrypto::encrypt(b"other content".to_vec()).unwrap();
+
+        let encrypter = BlockingEncrypter::new(Some(&file_key)).unwrap();
+        let encrypted = encrypter.encrypt(plain.clone(), true).await.unwrap();
+
+        let plain_file_key = encrypter.plain_file_key().unwrap();
+        assert_eq!(plain_file_key.key, file_key.key);
+        assert_eq!(plain_file_key.iv, file_key.iv);
+
+        let mut decrypter = ChunkedDecrypter::new(&plain_file_key).unwrap();
+        let mut decrypted = decrypter.update(&encrypted).unwrap();
+        decrypted.extend(decrypter.finalize().unwrap());
+
+        assert_eq!(decrypted, plain);
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_reader() {
+        let plain = b"some plain text that is decrypted from a reader".to_vec();
+        let (encrypted, plain_file_key) = DracoonCrypto::encrypt(plain.clone()).unwrap();
+
+        let mut reader = encrypted.as_slice();
+        let mut writer = Vec::new();
+
+        let bytes = decrypt_reader(&mut reader, &mut writer, &plain_file_key)
+            .await
+            .unwrap();
+
+        assert_eq!(bytes, plain.len() as u64);
+        assert_eq!(writer, plain);
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_reader_with_keypair() {
+        let plain = b"some plain text".to_vec();
+        let (encrypted, plain_file_key) = DracoonCrypto::encrypt(plain.clone()).unwrap();
+
+        let keypair =
+            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
+                .unwrap();
+        let file_key = DracoonCrypto::encrypt_file_key(plain_file_key, keypair.clone()).unwrap();
+
+        let mut reader = encrypted.as_slice();
+        let mut writer = Vec::new();
+
+        decrypt_reader_with_keypair(&mut reader, &mut writer, file_key, keypair)
+            .await
+            .unwrap();
+
+        assert_eq!(writer, plain);
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_reader_invalid_content() {
+        let plain = b"some plain text".to_vec();
+        let (mut encrypted, plain_file_key) = DracoonCrypto::encrypt(plain).unwrap();
+        encrypted[0] ^= 0xff;
+
+        let mut reader = encrypted.as_slice();
+        let mut writer = Vec::new();
+
+        let res = decrypt_reader(&mut reader, &mut writer, &plain_file_key).await;
+
+        assert!(res.is_err());
+    }
+}
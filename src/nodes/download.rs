@@ -1,22 +1,56 @@
 use super::{
-    models::{DownloadProgressCallback, DownloadUrlResponse, Node},
+    crypto::ChunkedDecrypter,
+    models::{
+        CreateZipDownloadRequest, DeletedNodeVersion, DownloadCondition, DownloadOutcome,
+        DownloadProgressCallback, DownloadRequest, DownloadStream, DownloadUrlResponse, Node,
+        ProgressTracker, TransferEvent, TransferEventStream, TransferFuture, TransferProgress,
+        UseKey,
+    },
     Download,
 };
 use crate::{
-    client::{errors::DracoonClientError, Connected},
+    client::{
+        errors::DracoonClientError,
+        retry::{is_transient, next_retry_delay},
+        throttle::PauseSignal,
+        Connected,
+    },
     constants::{
-        DEFAULT_CHUNK_SIZE, DRACOON_API_PREFIX, FILES_BASE, FILES_FILE_KEY, NODES_BASE,
-        NODES_DOWNLOAD_URL,
+        DEFAULT_CHUNK_SIZE, DOWNLOAD_BUFFER_SIZE, DRACOON_API_PREFIX, FILES_BASE, FILES_FILE_KEY,
+        NODES_BASE, NODES_DOWNLOAD_URL, NODES_ZIP,
     },
+    settings::keypair::RescueKeypairInternal,
     utils::{build_s3_error, FromResponse},
     Dracoon,
 };
+use async_stream::try_stream;
 use async_trait::async_trait;
-use dco3_crypto::{ChunkedEncryption, Decrypter, DracoonCrypto, DracoonRSACrypto, FileKey};
-use futures_util::TryStreamExt;
-use reqwest::header::{self, CONTENT_LENGTH, RANGE};
-use std::cmp::min;
-use tokio::io::{AsyncWrite, AsyncWriteExt};
+use bytes::Bytes;
+use dco3_crypto::{
+    DracoonCrypto, DracoonRSACrypto, FileKey, PlainFileKey, PlainUserKeyPairContainer,
+};
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+use reqwest::{
+    header::{self, CONTENT_LENGTH, ETAG, RANGE},
+    Response,
+};
+use reqwest_middleware::ClientWithMiddleware;
+use reqwest_retry::policies::ExponentialBackoff;
+use std::{
+    cmp::min,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufWriter},
+    sync::{
+        mpsc::{self, UnboundedSender},
+        OwnedSemaphorePermit,
+    },
+};
+use tokio_util::{io::StreamReader, sync::CancellationToken};
 use tracing::{debug, error};
 
 #[async_trait]
@@ -27,57 +61,392 @@ impl Download for Dracoon<Connected> {
         writer: &'w mut (dyn AsyncWrite + Send + Unpin),
         callback: Option<DownloadProgressCallback>,
     ) -> Result<(), DracoonClientError> {
+        self.download_from_offset(node, 0, writer, callback).await
+    }
+
+    async fn download_from_offset<'w>(
+        &'w self,
+        node: &Node,
+        offset: u64,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        callback: Option<DownloadProgressCallback>,
+    ) -> Result<(), DracoonClientError> {
+        self.download_node(
+            node,
+            offset,
+            writer,
+            callback,
+            None,
+            &CancellationToken::new(),
+        )
+        .await
+    }
+
+    async fn download_version<'w>(
+        &'w self,
+        version: &DeletedNodeVersion,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        callback: Option<DownloadProgressCallback>,
+    ) -> Result<(), DracoonClientError> {
+        self.download_file(
+            version.id,
+            version.size,
+            version.is_encrypted == Some(true),
+            0,
+            writer,
+            callback,
+            None,
+            &CancellationToken::new(),
+        )
+        .await
+    }
+
+    async fn download_with_rescue_keypair<'w>(
+        &'w self,
+        node: &Node,
+        keypair: &PlainUserKeyPairContainer,
+        use_key: UseKey,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        callback: Option<DownloadProgressCallback>,
+    ) -> Result<(), DracoonClientError> {
+        if node.is_encrypted != Some(true) {
+            return self.download(node, writer, callback).await;
+        }
+
+        let file_key = self
+            .settings()
+            .get_rescue_file_key(use_key, node.id)
+            .await?;
+        let plain_key = DracoonCrypto::decrypt_file_key(file_key, keypair.clone())?;
+
+        // wait for a free transfer slot (released when the download is finished)
+        let _permit = self.client.acquire_transfer_permit().await;
+
         let download_url_response = self.get_download_url(node.id).await?;
 
-        match node.is_encrypted {
-            Some(encrypted) => {
-                if encrypted {
-                    self.download_encrypted(
-                        &download_url_response.download_url,
-                        node.id,
-                        writer,
-                        node.size,
-                        callback,
-                    )
-                    .await
-                } else {
-                    self.download_unencrypted(
-                        &download_url_response.download_url,
-                        writer,
-                        node.size,
-                        callback,
-                    )
-                    .await
-                }
-            }
-            None => {
-                self.download_unencrypted(
-                    &download_url_response.download_url,
-                    writer,
-                    node.size,
-                    callback,
-                )
+        self.download_with_file_key(
+            &download_url_response.download_url,
+            plain_key,
+            writer,
+            node.size,
+            0,
+            callback,
+            None,
+            &CancellationToken::new(),
+        )
+        .await
+    }
+
+    async fn download_with_cancellation<'w>(
+        &'w self,
+        node: &Node,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        callback: Option<DownloadProgressCallback>,
+        cancellation_token: CancellationToken,
+    ) -> Result<(), DracoonClientError> {
+        let result = self
+            .download_node(node, 0, writer, callback, None, &cancellation_token)
+            .await;
+
+        // flush partial data
+        if let Err(DracoonClientError::Cancelled) = result {
+            debug!("Download of node {} cancelled", node.id);
+            writer.flush().await.or(Err(DracoonClientError::IoError))?;
+        }
+
+        result
+    }
+
+    async fn download_stream(&self, node: &Node) -> Result<DownloadStream, DracoonClientError> {
+        self.download_chunks(node, None).await
+    }
+
+    async fn download_reader(
+        &self,
+        node: &Node,
+    ) -> Result<Pin<Box<dyn AsyncRead + Send>>, DracoonClientError> {
+        let stream = self
+            .download_stream(node)
+            .await?
+            .map_err(|err| std::io::Error::other(err.to_string()));
+
+        Ok(Box::pin(StreamReader::new(stream)))
+    }
+
+    async fn download_zip<'w>(
+        &'w self,
+        req: CreateZipDownloadRequest,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        mut callback: Option<DownloadProgressCallback>,
+    ) -> Result<(), DracoonClientError> {
+        let _permit = self.client.acquire_transfer_permit().await;
+
+        let response = self.get_zip_download(req).await?;
+
+        // archive size is unknown if the archive is not created yet
+        let total = response.content_length().unwrap_or(0);
+        let mut progress = ProgressTracker::new();
+
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk) = stream.try_next().await? {
+            writer
+                .write_all(&chunk)
                 .await
+                .or(Err(DracoonClientError::IoError))?;
+
+            if let Some(ref mut callback) = callback {
+                callback(progress.update(chunk.len() as u64, total));
+            }
+        }
+
+        writer.flush().await.or(Err(DracoonClientError::IoError))?;
+
+        Ok(())
+    }
+
+    async fn download_zip_stream(
+        &self,
+        req: CreateZipDownloadRequest,
+    ) -> Result<DownloadStream, DracoonClientError> {
+        let permit = self.client.acquire_transfer_permit().await;
+
+        let response = self.get_zip_download(req).await?;
+
+        Ok(Box::pin(hold_permit(
+            response.bytes_stream().map_err(DracoonClientError::from),
+            permit,
+        )))
+    }
+
+    async fn download_to_path(
+        &self,
+        node: &Node,
+        path: &Path,
+        callback: Option<DownloadProgressCallback>,
+    ) -> Result<PathBuf, DracoonClientError> {
+        let target_path = if path.is_dir() {
+            path.join(&node.name)
+        } else {
+            path.to_path_buf()
+        };
+
+        let file = tokio::fs::File::create(&target_path).await.map_err(|err| {
+            error!("Error creating file {}: {}", target_path.display(), err);
+            DracoonClientError::IoError
+        })?;
+
+        let mut writer = BufWriter::with_capacity(DOWNLOAD_BUFFER_SIZE, file);
+
+        let result = async {
+            self.download(node, &mut writer, callback).await?;
+            writer.flush().await.or(Err(DracoonClientError::IoError))?;
+
+            if let Some(timestamp) = node.timestamp_modification {
+                let file = writer.into_inner().into_std().await;
+                file.set_modified(SystemTime::from(timestamp))
+                    .or(Err(DracoonClientError::IoError))?;
+            }
+
+            Ok(())
+        }
+        .await;
+
+        // remove partial file
+        if let Err(err) = result {
+            error!("Error downloading node {}: {}", node.id, err);
+            let _ = tokio::fs::remove_file(&target_path).await;
+            return Err(err);
+        }
+
+        Ok(target_path)
+    }
+
+    async fn get_download_url(
+        &self,
+        node_id: u64,
+    ) -> Result<DownloadUrlResponse, DracoonClientError> {
+        let url_part = format!(
+            "{DRACOON_API_PREFIX}/{NODES_BASE}/{FILES_BASE}/{node_id}/{NODES_DOWNLOAD_URL}"
+        );
+
+        let api_url = self.build_api_url(&url_part);
+
+        let response = self
+            .client
+            .http
+            .post(api_url)
+            .header(header::AUTHORIZATION, self.get_auth_header().await?)
+            .header(header::CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        DownloadUrlResponse::from_response(response).await
+    }
+
+    async fn download_many(
+        &self,
+        requests: Vec<DownloadRequest>,
+        concurrency: usize,
+        callback: Option<DownloadProgressCallback>,
+    ) -> Vec<Result<PathBuf, DracoonClientError>> {
+        let total_size = requests
+            .iter()
+            .map(|req| req.node.size.unwrap_or(0))
+            .sum::<u64>();
+
+        // aggregates the progress of all downloads
+        let progress =
+            callback.map(|callback| Arc::new(Mutex::new((callback, ProgressTracker::new()))));
+
+        stream::iter(requests)
+            .map(|req| {
+                let file_callback = progress.clone().map(|progress| {
+                    Box::new(move |file_progress: TransferProgress| {
+                        let mut guard = progress.lock().unwrap();
+                        let (callback, tracker) = &mut *guard;
+                        callback(tracker.update(file_progress.bytes_transferred, total_size));
+                    }) as DownloadProgressCallback
+                });
+
+                async move {
+                    self.download_to_path(&req.node, &req.target, file_callback)
+                        .await
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    async fn download_if_modified<'w>(
+        &'w self,
+        node: &Node,
+        condition: DownloadCondition,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        callback: Option<DownloadProgressCallback>,
+    ) -> Result<DownloadOutcome, DracoonClientError> {
+        let unchanged = match (condition.is_unchanged(node), condition.etag()) {
+            (Some(unchanged), _) => unchanged,
+            (None, Some(known_etag)) => {
+                let url = self.get_download_url(node.id).await?.download_url;
+                self.get_etag(&url)
+                    .await?
+                    .is_some_and(|etag| etag.trim_matches('"') == known_etag.trim_matches('"'))
             }
+            (None, None) => false,
+        };
+
+        if unchanged {
+            debug!("Node {} not modified - skipping download", node.id);
+            return Ok(DownloadOutcome::NotModified);
         }
+
+        self.download(node, writer, callback).await?;
+
+        Ok(DownloadOutcome::Downloaded)
+    }
+
+    fn download_with_events<'w>(
+        &'w self,
+        node: &'w Node,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+    ) -> (TransferFuture<'w>, TransferEventStream) {
+        let (events, mut receiver) = mpsc::unbounded_channel();
+
+        let progress_events = events.clone();
+        let callback: DownloadProgressCallback = Box::new(move |progress| {
+            let _ = progress_events.send(TransferEvent::Progressed(progress));
+        });
+
+        // retries are reported from within the download (see retry_or_fail)
+        let download = async move {
+            let _ = events.send(TransferEvent::Started {
+                total: node.size.unwrap_or(0),
+            });
+
+            self.download_node(
+                node,
+                0,
+                writer,
+                Some(callback),
+                Some(&events),
+                &CancellationToken::new(),
+            )
+            .await?;
+
+            let _ = events.send(TransferEvent::Finished);
+
+            Ok::<(), DracoonClientError>(())
+        };
+
+        // the stream ends when all senders are dropped (download finished or failed)
+        let event_stream = async_stream::stream! {
+            while let Some(event) = receiver.recv().await {
+                yield event;
+            }
+        };
+
+        (Box::pin(download), Box::pin(event_stream))
     }
 }
 
 #[async_trait]
-trait DownloadInternal {
-    async fn get_download_url(
+pub(crate) trait DownloadInternal {
+    async fn download_node(
+        &self,
+        node: &Node,
+        offset: u64,
+        writer: &mut (dyn AsyncWrite + Send + Unpin),
+        callback: Option<DownloadProgressCallback>,
+        events: Option<&UnboundedSender<TransferEvent>>,
+        cancellation_token: &CancellationToken,
+    ) -> Result<(), DracoonClientError>;
+
+    async fn download_file(
         &self,
         node_id: u64,
-    ) -> Result<DownloadUrlResponse, DracoonClientError>;
+        size: Option<u64>,
+        is_encrypted: bool,
+        offset: u64,
+        writer: &mut (dyn AsyncWrite + Send + Unpin),
+        callback: Option<DownloadProgressCallback>,
+        events: Option<&UnboundedSender<TransferEvent>>,
+        cancellation_token: &CancellationToken,
+    ) -> Result<(), DracoonClientError>;
+
+    /// returns the (decrypted) content as a stream of chunks - a paused download
+    /// waits before requesting the next chunk
+    async fn download_chunks(
+        &self,
+        node: &Node,
+        pause: Option<PauseSignal>,
+    ) -> Result<DownloadStream, DracoonClientError>;
 
     async fn get_file_key(&self, node_id: u64) -> Result<FileKey, DracoonClientError>;
 
+    async fn get_zip_download(
+        &self,
+        req: CreateZipDownloadRequest,
+    ) -> Result<Response, DracoonClientError>;
+
+    async fn get_content_length(
+        &self,
+        url: &str,
+        size: Option<u64>,
+    ) -> Result<u64, DracoonClientError>;
+
+    async fn get_etag(&self, url: &str) -> Result<Option<String>, DracoonClientError>;
+
     async fn download_unencrypted(
         &self,
         url: &str,
         writer: &mut (dyn AsyncWrite + Send + Unpin),
         size: Option<u64>,
+        offset: u64,
         mut callback: Option<DownloadProgressCallback>,
+        events: Option<&UnboundedSender<TransferEvent>>,
+        cancellation_token: &CancellationToken,
     ) -> Result<(), DracoonClientError>;
 
     async fn download_encrypted(
@@ -86,32 +455,126 @@ trait DownloadInternal {
         node_id: u64,
         writer: &mut (dyn AsyncWrite + Send + Unpin),
         size: Option<u64>,
+        offset: u64,
+        mut callback: Option<DownloadProgressCallback>,
+        events: Option<&UnboundedSender<TransferEvent>>,
+        cancellation_token: &CancellationToken,
+    ) -> Result<(), DracoonClientError>;
+
+    /// downloads and decrypts a file with the given (plain) file key
+    async fn download_with_file_key(
+        &self,
+        url: &str,
+        plain_key: PlainFileKey,
+        writer: &mut (dyn AsyncWrite + Send + Unpin),
+        size: Option<u64>,
+        offset: u64,
         mut callback: Option<DownloadProgressCallback>,
+        events: Option<&UnboundedSender<TransferEvent>>,
+        cancellation_token: &CancellationToken,
     ) -> Result<(), DracoonClientError>;
 }
 
 #[async_trait]
 impl DownloadInternal for Dracoon<Connected> {
-    async fn get_download_url(
+    async fn download_node(
+        &self,
+        node: &Node,
+        offset: u64,
+        writer: &mut (dyn AsyncWrite + Send + Unpin),
+        callback: Option<DownloadProgressCallback>,
+        events: Option<&UnboundedSender<TransferEvent>>,
+        cancellation_token: &CancellationToken,
+    ) -> Result<(), DracoonClientError> {
+        self.download_file(
+            node.id,
+            node.size,
+            node.is_encrypted == Some(true),
+            offset,
+            writer,
+            callback,
+            events,
+            cancellation_token,
+        )
+        .await
+    }
+
+    async fn download_file(
         &self,
         node_id: u64,
-    ) -> Result<DownloadUrlResponse, DracoonClientError> {
-        let url_part = format!(
-            "{DRACOON_API_PREFIX}/{NODES_BASE}/{FILES_BASE}/{node_id}/{NODES_DOWNLOAD_URL}"
-        );
+        size: Option<u64>,
+        is_encrypted: bool,
+        offset: u64,
+        writer: &mut (dyn AsyncWrite + Send + Unpin),
+        callback: Option<DownloadProgressCallback>,
+        events: Option<&UnboundedSender<TransferEvent>>,
+        cancellation_token: &CancellationToken,
+    ) -> Result<(), DracoonClientError> {
+        // wait for a free transfer slot (released when the download is finished)
+        let _permit = self.client.acquire_transfer_permit().await;
+
+        let download_url_response = self.get_download_url(node_id).await?;
+
+        if is_encrypted {
+            self.download_encrypted(
+                &download_url_response.download_url,
+                node_id,
+                writer,
+                size,
+                offset,
+                callback,
+                events,
+                cancellation_token,
+            )
+            .await
+        } else {
+            self.download_unencrypted(
+                &download_url_response.download_url,
+                writer,
+                size,
+                offset,
+                callback,
+                events,
+                cancellation_token,
+            )
+            .await
+        }
+    }
 
-        let api_url = self.build_api_url(&url_part);
+    async fn download_chunks(
+        &self,
+        node: &Node,
+        pause: Option<PauseSignal>,
+    ) -> Result<DownloadStream, DracoonClientError> {
+        let permit = self.client.acquire_transfer_permit().await;
 
-        let response = self
-            .client
-            .http
-            .post(api_url)
-            .header(header::AUTHORIZATION, self.get_auth_header().await?)
-            .header(header::CONTENT_TYPE, "application/json")
-            .send()
-            .await?;
+        let download_url_response = self.get_download_url(node.id).await?;
+        let url = download_url_response.download_url;
 
-        DownloadUrlResponse::from_response(response).await
+        let content_length = self.get_content_length(&url, node.size).await?;
+
+        let chunks = stream_chunks(
+            self.client.http.clone(),
+            url,
+            content_length,
+            *self.client.retry_policy(),
+            pause,
+        );
+
+        // encrypted files are decrypted chunk by chunk
+        if node.is_encrypted == Some(true) {
+            let file_key = self.get_file_key(node.id).await?;
+            let keypair = self.get_keypair(None).await?;
+            let plain_key = DracoonCrypto::decrypt_file_key(file_key, keypair)?;
+            let decrypter = ChunkedDecrypter::new(&plain_key)?;
+
+            return Ok(Box::pin(hold_permit(
+                decrypt_chunks(chunks, decrypter),
+                permit,
+            )));
+        }
+
+        Ok(Box::pin(hold_permit(chunks, permit)))
     }
 
     async fn download_unencrypted(
@@ -119,267 +582,1285 @@ impl DownloadInternal for Dracoon<Connected> {
         url: &str,
         writer: &mut (dyn AsyncWrite + Send + Unpin),
         size: Option<u64>,
+        offset: u64,
         mut callback: Option<DownloadProgressCallback>,
+        events: Option<&UnboundedSender<TransferEvent>>,
+        cancellation_token: &CancellationToken,
     ) -> Result<(), DracoonClientError> {
-        // get content length from header
-        let content_length = self
-            .client
-            .http
-            .head(url)
-            .send()
-            .await
-            .map_err(|err| {
-                debug!("Error while getting content length: {}", err);
-                err
-            })?
-            .headers()
-            .get(CONTENT_LENGTH)
-            .and_then(|val| val.to_str().ok())
-            .and_then(|val| val.parse().ok())
-            .unwrap_or(0);
+        let content_length = self.get_content_length(url, size).await?;
 
-        // if size is given, use it
-        let content_length = size.unwrap_or(content_length);
+        // offset (in bytes) - resume after already downloaded bytes
+        let mut downloaded_bytes = offset;
+
+        debug!("Content length: {}", content_length);
+        debug!("Starting at offset: {}", offset);
+
+        let mut progress = ProgressTracker::with_offset(offset);
+
+        let mut retries = 0u32;
+
+        // loop until all bytes are downloaded
+        while downloaded_bytes < content_length {
+            // calculate range
+            let start = downloaded_bytes;
+            let end = min(start + DEFAULT_CHUNK_SIZE as u64 - 1, content_length - 1);
+            let range = format!("bytes={start}-{end}");
+
+            // get chunk (failed ranges are retried)
+            let response = match get_range(&self.client.http, url, range).await {
+                Ok(response) => response,
+                Err(err) => {
+                    retry_or_fail(self.client.retry_policy(), err, events, &mut retries).await?;
+                    continue;
+                }
+            };
+
+            // write chunk to writer
+            let mut stream = response.bytes_stream();
+
+            loop {
+                let chunk = match next_chunk(&mut stream, cancellation_token).await {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => break,
+                    // retry the remaining range
+                    Err(err) => {
+                        retry_or_fail(self.client.retry_policy(), err, events, &mut retries)
+                            .await?;
+                        break;
+                    }
+                };
+                retries = 0;
+
+                let len = chunk.len() as u64;
+                writer
+                    .write_all(&chunk)
+                    .await
+                    .or(Err(DracoonClientError::IoError))?;
+                downloaded_bytes += len;
+
+                // call progress callback if provided
+                if let Some(ref mut callback) = callback {
+                    callback(progress.update(len, content_length));
+                }
+                if downloaded_bytes >= content_length {
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn download_encrypted(
+        &self,
+        url: &str,
+        node_id: u64,
+        writer: &mut (dyn AsyncWrite + Send + Unpin),
+        size: Option<u64>,
+        offset: u64,
+        callback: Option<DownloadProgressCallback>,
+        events: Option<&UnboundedSender<TransferEvent>>,
+        cancellation_token: &CancellationToken,
+    ) -> Result<(), DracoonClientError> {
+        // get file key
+        let file_key = self.get_file_key(node_id).await?;
+
+        let keypair = self.get_keypair(None).await?;
+
+        let plain_key = DracoonCrypto::decrypt_file_key(file_key, keypair)?;
+
+        self.download_with_file_key(
+            url,
+            plain_key,
+            writer,
+            size,
+            offset,
+            callback,
+            events,
+            cancellation_token,
+        )
+        .await
+    }
+
+    async fn download_with_file_key(
+        &self,
+        url: &str,
+        plain_key: PlainFileKey,
+        writer: &mut (dyn AsyncWrite + Send + Unpin),
+        size: Option<u64>,
+        offset: u64,
+        mut callback: Option<DownloadProgressCallback>,
+        events: Option<&UnboundedSender<TransferEvent>>,
+        cancellation_token: &CancellationToken,
+    ) -> Result<(), DracoonClientError> {
+        let content_length = self.get_content_length(url, size).await?;
+
+        let mut progress = ProgressTracker::new();
+
+        // decrypt chunk by chunk - the authentication tag is verified when finalizing
+        let mut decrypter = ChunkedDecrypter::new(&plain_key)?;
+
+        // offset (in bytes)
+        let mut downloaded_bytes = 0u64;
+
+        // decrypted bytes (to skip already written bytes when resuming)
+        let mut decrypted_bytes = 0u64;
+
+        debug!("Content length: {}", content_length);
+
+        let mut retries = 0u32;
+
+        // loop until all bytes are downloaded
+        while downloaded_bytes < content_length {
+            // calculate range
+            let start = downloaded_bytes;
+            let end = min(start + DEFAULT_CHUNK_SIZE as u64 - 1, content_length - 1);
+            let range = format!("bytes={start}-{end}");
+
+            // get chunk (failed ranges are retried)
+            let response = match get_range(&self.client.http, url, range).await {
+                Ok(response) => response,
+                Err(err) => {
+                    retry_or_fail(self.client.retry_policy(), err, events, &mut retries).await?;
+                    continue;
+                }
+            };
+
+            // decrypt chunk and write to writer
+            let mut stream = response.bytes_stream();
+
+            loop {
+                let chunk = match next_chunk(&mut stream, cancellation_token).await {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => break,
+                    // retry the remaining range
+                    Err(err) => {
+                        retry_or_fail(self.client.retry_policy(), err, events, &mut retries)
+                            .await?;
+                        break;
+                    }
+                };
+                retries = 0;
+
+                let len = chunk.len() as u64;
+
+                let plain = decrypter.update(&chunk)?;
+                write_plain_bytes(writer, &plain, &mut decrypted_bytes, offset).await?;
+                downloaded_bytes += len;
+
+                // call progress callback if provided
+                if let Some(ref mut callback) = callback {
+                    callback(progress.update(len, content_length));
+                }
+                if downloaded_bytes >= content_length {
+                    break;
+                }
+            }
+        }
+
+        let plain = decrypter.finalize()?;
+        write_plain_bytes(writer, &plain, &mut decrypted_bytes, offset).await?;
+
+        Ok(())
+    }
+
+    async fn get_zip_download(
+        &self,
+        req: CreateZipDownloadRequest,
+    ) -> Result<Response, DracoonClientError> {
+        let url_part = format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{NODES_ZIP}");
+
+        let api_url = self.build_api_url(&url_part);
+
+        let response = self
+            .client
+            .http
+            .post(api_url)
+            .header(header::AUTHORIZATION, self.get_auth_header().await?)
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&req)
+            .send()
+            .await?;
+
+        let download_url_response = DownloadUrlResponse::from_response(response).await?;
+
+        // the archive is streamed - no ranged requests
+        let response = self
+            .client
+            .http
+            .get(download_url_response.download_url)
+            .send()
+            .await?;
+
+        if response.error_for_status_ref().is_err() {
+            return Err(build_s3_error(response).await);
+        }
+
+        Ok(response)
+    }
+
+    async fn get_content_length(
+        &self,
+        url: &str,
+        size: Option<u64>,
+    ) -> Result<u64, DracoonClientError> {
+        // get content length from header
+        let content_length = self
+            .client
+            .http
+            .head(url)
+            .send()
+            .await
+            .map_err(|err| {
+                debug!("Error while getting content length: {}", err);
+                err
+            })?
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|val| val.to_str().ok())
+            .and_then(|val| val.parse().ok())
+            .unwrap_or(0);
+
+        // if size is given, use it
+        Ok(size.unwrap_or(content_length))
+    }
+
+    async fn get_etag(&self, url: &str) -> Result<Option<String>, DracoonClientError> {
+        let response = self.client.http.head(url).send().await?;
+
+        if response.error_for_status_ref().is_err() {
+            return Err(build_s3_error(response).await);
+        }
+
+        Ok(response
+            .headers()
+            .get(ETAG)
+            .and_then(|val| val.to_str().ok())
+            .map(ToString::to_string))
+    }
+
+    async fn get_file_key(&self, node_id: u64) -> Result<FileKey, DracoonClientError> {
+        let url_part =
+            format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{FILES_BASE}/{node_id}/{FILES_FILE_KEY}");
+
+        let response = self
+            .client
+            .http
+            .get(self.build_api_url(&url_part))
+            .header(header::AUTHORIZATION, self.get_auth_header().await?)
+            .send()
+            .await?;
+
+        FileKey::from_response(response).await
+    }
+}
+
+/// Writes decrypted bytes to the writer - bytes before the offset are skipped
+/// (encrypted files can only be decrypted from the start)
+async fn write_plain_bytes(
+    writer: &mut (dyn AsyncWrite + Send + Unpin),
+    plain: &[u8],
+    decrypted_bytes: &mut u64,
+    offset: u64,
+) -> Result<(), DracoonClientError> {
+    // truncation is safe: the skipped bytes are limited by the chunk length
+    #[allow(clippy::cast_possible_truncation)]
+    let skip = min(offset.saturating_sub(*decrypted_bytes), plain.len() as u64) as usize;
+    *decrypted_bytes += plain.len() as u64;
+
+    writer
+        .write_all(&plain[skip..])
+        .await
+        .or(Err(DracoonClientError::IoError))
+}
+
+/// Requests a range of a file - returns an error for failed requests (S3 error)
+async fn get_range(
+    http: &ClientWithMiddleware,
+    url: &str,
+    range: String,
+) -> Result<Response, DracoonClientError> {
+    let response = http
+        .get(url)
+        .header(RANGE, range)
+        .send()
+        .await
+        .map_err(|err| {
+            error!("Error while downloading chunk: {}", err);
+            err
+        })?;
+
+    if response.error_for_status_ref().is_err() {
+        return Err(build_s3_error(response).await);
+    }
+
+    Ok(response)
+}
+
+/// Waits for the next retry if the error is transient and the retry policy allows another retry.
+/// Returns the error otherwise.
+async fn retry_or_fail(
+    retry_policy: &ExponentialBackoff,
+    err: DracoonClientError,
+    events: Option<&UnboundedSender<TransferEvent>>,
+    retries: &mut u32,
+) -> Result<(), DracoonClientError> {
+    if !is_transient(&err) {
+        return Err(err);
+    }
+
+    let delay = next_retry_delay(retry_policy, err, retries)?;
+    if let Some(events) = events {
+        let _ = events.send(TransferEvent::ChunkRetried {
+            retry: *retries,
+            delay,
+        });
+    }
+    tokio::time::sleep(delay).await;
+
+    Ok(())
+}
+
+/// Returns the next chunk of a response stream or an error if the transfer is cancelled
+async fn next_chunk<S>(
+    stream: &mut S,
+    cancellation_token: &CancellationToken,
+) -> Result<Option<Bytes>, DracoonClientError>
+where
+    S: Stream<Item = Result<Bytes, reqwest::Error>> + Unpin + Send,
+{
+    tokio::select! {
+        biased;
+        () = cancellation_token.cancelled() => Err(DracoonClientError::Cancelled),
+        chunk = stream.try_next() => Ok(chunk?),
+    }
+}
+
+/// Streams the content of a (plain) file in ranged chunks
+fn stream_chunks(
+    http: ClientWithMiddleware,
+    url: String,
+    content_length: u64,
+    retry_policy: ExponentialBackoff,
+    pause: Option<PauseSignal>,
+) -> impl Stream<Item = Result<Bytes, DracoonClientError>> + Send {
+    try_stream! {
+        let mut downloaded_bytes = 0u64;
+        let mut retries = 0u32;
+
+        while downloaded_bytes < content_length {
+            // pause between chunks - no request is in flight while paused
+            if let Some(pause) = &pause {
+                pause.wait_while_paused().await;
+            }
+
+            let start = downloaded_bytes;
+            let end = min(start + DEFAULT_CHUNK_SIZE as u64 - 1, content_length - 1);
+            let range = format!("bytes={start}-{end}");
+
+            let response = match get_range(&http, url.as_str(), range).await {
+                Ok(response) => response,
+                Err(err) => {
+                    retry_or_fail(&retry_policy, err, None, &mut retries).await?;
+                    continue;
+                }
+            };
+
+            let mut chunks = response.bytes_stream();
+            let mut interrupted = false;
+
+            loop {
+                let chunk = match chunks.try_next().await {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => break,
+                    // retry the remaining range
+                    Err(err) => {
+                        retry_or_fail(&retry_policy, err.into(), None, &mut retries).await?;
+                        interrupted = true;
+                        break;
+                    }
+                };
+                retries = 0;
+                downloaded_bytes += chunk.len() as u64;
+                yield chunk;
+            }
+
+            // guard against empty responses
+            if downloaded_bytes <= start && !interrupted {
+                Err::<(), _>(DracoonClientError::IoError)?;
+            }
+        }
+    }
+}
+
+/// Keeps the transfer permit (if any) until the stream is dropped
+fn hold_permit(
+    chunks: impl Stream<Item = Result<Bytes, DracoonClientError>> + Send,
+    permit: Option<OwnedSemaphorePermit>,
+) -> impl Stream<Item = Result<Bytes, DracoonClientError>> + Send {
+    chunks.map(move |chunk| {
+        let _ = &permit;
+        chunk
+    })
+}
+
+/// Decrypts a stream of encrypted chunks - the last item is an error if the authentication fails
+fn decrypt_chunks(
+    chunks: impl Stream<Item = Result<Bytes, DracoonClientError>> + Send,
+    mut decrypter: ChunkedDecrypter,
+) -> impl Stream<Item = Result<Bytes, DracoonClientError>> + Send {
+    try_stream! {
+        futures_util::pin_mut!(chunks);
+
+        while let Some(chunk) = chunks.try_next().await? {
+            yield Bytes::from(decrypter.update(&chunk)?);
+        }
+
+        yield Bytes::from(decrypter.finalize()?);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // separate from test folder due to internal trait (DownloadInternal)
+
+    use dco3_crypto::Encrypt;
+
+    use std::time::Duration;
+
+    use super::*;
+
+    use crate::{nodes::DeletedNodeVersionsList, tests::dracoon::get_connected_client};
+
+    #[tokio::test]
+    async fn test_get_download_url() {
+        let download_url_str = "https://test.dracoon.com/not/real/download_url";
+
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let download_url_res = include_str!("../tests/responses/download/download_url_ok.json");
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/1234/downloads")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res)
+            .create();
+
+        let download_url = dracoon.get_download_url(1234).await.unwrap();
+
+        download_url_mock.assert();
+
+        assert_eq!(download_url.download_url, download_url_str);
+    }
+
+    #[test]
+    fn test_download_url_expires_at() {
+        let download_url = DownloadUrlResponse {
+            download_url: "https://s3.example.com/bucket/file?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Date=20240101T120000Z&X-Amz-Expires=3600&X-Amz-Signature=abc".to_string(),
+        };
+
+        let expires_at = download_url.expires_at().unwrap();
+
+        assert_eq!(expires_at.to_rfc3339(), "2024-01-01T13:00:00+00:00");
+    }
+
+    #[test]
+    fn test_download_url_expires_at_unknown() {
+        let download_url = DownloadUrlResponse {
+            download_url: "https://test.dracoon.com/not/real/download_url".to_string(),
+        };
+
+        assert!(download_url.expires_at().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_file_key() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let file_key_res = include_str!("../tests/responses/download/file_key_ok.json");
+
+        let file_key_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/1234/user_file_key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(file_key_res)
+            .create();
+
+        let file_key = dracoon.get_file_key(1234).await.unwrap();
+
+        file_key_mock.assert();
+
+        assert_eq!(file_key.key, "string");
+        assert_eq!(file_key.iv, "string");
+        assert!(file_key.tag.is_some());
+        assert_eq!(file_key.tag.unwrap(), "string");
+        // TODO: implement PartialEq for FileKeyVersion
+        // assert_eq!(file_key.version, FileKeyVersion::RSA4096_AES256GCM);
+    }
+
+    #[tokio::test]
+    async fn test_download_unencrypted() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
+
+        // create bytes for mocking byte response
+        let mock_bytes: [u8; 16] = [
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(mock_bytes)
+            .create();
+
+        let download_url = format!("{}some/download/url", dracoon.get_base_url());
+
+        let buffer = Vec::with_capacity(16);
+
+        let mut writer = tokio::io::BufWriter::new(buffer);
+
+        dracoon
+            .download_unencrypted(
+                &download_url,
+                &mut writer,
+                Some(16),
+                0,
+                None,
+                None,
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+
+        content_length_mock.assert();
+
+        download_mock.assert();
+
+        assert_eq!(writer.buffer(), mock_bytes.to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_download_unencrypted_from_offset() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
+
+        let mock_bytes: [u8; 8] = [99, 111, 222, 255, 0, 12, 33, 44];
+
+        let download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .match_header("range", "bytes=8-15")
+            .with_status(206)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(mock_bytes)
+            .create();
+
+        let download_url = format!("{}some/download/url", dracoon.get_base_url());
+
+        let buffer = Vec::with_capacity(8);
+
+        let mut writer = tokio::io::BufWriter::new(buffer);
+
+        dracoon
+            .download_unencrypted(
+                &download_url,
+                &mut writer,
+                Some(16),
+                8,
+                None,
+                None,
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+
+        content_length_mock.assert();
+
+        download_mock.assert();
+
+        assert_eq!(writer.buffer(), mock_bytes.to_vec());
+    }
+
+    fn file_node(node: &str) -> Node {
+        serde_json::from_str(node).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_download_to_path() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let node = file_node(include_str!(
+            "../tests/responses/download/file_node_4711_ok.json"
+        ));
+
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", dracoon.get_base_url())
+        });
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4711/downloads")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res.to_string())
+            .create();
+
+        let content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
+
+        let mock_bytes: [u8; 16] = [
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(mock_bytes)
+            .create();
+
+        let path = dracoon
+            .download_to_path(&node, &std::env::temp_dir(), None)
+            .await
+            .unwrap();
+
+        download_url_mock.assert();
+        content_length_mock.assert();
+        download_mock.assert();
+
+        assert_eq!(path, std::env::temp_dir().join(&node.name));
+
+        let content = tokio::fs::read(&path).await.unwrap();
+        assert_eq!(content, mock_bytes.to_vec());
+
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        assert_eq!(
+            modified,
+            SystemTime::from(node.timestamp_modification.unwrap())
+        );
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_download_version() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let versions: DeletedNodeVersionsList = serde_json::from_str(include_str!(
+            "../tests/responses/nodes/file_versions_ok.json"
+        ))
+        .unwrap();
+        let version = versions.items.first().unwrap();
+
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", dracoon.get_base_url())
+        });
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/42/downloads")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res.to_string())
+            .create();
+
+        let content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
+
+        let mock_bytes: [u8; 16] = [
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(mock_bytes)
+            .create();
+
+        let mut writer = tokio::io::BufWriter::new(Vec::with_capacity(16));
+
+        dracoon
+            .download_version(version, &mut writer, None)
+            .await
+            .unwrap();
+
+        download_url_mock.assert();
+        content_length_mock.assert();
+        download_mock.assert();
+
+        writer.flush().await.unwrap();
+        assert_eq!(writer.get_ref(), &mock_bytes.to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_download_reader() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let node = file_node(include_str!(
+            "../tests/responses/download/file_node_4713_ok.json"
+        ));
+
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", dracoon.get_base_url())
+        });
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4713/downloads")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res.to_string())
+            .create();
+
+        let content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
+
+        let mock_bytes: [u8; 16] = [
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .match_header("range", "bytes=0-15")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(mock_bytes)
+            .create();
+
+        let mut reader = dracoon.download_reader(&node).await.unwrap();
+
+        let mut content = Vec::new();
+        tokio::io::copy(&mut reader, &mut content).await.unwrap();
+
+        download_url_mock.assert();
+        content_length_mock.assert();
+        download_mock.assert();
+
+        assert_eq!(content, mock_bytes.to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_download_with_rescue_keypair() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let mut node = file_node(include_str!(
+            "../tests/responses/download/file_node_4715_ok.json"
+        ));
+        node.is_encrypted = Some(true);
+
+        let mock_bytes: [u8; 16] = [
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+        let mock_bytes_encrypted = DracoonCrypto::encrypt(mock_bytes).unwrap();
+        let plain_key = mock_bytes_encrypted.1.clone();
+
+        let rescue_keypair =
+            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
+                .unwrap();
+        let rescue_file_key =
+            DracoonCrypto::encrypt_file_key(plain_key, rescue_keypair.clone()).unwrap();
+
+        let missing_keys_res = serde_json::json!({
+            "range": { "offset": 0, "limit": 100, "total": 1 },
+            "items": [],
+            "users": [],
+            "files": [{ "id": 4715, "fileKeyContainer": rescue_file_key }]
+        });
+
+        let missing_keys_mock = mock_server
+            .mock("GET", "/api/v4/nodes/missingFileKeys")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("use_key".into(), "room_rescue_key".into()),
+                mockito::Matcher::UrlEncoded("file_id".into(), "4715".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(missing_keys_res.to_string())
+            .create();
+
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", dracoon.get_base_url())
+        });
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4715/downloads")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res.to_string())
+            .create();
+
+        let content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
+
+        let download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .match_header("range", "bytes=0-15")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(&mock_bytes_encrypted.0)
+            .create();
+
+        // the user file key is not used
+        let file_key_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/4715/user_file_key")
+            .expect(0)
+            .create();
+
+        let mut writer = tokio::io::BufWriter::new(Vec::new());
+
+        dracoon
+            .download_with_rescue_keypair(
+                &node,
+                &rescue_keypair,
+                UseKey::RoomRescueKey,
+                &mut writer,
+                None,
+            )
+            .await
+            .unwrap();
+
+        missing_keys_mock.assert();
+        download_url_mock.assert();
+        content_length_mock.assert();
+        download_mock.assert();
+        file_key_mock.assert();
+
+        writer.flush().await.unwrap();
+        assert_eq!(writer.get_ref(), &mock_bytes.to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_download_stream_encrypted() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let mut node = file_node(include_str!(
+            "../tests/responses/download/file_node_4714_ok.json"
+        ));
+        node.is_encrypted = Some(true);
+
+        let mock_bytes: [u8; 16] = [
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+        let mock_bytes_encrypted = DracoonCrypto::encrypt(mock_bytes).unwrap();
+        let plain_key = mock_bytes_encrypted.1.clone();
+
+        let keypair =
+            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
+                .unwrap();
+        let enc_keypair =
+            DracoonCrypto::encrypt_private_key("TopSecret1234!", keypair.clone()).unwrap();
+        let file_key = DracoonCrypto::encrypt_file_key(plain_key, keypair).unwrap();
+
+        let keypair_mock = mock_server
+            .mock("GET", "/api/v4/user/account/keypair")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&enc_keypair).unwrap())
+            .create();
+
+        let _kp = dracoon
+            .get_keypair(Some("TopSecret1234!".into()))
+            .await
+            .unwrap();
+
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", dracoon.get_base_url())
+        });
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4714/downloads")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res.to_string())
+            .create();
+
+        let content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
+
+        let file_key_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/4714/user_file_key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&file_key).unwrap())
+            .create();
+
+        let download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .match_header("range", "bytes=0-15")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(&mock_bytes_encrypted.0)
+            .create();
+
+        let chunks = dracoon
+            .download_stream(&node)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        keypair_mock.assert();
+        download_url_mock.assert();
+        content_length_mock.assert();
+        file_key_mock.assert();
+        download_mock.assert();
+
+        assert_eq!(chunks.concat(), mock_bytes.to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_download_with_cancellation() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let node = file_node(include_str!(
+            "../tests/responses/download/file_node_4714_ok.json"
+        ));
+
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", dracoon.get_base_url())
+        });
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4714/downloads")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res.to_string())
+            .create();
+
+        let _content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
+
+        let _download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body([0u8; 16])
+            .create();
 
-        // offset (in bytes)
-        let mut downloaded_bytes = 0u64;
+        let token = CancellationToken::new();
+        token.cancel();
 
-        debug!("Content length: {}", content_length);
+        let mut writer = tokio::io::BufWriter::new(Vec::new());
 
-        // loop until all bytes are downloaded
-        while downloaded_bytes < content_length {
-            // calculate range
-            let start = downloaded_bytes;
-            let end = min(start + DEFAULT_CHUNK_SIZE as u64 - 1, content_length - 1);
-            let range = format!("bytes={start}-{end}");
+        let res = dracoon
+            .download_with_cancellation(&node, &mut writer, None, token)
+            .await;
 
-            // get chunk
-            let response = self
-                .client
-                .http
-                .get(url)
-                .header(RANGE, range)
-                .send()
-                .await
-                .map_err(|err| {
-                    error!("Error while downloading chunk: {}", err);
-                    err
-                })?;
-
-            // handle error
-            if response.error_for_status_ref().is_err() {
-                let error = build_s3_error(response).await;
-                return Err(error);
-            }
+        download_url_mock.assert();
 
-            // write chunk to writer
-            let mut stream = response.bytes_stream();
+        assert_eq!(res.unwrap_err(), DracoonClientError::Cancelled);
+        assert!(writer.get_ref().is_empty());
+    }
 
-            while let Some(chunk) = stream.try_next().await? {
-                let len = chunk.len() as u64;
-                writer
-                    .write_all(&chunk)
-                    .await
-                    .or(Err(DracoonClientError::IoError))?;
-                downloaded_bytes += len;
+    #[tokio::test]
+    async fn test_download_zip() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
 
-                // call progress callback if provided
-                if let Some(ref mut callback) = callback {
-                    callback(len, content_length);
-                }
-                if downloaded_bytes >= content_length {
-                    break;
-                }
-            }
-        }
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/zip/url", dracoon.get_base_url())
+        });
 
-        Ok(())
-    }
+        let zip_mock = mock_server
+            .mock("POST", "/api/v4/nodes/zip")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "nodeIds": [1, 2, 3]
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res.to_string())
+            .create();
 
-    async fn download_encrypted(
-        &self,
-        url: &str,
-        node_id: u64,
-        writer: &mut (dyn AsyncWrite + Send + Unpin),
-        size: Option<u64>,
-        mut callback: Option<DownloadProgressCallback>,
-    ) -> Result<(), DracoonClientError> {
-        // get file key
-        let file_key = self.get_file_key(node_id).await?;
+        let mock_bytes = b"PK-not-a-real-archive".to_vec();
 
-        let keypair = self.get_keypair(None).await?;
+        let download_mock = mock_server
+            .mock("GET", "/some/zip/url")
+            .with_status(200)
+            .with_header("content-type", "application/zip")
+            .with_body(&mock_bytes)
+            .create();
 
-        let plain_key = DracoonCrypto::decrypt_file_key(file_key, keypair)?;
+        let mut writer = tokio::io::BufWriter::new(Vec::new());
 
-        // get content length from header
-        let content_length = self
-            .client
-            .http
-            .head(url)
-            .send()
+        dracoon
+            .download_zip(vec![1, 2, 3].into(), &mut writer, None)
             .await
-            .map_err(|err| {
-                debug!("Error while getting content length: {}", err);
-                err
-            })?
-            .headers()
-            .get(CONTENT_LENGTH)
-            .and_then(|val| val.to_str().ok())
-            .and_then(|val| val.parse().ok())
-            .unwrap_or(0);
+            .unwrap();
 
-        // if size is given, use it
-        let content_length = size.unwrap_or(content_length);
+        zip_mock.assert();
+        download_mock.assert();
 
-        // this is safe, because the maximum size of a file (encrypted) is 60 GB
-        #[allow(clippy::cast_possible_truncation)]
-        let mut buffer = vec![0u8; content_length as usize];
+        assert_eq!(writer.get_ref(), &mock_bytes);
+    }
 
-        let mut crypter = DracoonCrypto::decrypter(plain_key, &mut buffer)?;
+    #[tokio::test]
+    async fn test_download_many() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
 
-        // offset (in bytes)
-        let mut downloaded_bytes = 0u64;
+        let node = file_node(include_str!(
+            "../tests/responses/download/file_node_4713_ok.json"
+        ));
+        let failing_node = file_node(include_str!(
+            "../tests/responses/download/file_node_4714_ok.json"
+        ));
 
-        debug!("Content length: {}", content_length);
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", dracoon.get_base_url())
+        });
 
-        // loop until all bytes are downloaded
-        while downloaded_bytes < content_length {
-            // calculate range
-            let start = downloaded_bytes;
-            let end = min(start + DEFAULT_CHUNK_SIZE as u64 - 1, content_length - 1);
-            let range = format!("bytes={start}-{end}");
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4713/downloads")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res.to_string())
+            .create();
 
-            // get chunk
-            let response = self
-                .client
-                .http
-                .get(url)
-                .header(RANGE, range)
-                .send()
-                .await
-                .map_err(|err| {
-                    error!("Error while downloading chunk: {}", err);
-                    err
-                })?;
-
-            // handle error
-            if response.error_for_status_ref().is_err() {
-                let error = build_s3_error(response).await;
-                return Err(error);
-            }
+        let failing_download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4714/downloads")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"code":404,"message":"Not Found","debugInfo":"Node not found","errorCode":-41000}"#)
+            .create();
 
-            // write chunk to writer
-            let mut stream = response.bytes_stream();
+        let content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
 
-            while let Some(chunk) = stream.try_next().await? {
-                let len = chunk.len() as u64;
+        let mock_bytes: [u8; 16] = [
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
 
-                crypter.update(&chunk)?;
-                downloaded_bytes += len;
+        let download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(mock_bytes)
+            .create();
 
-                // call progress callback if provided
-                if let Some(ref mut callback) = callback {
-                    callback(len, content_length);
-                }
-                if downloaded_bytes >= content_length {
-                    break;
-                }
-            }
-        }
+        let bytes_done = Arc::new(Mutex::new((0u64, 0u64)));
+        let bytes_done_clone = bytes_done.clone();
 
-        crypter.finalize()?;
+        let requests = vec![
+            DownloadRequest::new(node.clone(), std::env::temp_dir()),
+            DownloadRequest::new(failing_node, std::env::temp_dir()),
+        ];
 
-        writer
-            .write_all(&buffer)
-            .await
-            .or(Err(DracoonClientError::IoError))?;
-        Ok(())
-    }
+        let results = dracoon
+            .download_many(
+                requests,
+                2,
+                Some(Box::new(move |progress| {
+                    *bytes_done_clone.lock().unwrap() = (progress.bytes_done, progress.total);
+                })),
+            )
+            .await;
 
-    async fn get_file_key(&self, node_id: u64) -> Result<FileKey, DracoonClientError> {
-        let url_part =
-            format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{FILES_BASE}/{node_id}/{FILES_FILE_KEY}");
+        download_url_mock.assert();
+        failing_download_url_mock.assert();
+        content_length_mock.assert();
+        download_mock.assert();
 
-        let response = self
-            .client
-            .http
-            .get(self.build_api_url(&url_part))
-            .header(header::AUTHORIZATION, self.get_auth_header().await?)
-            .send()
-            .await?;
+        assert_eq!(results.len(), 2);
 
-        FileKey::from_response(response).await
+        let path = results[0].as_ref().unwrap();
+        assert_eq!(path, &std::env::temp_dir().join(&node.name));
+        assert!(results[1].is_err());
+
+        assert_eq!(*bytes_done.lock().unwrap(), (16, 32));
+
+        tokio::fs::remove_file(path).await.unwrap();
     }
-}
 
-#[cfg(test)]
-mod tests {
-    // separate from test folder due to internal trait (DownloadInternal)
+    #[tokio::test]
+    async fn test_download_if_modified_unchanged_hash() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
 
-    use dco3_crypto::Encrypt;
+        let mut node = file_node(include_str!(
+            "../tests/responses/download/file_node_4715_ok.json"
+        ));
+        node.hash = Some("ABCDEF".to_string());
 
-    use super::*;
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4715/downloads")
+            .expect(0)
+            .create();
 
-    use crate::tests::dracoon::get_connected_client;
+        let mut writer = tokio::io::BufWriter::new(Vec::new());
 
-    #[tokio::test]
-    async fn test_get_download_url() {
-        let download_url_str = "https://test.dracoon.com/not/real/download_url";
+        let outcome = dracoon
+            .download_if_modified(
+                &node,
+                DownloadCondition::new().with_hash("abcdef"),
+                &mut writer,
+                None,
+            )
+            .await
+            .unwrap();
+
+        download_url_mock.assert();
+
+        assert!(outcome.is_not_modified());
+        assert!(writer.buffer().is_empty());
+    }
 
+    #[tokio::test]
+    async fn test_download_if_modified_unchanged_etag() {
         let (dracoon, mut mock_server) = get_connected_client().await;
 
-        let download_url_res = include_str!("../tests/responses/download/download_url_ok.json");
+        let mut node = file_node(include_str!(
+            "../tests/responses/download/file_node_4716_ok.json"
+        ));
+        node.hash = None;
+        node.updated_at = None;
+        node.timestamp_modification = None;
+
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", dracoon.get_base_url())
+        });
 
         let download_url_mock = mock_server
-            .mock("POST", "/api/v4/nodes/files/1234/downloads")
+            .mock("POST", "/api/v4/nodes/files/4716/downloads")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(download_url_res)
+            .with_body(download_url_res.to_string())
             .create();
 
-        let download_url = dracoon.get_download_url(1234).await.unwrap();
+        let etag_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("etag", "\"some-etag\"")
+            .create();
+
+        let mut writer = tokio::io::BufWriter::new(Vec::new());
+
+        let outcome = dracoon
+            .download_if_modified(
+                &node,
+                DownloadCondition::new().with_etag("some-etag"),
+                &mut writer,
+                None,
+            )
+            .await
+            .unwrap();
 
         download_url_mock.assert();
+        etag_mock.assert();
 
-        assert_eq!(download_url.download_url, download_url_str);
+        assert_eq!(outcome, DownloadOutcome::NotModified);
     }
 
     #[tokio::test]
-    async fn test_get_file_key() {
+    async fn test_download_if_modified_changed() {
         let (dracoon, mut mock_server) = get_connected_client().await;
 
-        let file_key_res = include_str!("../tests/responses/download/file_key_ok.json");
+        let mut node = file_node(include_str!(
+            "../tests/responses/download/file_node_4717_ok.json"
+        ));
+        node.hash = Some("new-hash".to_string());
 
-        let file_key_mock = mock_server
-            .mock("GET", "/api/v4/nodes/files/1234/user_file_key")
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", dracoon.get_base_url())
+        });
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4717/downloads")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(file_key_res)
+            .with_body(download_url_res.to_string())
             .create();
 
-        let file_key = dracoon.get_file_key(1234).await.unwrap();
+        let content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
 
-        file_key_mock.assert();
+        let mock_bytes: [u8; 16] = [
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
 
-        assert_eq!(file_key.key, "string");
-        assert_eq!(file_key.iv, "string");
-        assert!(file_key.tag.is_some());
-        assert_eq!(file_key.tag.unwrap(), "string");
-        // TODO: implement PartialEq for FileKeyVersion
-        // assert_eq!(file_key.version, FileKeyVersion::RSA4096_AES256GCM);
+        let download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(mock_bytes)
+            .create();
+
+        let mut writer = tokio::io::BufWriter::new(Vec::new());
+
+        let outcome = dracoon
+            .download_if_modified(
+                &node,
+                DownloadCondition::new().with_hash("old-hash"),
+                &mut writer,
+                None,
+            )
+            .await
+            .unwrap();
+
+        download_url_mock.assert();
+        content_length_mock.assert();
+        download_mock.assert();
+
+        assert_eq!(outcome, DownloadOutcome::Downloaded);
+        assert_eq!(writer.buffer(), mock_bytes.to_vec());
     }
 
     #[tokio::test]
-    async fn test_download_unencrypted() {
+    async fn test_download_with_events() {
         let (dracoon, mut mock_server) = get_connected_client().await;
 
+        let node = file_node(include_str!(
+            "../tests/responses/download/file_node_4718_ok.json"
+        ));
+
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", dracoon.get_base_url())
+        });
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4718/downloads")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res.to_string())
+            .create();
+
         let content_length_mock = mock_server
             .mock("HEAD", "/some/download/url")
             .with_status(200)
             .with_header("content-length", "16")
             .create();
 
-        // create bytes for mocking byte response
         let mock_bytes: [u8; 16] = [
             0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
         ];
@@ -391,24 +1872,58 @@ mod tests {
             .with_body(mock_bytes)
             .create();
 
-        let download_url = format!("{}some/download/url", dracoon.get_base_url());
+        let mut writer = tokio::io::BufWriter::new(Vec::new());
 
-        let buffer = Vec::with_capacity(16);
+        let (download, events) = dracoon.download_with_events(&node, &mut writer);
 
-        let mut writer = tokio::io::BufWriter::new(buffer);
+        let (result, events) = tokio::join!(download, events.collect::<Vec<_>>());
 
-        dracoon
-            .download_unencrypted(&download_url, &mut writer, Some(16), None)
-            .await
-            .unwrap();
+        result.unwrap();
 
+        download_url_mock.assert();
         content_length_mock.assert();
-
         download_mock.assert();
 
+        assert_eq!(events.first(), Some(&TransferEvent::Started { total: 16 }));
+        assert_eq!(events.last(), Some(&TransferEvent::Finished));
+
+        let bytes_done = events
+            .iter()
+            .filter_map(|event| match event {
+                TransferEvent::Progressed(progress) => Some(progress.bytes_done),
+                _ => None,
+            })
+            .last();
+
+        assert_eq!(bytes_done, Some(16));
         assert_eq!(writer.buffer(), mock_bytes.to_vec());
     }
 
+    #[tokio::test]
+    async fn test_download_to_path_removes_partial_file() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let node = file_node(include_str!(
+            "../tests/responses/download/file_node_4712_ok.json"
+        ));
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/4712/downloads")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"code":404,"message":"Not Found","debugInfo":"Node not found","errorCode":-41000}"#)
+            .create();
+
+        let path = std::env::temp_dir().join(&node.name);
+
+        let res = dracoon.download_to_path(&node, &path, None).await;
+
+        download_url_mock.assert();
+
+        assert!(res.is_err());
+        assert!(!path.exists());
+    }
+
     #[tokio::test]
     async fn test_download_encrypted() {
         let (dracoon, mut mock_server) = get_connected_client().await;
@@ -472,7 +1987,16 @@ mod tests {
         let mut writer = tokio::io::BufWriter::new(buffer);
 
         dracoon
-            .download_encrypted(&download_url, 1234, &mut writer, None, None)
+            .download_encrypted(
+                &download_url,
+                1234,
+                &mut writer,
+                None,
+                0,
+                None,
+                None,
+                &CancellationToken::new(),
+            )
             .await
             .unwrap();
 
@@ -521,7 +2045,16 @@ mod tests {
         let mut writer = tokio::io::BufWriter::new(buffer);
 
         let download_res = dracoon
-            .download_encrypted(&download_url, 1234, &mut writer, None, None)
+            .download_encrypted(
+                &download_url,
+                1234,
+                &mut writer,
+                None,
+                0,
+                None,
+                None,
+                &CancellationToken::new(),
+            )
             .await;
 
         assert!(download_res.is_err());
@@ -538,4 +2071,43 @@ mod tests {
     async fn test_download_full_encrypted_node() {
         todo!()
     }
+
+    #[tokio::test]
+    async fn test_retry_or_fail_transient_error() {
+        let retry_policy = ExponentialBackoff::builder()
+            .retry_bounds(Duration::from_millis(1), Duration::from_millis(2))
+            .build_with_max_retries(1);
+        let mut retries = 0;
+
+        let err = DracoonClientError::ConnectionFailed("Connection failed".into());
+        retry_or_fail(&retry_policy, err, None, &mut retries)
+            .await
+            .expect("should retry");
+
+        assert_eq!(retries, 1);
+
+        // no retries left
+        let err = DracoonClientError::ConnectionFailed("Connection failed".into());
+        let res = retry_or_fail(&retry_policy, err, None, &mut retries).await;
+
+        assert!(res.is_err());
+        assert_eq!(retries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_or_fail_non_transient_error() {
+        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
+        let mut retries = 0;
+
+        let res = retry_or_fail(
+            &retry_policy,
+            DracoonClientError::Cancelled,
+            None,
+            &mut retries,
+        )
+        .await;
+
+        assert!(res.unwrap_err().is_cancelled());
+        assert_eq!(retries, 0);
+    }
 }
@@ -1,15 +1,29 @@
 //! This module implements a subset of the nodes DRACOON API.
 //! Documentation can be found here: <https://download.dracoon.com/api/swagger-ui/index.html?configUrl=/api/spec_v4/swagger-config#/nodes>
-pub use self::{models::*, rooms::models::*};
-use super::{client::errors::DracoonClientError, models::ListAllParams};
+pub use self::{
+    crypto::{decrypt_reader, decrypt_reader_with_keypair},
+    models::*,
+    rooms::models::*,
+    transfers::{TransferManager, TransferManagerBuilder},
+};
+use super::{client::errors::DracoonClientError, config::S3TagList, models::ListAllParams};
 use async_trait::async_trait;
+use dco3_crypto::PlainUserKeyPairContainer;
+use std::{
+    path::{Path, PathBuf},
+    pin::Pin,
+};
 use tokio::io::{AsyncRead, AsyncWrite, BufReader};
+pub use tokio_util::sync::CancellationToken;
 
+pub(crate) mod crypto;
 pub mod download;
 pub mod folders;
 pub mod models;
 pub mod nodes;
 pub mod rooms;
+pub mod sync;
+pub mod transfers;
 pub mod upload;
 
 /// This trait provides methods to manage nodes.
@@ -178,6 +192,53 @@ pub trait Nodes {
     /// ```
 
     async fn delete_nodes(&self, req: DeleteNodesRequest) -> Result<(), DracoonClientError>;
+    /// Returns previous versions of a file (in the recycle bin) by parent id and file name.
+    /// A version can be downloaded with [Download::download_version].
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Nodes};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let versions = dracoon.nodes().get_file_versions(123, "test.txt", None).await.unwrap();
+    /// # }
+    /// ```
+    async fn get_file_versions(
+        &self,
+        parent_id: u64,
+        name: &str,
+        params: Option<ListAllParams>,
+    ) -> Result<DeletedNodeVersionsList, DracoonClientError>;
+    /// Permanently deletes selected deleted nodes (versions in the recycle bin) by ids.
+    /// Large lists of ids are split into multiple requests.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Nodes};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let deleted_node_ids = vec![123, 456];
+    /// dracoon.nodes().delete_deleted_nodes(deleted_node_ids.into()).await.unwrap();
+    /// # }
+    /// ```
+    async fn delete_deleted_nodes(
+        &self,
+        req: DeleteDeletedNodesRequest,
+    ) -> Result<(), DracoonClientError>;
     /// Move nodes to a target parent node (folder or room).
     /// ```no_run
     /// # use dco3::{Dracoon, auth::OAuth2Flow, Nodes};
@@ -224,6 +285,139 @@ pub trait Nodes {
         req: TransferNodesRequest,
         target_parent_id: u64,
     ) -> Result<Node, DracoonClientError>;
+
+    /// Walks the subtree of a room or folder and returns the aggregated file count and size
+    /// for the node and every room or folder below it (including all sub nodes).
+    /// The first entry is the summary of the passed node.
+    /// Children are listed with bounded concurrency (default: 5 parallel requests).
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Nodes};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let summaries = dracoon.nodes().get_node_size_summaries(123, Some(10)).await.unwrap();
+    /// let room = summaries.first().unwrap();
+    /// println!("{} files ({} bytes)", room.file_count, room.size);
+    /// # }
+    /// ```
+    async fn get_node_size_summaries(
+        &self,
+        node_id: u64,
+        max_concurrency: Option<usize>,
+    ) -> Result<Vec<NodeSizeSummary>, DracoonClientError>;
+
+    /// Returns a node and its sub nodes organized as a tree up to the given depth.
+    /// A depth of 0 only returns the node itself, a depth of 1 includes the direct children etc.
+    /// All children of a level are fetched (paginated) before descending to the next level.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Nodes};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let tree = dracoon.nodes().get_node_tree(123, 2).await.unwrap();
+    ///
+    /// for child in &tree.children {
+    ///    println!("{} ({} sub nodes)", child.node.name, child.children.len());
+    /// }
+    /// # }
+    /// ```
+    async fn get_node_tree(&self, root_id: u64, depth: u32)
+        -> Result<NodeTree, DracoonClientError>;
+
+    /// Updates the expiration and / or classification of multiple files.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Nodes, nodes::UpdateFilesBulkRequest};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let update = UpdateFilesBulkRequest::builder(vec![1, 2, 3])
+    ///                              .with_classification(3)
+    ///                              .build();
+    /// dracoon.nodes().update_files(update).await.unwrap();
+    /// # }
+    /// ```
+    async fn update_files(&self, req: UpdateFilesBulkRequest) -> Result<(), DracoonClientError>;
+
+    /// Applies a classification to all files below a room or folder (recursively).
+    /// Files are updated in batches; the optional callback is called after each batch
+    /// with the amount of updated files and the total amount of files to update.
+    /// Files already having the classification are skipped.
+    /// Returns the amount of updated files.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Nodes};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let updated = dracoon
+    ///     .nodes()
+    ///     .reclassify_files(123, 3, Some(Box::new(|updated, total| {
+    ///         println!("Updated {updated} of {total} files");
+    ///     })))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    async fn reclassify_files(
+        &self,
+        node_id: u64,
+        classification: u8,
+        callback: Option<ReclassificationProgressCallback>,
+    ) -> Result<u64, DracoonClientError>;
+
+    /// Cancels an upload started earlier (e.g. a persisted [UploadState]) and removes its upload channel.
+    /// Uploaded content is discarded - the upload cannot be resumed afterwards.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Nodes, nodes::UploadState};
+    /// # fn load_state() -> UploadState { unimplemented!() }
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let state = load_state();
+    ///
+    /// dracoon.nodes().cancel_upload(state.upload_id()).await.unwrap();
+    /// # }
+    /// ```
+    async fn cancel_upload(&self, upload_id: &str) -> Result<(), DracoonClientError>;
 }
 
 #[async_trait]
@@ -340,6 +534,7 @@ pub trait Folders {
 #[async_trait]
 pub trait Rooms {
     /// Creates a room.
+    /// If S3 tags are set and assigning them fails, the created room is deleted again.
     /// ```no_run
     /// # use dco3::{Dracoon, auth::OAuth2Flow, Rooms, nodes::CreateRoomRequest};
     /// # #[tokio::main]
@@ -526,7 +721,7 @@ pub trait Rooms {
     /// #  .unwrap();
     ///
     /// // add a a list of updates
-    /// let group_updates = vec![RoomGroupsAddBatchRequestItem::new(123, NodePermissions::new_with_read_permissions(), None)];
+    /// let group_updates = vec![RoomGroupsAddBatchRequestItem::new(123, NodePermissions::read_only(), None)];
     /// dracoon.nodes().update_room_groups(123, group_updates.into()).await.unwrap();
     /// # }
     /// ```
@@ -604,7 +799,7 @@ pub trait Rooms {
     /// #  .unwrap();
     ///
     /// // add a a list of updates
-    /// let user_updates = vec![RoomUsersAddBatchRequestItem::new(123, NodePermissions::new_with_read_permissions())];
+    /// let user_updates = vec![RoomUsersAddBatchRequestItem::new(123, NodePermissions::read_only())];
     /// dracoon.nodes().update_room_users(123, user_updates.into()).await.unwrap();
     /// # }
     /// ```
@@ -638,125 +833,1230 @@ pub trait Rooms {
         room_id: u64,
         room_users_del_req: RoomUsersDeleteBatchRequest,
     ) -> Result<(), DracoonClientError>;
-}
-/// This trait represents the download functionality and provides
-/// a signle method to download a stream of bytes to a writer.
-/// This rquires a mutable reference to the client because the download method
-/// needs to be able to check for the secret and set it for the client if encryption is used.
-#[async_trait]
-pub trait Download {
-    /// Downloads a file (node) to the given writer buffer
-    /// Example
+    /// Gets webhooks of a room by id with optional params.
     /// ```no_run
-    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes};
-    ///
-    /// #[tokio::main]
-    /// async fn main() {
-    ///    let client = Dracoon::builder()
-    ///      .with_base_url("https://dracoon.team")
-    ///      .with_client_id("client_id")
-    ///      .with_client_secret("client_secret")
-    ///      // if you do not pass a password, encrypted uploads will fail with an error
-    ///      .with_encryption_password("encryption_password")
-    ///      .build()
-    ///      .unwrap()
-    ///      .connect(OAuth2Flow::password_flow("username", "password"))
-    ///      .await
-    ///      .unwrap();
-    ///
-    ///   let node_id = 123u64;
-    ///
-    ///   let node = client.nodes().get_node(node_id).await.unwrap();
-    ///
-    ///   let mut writer = tokio::io::BufWriter::new(tokio::fs::File::create("test.txt").await.unwrap());
-    ///
-    ///   client.download(&node, &mut writer, None).await.unwrap();
-    ///
-    ///   // or with progress callback (boxed closure)
-    ///   client.download(&node, &mut writer, Some(Box::new(|progress, total| {
-    ///    println!("Download progress: {}", progress);
-    ///    println!("File total: {}", total);
-    ///  }))).await.unwrap();
-    /// }
+    /// # use dco3::{Dracoon, OAuth2Flow, Rooms};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let webhooks = dracoon.nodes().get_room_webhooks(123, None).await.unwrap();
+    /// # }
     /// ```
-    ///
-    ///
-    async fn download<'w>(
-        &'w self,
-        node: &Node,
-        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
-        mut callback: Option<DownloadProgressCallback>,
-    ) -> Result<(), DracoonClientError>;
-}
-
-/// This trait represents the upload functionality and provides
-/// a single method to upload a stream of bytes by passing a buffered reader.
-/// This rquires a mutable reference to the client because the upload method
-/// needs to be able to check for the secret and set it for the client if encryption is used.
-#[async_trait]
-pub trait Upload<R: AsyncRead> {
-    /// Uploads a stream (buffered reader) with given file meta info to the given parent node
-    /// # Example
+    async fn get_room_webhooks(
+        &self,
+        room_id: u64,
+        params: Option<ListAllParams>,
+    ) -> Result<RoomWebhookList, DracoonClientError>;
+    /// Assigns or unassigns webhooks to a room by id.
     /// ```no_run
-    /// use dco3::{Dracoon, OAuth2Flow, Upload, Nodes, nodes::{FileMeta, UploadOptions, ResolutionStrategy}};
-    /// #[cfg(not(doctest))]
-    /// #[tokio::main]
-    /// async fn main() {
-    ///    let client = Dracoon::builder()
-    ///      .with_base_url("https://dracoon.team")
-    ///      .with_client_id("client_id")
-    ///      .with_client_secret("client_secret")
-    ///      // if you do not pass a password, encrypted uploads will fail with an error
-    ///      .with_encryption_password("encryption_password")
-    ///      .build()
-    ///      .unwrap()
-    ///      .connect(OAuth2Flow::password_flow("username", "password"))
-    ///      .await
-    ///      .unwrap();
-    ///
-    /// let file = tokio::fs::File::open("test.txt").await.unwrap();
-    /// let file_meta = FileMeta::builder("test.txt", 123456)
-    /// .with_timestamp_modification("2020-01-01T00:00:00.000Z".parse().unwrap())
-    /// .build();
-    ///
-    ///
-    /// let parent_node_id = 123u64;
-    ///
-    /// let parent_node = client.nodes().get_node(parent_node_id).await.unwrap();
-    ///
-    /// let reader = tokio::io::BufReader::new(file);
-    ///
-    /// let options = UploadOptions::builder(file_meta)
-    ///               .with_resolution_strategy(ResolutionStrategy::AutoRename)
-    ///               .build();
-    ///
-    /// let chunk_size = 1024 * 1024 * 10; // 10 MB - DEFAULT is 32 MB
-    ///
-    /// client.upload(&parent_node, options, reader, None, Some(chunk_size)).await.unwrap();
-    ///
-    /// // or with progress callback (boxed closure)
-    /// let file = tokio::fs::File::open("test.txt").await.unwrap();
-    /// let file_meta = FileMeta::builder("test.txt", 123456)
-    /// .with_timestamp_modification("2020-01-01T00:00:00.000Z".parse().unwrap())
-    /// .build();
-    /// let options = UploadOptions::builder(file_meta)
-    ///               .with_resolution_strategy(ResolutionStrategy::AutoRename)
-    ///               .build();
-    /// let reader = tokio::io::BufReader::new(file);
-    /// client.upload(&parent_node, options, reader, Some(Box::new(|progress, total| {  
-    ///   println!("Upload progress: {}", progress);
-    ///  println!("File total: {}", total);
-    /// })), Some(chunk_size)).await.unwrap();
-    /// }
+    /// # use dco3::{Dracoon, OAuth2Flow, Rooms, nodes::RoomWebhookAssignment};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let assignments = vec![
+    ///     RoomWebhookAssignment::assign(1),
+    ///     RoomWebhookAssignment::unassign(2),
+    /// ];
+    /// let webhooks = dracoon.nodes().update_room_webhooks(123, assignments.into()).await.unwrap();
+    /// # }
     /// ```
-    ///
-    ///
-    async fn upload<'r>(
-        &'r self,
-        parent_node: &Node,
-        upload_options: UploadOptions,
-        mut reader: BufReader<R>,
-        mut callback: Option<UploadProgressCallback>,
-        chunk_size: Option<usize>,
+    async fn update_room_webhooks(
+        &self,
+        room_id: u64,
+        room_webhooks_update_req: UpdateRoomWebhooksRequest,
+    ) -> Result<RoomWebhookList, DracoonClientError>;
+    /// Gets S3 tags assigned to a room by id.
+    /// Available S3 tags can be listed via `get_s3_tags` of the [Config](crate::Config) trait.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, Rooms};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let s3_tags = dracoon.nodes().get_room_s3_tags(123).await.unwrap();
+    /// # }
+    /// ```
+    async fn get_room_s3_tags(&self, room_id: u64) -> Result<S3TagList, DracoonClientError>;
+    /// Assigns S3 tags to a room by id (replaces all assigned tags).
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, Rooms};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// // You can use a vec of S3 tag ids
+    /// let s3_tag_ids = vec![1, 2, 3];
+    /// let s3_tags = dracoon.nodes().update_room_s3_tags(123, s3_tag_ids.into()).await.unwrap();
+    /// # }
+    /// ```
+    async fn update_room_s3_tags(
+        &self,
+        room_id: u64,
+        s3_tags_req: RoomS3TagsRequest,
+    ) -> Result<S3TagList, DracoonClientError>;
+    /// Sets the room administrators (users and groups) of a room by id.
+    /// Only the required permission changes are sent: missing admins are granted manage permissions
+    /// and admins not contained in the given lists lose the manage permission (other permissions are kept).
+    /// Grants are applied before revokes so the room always keeps an admin.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, Rooms};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// // users 1 and 2 and group 3 are the only room admins afterwards
+    /// dracoon.nodes().set_room_admins(123, vec![1, 2], vec![3]).await.unwrap();
+    /// # }
+    /// ```
+    async fn set_room_admins(
+        &self,
+        room_id: u64,
+        admin_user_ids: Vec<u64>,
+        admin_group_ids: Vec<u64>,
+    ) -> Result<(), DracoonClientError>;
+    /// Clones a room into the given parent room (or the root level if no parent is passed).
+    /// The new room has the same name, configuration (quota, recycle bin retention period, classification,
+    /// activities log, encryption), policies and user / group permissions as the source room.
+    /// If `include_files` is true, all folders and files are copied - otherwise only the folder structure
+    /// is recreated. Nested rooms are not cloned.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, Rooms};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// // clone the template room 123 into room 456 without files
+    /// let room = dracoon.nodes().clone_room(123, Some(456), false).await.unwrap();
+    /// # }
+    /// ```
+    async fn clone_room(
+        &self,
+        source_room_id: u64,
+        target_parent_id: Option<u64>,
+        include_files: bool,
+    ) -> Result<Node, DracoonClientError>;
+    /// Returns all rooms below the given parent room (or all rooms if no parent is passed),
+    /// including nested rooms. Folders and files are not listed.
+    /// Room nodes contain the room configuration (quota, recycle bin retention period, inherit permissions etc.).
+    /// Parents are always listed before their sub rooms.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, Rooms};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// // all rooms
+    /// let rooms = dracoon.nodes().get_all_rooms(None).await.unwrap();
+    ///
+    /// // all rooms below room 123
+    /// let sub_rooms = dracoon.nodes().get_all_rooms(Some(123)).await.unwrap();
+    /// # }
+    /// ```
+    async fn get_all_rooms(&self, parent_id: Option<u64>) -> Result<Vec<Node>, DracoonClientError>;
+    /// Creates a room from a template in the given parent room (or on root level if no parent is passed).
+    /// The room is created first, followed by user and group permissions, policies and the folder layout.
+    /// If any step fails, the created room is deleted again and the error is returned.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, Rooms, nodes::{NodePermissions, RoomTemplate}};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let template = RoomTemplate::builder("Project X")
+    ///     .with_classification(3)
+    ///     .with_inherit_permissions(false)
+    ///     .with_admin_ids(vec![1])
+    ///     .with_group_permissions(2, NodePermissions::edit())
+    ///     .with_folder("Contracts")
+    ///     .with_folder("Documents/Internal")
+    ///     .build();
+    ///
+    /// let room = dracoon.nodes().apply_template(Some(123), &template).await.unwrap();
+    /// # }
+    /// ```
+    async fn apply_template(
+        &self,
+        parent_id: Option<u64>,
+        template: &RoomTemplate,
+    ) -> Result<Node, DracoonClientError>;
+}
+
+/// This trait provides a high-level helper to invite (guest) users to rooms.
+/// It combines the required users and rooms API calls.
+#[async_trait]
+pub trait RoomInvitations {
+    /// Invites a user by email to a room.
+    /// If no user with the given email exists, a new user is created (basic auth, email as user name).
+    /// The user is then granted the permissions of the invitation (read permissions by default).
+    /// The notification email is only sent to newly created users if `notify_user` is set.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, RoomInvitations, nodes::RoomInvitation};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let invitation = RoomInvitation::builder("guest@example.com", "Jane", "Doe")
+    ///    .with_edit_permissions()
+    ///    .with_notify_user(true)
+    ///    .build();
+    ///
+    /// let result = dracoon.invite_to_room(123, invitation).await.unwrap();
+    ///
+    /// if result.created {
+    ///   println!("Created user {}", result.user_id);
+    /// }
+    /// # }
+    /// ```
+    async fn invite_to_room(
+        &self,
+        room_id: u64,
+        invitation: RoomInvitation,
+    ) -> Result<RoomInvitationResult, DracoonClientError>;
+}
+/// This trait represents the download functionality and provides
+/// a signle method to download a stream of bytes to a writer.
+/// This rquires a mutable reference to the client because the download method
+/// needs to be able to check for the secret and set it for the client if encryption is used.
+#[async_trait]
+pub trait Download {
+    /// Downloads a file (node) to the given writer buffer
+    ///
+    /// Encrypted files are decrypted chunk by chunk: content is written before the authentication
+    /// tag is verified after the last chunk. If the verification fails,
+    /// `DracoonClientError::IntegrityCheckFailed` is returned and the written content must be discarded.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      // if you do not pass a password, encrypted uploads will fail with an error
+    ///      .with_encryption_password("encryption_password")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let node_id = 123u64;
+    ///
+    ///   let node = client.nodes().get_node(node_id).await.unwrap();
+    ///
+    ///   let mut writer = tokio::io::BufWriter::new(tokio::fs::File::create("test.txt").await.unwrap());
+    ///
+    ///   client.download(&node, &mut writer, None).await.unwrap();
+    ///
+    ///   // or with progress callback (boxed closure)
+    ///   client.download(&node, &mut writer, Some(Box::new(|progress| {
+    ///    println!("Download progress: {} / {}", progress.bytes_done, progress.total);
+    ///    println!("Rate: {} bytes/s, ETA: {:?}", progress.rate, progress.eta);
+    ///  }))).await.unwrap();
+    /// }
+    /// ```
+    ///
+    ///
+    async fn download<'w>(
+        &'w self,
+        node: &Node,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        mut callback: Option<DownloadProgressCallback>,
+    ) -> Result<(), DracoonClientError>;
+
+    /// Downloads a previous version of a file (see [Nodes::get_file_versions]) to the given writer buffer.
+    /// Encrypted versions are decrypted with the file key of the version.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let versions = client.nodes().get_file_versions(123, "test.txt", None).await.unwrap();
+    ///   let version = versions.items.first().unwrap();
+    ///
+    ///   let mut writer = tokio::io::BufWriter::new(tokio::fs::File::create("test.txt").await.unwrap());
+    ///
+    ///   client.download_version(version, &mut writer, None).await.unwrap();
+    /// }
+    /// ```
+    async fn download_version<'w>(
+        &'w self,
+        version: &DeletedNodeVersion,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        mut callback: Option<DownloadProgressCallback>,
+    ) -> Result<(), DracoonClientError>;
+
+    /// Downloads a file (node) decrypted with a rescue keypair instead of the user keypair
+    /// (see [crate::RescueKeyPair]) - e.g. to recover files of a room without a user file key.
+    /// The file key encrypted for the rescue key is only available while the file key of
+    /// any user (e.g. the current user) is missing. Unencrypted files are downloaded as usual.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes, RescueKeyPair, nodes::UseKey};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let node = client.nodes().get_node(123).await.unwrap();
+    ///   let room_id = node.auth_parent_id.unwrap();
+    ///   let keypair = client.settings().get_room_rescue_keypair(room_id, "room_rescue_key_secret").await.unwrap();
+    ///
+    ///   let mut writer = tokio::io::BufWriter::new(tokio::fs::File::create("test.txt").await.unwrap());
+    ///
+    ///   client.download_with_rescue_keypair(&node, &keypair, UseKey::RoomRescueKey, &mut writer, None).await.unwrap();
+    /// }
+    /// ```
+    async fn download_with_rescue_keypair<'w>(
+        &'w self,
+        node: &Node,
+        keypair: &PlainUserKeyPairContainer,
+        use_key: UseKey,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        mut callback: Option<DownloadProgressCallback>,
+    ) -> Result<(), DracoonClientError>;
+
+    /// Resumes an interrupted download of a file (node) at the given offset (in bytes) and
+    /// writes the remaining bytes to the given writer (e.g. a file opened in append mode).
+    /// Encrypted files need to be downloaded from the start for decryption - only the
+    /// remaining bytes are written to the writer.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let file = tokio::fs::OpenOptions::new().append(true).open("test.txt").await.unwrap();
+    ///   let offset = file.metadata().await.unwrap().len();
+    ///   let mut writer = tokio::io::BufWriter::new(file);
+    ///
+    ///   client.download_from_offset(&node, offset, &mut writer, None).await.unwrap();
+    /// }
+    /// ```
+    async fn download_from_offset<'w>(
+        &'w self,
+        node: &Node,
+        offset: u64,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        mut callback: Option<DownloadProgressCallback>,
+    ) -> Result<(), DracoonClientError>;
+
+    /// Downloads a file (node) to the given path and returns the path of the created file.
+    /// If the path is an existing directory, the file is created in it using the node name.
+    /// The modification timestamp is set from the node metadata - partial files are removed on error.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let path = client.download_to_path(&node, std::path::Path::new("downloads"), None).await.unwrap();
+    /// }
+    /// ```
+    async fn download_to_path(
+        &self,
+        node: &Node,
+        path: &Path,
+        mut callback: Option<DownloadProgressCallback>,
+    ) -> Result<PathBuf, DracoonClientError>;
+
+    /// Downloads a file (node) to the given writer buffer - the download can be aborted
+    /// by cancelling the passed token. Already written data is flushed and the download returns
+    /// `DracoonClientError::Cancelled`.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes, nodes::CancellationToken};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let mut writer = tokio::io::BufWriter::new(tokio::fs::File::create("test.txt").await.unwrap());
+    ///
+    ///   let token = CancellationToken::new();
+    ///   let child_token = token.child_token();
+    ///
+    ///   // e.g. cancel on ctrl-c
+    ///   tokio::spawn(async move {
+    ///     tokio::signal::ctrl_c().await.unwrap();
+    ///     token.cancel();
+    ///   });
+    ///
+    ///   let result = client.download_with_cancellation(&node, &mut writer, None, child_token).await;
+    ///
+    ///   if let Err(err) = result {
+    ///     if err.is_cancelled() {
+    ///       println!("Download cancelled");
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    async fn download_with_cancellation<'w>(
+        &'w self,
+        node: &Node,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        mut callback: Option<DownloadProgressCallback>,
+        cancellation_token: CancellationToken,
+    ) -> Result<(), DracoonClientError>;
+
+    /// Returns the content of a file (node) as a stream of bytes - e.g. to pipe the content
+    /// into a hasher, an archive or a HTTP response without a temporary file.
+    /// Files are streamed in ranged chunks, encrypted files are decrypted chunk by chunk
+    /// (the file is never buffered in memory). The content of an encrypted file is authenticated
+    /// after the last chunk: if the stream ends with `DracoonClientError::IntegrityCheckFailed`,
+    /// all received chunks must be discarded.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes};
+    /// use futures_util::TryStreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let mut stream = client.download_stream(&node).await.unwrap();
+    ///
+    ///   while let Some(chunk) = stream.try_next().await.unwrap() {
+    ///     println!("Received {} bytes", chunk.len());
+    ///   }
+    /// }
+    /// ```
+    async fn download_stream(&self, node: &Node) -> Result<DownloadStream, DracoonClientError>;
+
+    /// Returns the content of a file (node) as an async reader (see [Download::download_stream]).
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let mut reader = client.download_reader(&node).await.unwrap();
+    ///   let mut file = tokio::fs::File::create("test.txt").await.unwrap();
+    ///
+    ///   tokio::io::copy(&mut reader, &mut file).await.unwrap();
+    /// }
+    /// ```
+    async fn download_reader(
+        &self,
+        node: &Node,
+    ) -> Result<Pin<Box<dyn AsyncRead + Send>>, DracoonClientError>;
+
+    /// Downloads multiple nodes as ZIP archive to the given writer.
+    /// The archive is created on the fly and streamed to the writer (encrypted nodes are not supported).
+    /// The total passed to the progress callback is 0 if the archive size is unknown.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let mut writer = tokio::io::BufWriter::new(tokio::fs::File::create("archive.zip").await.unwrap());
+    ///
+    ///   client.download_zip(vec![123, 456].into(), &mut writer, None).await.unwrap();
+    /// }
+    /// ```
+    async fn download_zip<'w>(
+        &'w self,
+        req: CreateZipDownloadRequest,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        mut callback: Option<DownloadProgressCallback>,
+    ) -> Result<(), DracoonClientError>;
+
+    /// Returns a ZIP archive of multiple nodes as a stream of bytes (see [Download::download_zip]).
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download};
+    /// use futures_util::TryStreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let mut stream = client.download_zip_stream(vec![123, 456].into()).await.unwrap();
+    ///
+    ///   while let Some(chunk) = stream.try_next().await.unwrap() {
+    ///     println!("Received {} bytes", chunk.len());
+    ///   }
+    /// }
+    /// ```
+    async fn download_zip_stream(
+        &self,
+        req: CreateZipDownloadRequest,
+    ) -> Result<DownloadStream, DracoonClientError>;
+
+    /// Generates a (time-limited) download url for a file (node) without downloading it.
+    /// The url can be passed to other components (e.g. a browser) - encrypted files
+    /// are not decrypted and need to be decrypted by the receiver.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let download_url = client.get_download_url(123).await.unwrap();
+    ///
+    ///   println!("Download url: {}", download_url.download_url);
+    ///
+    ///   if let Some(expires_at) = download_url.expires_at() {
+    ///     println!("Expires at: {}", expires_at);
+    ///   }
+    /// }
+    /// ```
+    async fn get_download_url(
+        &self,
+        node_id: u64,
+    ) -> Result<DownloadUrlResponse, DracoonClientError>;
+
+    /// Downloads multiple files (nodes) to the given targets with at most `concurrency` parallel downloads.
+    /// The progress callback receives the aggregated progress of all downloads.
+    /// Returns the result (target path or error) for each request in the order of the requests -
+    /// a failed download does not cancel the remaining downloads.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes, nodes::DownloadRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let first = client.nodes().get_node(123).await.unwrap();
+    ///   let second = client.nodes().get_node(456).await.unwrap();
+    ///
+    ///   let requests = vec![
+    ///     DownloadRequest::new(first, "/tmp"),
+    ///     DownloadRequest::new(second, "/tmp/renamed.txt"),
+    ///   ];
+    ///
+    ///   let results = client.download_many(requests, 4, Some(Box::new(|progress| {
+    ///     println!("Downloaded {} of {} bytes", progress.bytes_done, progress.total);
+    ///   }))).await;
+    ///
+    ///   for result in results {
+    ///     match result {
+    ///       Ok(path) => println!("Downloaded to {}", path.display()),
+    ///       Err(err) => println!("Download failed: {}", err),
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    async fn download_many(
+        &self,
+        requests: Vec<DownloadRequest>,
+        concurrency: usize,
+        callback: Option<DownloadProgressCallback>,
+    ) -> Vec<Result<PathBuf, DracoonClientError>>;
+
+    /// Downloads a file (node) only if it changed compared to the known state (hash, ETag or
+    /// last update) and returns [DownloadOutcome::NotModified] otherwise.
+    /// The node metadata (hash, update timestamp) is compared first - the ETag is only checked
+    /// if the metadata cannot be compared. Pass an up to date node (e.g. from `get_node`).
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes, nodes::DownloadCondition};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let condition = DownloadCondition::new().with_hash("known-md5-hash");
+    ///
+    ///   let mut writer = tokio::io::BufWriter::new(tokio::fs::File::create("test.txt").await.unwrap());
+    ///
+    ///   let outcome = client.download_if_modified(&node, condition, &mut writer, None).await.unwrap();
+    ///
+    ///   if outcome.is_not_modified() {
+    ///     println!("File unchanged - skipped download");
+    ///   }
+    /// }
+    /// ```
+    async fn download_if_modified<'w>(
+        &'w self,
+        node: &Node,
+        condition: DownloadCondition,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+        mut callback: Option<DownloadProgressCallback>,
+    ) -> Result<DownloadOutcome, DracoonClientError>;
+
+    /// Downloads a file (node) to the given writer and reports progress as a stream of
+    /// [TransferEvent]s instead of a callback (e.g. for GUI event loops).
+    /// Returns the download (needs to be awaited or polled) alongside the event stream.
+    /// The event stream ends when the download is finished or failed.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Download, Nodes, nodes::TransferEvent};
+    /// use futures_util::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let mut writer = tokio::io::BufWriter::new(tokio::fs::File::create("test.txt").await.unwrap());
+    ///
+    ///   let (download, mut events) = client.download_with_events(&node, &mut writer);
+    ///
+    ///   let print_events = async {
+    ///     while let Some(event) = events.next().await {
+    ///       match event {
+    ///         TransferEvent::Started { total } => println!("Started ({} bytes)", total),
+    ///         TransferEvent::Progressed(progress) => println!("{:.0}%", progress.fraction() * 100.0),
+    ///         TransferEvent::ChunkRetried { retry, .. } => println!("Retrying chunk ({})", retry),
+    ///         TransferEvent::Finished => println!("Finished"),
+    ///       }
+    ///     }
+    ///   };
+    ///
+    ///   let (result, _) = tokio::join!(download, print_events);
+    ///   result.unwrap();
+    /// }
+    /// ```
+    fn download_with_events<'w>(
+        &'w self,
+        node: &'w Node,
+        writer: &'w mut (dyn AsyncWrite + Send + Unpin),
+    ) -> (TransferFuture<'w>, TransferEventStream);
+}
+
+/// This trait represents the upload functionality and provides
+/// a single method to upload a stream of bytes by passing a buffered reader.
+/// This rquires a mutable reference to the client because the upload method
+/// needs to be able to check for the secret and set it for the client if encryption is used.
+#[async_trait]
+pub trait Upload<R: AsyncRead> {
+    /// Uploads a stream (buffered reader) with given file meta info to the given parent node.
+    /// For S3 uploads, the chunk size must be between 5 MB and 5 GB (max. 10000 parts) - if no
+    /// chunk size is passed, it is selected based on the file size.
+    /// # Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Upload, Nodes, nodes::{FileMeta, UploadOptions, ResolutionStrategy}};
+    /// #[cfg(not(doctest))]
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      // if you do not pass a password, encrypted uploads will fail with an error
+    ///      .with_encryption_password("encryption_password")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    /// let file = tokio::fs::File::open("test.txt").await.unwrap();
+    /// let file_meta = FileMeta::builder("test.txt", 123456)
+    /// .with_timestamp_modification("2020-01-01T00:00:00.000Z".parse().unwrap())
+    /// .build();
+    ///
+    ///
+    /// let parent_node_id = 123u64;
+    ///
+    /// let parent_node = client.nodes().get_node(parent_node_id).await.unwrap();
+    ///
+    /// let reader = tokio::io::BufReader::new(file);
+    ///
+    /// let options = UploadOptions::builder(file_meta)
+    ///               .with_resolution_strategy(ResolutionStrategy::AutoRename)
+    ///               .build();
+    ///
+    /// let chunk_size = 1024 * 1024 * 10; // 10 MB - DEFAULT is 32 MB
+    ///
+    /// client.upload(&parent_node, options, reader, None, Some(chunk_size)).await.unwrap();
+    ///
+    /// // or with progress callback (boxed closure)
+    /// let file = tokio::fs::File::open("test.txt").await.unwrap();
+    /// let file_meta = FileMeta::builder("test.txt", 123456)
+    /// .with_timestamp_modification("2020-01-01T00:00:00.000Z".parse().unwrap())
+    /// .build();
+    /// let options = UploadOptions::builder(file_meta)
+    ///               .with_resolution_strategy(ResolutionStrategy::AutoRename)
+    ///               .build();
+    /// let reader = tokio::io::BufReader::new(file);
+    /// client.upload(&parent_node, options, reader, Some(Box::new(|progress| {
+    ///   println!("Upload progress: {} / {}", progress.bytes_done, progress.total);
+    ///   println!("Rate: {} bytes/s, ETA: {:?}", progress.rate, progress.eta);
+    /// })), Some(chunk_size)).await.unwrap();
+    /// }
+    /// ```
+    ///
+    ///
+    async fn upload<'r>(
+        &'r self,
+        parent_node: &Node,
+        upload_options: UploadOptions,
+        mut reader: BufReader<R>,
+        mut callback: Option<UploadProgressCallback>,
+        chunk_size: Option<usize>,
+    ) -> Result<Node, DracoonClientError>;
+
+    /// Uploads a stream (buffered reader) to the given parent node - the upload can be aborted
+    /// by cancelling the passed token and returns `DracoonClientError::Cancelled`.
+    /// If the upload is cancelled or fails, the upload channel is removed from the server.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Upload, Nodes, nodes::{CancellationToken, FileMeta, UploadOptions}};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let parent_node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let reader = tokio::io::BufReader::new(tokio::fs::File::open("test.txt").await.unwrap());
+    ///   let options = UploadOptions::builder(FileMeta::builder("test.txt", 123456).build()).build();
+    ///
+    ///   let token = CancellationToken::new();
+    ///   let child_token = token.child_token();
+    ///
+    ///   // e.g. cancel on ctrl-c
+    ///   tokio::spawn(async move {
+    ///     tokio::signal::ctrl_c().await.unwrap();
+    ///     token.cancel();
+    ///   });
+    ///
+    ///   let result = client.upload_with_cancellation(&parent_node, options, reader, None, None, child_token).await;
+    ///
+    ///   if let Err(err) = result {
+    ///     if err.is_cancelled() {
+    ///       println!("Upload cancelled");
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    async fn upload_with_cancellation<'r>(
+        &'r self,
+        parent_node: &Node,
+        upload_options: UploadOptions,
+        reader: BufReader<R>,
+        callback: Option<UploadProgressCallback>,
+        chunk_size: Option<usize>,
+        cancellation_token: CancellationToken,
+    ) -> Result<Node, DracoonClientError>;
+
+    /// Uploads a stream (buffered reader) with unknown size to the given parent node
+    /// (e.g. piped stdin or generated data). The reader is read in chunks until EOF and the size
+    /// of the file meta is ignored. Requires S3 storage - otherwise
+    /// `DracoonClientError::UnknownSizeNotSupported` is returned.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Upload, Nodes, nodes::{FileMeta, UploadOptions}};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let parent_node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let reader = tokio::io::BufReader::new(tokio::io::stdin());
+    ///   let options = UploadOptions::builder(FileMeta::builder("stdin.txt", 0).build()).build();
+    ///
+    ///   let node = client.upload_with_unknown_size(&parent_node, options, reader, None, None).await.unwrap();
+    /// }
+    /// ```
+    async fn upload_with_unknown_size<'r>(
+        &'r self,
+        parent_node: &Node,
+        upload_options: UploadOptions,
+        reader: BufReader<R>,
+        callback: Option<UploadProgressCallback>,
+        chunk_size: Option<usize>,
+    ) -> Result<Node, DracoonClientError>;
+
+    /// Creates an upload channel for a resumable upload and returns its state.
+    /// Only unencrypted uploads to S3 storage are resumable - otherwise
+    /// `DracoonClientError::UploadNotResumable` is returned.
+    /// The state is serializable and can be persisted (e.g. as JSON) to resume the upload later.
+    /// # Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Upload, Nodes, nodes::{FileMeta, UploadOptions}};
+    /// #[cfg(not(doctest))]
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    /// let parent_node = client.nodes().get_node(123).await.unwrap();
+    /// let options = UploadOptions::builder(FileMeta::builder("test.txt", 123456).build()).build();
+    ///
+    /// let mut state = <Dracoon<_> as Upload<tokio::fs::File>>::create_upload_state(&client, &parent_node, options, None).await.unwrap();
+    ///
+    /// let reader = tokio::io::BufReader::new(tokio::fs::File::open("test.txt").await.unwrap());
+    ///
+    /// if let Err(err) = client.resume_upload(&mut state, reader, None).await {
+    ///   // persist the state and resume the upload later
+    ///   let state = serde_json::to_string(&state).unwrap();
+    /// }
+    /// }
+    /// ```
+    async fn create_upload_state(
+        &self,
+        parent_node: &Node,
+        upload_options: UploadOptions,
+        chunk_size: Option<usize>,
+    ) -> Result<UploadState, DracoonClientError>;
+
+    /// Uploads all parts missing in the given state and completes the upload.
+    /// The reader must start at the beginning of the file - already uploaded parts are skipped.
+    /// Uploaded parts are added to the state, so it can be persisted again if the upload fails.
+    async fn resume_upload(
+        &self,
+        state: &mut UploadState,
+        reader: BufReader<R>,
+        callback: Option<UploadProgressCallback>,
     ) -> Result<Node, DracoonClientError>;
+
+    /// Uploads a stream (buffered reader) and reports progress, completed parts, retries and
+    /// finalizing as a stream of [UploadEvent]s instead of a callback (e.g. for GUI event loops).
+    /// Returns the upload (needs to be awaited or polled) alongside the event stream.
+    /// The event stream ends when the upload is finished or failed.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, Upload, Nodes, nodes::{FileMeta, UploadEvent, UploadOptions}};
+    /// use futures_util::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let parent_node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let file = tokio::fs::File::open("test.txt").await.unwrap();
+    ///   let size = file.metadata().await.unwrap().len();
+    ///   let reader = tokio::io::BufReader::new(file);
+    ///   let options = UploadOptions::builder(FileMeta::builder("test.txt", size).build()).build();
+    ///
+    ///   let (upload, mut events) = client.upload_with_events(&parent_node, options, reader, None);
+    ///
+    ///   let print_events = async {
+    ///     while let Some(event) = events.next().await {
+    ///       match event {
+    ///         UploadEvent::Started { total } => println!("Started ({} bytes)", total),
+    ///         UploadEvent::Progressed(progress) => println!("{:.0}%", progress.fraction() * 100.0),
+    ///         UploadEvent::PartCompleted { part_number, .. } => println!("Part {} uploaded", part_number),
+    ///         UploadEvent::PartRetried { part_number, retry } => println!("Retrying part {} ({})", part_number, retry),
+    ///         UploadEvent::Finalizing => println!("Finalizing"),
+    ///         UploadEvent::Finished => println!("Finished"),
+    ///       }
+    ///     }
+    ///   };
+    ///
+    ///   let (result, _) = tokio::join!(upload, print_events);
+    ///   let node = result.unwrap();
+    /// }
+    /// ```
+    fn upload_with_events<'r>(
+        &'r self,
+        parent_node: &'r Node,
+        upload_options: UploadOptions,
+        reader: BufReader<R>,
+        chunk_size: Option<usize>,
+    ) -> (UploadFuture<'r>, UploadEventStream);
+}
+
+/// This trait provides a batch upload of local files or readers.
+/// To upload a single file (or any other reader), use the [Upload] trait.
+#[async_trait]
+pub trait UploadMany {
+    /// Uploads multiple local files with the given max. number of concurrent uploads.
+    /// The progress callback receives the aggregated progress of all uploads.
+    /// Failed uploads are retried (transient errors only) - a failed upload does not cancel the
+    /// remaining uploads. The returned report contains the uploaded files, conflicts (a node with the
+    /// same name exists) and failed uploads.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, UploadMany, Nodes, nodes::UploadRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let parent_node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let requests = vec![
+    ///     UploadRequest::new("/tmp/first.txt", parent_node.clone()),
+    ///     UploadRequest::new("/tmp/second.txt", parent_node),
+    ///   ];
+    ///
+    ///   let report = client.upload_many(requests, 4, Some(Box::new(|progress| {
+    ///     println!("Uploaded {} of {} bytes", progress.bytes_done, progress.total);
+    ///   }))).await;
+    ///
+    ///   for (path, err) in report.conflicts.iter().chain(report.failed.iter()) {
+    ///     println!("Upload of {} failed: {}", path.display(), err);
+    ///   }
+    /// }
+    /// ```
+    async fn upload_many(
+        &self,
+        requests: Vec<UploadRequest>,
+        concurrency: usize,
+        callback: Option<UploadProgressCallback>,
+    ) -> UploadReport;
+
+    /// Uploads multiple readers with the given max. number of concurrent uploads.
+    /// Readers can't be read again - failed uploads are not retried. Uploads are reported
+    /// by the file name of their upload options.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, UploadMany, Nodes, nodes::{FileMeta, ReaderUploadRequest, UploadOptions}};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let parent_node = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let requests = vec![
+    ///     ReaderUploadRequest::new(
+    ///       std::io::Cursor::new(b"first".to_vec()),
+    ///       parent_node.clone(),
+    ///       UploadOptions::builder(FileMeta::builder("first.txt", 5).build()).build(),
+    ///     ),
+    ///     ReaderUploadRequest::new(
+    ///       std::io::Cursor::new(b"second".to_vec()),
+    ///       parent_node,
+    ///       UploadOptions::builder(FileMeta::builder("second.txt", 6).build()).build(),
+    ///     ),
+    ///   ];
+    ///
+    ///   let report = client.upload_many_readers(requests, 2, None).await;
+    ///
+    ///   assert!(report.is_success());
+    /// }
+    /// ```
+    async fn upload_many_readers<R: AsyncRead + Sync + Send + Unpin + 'static>(
+        &self,
+        requests: Vec<ReaderUploadRequest<R>>,
+        concurrency: usize,
+        callback: Option<UploadProgressCallback>,
+    ) -> UploadReport;
+}
+
+/// This trait provides uploads to a writer - e.g. for archivers or encoders that only write to a sink.
+/// To upload a reader, use the [Upload] trait.
+pub trait OpenUpload {
+    /// Starts an upload in the background and returns a writer - written content is uploaded while writing.
+    /// The size of the file meta (upload options) must match the written content.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, OpenUpload, Nodes, nodes::{FileMeta, UploadOptions}};
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let parent_node = client.nodes().get_node(123).await.unwrap();
+    ///   let options = UploadOptions::builder(FileMeta::builder("test.txt", 11).build()).build();
+    ///
+    ///   let mut writer = client.open_upload(&parent_node, options, None, None);
+    ///
+    ///   writer.write_all(b"hello world").await.unwrap();
+    ///
+    ///   let node = writer.finish().await.unwrap();
+    /// }
+    /// ```
+    fn open_upload(
+        &self,
+        parent_node: &Node,
+        upload_options: UploadOptions,
+        callback: Option<UploadProgressCallback>,
+        chunk_size: Option<usize>,
+    ) -> UploadWriter;
+}
+
+/// This trait provides a one-way sync (mirror) of a local directory to a node (room or folder).
+#[async_trait]
+pub trait SyncDirectory {
+    /// Syncs a local directory to the given node (room or folder): new and changed files are uploaded,
+    /// missing folders are created and - if enabled - remote files and folders missing locally are deleted.
+    /// Files are compared by name, size and modification timestamp (see [diff_nodes]).
+    /// Changed files are handled according to the conflict policy of the options.
+    /// Failed uploads and folders are reported per item - nothing is deleted if any of them failed.
+    /// Example
+    /// ```no_run
+    /// use dco3::{Dracoon, OAuth2Flow, SyncDirectory, Nodes, nodes::{SyncConflictPolicy, SyncOptions}};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let client = Dracoon::builder()
+    ///      .with_base_url("https://dracoon.team")
+    ///      .with_client_id("client_id")
+    ///      .with_client_secret("client_secret")
+    ///      .build()
+    ///      .unwrap()
+    ///      .connect(OAuth2Flow::password_flow("username", "password"))
+    ///      .await
+    ///      .unwrap();
+    ///
+    ///   let room = client.nodes().get_node(123).await.unwrap();
+    ///
+    ///   let options = SyncOptions::new()
+    ///     .with_conflict_policy(SyncConflictPolicy::Overwrite)
+    ///     .with_delete_removed(true);
+    ///
+    ///   let report = client.sync_dir_to_node(std::path::Path::new("/tmp/data"), &room, options).await.unwrap();
+    ///
+    ///   println!("Uploaded {} files, deleted {} nodes", report.uploads.uploaded.len(), report.deleted.len());
+    /// }
+    /// ```
+    async fn sync_dir_to_node(
+        &self,
+        local: &Path,
+        remote: &Node,
+        options: SyncOptions,
+    ) -> Result<SyncReport, DracoonClientError>;
 }
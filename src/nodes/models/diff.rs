@@ -0,0 +1,214 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+
+use super::{FileMeta, Node, NodeType};
+
+/// Entry of a local manifest (a file) that is compared against a remote node listing
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestEntry {
+    pub name: String,
+    pub size: u64,
+    pub timestamp_modification: Option<DateTime<Utc>>,
+    pub hash: Option<String>,
+}
+
+pub struct ManifestEntryBuilder {
+    name: String,
+    size: u64,
+    timestamp_modification: Option<DateTime<Utc>>,
+    hash: Option<String>,
+}
+
+impl ManifestEntry {
+    pub fn builder(name: impl Into<String>, size: u64) -> ManifestEntryBuilder {
+        ManifestEntryBuilder {
+            name: name.into(),
+            size,
+            timestamp_modification: None,
+            hash: None,
+        }
+    }
+}
+
+impl ManifestEntryBuilder {
+    pub fn with_timestamp_modification(mut self, timestamp_modification: DateTime<Utc>) -> Self {
+        self.timestamp_modification = Some(timestamp_modification);
+        self
+    }
+
+    /// MD5 hash of the file content (hex)
+    pub fn with_hash(mut self, hash: impl Into<String>) -> Self {
+        self.hash = Some(hash.into());
+        self
+    }
+
+    pub fn build(self) -> ManifestEntry {
+        ManifestEntry {
+            name: self.name,
+            size: self.size,
+            timestamp_modification: self.timestamp_modification,
+            hash: self.hash,
+        }
+    }
+}
+
+impl From<FileMeta> for ManifestEntry {
+    fn from(file_meta: FileMeta) -> Self {
+        Self {
+            name: file_meta.name,
+            size: file_meta.size,
+            timestamp_modification: file_meta.timestamp_modification,
+            hash: None,
+        }
+    }
+}
+
+/// A local file that differs from the remote file with the same name
+#[derive(Debug, Clone)]
+pub struct ChangedNode {
+    pub local: ManifestEntry,
+    pub remote: Node,
+}
+
+/// Result of comparing a local manifest against a remote listing
+/// - added: local files missing in the remote listing
+/// - changed: local files that differ from the remote file
+/// - removed: remote files missing in the local manifest
+#[derive(Debug, Clone, Default)]
+pub struct NodeDiff {
+    pub added: Vec<ManifestEntry>,
+    pub changed: Vec<ChangedNode>,
+    pub removed: Vec<Node>,
+}
+
+impl NodeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Compares a local manifest against a remote listing (files of a single parent) by name.
+/// Rooms and folders in the remote listing are ignored.
+///
+/// A file is considered changed if the size differs or - if both hashes are present - the hashes differ.
+/// Without hashes, the modification timestamps are compared (in seconds) if both are present.
+pub fn diff_nodes(local: &[ManifestEntry], remote: &[Node]) -> NodeDiff {
+    let remote_files: HashMap<&str, &Node> = remote
+        .iter()
+        .filter(|node| node.node_type == NodeType::File)
+        .map(|node| (node.name.as_str(), node))
+        .collect();
+
+    let mut diff = NodeDiff::default();
+
+    for entry in local {
+        match remote_files.get(entry.name.as_str()) {
+            None => diff.added.push(entry.clone()),
+            Some(node) if is_changed(entry, node) => diff.changed.push(ChangedNode {
+                local: entry.clone(),
+                remote: (*node).clone(),
+            }),
+            Some(_) => (),
+        }
+    }
+
+    let local_names: HashSet<&str> = local.iter().map(|entry| entry.name.as_str()).collect();
+
+    diff.removed = remote
+        .iter()
+        .filter(|node| {
+            node.node_type == NodeType::File && !local_names.contains(node.name.as_str())
+        })
+        .cloned()
+        .collect();
+
+    diff
+}
+
+fn is_changed(entry: &ManifestEntry, node: &Node) -> bool {
+    if node.size.is_some_and(|size| size != entry.size) {
+        return true;
+    }
+
+    if let (Some(local_hash), Some(remote_hash)) = (&entry.hash, &node.hash) {
+        return !local_hash.eq_ignore_ascii_case(remote_hash);
+    }
+
+    match (entry.timestamp_modification, node.timestamp_modification) {
+        (Some(local), Some(remote)) => local.timestamp() != remote.timestamp(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::NodeList;
+
+    fn remote_nodes(nodes: &str) -> Vec<Node> {
+        serde_json::from_str::<NodeList>(nodes).unwrap().items
+    }
+
+    #[test]
+    fn test_diff_nodes_added_changed_removed() {
+        let timestamp = DateTime::parse_from_rfc3339("2020-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let local = vec![
+            ManifestEntry::builder("new.txt", 10).build(),
+            ManifestEntry::builder("same.txt", 10)
+                .with_timestamp_modification(timestamp)
+                .build(),
+            ManifestEntry::builder("bigger.txt", 20).build(),
+            ManifestEntry::builder("hash.txt", 10)
+                .with_hash("abc")
+                .build(),
+        ];
+
+        let remote = remote_nodes(include_str!(
+            "../../tests/responses/nodes/diff_remote_nodes_ok.json"
+        ));
+
+        let diff = diff_nodes(&local, &remote);
+
+        assert!(!diff.is_empty());
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "new.txt");
+        assert_eq!(diff.changed.len(), 2);
+        assert_eq!(diff.changed[0].local.name, "bigger.txt");
+        assert_eq!(diff.changed[1].local.name, "hash.txt");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "old.txt");
+    }
+
+    #[test]
+    fn test_diff_nodes_timestamp_and_hash() {
+        let timestamp = DateTime::parse_from_rfc3339("2021-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let local = vec![
+            ManifestEntry::builder("modified.txt", 10)
+                .with_timestamp_modification(timestamp)
+                .build(),
+            // equal hash wins over a different timestamp
+            ManifestEntry::builder("hash.txt", 10)
+                .with_timestamp_modification(timestamp)
+                .with_hash("ABC")
+                .build(),
+        ];
+
+        let remote = remote_nodes(include_str!(
+            "../../tests/responses/nodes/diff_remote_nodes_hash_ok.json"
+        ));
+
+        let diff = diff_nodes(&local, &remote);
+
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].local.name, "modified.txt");
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+    }
+}
@@ -1,35 +1,52 @@
+pub mod diff;
 pub mod filters;
+pub mod path;
+pub mod progress;
 pub mod sorts;
+pub mod transfers;
+pub mod writer;
 
+use bytes::Bytes;
 use dco3_crypto::DracoonCrypto;
 use dco3_crypto::DracoonRSACrypto;
+use dco3_crypto::PlainFileKey;
 use dco3_crypto::PlainUserKeyPairContainer;
 use dco3_derive::FromResponse;
+pub use diff::*;
 pub use filters::*;
+use futures_util::Stream;
+pub use path::*;
+pub use progress::*;
 pub use sorts::*;
 use tracing::debug;
 use tracing::error;
+pub use writer::*;
 
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::Mutex;
 
-use crate::client::DracoonClient;
+use crate::client::{throttle::PauseSignal, DracoonClient};
 use crate::{
     client::{errors::DracoonClientError, models::DracoonErrorResponse},
+    constants::{DEFAULT_NODES_CONCURRENCY, MAX_NODE_NAME_LENGTH},
     models::{ObjectExpiration, Range, RangedItems},
     utils::parse_body,
     utils::FromResponse,
 };
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use dco3_crypto::FileKey;
 use dco3_crypto::PublicKeyContainer;
 use reqwest::{Response, StatusCode};
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
 
 use super::rooms::models::NodePermissionsBuilder;
 
@@ -53,13 +70,33 @@ impl<S> NodesEndpoint<S> {
 }
 
 /// A callback function that is called after each chunk is processed (download)
-pub type DownloadProgressCallback = Box<dyn FnMut(u64, u64) + Send + Sync>;
+pub type DownloadProgressCallback = Box<dyn FnMut(TransferProgress) + Send + Sync>;
+
+/// A stream of (decrypted) file content chunks
+pub type DownloadStream = Pin<Box<dyn Stream<Item = Result<Bytes, DracoonClientError>> + Send>>;
+
+/// A stream of transfer events (ends when the transfer is finished or failed)
+pub type TransferEventStream = Pin<Box<dyn Stream<Item = TransferEvent> + Send>>;
+
+/// A transfer running in the background of a [TransferEventStream]
+pub type TransferFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<(), DracoonClientError>> + Send + 'a>>;
+
+/// A stream of upload events (ends when the upload is finished or failed)
+pub type UploadEventStream = Pin<Box<dyn Stream<Item = UploadEvent> + Send>>;
+
+/// An upload running in the background of an [UploadEventStream] (returns the uploaded node)
+pub type UploadFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Node, DracoonClientError>> + Send + 'a>>;
 
 /// A callback function that is called after each chunk is processed (upload)
-pub type UploadProgressCallback = Box<dyn FnMut(u64, u64) + Send + Sync>;
+pub type UploadProgressCallback = Box<dyn FnMut(TransferProgress) + Send + Sync>;
+
+/// A callback function that is called after each processed batch (updated files, total files)
+pub type ReclassificationProgressCallback = Box<dyn FnMut(u64, u64) + Send + Sync>;
 
 /// A callback function (thread-safe) that can be cloned and called from multiple threads (upload)
-pub struct CloneableUploadProgressCallback(Arc<Mutex<UploadProgressCallback>>);
+pub struct CloneableUploadProgressCallback(Arc<Mutex<(UploadProgressCallback, ProgressTracker)>>);
 
 impl Clone for CloneableUploadProgressCallback {
     fn clone(&self) -> Self {
@@ -70,13 +107,28 @@ impl Clone for CloneableUploadProgressCallback {
 impl CloneableUploadProgressCallback {
     pub fn new<F>(callback: F) -> Self
     where
-        F: 'static + FnMut(u64, u64) + Send + Sync,
+        F: 'static + FnMut(TransferProgress) + Send + Sync,
     {
-        Self(Arc::new(Mutex::new(Box::new(callback))))
+        Self(Arc::new(Mutex::new((
+            Box::new(callback),
+            ProgressTracker::new(),
+        ))))
     }
 
     pub fn call(&self, bytes_read: u64, total_size: u64) {
-        (self.0.lock().unwrap())(bytes_read, total_size);
+        let mut guard = self.0.lock().unwrap();
+        let (callback, tracker) = &mut *guard;
+        callback(tracker.update(bytes_read, total_size));
+    }
+
+    /// Reports progress including the progress of the current part
+    pub fn call_part(&self, bytes_read: u64, total_size: u64, part: PartProgress) {
+        let mut guard = self.0.lock().unwrap();
+        let (callback, tracker) = &mut *guard;
+        callback(TransferProgress {
+            part: Some(part),
+            ..tracker.update(bytes_read, total_size)
+        });
     }
 }
 
@@ -134,19 +186,109 @@ impl FileMetaBuilder {
 }
 
 /// upload options (expiration, classification, keep share links, resolution strategy)
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct UploadOptions {
     pub expiration: Option<ObjectExpiration>,
     pub classification: Option<u8>,
     pub keep_share_links: Option<bool>,
     pub resolution_strategy: Option<ResolutionStrategy>,
     pub file_meta: FileMeta,
+    /// max. number of chunks (S3 parts) uploaded in parallel (default: 1)
+    pub parallelism: Option<usize>,
+    /// max. upload rate in bytes per second (default: unlimited)
+    pub max_rate: Option<u64>,
+    /// wait for the virus scan verdict after the upload (default: false)
+    pub wait_for_virus_scan: bool,
+    /// validate the options before uploading (default: false)
+    pub validate_policies: bool,
+    /// file key used to encrypt the file (default: generated per upload)
+    pub file_key: Option<PlainFileKey>,
+    /// users the file key is encrypted for (default: users missing the file key or share keys)
+    pub public_keys: Option<Vec<UserUserPublicKey>>,
+    /// pause state of a managed transfer (see [crate::nodes::TransferManager])
+    pub(crate) pause: Option<PauseSignal>,
+    /// events of an upload started with [crate::nodes::Upload::upload_with_events]
+    pub(crate) events: Option<UnboundedSender<UploadEvent>>,
+    /// upload channel created within the upload (removed if the upload fails)
+    pub(crate) upload_channel: Option<UploadChannelSlot>,
+}
+
+// the plain file key must never show up in logs
+impl Debug for UploadOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UploadOptions")
+            .field("expiration", &self.expiration)
+            .field("classification", &self.classification)
+            .field("keep_share_links", &self.keep_share_links)
+            .field("resolution_strategy", &self.resolution_strategy)
+            .field("file_meta", &self.file_meta)
+            .field("parallelism", &self.parallelism)
+            .field("max_rate", &self.max_rate)
+            .field("wait_for_virus_scan", &self.wait_for_virus_scan)
+            .field("validate_policies", &self.validate_policies)
+            .field("file_key", &self.file_key.as_ref().map(|_| "<redacted>"))
+            .field("public_keys", &self.public_keys)
+            .finish_non_exhaustive()
+    }
 }
 
 impl UploadOptions {
     pub fn builder(file_meta: FileMeta) -> UploadOptionsBuilder {
         UploadOptionsBuilder::new(file_meta)
     }
+
+    /// Validates the options against the constraints of the API:
+    /// - the file name must be a valid node name (max. 150 characters)
+    /// - the classification must be between 1 (public) and 4 (strictly confidential)
+    /// - an enabled expiration must be a valid date in the future
+    pub fn validate(&self) -> Result<(), DracoonClientError> {
+        let name = &self.file_meta.name;
+
+        if !is_valid_node_name(name) {
+            error!("Invalid file name: {}", name);
+            return Err(DracoonClientError::InvalidUploadOptions(format!(
+                "invalid file name '{name}'"
+            )));
+        }
+
+        if name.chars().count() > MAX_NODE_NAME_LENGTH {
+            error!("File name too long: {}", name);
+            return Err(DracoonClientError::InvalidUploadOptions(format!(
+                "file name exceeds {MAX_NODE_NAME_LENGTH} characters"
+            )));
+        }
+
+        if let Some(classification) = self.classification {
+            if !(1..=4).contains(&classification) {
+                error!("Invalid classification: {}", classification);
+                return Err(DracoonClientError::InvalidUploadOptions(format!(
+                    "invalid classification {classification} (must be between 1 and 4)"
+                )));
+            }
+        }
+
+        if let Some(expiration) = &self.expiration {
+            expiration.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// upload channel (upload id) created within an upload - shared by clones
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UploadChannelSlot(Arc<Mutex<Option<String>>>);
+
+impl UploadChannelSlot {
+    pub fn record(&self, upload_id: &str) {
+        if let Ok(mut upload_channel) = self.0.lock() {
+            upload_channel.replace(upload_id.to_string());
+        }
+    }
+
+    pub fn take(&self) -> Option<String> {
+        self.0.lock().expect("upload channel lock poisoned").take()
+    }
 }
 
 pub struct UploadOptionsBuilder {
@@ -155,6 +297,12 @@ pub struct UploadOptionsBuilder {
     classification: Option<u8>,
     keep_share_links: Option<bool>,
     resolution_strategy: Option<ResolutionStrategy>,
+    parallelism: Option<usize>,
+    max_rate: Option<u64>,
+    wait_for_virus_scan: bool,
+    validate_policies: bool,
+    file_key: Option<PlainFileKey>,
+    public_keys: Option<Vec<UserUserPublicKey>>,
 }
 
 impl UploadOptionsBuilder {
@@ -164,6 +312,12 @@ impl UploadOptionsBuilder {
             classification: None,
             keep_share_links: None,
             resolution_strategy: None,
+            parallelism: None,
+            max_rate: None,
+            wait_for_virus_scan: false,
+            validate_policies: false,
+            file_key: None,
+            public_keys: None,
             file_meta,
         }
     }
@@ -188,6 +342,49 @@ impl UploadOptionsBuilder {
         self
     }
 
+    /// Sets the max. number of chunks uploaded in parallel (S3 only).
+    /// Each chunk in flight is buffered in memory (chunk size * parallelism).
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = Some(parallelism);
+        self
+    }
+
+    /// Sets the max. upload rate of this upload (in bytes per second).
+    /// A rate limit of the client (if configured) applies in addition.
+    pub fn with_max_rate(mut self, max_rate: u64) -> Self {
+        self.max_rate = Some(max_rate);
+        self
+    }
+
+    /// Waits for the virus scan after the upload (if virus protection is enabled for the room).
+    /// The uploaded node is returned with the verdict (`virus_protection_info`).
+    pub fn with_wait_for_virus_scan(mut self, wait_for_virus_scan: bool) -> Self {
+        self.wait_for_virus_scan = wait_for_virus_scan;
+        self
+    }
+
+    /// Validates the options (file name, classification and expiration) before any content
+    /// is uploaded (see [UploadOptions::validate]).
+    pub fn with_validate_policies(mut self, validate_policies: bool) -> Self {
+        self.validate_policies = validate_policies;
+        self
+    }
+
+    /// Encrypts the file with a pre-generated file key instead of a generated one (encrypted uploads only).
+    /// The authentication tag is computed during the upload - never reuse a file key for different content.
+    pub fn with_file_key(mut self, file_key: PlainFileKey) -> Self {
+        self.file_key = Some(file_key);
+        self
+    }
+
+    /// Encrypts the file key for the given users instead of the users missing a file key
+    /// (or the public keys of the upload share) - encrypted uploads only.
+    /// Uploads to rooms still require the keypair of the current user.
+    pub fn with_public_keys(mut self, public_keys: Vec<UserUserPublicKey>) -> Self {
+        self.public_keys = Some(public_keys);
+        self
+    }
+
     pub fn build(self) -> UploadOptions {
         UploadOptions {
             expiration: self.expiration,
@@ -195,8 +392,90 @@ impl UploadOptionsBuilder {
             keep_share_links: self.keep_share_links,
             resolution_strategy: self.resolution_strategy,
             file_meta: self.file_meta,
+            parallelism: self.parallelism,
+            max_rate: self.max_rate,
+            wait_for_virus_scan: self.wait_for_virus_scan,
+            validate_policies: self.validate_policies,
+            file_key: self.file_key,
+            public_keys: self.public_keys,
+            pause: None,
+            events: None,
+            upload_channel: None,
+        }
+    }
+}
+
+/// state of a resumable (S3) upload - serialize and persist it to resume an interrupted upload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadState {
+    upload_id: String,
+    size: u64,
+    chunk_size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parallelism: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolution_strategy: Option<ResolutionStrategy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keep_share_links: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_rate: Option<u64>,
+    parts: Vec<S3FileUploadPart>,
+}
+
+impl UploadState {
+    pub(crate) fn new(
+        upload_id: String,
+        upload_options: &UploadOptions,
+        chunk_size: usize,
+    ) -> Self {
+        Self {
+            upload_id,
+            size: upload_options.file_meta.size,
+            chunk_size,
+            parallelism: upload_options.parallelism,
+            resolution_strategy: upload_options.resolution_strategy.clone(),
+            keep_share_links: upload_options.keep_share_links,
+            max_rate: upload_options.max_rate,
+            parts: Vec::new(),
         }
     }
+
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    pub(crate) fn parallelism(&self) -> Option<usize> {
+        self.parallelism
+    }
+
+    pub(crate) fn max_rate(&self) -> Option<u64> {
+        self.max_rate
+    }
+
+    /// parts already uploaded (skipped when resuming)
+    pub fn parts(&self) -> &[S3FileUploadPart] {
+        &self.parts
+    }
+
+    pub(crate) fn parts_mut(&mut self) -> &mut Vec<S3FileUploadPart> {
+        &mut self.parts
+    }
+
+    pub(crate) fn complete_request(&self) -> CompleteS3FileUploadRequest {
+        CompleteS3FileUploadRequest::builder(self.parts.clone())
+            .with_resolution_strategy(self.resolution_strategy.clone().unwrap_or_default())
+            .with_keep_share_links(self.keep_share_links.unwrap_or(false))
+            .build()
+    }
 }
 
 /// A list of nodes in DRACOON - GET /nodes
@@ -271,6 +550,53 @@ pub struct Node {
     pub cnt_folders: Option<u64>,
     pub cnt_files: Option<u64>,
     pub auth_parent_id: Option<u64>,
+    pub virus_protection_info: Option<VirusProtectionInfo>,
+}
+
+/// Aggregated file count and size (in bytes) of a container node (room or folder)
+/// including all sub nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSizeSummary {
+    pub node_id: u64,
+    pub parent_id: Option<u64>,
+    pub file_count: u64,
+    pub size: u64,
+}
+
+impl NodeSizeSummary {
+    pub fn new(node_id: u64, parent_id: Option<u64>) -> Self {
+        Self {
+            node_id,
+            parent_id,
+            file_count: 0,
+            size: 0,
+        }
+    }
+}
+
+/// A node with its sub nodes (children) organized as a tree
+#[derive(Debug, Clone)]
+pub struct NodeTree {
+    pub node: Node,
+    pub children: Vec<NodeTree>,
+}
+
+impl NodeTree {
+    pub fn new(node: Node) -> Self {
+        Self {
+            node,
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the total amount of nodes in the tree (including the root node)
+    pub fn node_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(NodeTree::node_count)
+            .sum::<usize>()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -325,22 +651,8 @@ impl NodePermissions {
         NodePermissionsBuilder::new()
     }
 
-    pub fn new_with_edit_permissions() -> Self {
-        Self {
-            manage: false,
-            read: true,
-            create: true,
-            change: true,
-            delete: true,
-            manage_download_share: true,
-            manage_upload_share: true,
-            read_recycle_bin: true,
-            restore_recycle_bin: true,
-            delete_recycle_bin: false,
-        }
-    }
-
-    pub fn new_with_read_permissions() -> Self {
+    /// Read permissions (read only access)
+    pub fn read_only() -> Self {
         Self {
             manage: false,
             read: true,
@@ -355,7 +667,24 @@ impl NodePermissions {
         }
     }
 
-    pub fn new_with_manage_permissions() -> Self {
+    /// Edit permissions (read, create, change, delete, manage shares, read and restore recycle bin)
+    pub fn edit() -> Self {
+        Self {
+            manage: false,
+            read: true,
+            create: true,
+            change: true,
+            delete: true,
+            manage_download_share: true,
+            manage_upload_share: true,
+            read_recycle_bin: true,
+            restore_recycle_bin: true,
+            delete_recycle_bin: false,
+        }
+    }
+
+    /// Room administrator permissions (all permissions)
+    pub fn room_admin() -> Self {
         Self {
             manage: true,
             read: true,
@@ -369,6 +698,21 @@ impl NodePermissions {
             delete_recycle_bin: true,
         }
     }
+
+    #[deprecated = "use NodePermissions::edit() instead"]
+    pub fn new_with_edit_permissions() -> Self {
+        Self::edit()
+    }
+
+    #[deprecated = "use NodePermissions::read_only() instead"]
+    pub fn new_with_read_permissions() -> Self {
+        Self::read_only()
+    }
+
+    #[deprecated = "use NodePermissions::room_admin() instead"]
+    pub fn new_with_manage_permissions() -> Self {
+        Self::room_admin()
+    }
 }
 
 impl Display for NodePermissions {
@@ -412,9 +756,30 @@ pub struct EncryptionInfo {
     data_space_key_state: String,
 }
 
-/// DRACOON user info on nodes (`created_by`, `updated_by`)
+/// DRACOON virus protection info of a file (scan result)
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
+pub struct VirusProtectionInfo {
+    pub verdict: VirusProtectionVerdict,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VirusProtectionVerdict {
+    NoScanning,
+    InProgress,
+    Clean,
+    Malicious,
+    /// verdict not known to this client (e.g. added in a newer DRACOON version)
+    #[serde(other)]
+    Unknown,
+}
+
+/// DRACOON user info on nodes (`created_by`, `updated_by`)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct UserInfo {
     pub id: i64,
     pub user_type: UserType,
@@ -425,7 +790,7 @@ pub struct UserInfo {
     pub email: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub enum UserType {
     #[serde(rename = "internal")]
     Internal,
@@ -452,6 +817,309 @@ pub struct DownloadUrlResponse {
     pub download_url: String,
 }
 
+impl DownloadUrlResponse {
+    /// Returns the expiry of the download url if available (presigned S3 urls).
+    /// Expiry is computed from the `X-Amz-Date` and `X-Amz-Expires` query parameters.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        let url = url::Url::parse(&self.download_url).ok()?;
+
+        let mut signed_at = None;
+        let mut expires_in = None;
+
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "X-Amz-Date" => {
+                    signed_at = NaiveDateTime::parse_from_str(&value, "%Y%m%dT%H%M%SZ")
+                        .ok()
+                        .map(|date| date.and_utc())
+                }
+                "X-Amz-Expires" => expires_in = value.parse::<i64>().ok(),
+                _ => {}
+            }
+        }
+
+        Some(signed_at? + chrono::Duration::seconds(expires_in?))
+    }
+}
+
+/// A single download of a batch download (see [crate::nodes::Download::download_many])
+#[derive(Debug, Clone)]
+pub struct DownloadRequest {
+    pub node: Node,
+    /// target file or directory (the node name is used as file name for directories)
+    pub target: PathBuf,
+}
+
+impl DownloadRequest {
+    pub fn new(node: Node, target: impl Into<PathBuf>) -> Self {
+        Self {
+            node,
+            target: target.into(),
+        }
+    }
+}
+
+/// A single upload of a batch upload (see [crate::nodes::UploadMany::upload_many])
+#[derive(Debug, Clone)]
+pub struct UploadRequest {
+    /// local file to upload
+    pub source: PathBuf,
+    pub parent_node: Node,
+    /// upload options - if not set, the file meta (name, size, modification) is read from the file
+    pub options: Option<UploadOptions>,
+}
+
+impl UploadRequest {
+    pub fn new(source: impl Into<PathBuf>, parent_node: Node) -> Self {
+        Self {
+            source: source.into(),
+            parent_node,
+            options: None,
+        }
+    }
+
+    pub fn with_options(mut self, options: UploadOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+}
+
+/// A reader upload of a batch upload (see [crate::nodes::UploadMany::upload_many_readers])
+pub struct ReaderUploadRequest<R> {
+    pub reader: R,
+    pub parent_node: Node,
+    /// upload options - the file meta (name and size) must match the content
+    pub options: UploadOptions,
+}
+
+impl<R> ReaderUploadRequest<R> {
+    pub fn new(reader: R, parent_node: Node, options: UploadOptions) -> Self {
+        Self {
+            reader,
+            parent_node,
+            options,
+        }
+    }
+}
+
+/// Result of a batch upload (see [crate::nodes::UploadMany::upload_many])
+/// Uploads of readers are reported by the file name of their upload options.
+/// - uploaded: uploaded files and the created nodes
+/// - conflicts: files not uploaded because a node with the same name exists
+/// - failed: files not uploaded because of any other error (after retries)
+#[derive(Debug, Default)]
+pub struct UploadReport {
+    pub uploaded: Vec<(PathBuf, Node)>,
+    pub conflicts: Vec<(PathBuf, DracoonClientError)>,
+    pub failed: Vec<(PathBuf, DracoonClientError)>,
+}
+
+impl UploadReport {
+    /// Returns true if all files were uploaded
+    pub fn is_success(&self) -> bool {
+        self.conflicts.is_empty() && self.failed.is_empty()
+    }
+}
+
+/// Handling of local files that differ from the remote file with the same name (directory sync)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SyncConflictPolicy {
+    /// the remote file is overwritten (new version)
+    #[default]
+    Overwrite,
+    /// the local file is uploaded with a new name (auto rename)
+    KeepBoth,
+    /// the local file is not uploaded
+    Skip,
+}
+
+/// Options of a directory sync (see [crate::nodes::SyncDirectory::sync_dir_to_node])
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    conflict_policy: SyncConflictPolicy,
+    delete_removed: bool,
+    recursive: bool,
+    keep_share_links: bool,
+    concurrency: usize,
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyncOptions {
+    /// Default options: overwrite changed files, keep removed files, include sub directories
+    pub fn new() -> Self {
+        Self {
+            conflict_policy: SyncConflictPolicy::default(),
+            delete_removed: false,
+            recursive: true,
+            keep_share_links: false,
+            concurrency: DEFAULT_NODES_CONCURRENCY,
+        }
+    }
+
+    pub fn with_conflict_policy(mut self, conflict_policy: SyncConflictPolicy) -> Self {
+        self.conflict_policy = conflict_policy;
+        self
+    }
+
+    /// deletes remote files and folders missing in the local directory
+    pub fn with_delete_removed(mut self, delete_removed: bool) -> Self {
+        self.delete_removed = delete_removed;
+        self
+    }
+
+    /// syncs sub directories (default: true)
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// keeps share links of overwritten files
+    pub fn with_keep_share_links(mut self, keep_share_links: bool) -> Self {
+        self.keep_share_links = keep_share_links;
+        self
+    }
+
+    /// max. number of concurrent uploads
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub(crate) fn conflict_policy(&self) -> SyncConflictPolicy {
+        self.conflict_policy
+    }
+
+    pub(crate) fn delete_removed(&self) -> bool {
+        self.delete_removed
+    }
+
+    pub(crate) fn recursive(&self) -> bool {
+        self.recursive
+    }
+
+    pub(crate) fn keep_share_links(&self) -> bool {
+        self.keep_share_links
+    }
+
+    pub(crate) fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+}
+
+/// Result of a directory sync
+/// - uploads: uploaded (new and changed) files
+/// - skipped: changed files not uploaded (conflict policy `Skip`)
+/// - deleted: remote nodes deleted because they are missing locally
+/// - created_folders: remote folders created for local sub directories
+/// - failed_folders: local sub directories not synced (remote folder could not be created)
+///
+/// Removed nodes are not deleted if any upload or folder failed.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub uploads: UploadReport,
+    pub skipped: Vec<PathBuf>,
+    pub deleted: Vec<Node>,
+    pub created_folders: Vec<Node>,
+    pub failed_folders: Vec<(PathBuf, DracoonClientError)>,
+}
+
+impl SyncReport {
+    /// Returns true if all files and sub directories were synced
+    pub fn is_success(&self) -> bool {
+        self.uploads.is_success() && self.failed_folders.is_empty()
+    }
+}
+
+/// Known state of a previously downloaded file - used to skip unchanged files
+/// (see [crate::nodes::Download::download_if_modified])
+#[derive(Debug, Clone, Default)]
+pub struct DownloadCondition {
+    hash: Option<String>,
+    etag: Option<String>,
+    modified_since: Option<DateTime<Utc>>,
+}
+
+impl DownloadCondition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// known file hash (MD5) of the node
+    pub fn with_hash(mut self, hash: impl Into<String>) -> Self {
+        self.hash = Some(hash.into());
+        self
+    }
+
+    /// known ETag of the file (requires a HEAD request on the download url)
+    pub fn with_etag(mut self, etag: impl Into<String>) -> Self {
+        self.etag = Some(etag.into());
+        self
+    }
+
+    /// last known update of the node
+    pub fn with_modified_since(mut self, modified_since: DateTime<Utc>) -> Self {
+        self.modified_since = Some(modified_since);
+        self
+    }
+
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Checks the node metadata (hash, update timestamp) against the known state.
+    /// Returns `None` if the node metadata cannot be compared.
+    pub fn is_unchanged(&self, node: &Node) -> Option<bool> {
+        if let (Some(known_hash), Some(hash)) = (&self.hash, &node.hash) {
+            return Some(known_hash.eq_ignore_ascii_case(hash));
+        }
+
+        let updated_at = node.updated_at.or(node.timestamp_modification);
+
+        if let (Some(modified_since), Some(updated_at)) = (self.modified_since, updated_at) {
+            return Some(updated_at <= modified_since);
+        }
+
+        None
+    }
+}
+
+/// Outcome of a conditional download (see [crate::nodes::Download::download_if_modified])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadOutcome {
+    Downloaded,
+    NotModified,
+}
+
+impl DownloadOutcome {
+    pub fn is_not_modified(&self) -> bool {
+        *self == DownloadOutcome::NotModified
+    }
+}
+
+/// Request to create a ZIP archive download of multiple nodes - POST /nodes/zip
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateZipDownloadRequest {
+    node_ids: Vec<u64>,
+}
+
+impl CreateZipDownloadRequest {
+    pub fn node_ids(&self) -> &[u64] {
+        &self.node_ids
+    }
+}
+
+impl From<Vec<u64>> for CreateZipDownloadRequest {
+    fn from(node_ids: Vec<u64>) -> Self {
+        Self { node_ids }
+    }
+}
+
 /// Error response for S3 requests (XML)
 #[derive(Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "PascalCase")]
@@ -606,6 +1274,12 @@ impl CreateFileUploadRequest {
 
         req.build()
     }
+
+    /// removes the size (unknown size - calculated by DRACOON on completion)
+    pub(crate) fn without_size(mut self) -> Self {
+        self.size = None;
+        self
+    }
 }
 
 pub struct CreateFileUploadRequestBuilder {
@@ -812,7 +1486,7 @@ impl CompleteUploadRequestBuilder {
     }
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub enum ResolutionStrategy {
     #[default]
     #[serde(rename = "autorename")]
@@ -823,7 +1497,7 @@ pub enum ResolutionStrategy {
     Fail,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct S3FileUploadPart {
     part_number: u32,
@@ -837,6 +1511,10 @@ impl S3FileUploadPart {
             part_etag,
         }
     }
+
+    pub fn part_number(&self) -> u32 {
+        self.part_number
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -851,6 +1529,59 @@ impl From<Vec<u64>> for DeleteNodesRequest {
     }
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteDeletedNodesRequest {
+    deleted_node_ids: Vec<u64>,
+}
+
+impl DeleteDeletedNodesRequest {
+    pub fn deleted_node_ids(&self) -> &[u64] {
+        &self.deleted_node_ids
+    }
+}
+
+impl From<Vec<u64>> for DeleteDeletedNodesRequest {
+    fn from(deleted_node_ids: Vec<u64>) -> Self {
+        Self { deleted_node_ids }
+    }
+}
+
+/// A (previous) version of a file in the recycle bin - GET /nodes/{nodeId}/deleted_nodes/versions
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedNodeVersion {
+    pub id: u64,
+    pub reference_id: Option<u64>,
+    #[serde(rename = "type")]
+    pub node_type: NodeType,
+    pub name: String,
+    pub size: Option<u64>,
+    pub classification: Option<u64>,
+    pub notes: Option<String>,
+    pub is_encrypted: Option<bool>,
+    pub expire_at: Option<DateTime<Utc>>,
+    pub accessed_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub created_by: Option<UserInfo>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub updated_by: Option<UserInfo>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub deleted_by: Option<UserInfo>,
+    pub timestamp_creation: Option<DateTime<Utc>>,
+    pub timestamp_modification: Option<DateTime<Utc>>,
+}
+
+pub type DeletedNodeVersionsList = RangedItems<DeletedNodeVersion>;
+
+#[async_trait]
+impl FromResponse for DeletedNodeVersionsList {
+    /// transforms a response into a DeletedNodeVersionsList
+    async fn from_response(res: Response) -> Result<Self, DracoonClientError> {
+        parse_body::<Self, DracoonErrorResponse>(res).await
+    }
+}
+
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TransferNodesRequest {
@@ -1123,6 +1854,52 @@ impl UpdateFolderRequestBuilder {
     }
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFilesBulkRequest {
+    object_ids: Vec<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expiration: Option<ObjectExpiration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    classification: Option<u8>,
+}
+
+pub struct UpdateFilesBulkRequestBuilder {
+    object_ids: Vec<u64>,
+    expiration: Option<ObjectExpiration>,
+    classification: Option<u8>,
+}
+
+impl UpdateFilesBulkRequest {
+    pub fn builder(object_ids: Vec<u64>) -> UpdateFilesBulkRequestBuilder {
+        UpdateFilesBulkRequestBuilder {
+            object_ids,
+            expiration: None,
+            classification: None,
+        }
+    }
+}
+
+impl UpdateFilesBulkRequestBuilder {
+    pub fn with_expiration(mut self, expiration: impl Into<ObjectExpiration>) -> Self {
+        self.expiration = Some(expiration.into());
+        self
+    }
+
+    pub fn with_classification(mut self, classification: u8) -> Self {
+        self.classification = Some(classification);
+        self
+    }
+
+    pub fn build(self) -> UpdateFilesBulkRequest {
+        UpdateFilesBulkRequest {
+            object_ids: self.object_ids,
+            expiration: self.expiration,
+            classification: self.classification,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UserIdFileItem {
@@ -1137,6 +1914,15 @@ pub struct UserUserPublicKey {
     pub public_key_container: PublicKeyContainer,
 }
 
+impl UserUserPublicKey {
+    pub fn new(id: u64, public_key_container: PublicKeyContainer) -> Self {
+        Self {
+            id,
+            public_key_container,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FileFileKeys {
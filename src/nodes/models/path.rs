@@ -0,0 +1,188 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::DracoonClientError;
+
+use super::Node;
+
+/// Characters that are not allowed in node names
+const INVALID_NAME_CHARS: [char; 9] = ['/', '\\', '<', '>', ':', '"', '|', '?', '*'];
+
+/// Absolute path of a node in DRACOON (e.g. `/room/folder/file.txt`).
+///
+/// Paths are normalized on creation: empty segments and `.` are removed, `..` removes the previous segment.
+/// Node names are stored in plain text in DRACOON (also in encrypted rooms), hence paths of encrypted nodes
+/// can be handled like any other path - the segments are validated against the node name rules.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct NodePath {
+    segments: Vec<String>,
+}
+
+impl NodePath {
+    /// The root path (`/`)
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Parses and normalizes a path - relative paths are treated as relative to the root.
+    pub fn new(path: impl AsRef<str>) -> Result<Self, DracoonClientError> {
+        Self::root().join(path)
+    }
+
+    /// Returns a new path with the given (relative) path appended.
+    pub fn join(&self, path: impl AsRef<str>) -> Result<Self, DracoonClientError> {
+        let path = path.as_ref();
+        let mut segments = self.segments.clone();
+
+        for segment in path.split('/') {
+            match segment {
+                "" | "." => continue,
+                ".." => {
+                    segments
+                        .pop()
+                        .ok_or_else(|| DracoonClientError::InvalidPath(path.to_string()))?;
+                }
+                name if !is_valid_node_name(name) => {
+                    return Err(DracoonClientError::InvalidPath(path.to_string()))
+                }
+                name => segments.push(name.to_string()),
+            }
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Returns the path of a node (parent path and name)
+    pub fn from_node(node: &Node) -> Result<Self, DracoonClientError> {
+        let parent_path = node.parent_path.as_deref().unwrap_or("/");
+        Self::new(parent_path)?.join(&node.name)
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Returns the parent path (`None` for the root path)
+    pub fn parent(&self) -> Option<Self> {
+        if self.is_root() {
+            return None;
+        }
+
+        Some(Self {
+            segments: self.segments[..self.segments.len() - 1].to_vec(),
+        })
+    }
+
+    /// Returns the name of the node (last segment) - `None` for the root path
+    pub fn name(&self) -> Option<&str> {
+        self.segments.last().map(String::as_str)
+    }
+
+    /// Returns the extension of the node name (if any)
+    pub fn extension(&self) -> Option<&str> {
+        self.name()
+            .and_then(|name| name.rsplit_once('.'))
+            .filter(|(stem, _)| !stem.is_empty())
+            .map(|(_, extension)| extension)
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Returns the depth level of the node below the root room (root room = 0)
+    pub fn depth(&self) -> u64 {
+        self.segments.len().saturating_sub(1) as u64
+    }
+
+    /// Returns the parent path in DRACOON format (always with trailing slash, e.g. `/room/folder/`)
+    pub fn parent_path(&self) -> String {
+        match self.parent() {
+            Some(parent) if !parent.is_root() => format!("{parent}/"),
+            _ => String::from("/"),
+        }
+    }
+}
+
+impl Display for NodePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "/{}", self.segments.join("/"))
+    }
+}
+
+impl FromStr for NodePath {
+    type Err = DracoonClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+/// Checks if a name is a valid node name (no invalid characters, not ending with a dot or whitespace)
+pub fn is_valid_node_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.contains(&INVALID_NAME_CHARS[..])
+        && !name.ends_with('.')
+        && !name.ends_with(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_path_normalization() {
+        let path = NodePath::new("//room/./folder/../sub/file.txt").unwrap();
+        assert_eq!(path.to_string(), "/room/sub/file.txt");
+        assert_eq!(path.segments().len(), 3);
+    }
+
+    #[test]
+    fn test_node_path_root() {
+        let path = NodePath::new("/").unwrap();
+        assert!(path.is_root());
+        assert_eq!(path.to_string(), "/");
+        assert_eq!(path.name(), None);
+        assert_eq!(path.parent(), None);
+        assert_eq!(path.parent_path(), "/");
+        assert_eq!(path.depth(), 0);
+    }
+
+    #[test]
+    fn test_node_path_parent_and_name() {
+        let path: NodePath = "/room/folder/file.txt".parse().unwrap();
+        assert_eq!(path.name(), Some("file.txt"));
+        assert_eq!(path.extension(), Some("txt"));
+        assert_eq!(path.parent().unwrap().to_string(), "/room/folder");
+        assert_eq!(path.parent_path(), "/room/folder/");
+        assert_eq!(path.depth(), 2);
+
+        let room = NodePath::new("/room").unwrap();
+        assert_eq!(room.parent_path(), "/");
+        assert_eq!(room.depth(), 0);
+    }
+
+    #[test]
+    fn test_node_path_join() {
+        let path = NodePath::new("/room").unwrap();
+        let joined = path.join("folder/sub").unwrap();
+        assert_eq!(joined.to_string(), "/room/folder/sub");
+        assert_eq!(joined.join("..").unwrap().to_string(), "/room/folder");
+    }
+
+    #[test]
+    fn test_node_path_invalid() {
+        assert!(NodePath::new("/..").is_err());
+        assert!(NodePath::new("/room/fo:lder").is_err());
+        assert!(NodePath::new("/room/folder./file").is_err());
+    }
+
+    #[test]
+    fn test_node_path_extension() {
+        assert_eq!(NodePath::new("/room/.hidden").unwrap().extension(), None);
+        assert_eq!(NodePath::new("/room/folder").unwrap().extension(), None);
+        assert_eq!(
+            NodePath::new("/room/archive.tar.gz").unwrap().extension(),
+            Some("gz")
+        );
+    }
+}
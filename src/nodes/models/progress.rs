@@ -0,0 +1,213 @@
+use std::time::{Duration, Instant};
+
+/// Weight of the latest measurement for the (smoothed) transfer rate
+const RATE_SMOOTHING: f64 = 0.3;
+
+/// Progress of a transfer (download or upload) passed to progress callbacks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferProgress {
+    /// bytes transferred since the last progress event
+    pub bytes_transferred: u64,
+    /// bytes transferred in total
+    pub bytes_done: u64,
+    /// total size of the transfer (in bytes)
+    pub total: u64,
+    /// current transfer rate (bytes per second)
+    pub rate: f64,
+    /// estimated time until the transfer is finished (unknown if no rate is available)
+    pub eta: Option<Duration>,
+    /// time since the transfer started
+    pub elapsed: Duration,
+    /// progress of the current part (chunk) - only set for uploads
+    pub part: Option<PartProgress>,
+}
+
+impl TransferProgress {
+    /// Returns the progress as fraction (0.0 - 1.0)
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 1.0;
+        }
+
+        (self.bytes_done as f64 / self.total as f64).min(1.0)
+    }
+}
+
+/// Progress of a single part (chunk) of an upload
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PartProgress {
+    /// number of the part (starting with 1)
+    pub part_number: u32,
+    /// bytes of the part transferred in total
+    pub bytes_done: u64,
+    /// size of the part (in bytes)
+    pub size: u64,
+}
+
+impl PartProgress {
+    pub fn new(part_number: u32, bytes_done: u64, size: u64) -> Self {
+        Self {
+            part_number,
+            bytes_done,
+            size,
+        }
+    }
+
+    /// Returns the progress of the part as fraction (0.0 - 1.0)
+    pub fn fraction(&self) -> f64 {
+        if self.size == 0 {
+            return 1.0;
+        }
+
+        (self.bytes_done as f64 / self.size as f64).min(1.0)
+    }
+}
+
+/// Keeps track of transferred bytes and the transfer rate to build [TransferProgress] events
+#[derive(Debug, Clone)]
+pub struct ProgressTracker {
+    started_at: Instant,
+    last_update: Instant,
+    bytes_done: u64,
+    rate: f64,
+}
+
+impl Default for ProgressTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressTracker {
+    pub fn new() -> Self {
+        Self::with_offset(0)
+    }
+
+    /// Creates a tracker for a resumed transfer (offset = bytes already transferred)
+    pub fn with_offset(offset: u64) -> Self {
+        let now = Instant::now();
+
+        Self {
+            started_at: now,
+            last_update: now,
+            bytes_done: offset,
+            rate: 0.0,
+        }
+    }
+
+    /// Removes transferred bytes (e.g. of a failed attempt that is transferred again)
+    pub fn rewind(&mut self, bytes: u64) {
+        self.bytes_done = self.bytes_done.saturating_sub(bytes);
+    }
+
+    /// Adds transferred bytes and returns the current progress
+    pub fn update(&mut self, bytes_transferred: u64, total: u64) -> TransferProgress {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_update).as_secs_f64();
+
+        self.bytes_done += bytes_transferred;
+
+        if elapsed > 0.0 {
+            let current_rate = bytes_transferred as f64 / elapsed;
+            self.rate = if self.rate > 0.0 {
+                RATE_SMOOTHING * current_rate + (1.0 - RATE_SMOOTHING) * self.rate
+            } else {
+                current_rate
+            };
+        }
+
+        self.last_update = now;
+
+        let remaining = total.saturating_sub(self.bytes_done);
+        let eta = if remaining == 0 {
+            Some(Duration::ZERO)
+        } else if self.rate > 0.0 {
+            Duration::try_from_secs_f64(remaining as f64 / self.rate).ok()
+        } else {
+            None
+        };
+
+        TransferProgress {
+            bytes_transferred,
+            bytes_done: self.bytes_done,
+            total,
+            rate: self.rate,
+            eta,
+            elapsed: now.duration_since(self.started_at),
+            part: None,
+        }
+    }
+}
+
+/// Event of a transfer - see [crate::nodes::Download::download_with_events]
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferEvent {
+    /// transfer started (total size in bytes)
+    Started { total: u64 },
+    /// a chunk was transferred
+    Progressed(TransferProgress),
+    /// a chunk failed with a transient error and is retried after the given delay
+    ChunkRetried { retry: u32, delay: Duration },
+    /// transfer finished successfully
+    Finished,
+}
+
+/// Event of an upload - see [crate::nodes::Upload::upload_with_events]
+#[derive(Debug, Clone, PartialEq)]
+pub enum UploadEvent {
+    /// upload started (total size in bytes)
+    Started { total: u64 },
+    /// a chunk was uploaded
+    Progressed(TransferProgress),
+    /// a part was uploaded completely (S3 only)
+    PartCompleted { part_number: u32, size: u64 },
+    /// a part failed with a transient error and is uploaded again (S3 only)
+    PartRetried { part_number: u32, retry: u32 },
+    /// all content is uploaded - the upload is finalized
+    Finalizing,
+    /// upload finished successfully
+    Finished,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_tracker_counts_bytes() {
+        let mut tracker = ProgressTracker::new();
+
+        let first = tracker.update(10, 100);
+        assert_eq!(first.bytes_transferred, 10);
+        assert_eq!(first.bytes_done, 10);
+        assert_eq!(first.total, 100);
+
+        std::thread::sleep(Duration::from_millis(5));
+
+        let second = tracker.update(40, 100);
+        assert_eq!(second.bytes_done, 50);
+        assert!(second.rate > 0.0);
+        assert!(second.eta.is_some());
+        assert!((second.fraction() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_progress_tracker_with_offset() {
+        let mut tracker = ProgressTracker::with_offset(90);
+
+        let progress = tracker.update(10, 100);
+        assert_eq!(progress.bytes_done, 100);
+        assert_eq!(progress.eta, Some(Duration::ZERO));
+        assert!((progress.fraction() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_part_progress_fraction() {
+        let part = PartProgress::new(2, 25, 100);
+        assert_eq!(part.part_number, 2);
+        assert!((part.fraction() - 0.25).abs() < f64::EPSILON);
+
+        let empty = PartProgress::new(1, 0, 0);
+        assert!((empty.fraction() - 1.0).abs() < f64::EPSILON);
+    }
+}
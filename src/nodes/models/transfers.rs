@@ -0,0 +1,77 @@
+use std::{fmt::Display, path::PathBuf, pin::Pin};
+
+use futures_util::Stream;
+
+use super::{Node, TransferProgress};
+
+/// Id of a transfer queued in a [crate::nodes::TransferManager]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TransferId(pub(crate) u64);
+
+impl Display for TransferId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// State of a managed transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferState {
+    /// waiting for a free transfer slot
+    Queued,
+    Running,
+    /// no content is transferred until the transfer is resumed
+    Paused,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl TransferState {
+    /// Returns true if the transfer is completed, failed or cancelled
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            TransferState::Completed | TransferState::Failed | TransferState::Cancelled
+        )
+    }
+}
+
+/// Event of a managed transfer
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManagedTransferEvent {
+    /// transfer is queued (waiting for a free transfer slot)
+    Queued,
+    /// transfer started (total size in bytes)
+    Started {
+        total: u64,
+    },
+    /// content was transferred
+    Progressed(TransferProgress),
+    Paused,
+    Resumed,
+    /// transfer finished successfully
+    Completed,
+    /// transfer failed (error message)
+    Failed(String),
+    Cancelled,
+}
+
+/// Event of a transfer managed by a [crate::nodes::TransferManager]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferUpdate {
+    pub id: TransferId,
+    pub event: ManagedTransferEvent,
+}
+
+/// Result of a managed transfer
+#[derive(Debug, Clone)]
+pub enum TransferOutcome {
+    /// uploaded node
+    Uploaded(Node),
+    /// path of the downloaded file
+    Downloaded(PathBuf),
+}
+
+/// Stream of the updates of all transfers of a [crate::nodes::TransferManager]
+pub type TransferUpdateStream = Pin<Box<dyn Stream<Item = TransferUpdate> + Send>>;
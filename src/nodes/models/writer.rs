@@ -0,0 +1,64 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt, DuplexStream},
+    task::JoinHandle,
+};
+use tracing::error;
+
+use crate::client::errors::DracoonClientError;
+
+use super::Node;
+
+/// Writer for an upload running in the background (see [crate::nodes::OpenUpload::open_upload]).
+/// Written content is uploaded while writing - call [UploadWriter::finish] after writing
+/// all content to complete the upload. Dropping the writer without finishing fails the upload
+/// (unless all content was written).
+pub struct UploadWriter {
+    writer: DuplexStream,
+    upload: JoinHandle<Result<Node, DracoonClientError>>,
+}
+
+impl UploadWriter {
+    pub(crate) fn new(
+        writer: DuplexStream,
+        upload: JoinHandle<Result<Node, DracoonClientError>>,
+    ) -> Self {
+        Self { writer, upload }
+    }
+
+    /// Closes the writer and waits for the upload to complete - returns the uploaded node
+    pub async fn finish(mut self) -> Result<Node, DracoonClientError> {
+        // writing fails if the upload failed already (the upload error is returned below)
+        if let Err(err) = self.writer.shutdown().await {
+            error!("Error closing upload writer: {}", err);
+        }
+
+        self.upload.await.map_err(|err| {
+            error!("Error running upload: {}", err);
+            DracoonClientError::Internal
+        })?
+    }
+}
+
+impl AsyncWrite for UploadWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.writer).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.writer).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.writer).poll_shutdown(cx)
+    }
+}
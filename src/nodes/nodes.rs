@@ -1,22 +1,30 @@
 #![allow(clippy::module_inception)]
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
+use futures_util::{stream, StreamExt, TryStreamExt};
 use reqwest::header;
 use tracing::{debug, error};
 
 use crate::{
     client::{errors::DracoonClientError, Connected},
     constants::{
-        DRACOON_API_PREFIX, FILES_BASE, FILES_KEYS, MISSING_FILE_KEYS, NODES_BASE, NODES_COPY,
-        NODES_MOVE, NODES_SEARCH,
+        DEFAULT_NODES_CONCURRENCY, DELETED_NODES_BATCH, DELETED_NODES_VERSIONS, DRACOON_API_PREFIX,
+        FILES_BASE, FILES_KEYS, FILES_UPDATE_BATCH, FILES_UPLOAD, LIST_ALL_PAGE_LIMIT,
+        MISSING_FILE_KEYS, NODES_BASE, NODES_COPY, NODES_DELETED_NODES, NODES_MOVE, NODES_SEARCH,
     },
     models::ListAllParams,
-    utils::FromResponse,
+    utils::{list_all, FromResponse},
     Dracoon,
 };
 
 use super::{
-    models::{DeleteNodesRequest, Node, NodeList, TransferNodesRequest},
+    models::{
+        DeleteDeletedNodesRequest, DeleteNodesRequest, DeletedNodeVersionsList, Node, NodeList,
+        NodePath, NodeSizeSummary, NodeTree, NodeType, NodesSearchFilter,
+        ReclassificationProgressCallback, TransferNodesRequest, UpdateFilesBulkRequest,
+    },
     MissingFileKeys, MissingKeysResponse, Nodes, NodesEndpoint, UserFileKeySetBatchRequest,
 };
 
@@ -62,42 +70,39 @@ impl Nodes for NodesEndpoint<Connected> {
     }
 
     async fn get_node_from_path(&self, path: &str) -> Result<Option<Node>, DracoonClientError> {
-        // TODO: refactor and make use of search_nodes
-        let url_part = format!("/{DRACOON_API_PREFIX}/{NODES_BASE}/{NODES_SEARCH}");
-
         let (parent_path, name, depth) = parse_node_path(path).map_err(|_| {
             error!("Failed to parse path: {}", path);
             DracoonClientError::InvalidPath(path.to_string())
         })?;
 
-        let mut api_url = self.client().build_api_url(&url_part);
+        // search is a "contains" search - paginate and match the exact name
+        let depth_level = i8::try_from(depth).unwrap_or(-1);
+        let mut offset = 0;
 
-        api_url
-            .query_pairs_mut()
-            .append_pair("search_string", &name)
-            .append_pair("depth_level", &depth.to_string())
-            .append_pair("filter", &format!("parentPath:eq:{parent_path}"))
-            .finish();
+        loop {
+            let params = ListAllParams::builder()
+                .with_filter(NodesSearchFilter::parent_path_equals(&parent_path))
+                .with_offset(offset)
+                .with_limit(LIST_ALL_PAGE_LIMIT)
+                .build();
 
-        let response = self
-            .client()
-            .http
-            .get(api_url)
-            .header(
-                header::AUTHORIZATION,
-                self.client().get_auth_header().await?,
-            )
-            .header(header::CONTENT_TYPE, "application/json")
-            .send()
-            .await?;
+            let nodes = self
+                .search_nodes(&name, None, Some(depth_level), Some(params))
+                .await?;
+
+            debug!("Found {} nodes", nodes.items.len());
+
+            let total = nodes.range.total;
 
-        let nodes = NodeList::from_response(response).await?;
+            if let Some(node) = nodes.items.into_iter().find(|node| node.name == name) {
+                return Ok(Some(node));
+            }
 
-        debug!("Found {} nodes", nodes.items.len());
+            offset += LIST_ALL_PAGE_LIMIT;
 
-        match nodes.items.len() {
-            1 => Ok(nodes.items.into_iter().next()),
-            _ => Ok(None),
+            if offset >= total {
+                return Ok(None);
+            }
         }
     }
 
@@ -215,6 +220,76 @@ impl Nodes for NodesEndpoint<Connected> {
         Ok(())
     }
 
+    async fn get_file_versions(
+        &self,
+        parent_id: u64,
+        name: &str,
+        params: Option<ListAllParams>,
+    ) -> Result<DeletedNodeVersionsList, DracoonClientError> {
+        let params = params.unwrap_or_default();
+        let url_part = format!(
+            "/{DRACOON_API_PREFIX}/{NODES_BASE}/{parent_id}/{NODES_DELETED_NODES}/{DELETED_NODES_VERSIONS}"
+        );
+
+        let mut api_url = self.client().build_api_url(&url_part);
+
+        let sorts = params.sort_to_string();
+
+        api_url
+            .query_pairs_mut()
+            .append_pair("type", "file")
+            .append_pair("name", name)
+            .extend_pairs(params.limit.map(|v| ("limit", v.to_string())))
+            .extend_pairs(params.offset.map(|v| ("offset", v.to_string())))
+            .extend_pairs(params.sort.map(|_| ("sort", sorts)))
+            .finish();
+
+        let response = self
+            .client()
+            .http
+            .get(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        DeletedNodeVersionsList::from_response(response).await
+    }
+
+    async fn delete_deleted_nodes(
+        &self,
+        req: DeleteDeletedNodesRequest,
+    ) -> Result<(), DracoonClientError> {
+        let url_part = format!("/{DRACOON_API_PREFIX}/{NODES_BASE}/{NODES_DELETED_NODES}");
+
+        for batch in req.deleted_node_ids().chunks(DELETED_NODES_BATCH) {
+            let api_url = self.client().build_api_url(&url_part);
+            let batch_req = DeleteDeletedNodesRequest::from(batch.to_vec());
+
+            let response = self
+                .client()
+                .http
+                .delete(api_url)
+                .header(
+                    header::AUTHORIZATION,
+                    self.client().get_auth_header().await?,
+                )
+                .header(header::CONTENT_TYPE, "application/json")
+                .json(&batch_req)
+                .send()
+                .await?;
+
+            if response.status().is_server_error() || response.status().is_client_error() {
+                return Err(DracoonClientError::from_response(response).await?);
+            }
+        }
+
+        Ok(())
+    }
+
     async fn move_nodes(
         &self,
         req: TransferNodesRequest,
@@ -266,6 +341,236 @@ impl Nodes for NodesEndpoint<Connected> {
 
         Node::from_response(response).await
     }
+
+    async fn get_node_size_summaries(
+        &self,
+        node_id: u64,
+        max_concurrency: Option<usize>,
+    ) -> Result<Vec<NodeSizeSummary>, DracoonClientError> {
+        let max_concurrency = max_concurrency.unwrap_or(DEFAULT_NODES_CONCURRENCY).max(1);
+
+        // summaries are stored in BFS order: children are always stored after their parent
+        let mut summaries = vec![NodeSizeSummary::new(node_id, None)];
+        let mut summary_idx = HashMap::from([(node_id, 0)]);
+        let mut level = vec![node_id];
+
+        while !level.is_empty() {
+            let children: Vec<(u64, Vec<Node>)> = stream::iter(level)
+                .map(|parent_id| async move {
+                    self.get_all_child_nodes(parent_id)
+                        .await
+                        .map(|nodes| (parent_id, nodes))
+                })
+                .buffer_unordered(max_concurrency)
+                .try_collect()
+                .await?;
+
+            let mut next_level = Vec::new();
+
+            for (parent_id, nodes) in children {
+                for node in nodes {
+                    if node.node_type == NodeType::File {
+                        let summary = &mut summaries[summary_idx[&parent_id]];
+                        summary.file_count += 1;
+                        summary.size += node.size.unwrap_or(0);
+                    } else {
+                        summary_idx.insert(node.id, summaries.len());
+                        summaries.push(NodeSizeSummary::new(node.id, Some(parent_id)));
+                        next_level.push(node.id);
+                    }
+                }
+            }
+
+            level = next_level;
+        }
+
+        // aggregate bottom-up
+        for idx in (1..summaries.len()).rev() {
+            let NodeSizeSummary {
+                parent_id,
+                file_count,
+                size,
+                ..
+            } = summaries[idx];
+
+            if let Some(parent_idx) = parent_id.and_then(|id| summary_idx.get(&id)) {
+                summaries[*parent_idx].file_count += file_count;
+                summaries[*parent_idx].size += size;
+            }
+        }
+
+        Ok(summaries)
+    }
+
+    async fn get_node_tree(
+        &self,
+        root_id: u64,
+        depth: u32,
+    ) -> Result<NodeTree, DracoonClientError> {
+        let root = self.get_node(root_id).await?;
+
+        let mut children_by_parent: HashMap<u64, Vec<Node>> = HashMap::new();
+        let mut level = if root.node_type == NodeType::File {
+            Vec::new()
+        } else {
+            vec![root_id]
+        };
+
+        for _ in 0..depth {
+            if level.is_empty() {
+                break;
+            }
+
+            let children: Vec<(u64, Vec<Node>)> = stream::iter(level)
+                .map(|parent_id| async move {
+                    self.get_all_child_nodes(parent_id)
+                        .await
+                        .map(|nodes| (parent_id, nodes))
+                })
+                .buffer_unordered(DEFAULT_NODES_CONCURRENCY)
+                .try_collect()
+                .await?;
+
+            level = children
+                .iter()
+                .flat_map(|(_, nodes)| nodes)
+                .filter(|node| node.node_type != NodeType::File)
+                .map(|node| node.id)
+                .collect();
+
+            children_by_parent.extend(children);
+        }
+
+        Ok(build_node_tree(root, &mut children_by_parent))
+    }
+
+    async fn update_files(&self, req: UpdateFilesBulkRequest) -> Result<(), DracoonClientError> {
+        let url_part = format!("/{DRACOON_API_PREFIX}/{NODES_BASE}/{FILES_BASE}");
+
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .put(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&req)
+            .send()
+            .await?;
+
+        if response.status().is_server_error() || response.status().is_client_error() {
+            return Err(DracoonClientError::from_response(response).await?);
+        }
+
+        Ok(())
+    }
+
+    async fn reclassify_files(
+        &self,
+        node_id: u64,
+        classification: u8,
+        mut callback: Option<ReclassificationProgressCallback>,
+    ) -> Result<u64, DracoonClientError> {
+        let files = list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_filter(NodesSearchFilter::is_file())
+                .with_offset(offset)
+                .with_limit(limit)
+                .build();
+
+            self.search_nodes("*", Some(node_id), Some(-1), Some(params))
+        })
+        .await?;
+
+        let file_ids = files
+            .into_iter()
+            .filter(|file| file.classification != Some(u64::from(classification)))
+            .map(|file| file.id)
+            .collect::<Vec<_>>();
+
+        let total = file_ids.len() as u64;
+        let mut updated = 0;
+
+        debug!("Reclassifying {} files below node {}", total, node_id);
+
+        for batch in file_ids.chunks(FILES_UPDATE_BATCH) {
+            let req = UpdateFilesBulkRequest::builder(batch.to_vec())
+                .with_classification(classification)
+                .build();
+
+            self.update_files(req).await?;
+
+            updated += batch.len() as u64;
+
+            if let Some(callback) = callback.as_mut() {
+                callback(updated, total);
+            }
+        }
+
+        Ok(updated)
+    }
+
+    async fn cancel_upload(&self, upload_id: &str) -> Result<(), DracoonClientError> {
+        let url_part =
+            format!("/{DRACOON_API_PREFIX}/{NODES_BASE}/{FILES_BASE}/{FILES_UPLOAD}/{upload_id}");
+
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .delete(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .send()
+            .await?;
+
+        if response.status().is_server_error() || response.status().is_client_error() {
+            error!("Error cancelling upload {}", upload_id);
+            return Err(DracoonClientError::from_response(response).await?);
+        }
+
+        Ok(())
+    }
+}
+
+fn build_node_tree(node: Node, children_by_parent: &mut HashMap<u64, Vec<Node>>) -> NodeTree {
+    let children = children_by_parent.remove(&node.id).unwrap_or_default();
+
+    NodeTree {
+        children: children
+            .into_iter()
+            .map(|child| build_node_tree(child, children_by_parent))
+            .collect(),
+        node,
+    }
+}
+
+#[async_trait]
+pub(crate) trait NodesInternal {
+    /// Returns all direct children of a node (paginated)
+    async fn get_all_child_nodes(&self, parent_id: u64) -> Result<Vec<Node>, DracoonClientError>;
+}
+
+#[async_trait]
+impl NodesInternal for NodesEndpoint<Connected> {
+    async fn get_all_child_nodes(&self, parent_id: u64) -> Result<Vec<Node>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_offset(offset)
+                .with_limit(limit)
+                .build();
+
+            self.get_nodes(Some(parent_id), None, Some(params))
+        })
+        .await
+    }
 }
 
 #[async_trait]
@@ -384,20 +689,10 @@ impl MissingFileKeysInternal for Dracoon<Connected> {
 type ParsedPath = (String, String, u64);
 
 pub fn parse_node_path(path: &str) -> Result<ParsedPath, DracoonClientError> {
-    if path == "/" {
-        return Ok((String::from("/"), String::new(), 0));
-    }
-
-    let path_parts: Vec<&str> = path.trim_end_matches('/').split('/').collect();
-    let name = String::from(
-        *path_parts
-            .last()
-            .ok_or(DracoonClientError::InvalidPath(path.to_string()))?,
-    );
-    let parent_path = format!("{}/", path_parts[..path_parts.len() - 1].join("/"));
-    let depth = path_parts.len().saturating_sub(2) as u64;
+    let node_path = NodePath::new(path)?;
+    let name = node_path.name().unwrap_or_default().to_string();
 
-    Ok((parent_path, name, depth))
+    Ok((node_path.parent_path(), name, node_path.depth()))
 }
 
 #[cfg(test)]
@@ -1,23 +1,39 @@
+use std::collections::{HashMap, HashSet};
+
 use async_trait::async_trait;
+use futures_util::{stream, StreamExt, TryStreamExt};
 use reqwest::header;
+use tracing::{debug, error};
 
 use crate::{
     client::{errors::DracoonClientError, Connected},
+    config::S3TagList,
     constants::{
-        DRACOON_API_PREFIX, NODES_BASE, ROOMS_BASE, ROOMS_CONFIG, ROOMS_ENCRYPT, ROOMS_GROUPS,
-        ROOMS_POLICIES, ROOMS_USERS,
+        DEFAULT_NODES_CONCURRENCY, DRACOON_API_PREFIX, NODES_BASE, ROOMS_BASE, ROOMS_CONFIG,
+        ROOMS_ENCRYPT, ROOMS_GROUPS, ROOMS_POLICIES, ROOMS_S3_TAGS, ROOMS_USERS, ROOMS_WEBHOOKS,
     },
     models::ListAllParams,
-    utils::FromResponse,
+    users::UsersFilter,
+    utils::{list_all, FromResponse},
+    Dracoon, Users,
 };
 
 use self::models::{
-    ConfigRoomRequest, CreateRoomRequest, EncryptRoomRequest, RoomGroupList,
-    RoomGroupsAddBatchRequest, RoomGroupsDeleteBatchRequest, RoomPolicies, RoomPoliciesRequest,
-    RoomUserList, RoomUsersAddBatchRequest, RoomUsersDeleteBatchRequest, UpdateRoomRequest,
+    ConfigRoomRequest, CreateRoomRequest, EncryptRoomRequest, RoomGroup, RoomGroupList,
+    RoomGroupsAddBatchRequest, RoomGroupsAddBatchRequestItem, RoomGroupsDeleteBatchRequest,
+    RoomInvitation, RoomInvitationResult, RoomPolicies, RoomPoliciesRequest, RoomS3TagsRequest,
+    RoomTemplate, RoomUser, RoomUserList, RoomUsersAddBatchRequest, RoomUsersAddBatchRequestItem,
+    RoomUsersDeleteBatchRequest, RoomWebhookList, UpdateRoomRequest, UpdateRoomWebhooksRequest,
 };
 
-use super::{models::Node, NodesEndpoint, Rooms};
+use super::{
+    models::{
+        CreateFolderRequest, Node, NodePath, NodePermissions, NodeType, NodesFilter,
+        TransferNodesRequest,
+    },
+    nodes::NodesInternal,
+    Folders, Nodes, NodesEndpoint, RoomInvitations, Rooms,
+};
 
 pub mod models;
 
@@ -43,7 +59,24 @@ impl Rooms for NodesEndpoint<Connected> {
             .send()
             .await?;
 
-        Node::from_response(response).await
+        let room = Node::from_response(response).await?;
+
+        if let Some(s3_tag_ids) = create_room_req.s3_tag_ids() {
+            if let Err(err) = self
+                .update_room_s3_tags(room.id, s3_tag_ids.clone().into())
+                .await
+            {
+                error!("Failed to set S3 tags - deleting room {}", room.id);
+
+                if let Err(rollback_err) = self.delete_node(room.id).await {
+                    error!("Failed to delete room {}: {}", room.id, rollback_err);
+                }
+
+                return Err(err);
+            }
+        }
+
+        Ok(room)
     }
     async fn update_room(
         &self,
@@ -66,7 +99,14 @@ impl Rooms for NodesEndpoint<Connected> {
             .send()
             .await?;
 
-        Node::from_response(response).await
+        let room = Node::from_response(response).await?;
+
+        if let Some(s3_tag_ids) = update_room_req.s3_tag_ids() {
+            self.update_room_s3_tags(room_id, s3_tag_ids.clone().into())
+                .await?;
+        }
+
+        Ok(room)
     }
     async fn config_room(
         &self,
@@ -343,4 +383,588 @@ impl Rooms for NodesEndpoint<Connected> {
 
         Ok(())
     }
+    async fn get_room_webhooks(
+        &self,
+        room_id: u64,
+        params: Option<ListAllParams>,
+    ) -> Result<RoomWebhookList, DracoonClientError> {
+        let url_part =
+            format!("/{DRACOON_API_PREFIX}/{NODES_BASE}/{ROOMS_BASE}/{room_id}/{ROOMS_WEBHOOKS}");
+        let mut api_url = self.client().build_api_url(&url_part);
+
+        let params = params.unwrap_or_default();
+        let filters = params.filter_to_string();
+        let sorts = params.sort_to_string();
+
+        api_url
+            .query_pairs_mut()
+            .extend_pairs(params.limit.map(|limit| ("limit", limit.to_string())))
+            .extend_pairs(params.offset.map(|offset| ("offset", offset.to_string())))
+            .extend_pairs(params.filter.map(|_| ("filter", filters)))
+            .extend_pairs(params.sort.map(|_| ("sort", sorts)))
+            .finish();
+
+        let response = self
+            .client()
+            .http
+            .get(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .send()
+            .await?;
+
+        RoomWebhookList::from_response(response).await
+    }
+    async fn update_room_webhooks(
+        &self,
+        room_id: u64,
+        room_webhooks_update_req: UpdateRoomWebhooksRequest,
+    ) -> Result<RoomWebhookList, DracoonClientError> {
+        let url_part =
+            format!("/{DRACOON_API_PREFIX}/{NODES_BASE}/{ROOMS_BASE}/{room_id}/{ROOMS_WEBHOOKS}");
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .put(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&room_webhooks_update_req)
+            .send()
+            .await?;
+
+        RoomWebhookList::from_response(response).await
+    }
+    async fn get_room_s3_tags(&self, room_id: u64) -> Result<S3TagList, DracoonClientError> {
+        let url_part =
+            format!("/{DRACOON_API_PREFIX}/{NODES_BASE}/{ROOMS_BASE}/{room_id}/{ROOMS_S3_TAGS}");
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .get(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .send()
+            .await?;
+
+        S3TagList::from_response(response).await
+    }
+    async fn update_room_s3_tags(
+        &self,
+        room_id: u64,
+        s3_tags_req: RoomS3TagsRequest,
+    ) -> Result<S3TagList, DracoonClientError> {
+        let url_part =
+            format!("/{DRACOON_API_PREFIX}/{NODES_BASE}/{ROOMS_BASE}/{room_id}/{ROOMS_S3_TAGS}");
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .put(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&s3_tags_req)
+            .send()
+            .await?;
+
+        S3TagList::from_response(response).await
+    }
+    async fn set_room_admins(
+        &self,
+        room_id: u64,
+        admin_user_ids: Vec<u64>,
+        admin_group_ids: Vec<u64>,
+    ) -> Result<(), DracoonClientError> {
+        let admin_user_ids: HashSet<u64> = admin_user_ids.into_iter().collect();
+        let admin_group_ids: HashSet<u64> = admin_group_ids.into_iter().collect();
+
+        let room_users = self.get_all_granted_room_users(room_id).await?;
+        let room_groups = self.get_all_granted_room_groups(room_id).await?;
+
+        let (grant_users, revoke_users) = diff_room_admin_users(&room_users, &admin_user_ids);
+        let (grant_groups, revoke_groups) = diff_room_admin_groups(&room_groups, &admin_group_ids);
+
+        // grant first to never leave the room without an admin
+        if !grant_users.is_empty() {
+            self.update_room_users(room_id, grant_users.into()).await?;
+        }
+
+        if !grant_groups.is_empty() {
+            self.update_room_groups(room_id, grant_groups.into())
+                .await?;
+        }
+
+        if !revoke_users.is_empty() {
+            self.update_room_users(room_id, revoke_users.into()).await?;
+        }
+
+        if !revoke_groups.is_empty() {
+            self.update_room_groups(room_id, revoke_groups.into())
+                .await?;
+        }
+
+        Ok(())
+    }
+    async fn clone_room(
+        &self,
+        source_room_id: u64,
+        target_parent_id: Option<u64>,
+        include_files: bool,
+    ) -> Result<Node, DracoonClientError> {
+        let source = self.get_node(source_room_id).await?;
+        let policies = self.get_room_policies(source_room_id).await?;
+        let inherit_permissions = source.inherit_permissions.unwrap_or(false);
+
+        let (room_users, room_groups) = if inherit_permissions {
+            (Vec::new(), Vec::new())
+        } else {
+            (
+                self.get_all_granted_room_users(source_room_id).await?,
+                self.get_all_granted_room_groups(source_room_id).await?,
+            )
+        };
+
+        let is_admin =
+            |permissions: &Option<NodePermissions>| permissions.as_ref().is_some_and(|p| p.manage);
+
+        let admin_ids: Vec<u64> = room_users
+            .iter()
+            .filter(|user| is_admin(&user.permissions))
+            .map(|user| user.user_info.id)
+            .collect();
+
+        let admin_group_ids: Vec<u64> = room_groups
+            .iter()
+            .filter(|group| is_admin(&group.permissions))
+            .map(|group| group.id)
+            .collect();
+
+        let mut req =
+            CreateRoomRequest::builder(&source.name).with_inherit_permissions(inherit_permissions);
+
+        if let Some(parent_id) = target_parent_id {
+            req = req.with_parent_id(parent_id);
+        }
+
+        if let Some(quota) = source.quota.filter(|quota| *quota > 0) {
+            req = req.with_quota(quota);
+        }
+
+        if let Some(period) = source
+            .recycle_bin_retention_period
+            .and_then(|period| u32::try_from(period).ok())
+        {
+            req = req.with_recycle_bin_retention_period(period);
+        }
+
+        if let Some(classification) = source
+            .classification
+            .and_then(|classification| u8::try_from(classification).ok())
+        {
+            req = req.with_classification(classification);
+        }
+
+        if let Some(has_activities_log) = source.has_activities_log {
+            req = req.with_has_activities_log(has_activities_log);
+        }
+
+        if let Some(notes) = source.notes.clone() {
+            req = req.with_notes(notes);
+        }
+
+        if !admin_ids.is_empty() {
+            req = req.with_admin_ids(admin_ids);
+        }
+
+        if !admin_group_ids.is_empty() {
+            req = req.with_admin_group_ids(admin_group_ids);
+        }
+
+        let room = self.create_room(req.build()).await?;
+
+        // admins are already set on creation
+        let user_updates: Vec<_> = room_users
+            .into_iter()
+            .filter(|user| !is_admin(&user.permissions))
+            .filter_map(|user| {
+                user.permissions
+                    .map(|p| RoomUsersAddBatchRequestItem::new(user.user_info.id, p))
+            })
+            .collect();
+
+        if !user_updates.is_empty() {
+            self.update_room_users(room.id, user_updates.into()).await?;
+        }
+
+        let group_updates: Vec<_> = room_groups
+            .into_iter()
+            .filter(|group| !is_admin(&group.permissions))
+            .filter_map(|group| {
+                group.permissions.map(|p| {
+                    RoomGroupsAddBatchRequestItem::new(
+                        group.id,
+                        p,
+                        group.new_group_member_acceptance,
+                    )
+                })
+            })
+            .collect();
+
+        if !group_updates.is_empty() {
+            self.update_room_groups(room.id, group_updates.into())
+                .await?;
+        }
+
+        let policies_req = RoomPoliciesRequest::builder()
+            .with_default_expiration_period(policies.default_expiration_period)
+            .with_virus_protection_enabled(policies.is_virus_protection_enabled)
+            .build();
+
+        self.update_room_policies(room.id, policies_req).await?;
+
+        // encrypt before any content is added
+        if source.is_encrypted.unwrap_or(false) {
+            self.encrypt_room(room.id, EncryptRoomRequest::builder(true).build())
+                .await?;
+        }
+
+        if include_files {
+            let node_ids: Vec<u64> = self
+                .get_all_child_nodes(source_room_id)
+                .await?
+                .into_iter()
+                .filter(|node| node.node_type != NodeType::Room)
+                .map(|node| node.id)
+                .collect();
+
+            if !node_ids.is_empty() {
+                let req = TransferNodesRequest::from(node_ids);
+                self.copy_nodes(req, room.id).await?;
+            }
+        } else {
+            self.clone_folder_structure(source_room_id, room.id).await?;
+        }
+
+        Ok(room)
+    }
+    async fn get_all_rooms(&self, parent_id: Option<u64>) -> Result<Vec<Node>, DracoonClientError> {
+        let mut rooms = self.get_all_child_rooms(parent_id).await?;
+        let mut level: Vec<u64> = rooms.iter().map(|room| room.id).collect();
+
+        while !level.is_empty() {
+            let sub_rooms: Vec<Vec<Node>> = stream::iter(level)
+                .map(|room_id| self.get_all_child_rooms(Some(room_id)))
+                .buffer_unordered(DEFAULT_NODES_CONCURRENCY)
+                .try_collect()
+                .await?;
+
+            let sub_rooms: Vec<Node> = sub_rooms.into_iter().flatten().collect();
+            level = sub_rooms.iter().map(|room| room.id).collect();
+            rooms.extend(sub_rooms);
+        }
+
+        Ok(rooms)
+    }
+    async fn apply_template(
+        &self,
+        parent_id: Option<u64>,
+        template: &RoomTemplate,
+    ) -> Result<Node, DracoonClientError> {
+        let room = self
+            .create_room(template.to_create_room_request(parent_id))
+            .await?;
+
+        if let Err(err) = self.apply_template_contents(room.id, template).await {
+            error!(
+                "Failed to apply room template {} - deleting room {}",
+                template.name(),
+                room.id
+            );
+
+            if let Err(rollback_err) = self.delete_node(room.id).await {
+                error!("Failed to delete room {}: {}", room.id, rollback_err);
+            }
+
+            return Err(err);
+        }
+
+        Ok(room)
+    }
+}
+
+#[async_trait]
+impl RoomInvitations for Dracoon<Connected> {
+    async fn invite_to_room(
+        &self,
+        room_id: u64,
+        invitation: RoomInvitation,
+    ) -> Result<RoomInvitationResult, DracoonClientError> {
+        let params = ListAllParams::builder()
+            .with_filter(UsersFilter::email_equals(invitation.email()))
+            .build();
+
+        let users = self.users().get_users(Some(params), None, None).await?;
+
+        let existing_user = users.items.iter().find(|user| {
+            user.email
+                .as_ref()
+                .is_some_and(|email| email.eq_ignore_ascii_case(invitation.email()))
+        });
+
+        let (user_id, created) = match existing_user {
+            Some(user) => (user.id, false),
+            None => {
+                let user = self
+                    .users()
+                    .create_user(invitation.to_create_user_request())
+                    .await?;
+                (user.id, true)
+            }
+        };
+
+        let req = vec![RoomUsersAddBatchRequestItem::new(
+            user_id,
+            invitation.permissions().clone(),
+        )];
+
+        self.nodes().update_room_users(room_id, req.into()).await?;
+
+        Ok(RoomInvitationResult { user_id, created })
+    }
+}
+
+#[async_trait]
+trait RoomsInternal {
+    async fn get_all_granted_room_users(
+        &self,
+        room_id: u64,
+    ) -> Result<Vec<RoomUser>, DracoonClientError>;
+    async fn get_all_granted_room_groups(
+        &self,
+        room_id: u64,
+    ) -> Result<Vec<RoomGroup>, DracoonClientError>;
+    async fn clone_folder_structure(
+        &self,
+        source_parent_id: u64,
+        target_parent_id: u64,
+    ) -> Result<(), DracoonClientError>;
+    async fn get_all_child_rooms(
+        &self,
+        parent_id: Option<u64>,
+    ) -> Result<Vec<Node>, DracoonClientError>;
+    async fn apply_template_contents(
+        &self,
+        room_id: u64,
+        template: &RoomTemplate,
+    ) -> Result<(), DracoonClientError>;
+}
+
+#[async_trait]
+impl RoomsInternal for NodesEndpoint<Connected> {
+    async fn get_all_granted_room_users(
+        &self,
+        room_id: u64,
+    ) -> Result<Vec<RoomUser>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_filter(String::from("isGranted:eq:true"))
+                .with_offset(offset)
+                .with_limit(limit)
+                .build();
+
+            self.get_room_users(room_id, Some(params))
+        })
+        .await
+    }
+
+    async fn get_all_granted_room_groups(
+        &self,
+        room_id: u64,
+    ) -> Result<Vec<RoomGroup>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_filter(String::from("isGranted:eq:true"))
+                .with_offset(offset)
+                .with_limit(limit)
+                .build();
+
+            self.get_room_groups(room_id, Some(params))
+        })
+        .await
+    }
+
+    async fn clone_folder_structure(
+        &self,
+        source_parent_id: u64,
+        target_parent_id: u64,
+    ) -> Result<(), DracoonClientError> {
+        let mut pending = vec![(source_parent_id, target_parent_id)];
+
+        while let Some((source_id, target_id)) = pending.pop() {
+            for node in self.get_all_child_nodes(source_id).await? {
+                match node.node_type {
+                    NodeType::Folder => {
+                        let mut req = CreateFolderRequest::builder(&node.name, target_id);
+
+                        if let Some(notes) = node.notes.as_ref() {
+                            req = req.with_notes(notes);
+                        }
+
+                        let folder = self.create_folder(req.build()).await?;
+                        pending.push((node.id, folder.id));
+                    }
+                    NodeType::Room => debug!("Skipping nested room {} ({})", node.name, node.id),
+                    NodeType::File => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_all_child_rooms(
+        &self,
+        parent_id: Option<u64>,
+    ) -> Result<Vec<Node>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_filter(NodesFilter::is_room())
+                .with_offset(offset)
+                .with_limit(limit)
+                .build();
+
+            self.get_nodes(parent_id, None, Some(params))
+        })
+        .await
+    }
+
+    async fn apply_template_contents(
+        &self,
+        room_id: u64,
+        template: &RoomTemplate,
+    ) -> Result<(), DracoonClientError> {
+        if !template.user_permissions().is_empty() {
+            self.update_room_users(room_id, template.user_permissions().to_vec().into())
+                .await?;
+        }
+
+        if !template.group_permissions().is_empty() {
+            self.update_room_groups(room_id, template.group_permissions().to_vec().into())
+                .await?;
+        }
+
+        if let Some(policies) = template.policies() {
+            self.update_room_policies(room_id, policies.clone()).await?;
+        }
+
+        // created folders by path (relative to the room)
+        let mut folder_ids: HashMap<NodePath, u64> = HashMap::new();
+
+        for path in template.folders() {
+            let path = NodePath::new(path)?;
+            let mut parent_id = room_id;
+            let mut current_path = NodePath::root();
+
+            for name in path.segments() {
+                current_path = current_path.join(name)?;
+
+                parent_id = match folder_ids.get(&current_path) {
+                    Some(folder_id) => *folder_id,
+                    None => {
+                        let req = CreateFolderRequest::builder(name, parent_id).build();
+                        let folder = self.create_folder(req).await?;
+                        folder_ids.insert(current_path.clone(), folder.id);
+                        folder.id
+                    }
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the permission updates needed to match the desired admin users (grants, revokes).
+/// Revoked admins keep all other permissions they had before.
+fn diff_room_admin_users(
+    room_users: &[RoomUser],
+    admin_user_ids: &HashSet<u64>,
+) -> (
+    Vec<RoomUsersAddBatchRequestItem>,
+    Vec<RoomUsersAddBatchRequestItem>,
+) {
+    let current_admins: HashSet<u64> = room_users
+        .iter()
+        .filter(|user| user.permissions.as_ref().is_some_and(|p| p.manage))
+        .map(|user| user.user_info.id)
+        .collect();
+
+    let grants = admin_user_ids
+        .difference(&current_admins)
+        .map(|id| RoomUsersAddBatchRequestItem::new(*id, NodePermissions::room_admin()))
+        .collect();
+
+    let revokes = room_users
+        .iter()
+        .filter(|user| {
+            current_admins.contains(&user.user_info.id)
+                && !admin_user_ids.contains(&user.user_info.id)
+        })
+        .filter_map(|user| {
+            user.permissions.clone().map(|mut permissions| {
+                permissions.manage = false;
+                RoomUsersAddBatchRequestItem::new(user.user_info.id, permissions)
+            })
+        })
+        .collect();
+
+    (grants, revokes)
+}
+
+/// Returns the permission updates needed to match the desired admin groups (grants, revokes).
+/// Revoked admin groups keep all other permissions they had before.
+fn diff_room_admin_groups(
+    room_groups: &[RoomGroup],
+    admin_group_ids: &HashSet<u64>,
+) -> (
+    Vec<RoomGroupsAddBatchRequestItem>,
+    Vec<RoomGroupsAddBatchRequestItem>,
+) {
+    let current_admins: HashSet<u64> = room_groups
+        .iter()
+        .filter(|group| group.permissions.as_ref().is_some_and(|p| p.manage))
+        .map(|group| group.id)
+        .collect();
+
+    let grants = admin_group_ids
+        .difference(&current_admins)
+        .map(|id| RoomGroupsAddBatchRequestItem::new(*id, NodePermissions::room_admin(), None))
+        .collect();
+
+    let revokes = room_groups
+        .iter()
+        .filter(|group| current_admins.contains(&group.id) && !admin_group_ids.contains(&group.id))
+        .filter_map(|group| {
+            group.permissions.clone().map(|mut permissions| {
+                permissions.manage = false;
+                RoomGroupsAddBatchRequestItem::new(group.id, permissions, None)
+            })
+        })
+        .collect();
+
+    (grants, revokes)
 }
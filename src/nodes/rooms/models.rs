@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use dco3_crypto::{
     DracoonCrypto, DracoonCryptoError, DracoonRSACrypto, PublicKeyContainer, UserKeyPairContainer,
     UserKeyPairVersion,
@@ -9,8 +10,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     client::{errors::DracoonClientError, models::DracoonErrorResponse},
-    models::RangedItems,
+    models::{ObjectExpiration, RangedItems},
     nodes::models::{NodePermissions, UserInfo},
+    users::{CreateUserRequest, UserAuthData},
     utils::{parse_body, FromResponse},
 };
 
@@ -42,6 +44,8 @@ pub struct CreateRoomRequest {
     timestamp_creation: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     timestamp_modification: Option<String>,
+    #[serde(skip)]
+    s3_tag_ids: Option<Vec<u64>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -68,8 +72,13 @@ impl CreateRoomRequest {
             classification: None,
             timestamp_creation: None,
             timestamp_modification: None,
+            s3_tag_ids: None,
         }
     }
+
+    pub fn s3_tag_ids(&self) -> Option<&Vec<u64>> {
+        self.s3_tag_ids.as_ref()
+    }
 }
 
 pub struct CreateRoomRequestBuilder {
@@ -86,6 +95,7 @@ pub struct CreateRoomRequestBuilder {
     classification: Option<u8>,
     timestamp_creation: Option<String>,
     timestamp_modification: Option<String>,
+    s3_tag_ids: Option<Vec<u64>>,
 }
 
 impl CreateRoomRequestBuilder {
@@ -152,6 +162,12 @@ impl CreateRoomRequestBuilder {
         self
     }
 
+    /// S3 tags are assigned to the room after creation (separate request).
+    pub fn with_s3_tag_ids(mut self, s3_tag_ids: Vec<u64>) -> Self {
+        self.s3_tag_ids = Some(s3_tag_ids);
+        self
+    }
+
     pub fn build(self) -> CreateRoomRequest {
         CreateRoomRequest {
             name: self.name,
@@ -167,6 +183,7 @@ impl CreateRoomRequestBuilder {
             classification: self.classification,
             timestamp_creation: self.timestamp_creation,
             timestamp_modification: self.timestamp_modification,
+            s3_tag_ids: self.s3_tag_ids,
         }
     }
 }
@@ -184,6 +201,8 @@ pub struct UpdateRoomRequest {
     timestamp_creation: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     timestamp_modification: Option<String>,
+    #[serde(skip)]
+    s3_tag_ids: Option<Vec<u64>>,
 }
 
 impl UpdateRoomRequest {
@@ -194,8 +213,13 @@ impl UpdateRoomRequest {
             notes: None,
             timestamp_creation: None,
             timestamp_modification: None,
+            s3_tag_ids: None,
         }
     }
+
+    pub fn s3_tag_ids(&self) -> Option<&Vec<u64>> {
+        self.s3_tag_ids.as_ref()
+    }
 }
 
 pub struct UpdateRoomRequestBuilder {
@@ -204,6 +228,7 @@ pub struct UpdateRoomRequestBuilder {
     notes: Option<String>,
     timestamp_creation: Option<String>,
     timestamp_modification: Option<String>,
+    s3_tag_ids: Option<Vec<u64>>,
 }
 
 impl UpdateRoomRequestBuilder {
@@ -235,6 +260,12 @@ impl UpdateRoomRequestBuilder {
         self
     }
 
+    /// S3 tags are assigned to the room after the update (separate request).
+    pub fn with_s3_tag_ids(mut self, s3_tag_ids: Vec<u64>) -> Self {
+        self.s3_tag_ids = Some(s3_tag_ids);
+        self
+    }
+
     pub fn build(self) -> UpdateRoomRequest {
         UpdateRoomRequest {
             name: self.name,
@@ -242,6 +273,7 @@ impl UpdateRoomRequestBuilder {
             notes: self.notes,
             timestamp_creation: self.timestamp_creation,
             timestamp_modification: self.timestamp_modification,
+            s3_tag_ids: self.s3_tag_ids,
         }
     }
 }
@@ -655,3 +687,384 @@ impl From<Vec<u64>> for RoomUsersDeleteBatchRequest {
         RoomUsersDeleteBatchRequest { ids }
     }
 }
+
+pub type RoomWebhookList = RangedItems<RoomWebhook>;
+
+#[async_trait]
+impl FromResponse for RoomWebhookList {
+    async fn from_response(response: Response) -> Result<Self, DracoonClientError> {
+        parse_body::<Self, DracoonErrorResponse>(response).await
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomWebhook {
+    pub is_assigned: bool,
+    pub webhook: Webhook,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub id: u64,
+    pub name: String,
+    pub url: String,
+    pub is_enabled: bool,
+    pub expire_at: DateTime<Utc>,
+    pub event_type_names: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub secret: Option<String>,
+    pub created_by: Option<UserInfo>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub updated_by: Option<UserInfo>,
+    pub fail_status: Option<u16>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRoomWebhooksRequest {
+    items: Vec<RoomWebhookAssignment>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomWebhookAssignment {
+    webhook_id: u64,
+    is_assigned: bool,
+}
+
+impl RoomWebhookAssignment {
+    pub fn new(webhook_id: u64, is_assigned: bool) -> Self {
+        RoomWebhookAssignment {
+            webhook_id,
+            is_assigned,
+        }
+    }
+
+    pub fn assign(webhook_id: u64) -> Self {
+        Self::new(webhook_id, true)
+    }
+
+    pub fn unassign(webhook_id: u64) -> Self {
+        Self::new(webhook_id, false)
+    }
+}
+
+impl From<Vec<RoomWebhookAssignment>> for UpdateRoomWebhooksRequest {
+    fn from(items: Vec<RoomWebhookAssignment>) -> Self {
+        UpdateRoomWebhooksRequest { items }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomS3TagsRequest {
+    ids: Vec<u64>,
+}
+
+impl From<Vec<u64>> for RoomS3TagsRequest {
+    fn from(ids: Vec<u64>) -> Self {
+        RoomS3TagsRequest { ids }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RoomInvitation {
+    email: String,
+    first_name: String,
+    last_name: String,
+    permissions: NodePermissions,
+    notify_user: Option<bool>,
+    receiver_language: Option<String>,
+    expiration: Option<ObjectExpiration>,
+}
+
+impl RoomInvitation {
+    pub fn builder(
+        email: impl Into<String>,
+        first_name: impl Into<String>,
+        last_name: impl Into<String>,
+    ) -> RoomInvitationBuilder {
+        RoomInvitationBuilder::new(email, first_name, last_name)
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    pub fn permissions(&self) -> &NodePermissions {
+        &self.permissions
+    }
+
+    /// Builds the request used to create the guest user if no user with the email exists.
+    /// The email is used as user name.
+    pub fn to_create_user_request(&self) -> CreateUserRequest {
+        let mut req = CreateUserRequest::builder(&self.first_name, &self.last_name)
+            .with_user_name(&self.email)
+            .with_email(&self.email)
+            .with_auth_data(UserAuthData::new_basic(None, None));
+
+        if let Some(notify_user) = self.notify_user {
+            req = req.with_notify_user(notify_user);
+        }
+
+        if let Some(receiver_language) = &self.receiver_language {
+            req = req.with_receiver_language(receiver_language);
+        }
+
+        if let Some(expiration) = &self.expiration {
+            req = req.with_expiration(expiration.clone());
+        }
+
+        req.build()
+    }
+}
+
+pub struct RoomInvitationBuilder {
+    email: String,
+    first_name: String,
+    last_name: String,
+    permissions: NodePermissions,
+    notify_user: Option<bool>,
+    receiver_language: Option<String>,
+    expiration: Option<ObjectExpiration>,
+}
+
+impl RoomInvitationBuilder {
+    pub fn new(
+        email: impl Into<String>,
+        first_name: impl Into<String>,
+        last_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            email: email.into(),
+            first_name: first_name.into(),
+            last_name: last_name.into(),
+            permissions: NodePermissions::read_only(),
+            notify_user: None,
+            receiver_language: None,
+            expiration: None,
+        }
+    }
+
+    pub fn with_permissions(mut self, permissions: NodePermissions) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
+    pub fn with_edit_permissions(mut self) -> Self {
+        self.permissions = NodePermissions::edit();
+        self
+    }
+
+    pub fn with_notify_user(mut self, notify_user: bool) -> Self {
+        self.notify_user = Some(notify_user);
+        self
+    }
+
+    pub fn with_receiver_language(mut self, receiver_language: impl Into<String>) -> Self {
+        self.receiver_language = Some(receiver_language.into());
+        self
+    }
+
+    pub fn with_expiration(mut self, expiration: impl Into<ObjectExpiration>) -> Self {
+        self.expiration = Some(expiration.into());
+        self
+    }
+
+    pub fn build(self) -> RoomInvitation {
+        RoomInvitation {
+            email: self.email,
+            first_name: self.first_name,
+            last_name: self.last_name,
+            permissions: self.permissions,
+            notify_user: self.notify_user,
+            receiver_language: self.receiver_language,
+            expiration: self.expiration,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RoomInvitationResult {
+    pub user_id: u64,
+    pub created: bool,
+}
+
+/// Declarative room specification (configuration, permissions, policies and folder layout)
+/// that can be applied multiple times via `apply_template` of the [Rooms](crate::Rooms) trait.
+#[derive(Debug, Clone)]
+pub struct RoomTemplate {
+    name: String,
+    classification: Option<u8>,
+    quota: Option<u64>,
+    recycle_bin_retention_period: Option<u32>,
+    inherit_permissions: Option<bool>,
+    has_activities_log: Option<bool>,
+    admin_ids: Option<Vec<u64>>,
+    admin_group_ids: Option<Vec<u64>>,
+    user_permissions: Vec<RoomUsersAddBatchRequestItem>,
+    group_permissions: Vec<RoomGroupsAddBatchRequestItem>,
+    policies: Option<RoomPoliciesRequest>,
+    folders: Vec<String>,
+}
+
+impl RoomTemplate {
+    pub fn builder(name: impl Into<String>) -> RoomTemplateBuilder {
+        RoomTemplateBuilder::new(name)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn user_permissions(&self) -> &[RoomUsersAddBatchRequestItem] {
+        &self.user_permissions
+    }
+
+    pub fn group_permissions(&self) -> &[RoomGroupsAddBatchRequestItem] {
+        &self.group_permissions
+    }
+
+    pub fn policies(&self) -> Option<&RoomPoliciesRequest> {
+        self.policies.as_ref()
+    }
+
+    /// Folder paths relative to the room (e.g. `Contracts/2024`)
+    pub fn folders(&self) -> &[String] {
+        &self.folders
+    }
+
+    pub fn to_create_room_request(&self, parent_id: Option<u64>) -> CreateRoomRequest {
+        CreateRoomRequest {
+            name: self.name.clone(),
+            parent_id,
+            recycle_bin_retention_period: self.recycle_bin_retention_period,
+            quota: self.quota,
+            inherit_permissions: self.inherit_permissions,
+            admin_ids: self.admin_ids.clone(),
+            admin_group_ids: self.admin_group_ids.clone(),
+            new_group_member_acceptance: None,
+            notes: None,
+            has_activities_log: self.has_activities_log,
+            classification: self.classification,
+            timestamp_creation: None,
+            timestamp_modification: None,
+            s3_tag_ids: None,
+        }
+    }
+}
+
+pub struct RoomTemplateBuilder {
+    name: String,
+    classification: Option<u8>,
+    quota: Option<u64>,
+    recycle_bin_retention_period: Option<u32>,
+    inherit_permissions: Option<bool>,
+    has_activities_log: Option<bool>,
+    admin_ids: Option<Vec<u64>>,
+    admin_group_ids: Option<Vec<u64>>,
+    user_permissions: Vec<RoomUsersAddBatchRequestItem>,
+    group_permissions: Vec<RoomGroupsAddBatchRequestItem>,
+    policies: Option<RoomPoliciesRequest>,
+    folders: Vec<String>,
+}
+
+impl RoomTemplateBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            classification: None,
+            quota: None,
+            recycle_bin_retention_period: None,
+            inherit_permissions: None,
+            has_activities_log: None,
+            admin_ids: None,
+            admin_group_ids: None,
+            user_permissions: Vec::new(),
+            group_permissions: Vec::new(),
+            policies: None,
+            folders: Vec::new(),
+        }
+    }
+
+    pub fn with_classification(mut self, classification: u8) -> Self {
+        self.classification = Some(classification);
+        self
+    }
+
+    pub fn with_quota(mut self, quota: u64) -> Self {
+        self.quota = Some(quota);
+        self
+    }
+
+    pub fn with_recycle_bin_retention_period(mut self, recycle_bin_retention_period: u32) -> Self {
+        self.recycle_bin_retention_period = Some(recycle_bin_retention_period);
+        self
+    }
+
+    pub fn with_inherit_permissions(mut self, inherit_permissions: bool) -> Self {
+        self.inherit_permissions = Some(inherit_permissions);
+        self
+    }
+
+    pub fn with_has_activities_log(mut self, has_activities_log: bool) -> Self {
+        self.has_activities_log = Some(has_activities_log);
+        self
+    }
+
+    pub fn with_admin_ids(mut self, admin_ids: Vec<u64>) -> Self {
+        self.admin_ids = Some(admin_ids);
+        self
+    }
+
+    pub fn with_admin_group_ids(mut self, admin_group_ids: Vec<u64>) -> Self {
+        self.admin_group_ids = Some(admin_group_ids);
+        self
+    }
+
+    pub fn with_user_permissions(mut self, user_id: u64, permissions: NodePermissions) -> Self {
+        self.user_permissions
+            .push(RoomUsersAddBatchRequestItem::new(user_id, permissions));
+        self
+    }
+
+    pub fn with_group_permissions(mut self, group_id: u64, permissions: NodePermissions) -> Self {
+        self.group_permissions
+            .push(RoomGroupsAddBatchRequestItem::new(
+                group_id,
+                permissions,
+                None,
+            ));
+        self
+    }
+
+    pub fn with_policies(mut self, policies: RoomPoliciesRequest) -> Self {
+        self.policies = Some(policies);
+        self
+    }
+
+    /// Adds a folder path relative to the room - missing parent folders are created as well.
+    pub fn with_folder(mut self, path: impl Into<String>) -> Self {
+        self.folders.push(path.into());
+        self
+    }
+
+    pub fn build(self) -> RoomTemplate {
+        RoomTemplate {
+            name: self.name,
+            classification: self.classification,
+            quota: self.quota,
+            recycle_bin_retention_period: self.recycle_bin_retention_period,
+            inherit_permissions: self.inherit_permissions,
+            has_activities_log: self.has_activities_log,
+            admin_ids: self.admin_ids,
+            admin_group_ids: self.admin_group_ids,
+            user_permissions: self.user_permissions,
+            group_permissions: self.group_permissions,
+            policies: self.policies,
+            folders: self.folders,
+        }
+    }
+}
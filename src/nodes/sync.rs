@@ -0,0 +1,319 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use tracing::{debug, error};
+
+use crate::{
+    client::{errors::DracoonClientError, Connected},
+    constants::DELETED_NODES_BATCH,
+    Dracoon,
+};
+
+use super::{
+    models::{
+        diff_nodes, CreateFolderRequest, FileMeta, ManifestEntry, Node, NodeType,
+        ResolutionStrategy, SyncConflictPolicy, SyncOptions, SyncReport, UploadOptions,
+        UploadRequest,
+    },
+    nodes::NodesInternal,
+    Folders, Nodes, SyncDirectory, UploadMany,
+};
+
+/// Content of a local directory (files and sub directories)
+struct LocalDirectory {
+    files: Vec<ManifestEntry>,
+    directories: Vec<(PathBuf, String)>,
+}
+
+#[async_trait]
+impl SyncDirectory for Dracoon<Connected> {
+    async fn sync_dir_to_node(
+        &self,
+        local: &Path,
+        remote: &Node,
+        options: SyncOptions,
+    ) -> Result<SyncReport, DracoonClientError> {
+        if !local.is_dir() {
+            error!("Not a directory: {}", local.display());
+            return Err(DracoonClientError::InvalidPath(local.display().to_string()));
+        }
+
+        let mut report = SyncReport::default();
+        let mut uploads = Vec::new();
+        let mut removed = Vec::new();
+        let mut pending = vec![(local.to_path_buf(), remote.clone())];
+
+        while let Some((local_dir, remote_parent)) = pending.pop() {
+            debug!(
+                "Syncing {} to node {}",
+                local_dir.display(),
+                remote_parent.id
+            );
+
+            let local_content = read_local_directory(&local_dir).await?;
+            let remote_children = self.nodes().get_all_child_nodes(remote_parent.id).await?;
+
+            let diff = diff_nodes(&local_content.files, &remote_children);
+
+            let source_of = |name: &str| local_dir.join(name);
+
+            for entry in diff.added {
+                let source = source_of(&entry.name);
+                uploads.push(
+                    UploadRequest::new(source, remote_parent.clone()).with_options(upload_options(
+                        &entry,
+                        ResolutionStrategy::Fail,
+                        &options,
+                    )),
+                );
+            }
+
+            for changed in diff.changed {
+                let source = source_of(&changed.local.name);
+                let resolution_strategy = match options.conflict_policy() {
+                    SyncConflictPolicy::Overwrite => ResolutionStrategy::Overwrite,
+                    SyncConflictPolicy::KeepBoth => ResolutionStrategy::AutoRename,
+                    SyncConflictPolicy::Skip => {
+                        report.skipped.push(source);
+                        continue;
+                    }
+                };
+                uploads.push(
+                    UploadRequest::new(source, remote_parent.clone()).with_options(upload_options(
+                        &changed.local,
+                        resolution_strategy,
+                        &options,
+                    )),
+                );
+            }
+
+            if options.delete_removed() {
+                removed.extend(diff.removed);
+            }
+
+            if !options.recursive() {
+                continue;
+            }
+
+            for (path, name) in &local_content.directories {
+                let existing = remote_children
+                    .iter()
+                    .find(|node| &node.name == name && node.node_type != NodeType::File);
+
+                let remote_dir = match existing {
+                    Some(node) => node.clone(),
+                    None => {
+                        // fails e.g. if a remote file has the name of the directory
+                        let folder = match self
+                            .nodes()
+                            .create_folder(
+                                CreateFolderRequest::builder(name, remote_parent.id).build(),
+                            )
+                            .await
+                        {
+                            Ok(folder) => folder,
+                            Err(err) => {
+                                error!("Error creating folder {}: {}", name, err);
+                                report.failed_folders.push((path.clone(), err));
+                                continue;
+                            }
+                        };
+                        report.created_folders.push(folder.clone());
+                        folder
+                    }
+                };
+
+                pending.push((path.clone(), remote_dir));
+            }
+
+            // rooms are never deleted - only folders missing locally
+            if options.delete_removed() {
+                let removed_folders = remote_children.iter().filter(|node| {
+                    node.node_type == NodeType::Folder
+                        && !local_content
+                            .directories
+                            .iter()
+                            .any(|(_, name)| name == &node.name)
+                });
+                removed.extend(removed_folders.cloned());
+            }
+        }
+
+        report.uploads = self.upload_many(uploads, options.concurrency(), None).await;
+
+        // an incomplete sync must not delete remote content
+        if !report.is_success() {
+            if !removed.is_empty() {
+                debug!(
+                    "Sync incomplete - skipping deletion of {} nodes",
+                    removed.len()
+                );
+            }
+            return Ok(report);
+        }
+
+        let removed_ids = removed.iter().map(|node| node.id).collect::<Vec<_>>();
+        for batch in removed_ids.chunks(DELETED_NODES_BATCH) {
+            self.nodes().delete_nodes(batch.to_vec().into()).await?;
+        }
+        report.deleted = removed;
+
+        Ok(report)
+    }
+}
+
+/// reads the files (with size and modification) and sub directories of a local directory
+async fn read_local_directory(path: &Path) -> Result<LocalDirectory, DracoonClientError> {
+    let io_error = |err: std::io::Error| {
+        error!("Error reading directory {}: {}", path.display(), err);
+        DracoonClientError::IoError
+    };
+
+    let mut entries = tokio::fs::read_dir(path).await.map_err(io_error)?;
+    let mut content = LocalDirectory {
+        files: Vec::new(),
+        directories: Vec::new(),
+    };
+
+    while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+        let metadata = entry.metadata().await.map_err(io_error)?;
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if metadata.is_dir() {
+            content.directories.push((entry.path(), name));
+        } else if metadata.is_file() {
+            let manifest_entry = ManifestEntry::builder(name, metadata.len());
+            let manifest_entry = match metadata.modified() {
+                Ok(modified) => manifest_entry.with_timestamp_modification(modified.into()),
+                Err(_) => manifest_entry,
+            };
+            content.files.push(manifest_entry.build());
+        }
+    }
+
+    Ok(content)
+}
+
+/// builds the upload options of a synced file (keeps the local modification timestamp)
+fn upload_options(
+    entry: &ManifestEntry,
+    resolution_strategy: ResolutionStrategy,
+    options: &SyncOptions,
+) -> UploadOptions {
+    let file_meta = FileMeta::builder(&entry.name, entry.size);
+    let file_meta = match entry.timestamp_modification {
+        Some(timestamp) => file_meta.with_timestamp_modification(timestamp),
+        None => file_meta,
+    };
+
+    UploadOptions::builder(file_meta.build())
+        .with_resolution_strategy(resolution_strategy)
+        .with_keep_share_links(options.keep_share_links())
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::dracoon::get_connected_client;
+
+    #[tokio::test]
+    async fn test_sync_dir_to_node_skip_and_delete() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let local = std::env::temp_dir().join("dco3_sync_skip_and_delete");
+        std::fs::create_dir_all(&local).unwrap();
+        std::fs::write(local.join("changed.txt"), vec![0u8; 16]).unwrap();
+
+        let remote: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let nodes_mock = mock_server
+            .mock("GET", "/api/v4/nodes")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(include_str!("../tests/responses/nodes/sync_nodes_ok.json"))
+            .with_header("content-type", "application/json")
+            .create();
+
+        let delete_mock = mock_server
+            .mock("DELETE", "/api/v4/nodes")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({ "nodeIds": [2, 3] }),
+            ))
+            .with_status(204)
+            .create();
+
+        let options = SyncOptions::new()
+            .with_conflict_policy(SyncConflictPolicy::Skip)
+            .with_delete_removed(true);
+
+        let report = client
+            .sync_dir_to_node(&local, &remote, options)
+            .await
+            .unwrap();
+
+        nodes_mock.assert();
+        delete_mock.assert();
+
+        assert_eq!(report.skipped, vec![local.join("changed.txt")]);
+        assert!(report.uploads.uploaded.is_empty());
+        assert!(report.created_folders.is_empty());
+        assert_eq!(report.deleted.len(), 2);
+
+        std::fs::remove_dir_all(local).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sync_dir_to_node_folder_name_clash() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let local = std::env::temp_dir().join("dco3_sync_folder_name_clash");
+        std::fs::create_dir_all(local.join("docs")).unwrap();
+
+        let remote: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let nodes_mock = mock_server
+            .mock("GET", "/api/v4/nodes")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(include_str!(
+                "../tests/responses/nodes/sync_nodes_name_clash_ok.json"
+            ))
+            .with_header("content-type", "application/json")
+            .create();
+
+        let folder_mock = mock_server
+            .mock("POST", "/api/v4/nodes/folders")
+            .with_status(409)
+            .with_body(r#"{"code":409,"message":"Conflict","debugInfo":"Node already exists","errorCode":-40001}"#)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let delete_mock = mock_server
+            .mock("DELETE", "/api/v4/nodes")
+            .expect(0)
+            .create();
+
+        let options = SyncOptions::new().with_delete_removed(true);
+
+        let report = client
+            .sync_dir_to_node(&local, &remote, options)
+            .await
+            .unwrap();
+
+        nodes_mock.assert();
+        folder_mock.assert();
+        delete_mock.assert();
+
+        assert!(!report.is_success());
+        assert!(report.created_folders.is_empty());
+        assert!(report.deleted.is_empty());
+        assert_eq!(report.failed_folders.len(), 1);
+        assert_eq!(report.failed_folders[0].0, local.join("docs"));
+        assert!(report.failed_folders[0].1.is_conflict());
+
+        std::fs::remove_dir_all(local).unwrap();
+    }
+}
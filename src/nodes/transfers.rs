@@ -0,0 +1,834 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::SystemTime,
+};
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use tokio::{
+    io::{AsyncRead, AsyncWriteExt, BufReader, BufWriter},
+    sync::{broadcast, watch, Semaphore},
+    task::JoinHandle,
+};
+use tokio_util::{
+    io::{ReaderStream, StreamReader},
+    sync::CancellationToken,
+};
+use tracing::{debug, error};
+
+use crate::{
+    client::{
+        errors::DracoonClientError,
+        throttle::{PauseSignal, RateLimiter},
+        Connected,
+    },
+    constants::{DEFAULT_MANAGED_TRANSFERS, DOWNLOAD_BUFFER_SIZE, TRANSFER_UPDATES_CAPACITY},
+    Dracoon,
+};
+
+use super::{
+    download::DownloadInternal,
+    models::{
+        DownloadRequest, ManagedTransferEvent, ProgressTracker, TransferId, TransferOutcome,
+        TransferState, TransferUpdate, TransferUpdateStream, UploadProgressCallback, UploadRequest,
+    },
+    upload::open_upload_file,
+    Upload,
+};
+
+type GatedStream = Pin<Box<dyn Stream<Item = std::io::Result<Bytes>> + Send + Sync>>;
+type GatedReader = StreamReader<GatedStream, Bytes>;
+type TransferResult = Result<TransferOutcome, DracoonClientError>;
+
+/// Queues uploads and downloads and runs them with a limited number of concurrent transfers
+/// and an (optional) bandwidth budget shared by all transfers.
+/// Transfers can be paused, resumed and cancelled individually - updates of all transfers
+/// are emitted via [TransferManager::subscribe].
+/// Clones share the same queue.
+///
+/// ```no_run
+/// # use dco3::{Dracoon, OAuth2Flow, Nodes, nodes::{TransferManager, UploadRequest, TransferOutcome}};
+/// # use futures_util::StreamExt;
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let dracoon = Dracoon::builder()
+/// #  .with_base_url("https://dracoon.team")
+/// #  .with_client_id("client_id")
+/// #  .with_client_secret("client_secret")
+/// #  .build()
+/// #  .unwrap()
+/// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+/// #  .await
+/// #  .unwrap();
+/// let parent_node = dracoon.nodes().get_node(123).await.unwrap();
+///
+/// let manager = TransferManager::builder(dracoon)
+///     .with_max_concurrent_transfers(2)
+///     .with_max_rate(10 * 1024 * 1024) // 10 MB/s for all transfers
+///     .build();
+///
+/// let mut updates = manager.subscribe();
+/// tokio::spawn(async move {
+///     while let Some(update) = updates.next().await {
+///         println!("Transfer {}: {:?}", update.id, update.event);
+///     }
+/// });
+///
+/// let id = manager.queue_upload(UploadRequest::new("/path/to/file.txt", parent_node));
+///
+/// manager.pause(id);
+/// manager.resume(id);
+///
+/// match manager.wait(id).await {
+///     Some(Ok(TransferOutcome::Uploaded(node))) => println!("Uploaded node {}", node.id),
+///     Some(Err(err)) => println!("Upload failed: {}", err),
+///     _ => (),
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TransferManager {
+    inner: Arc<TransferManagerInner>,
+}
+
+struct TransferManagerInner {
+    dracoon: Dracoon<Connected>,
+    slots: Arc<Semaphore>,
+    rate_limit: Option<RateLimiter>,
+    transfers: Mutex<HashMap<TransferId, ManagedTransfer>>,
+    updates: broadcast::Sender<TransferUpdate>,
+    next_id: AtomicU64,
+}
+
+struct ManagedTransfer {
+    state: TransferState,
+    paused: watch::Sender<bool>,
+    cancellation_token: CancellationToken,
+    handle: Option<JoinHandle<TransferResult>>,
+}
+
+pub struct TransferManagerBuilder {
+    dracoon: Dracoon<Connected>,
+    max_concurrent_transfers: usize,
+    max_rate: Option<u64>,
+}
+
+impl TransferManagerBuilder {
+    /// Sets the max. number of transfers running at once (default: 3)
+    pub fn with_max_concurrent_transfers(mut self, max_concurrent_transfers: usize) -> Self {
+        self.max_concurrent_transfers = max_concurrent_transfers.max(1);
+        self
+    }
+
+    /// Sets the max. transfer rate of all transfers (bytes per second)
+    pub fn with_max_rate(mut self, max_rate: u64) -> Self {
+        self.max_rate = Some(max_rate);
+        self
+    }
+
+    pub fn build(self) -> TransferManager {
+        let (updates, _) = broadcast::channel(TRANSFER_UPDATES_CAPACITY);
+
+        TransferManager {
+            inner: Arc::new(TransferManagerInner {
+                dracoon: self.dracoon,
+                slots: Arc::new(Semaphore::new(self.max_concurrent_transfers)),
+                rate_limit: self.max_rate.map(RateLimiter::new),
+                transfers: Mutex::new(HashMap::new()),
+                updates,
+                next_id: AtomicU64::new(1),
+            }),
+        }
+    }
+}
+
+impl TransferManager {
+    pub fn builder(dracoon: Dracoon<Connected>) -> TransferManagerBuilder {
+        TransferManagerBuilder {
+            dracoon,
+            max_concurrent_transfers: DEFAULT_MANAGED_TRANSFERS,
+            max_rate: None,
+        }
+    }
+
+    /// Returns a stream of the updates of all transfers queued after subscribing.
+    /// Updates are skipped if the subscriber falls behind.
+    pub fn subscribe(&self) -> TransferUpdateStream {
+        let mut receiver = self.inner.updates.subscribe();
+
+        Box::pin(async_stream::stream! {
+            loop {
+                match receiver.recv().await {
+                    Ok(update) => yield update,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug!("Skipped {} transfer updates", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Queues the upload of a local file - must be called within a tokio runtime
+    pub fn queue_upload(&self, request: UploadRequest) -> TransferId {
+        self.queue(move |inner, id, gate, cancellation_token| {
+            upload(inner, id, request, gate, cancellation_token)
+        })
+    }
+
+    /// Queues the download of a file node - must be called within a tokio runtime.
+    /// The file is downloaded to a temporary file next to the target (`<name>.part`) and
+    /// renamed once complete: an existing target file is only replaced by a complete download.
+    pub fn queue_download(&self, request: DownloadRequest) -> TransferId {
+        self.queue(move |inner, id, gate, cancellation_token| {
+            download(inner, id, request, gate, cancellation_token)
+        })
+    }
+
+    /// Pauses a queued or running transfer - returns false if the transfer is unknown or finished.
+    /// A running transfer finishes the parts or chunks in flight and waits before the next one.
+    /// A transfer paused while queued still takes a free slot and waits paused on it
+    /// (the slot is only released when the transfer is finished or cancelled).
+    pub fn pause(&self, id: TransferId) -> bool {
+        self.set_paused(id, true)
+    }
+
+    /// Resumes a paused transfer - returns false if the transfer is unknown or finished
+    pub fn resume(&self, id: TransferId) -> bool {
+        self.set_paused(id, false)
+    }
+
+    /// Cancels a transfer - returns false if the transfer is unknown or finished
+    pub fn cancel(&self, id: TransferId) -> bool {
+        let transfers = self.inner.lock_transfers();
+
+        match transfers.get(&id) {
+            Some(transfer) if !transfer.state.is_finished() => {
+                transfer.cancellation_token.cancel();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the state of a transfer (None if the transfer is unknown)
+    pub fn state(&self, id: TransferId) -> Option<TransferState> {
+        self.inner
+            .lock_transfers()
+            .get(&id)
+            .map(|transfer| transfer.state)
+    }
+
+    /// Returns the states of all transfers (ordered by queue position)
+    pub fn transfers(&self) -> Vec<(TransferId, TransferState)> {
+        let mut transfers = self
+            .inner
+            .lock_transfers()
+            .iter()
+            .map(|(id, transfer)| (*id, transfer.state))
+            .collect::<Vec<_>>();
+        transfers.sort_by_key(|(id, _)| *id);
+
+        transfers
+    }
+
+    /// Removes all finished transfers (completed, failed or cancelled)
+    pub fn clear_finished(&self) {
+        self.inner
+            .lock_transfers()
+            .retain(|_, transfer| !transfer.state.is_finished());
+    }
+
+    /// Waits until a transfer is finished and returns its result.
+    /// Returns None if the transfer is unknown or its result was already returned.
+    pub async fn wait(&self, id: TransferId) -> Option<TransferResult> {
+        let handle = self
+            .inner
+            .lock_transfers()
+            .get_mut(&id)
+            .and_then(|transfer| transfer.handle.take())?;
+
+        let result = handle.await.unwrap_or_else(|err| {
+            error!("Transfer {} panicked: {}", id, err);
+            Err(DracoonClientError::Unknown)
+        });
+
+        Some(result)
+    }
+
+    fn queue<F, T>(&self, run: F) -> TransferId
+    where
+        F: FnOnce(Arc<TransferManagerInner>, TransferId, TransferGate, CancellationToken) -> T,
+        T: Future<Output = TransferResult> + Send + 'static,
+    {
+        let id = TransferId(self.inner.next_id.fetch_add(1, Ordering::Relaxed));
+        let (paused, paused_receiver) = watch::channel(false);
+        let cancellation_token = CancellationToken::new();
+
+        let gate = TransferGate {
+            pause: PauseSignal::new(paused_receiver),
+            rate_limit: self.inner.rate_limit.clone(),
+        };
+        let transfer = run(self.inner.clone(), id, gate, cancellation_token.clone());
+
+        // the transfer is spawned while holding the lock: it needs the lock to start or finish,
+        // so the handle is always stored before the transfer can be removed
+        let mut transfers = self.inner.lock_transfers();
+
+        let inner = self.inner.clone();
+        let token = cancellation_token.clone();
+        let handle = tokio::spawn(async move {
+            let result = tokio::select! {
+                biased;
+                () = token.cancelled() => Err(DracoonClientError::Cancelled),
+                permit = inner.slots.clone().acquire_owned() => {
+                    let _permit = permit.expect("transfer slots are never closed");
+                    inner.start(id);
+                    transfer.await
+                }
+            };
+
+            inner.finish(id, &result);
+
+            result
+        });
+
+        transfers.insert(
+            id,
+            ManagedTransfer {
+                state: TransferState::Queued,
+                paused,
+                cancellation_token,
+                handle: Some(handle),
+            },
+        );
+        self.inner.notify(id, ManagedTransferEvent::Queued);
+        drop(transfers);
+
+        id
+    }
+
+    fn set_paused(&self, id: TransferId, paused: bool) -> bool {
+        let mut transfers = self.inner.lock_transfers();
+
+        let Some(transfer) = transfers.get_mut(&id) else {
+            return false;
+        };
+
+        if transfer.state.is_finished() {
+            return false;
+        }
+
+        let changed = transfer.paused.send_if_modified(|current| {
+            let changed = *current != paused;
+            *current = paused;
+            changed
+        });
+
+        if !changed {
+            return true;
+        }
+
+        transfer.state = match (transfer.state, paused) {
+            (TransferState::Running, true) => TransferState::Paused,
+            (TransferState::Paused, false) => TransferState::Running,
+            (state, _) => state,
+        };
+        drop(transfers);
+
+        let event = if paused {
+            ManagedTransferEvent::Paused
+        } else {
+            ManagedTransferEvent::Resumed
+        };
+        self.inner.notify(id, event);
+
+        true
+    }
+}
+
+impl TransferManagerInner {
+    fn lock_transfers(&self) -> std::sync::MutexGuard<'_, HashMap<TransferId, ManagedTransfer>> {
+        self.transfers.lock().expect("transfers lock poisoned")
+    }
+
+    fn notify(&self, id: TransferId, event: ManagedTransferEvent) {
+        // sending only fails without subscribers
+        let _ = self.updates.send(TransferUpdate { id, event });
+    }
+
+    /// marks a transfer as running (or paused if it was paused while queued)
+    fn start(&self, id: TransferId) {
+        if let Some(transfer) = self.lock_transfers().get_mut(&id) {
+            transfer.state = if *transfer.paused.borrow() {
+                TransferState::Paused
+            } else {
+                TransferState::Running
+            };
+        }
+    }
+
+    fn finish(&self, id: TransferId, result: &TransferResult) {
+        let (state, event) = match result {
+            Ok(_) => (TransferState::Completed, ManagedTransferEvent::Completed),
+            Err(DracoonClientError::Cancelled) => {
+                (TransferState::Cancelled, ManagedTransferEvent::Cancelled)
+            }
+            Err(err) => {
+                error!("Transfer {} failed: {}", id, err);
+                (
+                    TransferState::Failed,
+                    ManagedTransferEvent::Failed(err.to_string()),
+                )
+            }
+        };
+
+        if let Some(transfer) = self.lock_transfers().get_mut(&id) {
+            transfer.state = state;
+        }
+        self.notify(id, event);
+    }
+}
+
+/// Pauses a transfer between parts or chunks and holds back transferred content
+/// exceeding the bandwidth budget
+struct TransferGate {
+    pause: PauseSignal,
+    rate_limit: Option<RateLimiter>,
+}
+
+impl TransferGate {
+    async fn pass(&self, bytes: u64) {
+        if let Some(rate_limit) = &self.rate_limit {
+            rate_limit.acquire(bytes).await;
+        }
+    }
+
+    fn reader<R: AsyncRead + Send + Sync + Unpin + 'static>(self, reader: R) -> GatedReader {
+        let mut chunks = ReaderStream::new(reader);
+
+        let stream: GatedStream = Box::pin(async_stream::stream! {
+            while let Some(chunk) = chunks.next().await {
+                if let Ok(bytes) = &chunk {
+                    self.pass(bytes.len() as u64).await;
+                }
+                yield chunk;
+            }
+        });
+
+        StreamReader::new(stream)
+    }
+}
+
+async fn upload(
+    inner: Arc<TransferManagerInner>,
+    id: TransferId,
+    request: UploadRequest,
+    gate: TransferGate,
+    cancellation_token: CancellationToken,
+) -> TransferResult {
+    let (file, mut options) = open_upload_file(&request).await?;
+    options.pause = Some(gate.pause.clone());
+
+    inner.notify(
+        id,
+        ManagedTransferEvent::Started {
+            total: options.file_meta.size,
+        },
+    );
+
+    let progress = inner.clone();
+    let callback: UploadProgressCallback = Box::new(move |transfer_progress| {
+        progress.notify(id, ManagedTransferEvent::Progressed(transfer_progress));
+    });
+
+    let node = <Dracoon<Connected> as Upload<GatedReader>>::upload_with_cancellation(
+        &inner.dracoon,
+        &request.parent_node,
+        options,
+        BufReader::new(gate.reader(file)),
+        Some(callback),
+        None,
+        cancellation_token,
+    )
+    .await?;
+
+    Ok(TransferOutcome::Uploaded(node))
+}
+
+async fn download(
+    inner: Arc<TransferManagerInner>,
+    id: TransferId,
+    request: DownloadRequest,
+    gate: TransferGate,
+    cancellation_token: CancellationToken,
+) -> TransferResult {
+    let node = &request.node;
+    let target_path = if request.target.is_dir() {
+        request.target.join(&node.name)
+    } else {
+        request.target.clone()
+    };
+    let part_path = part_path(&target_path);
+    let total = node.size.unwrap_or(0);
+
+    inner.notify(id, ManagedTransferEvent::Started { total });
+
+    let result = async {
+        let mut chunks = inner
+            .dracoon
+            .download_chunks(node, Some(gate.pause.clone()))
+            .await?;
+
+        let file = tokio::fs::File::create(&part_path).await.map_err(|err| {
+            error!("Error creating file {}: {}", part_path.display(), err);
+            DracoonClientError::IoError
+        })?;
+        let mut writer = BufWriter::with_capacity(DOWNLOAD_BUFFER_SIZE, file);
+        let mut progress = ProgressTracker::new();
+
+        loop {
+            let chunk = tokio::select! {
+                biased;
+                () = cancellation_token.cancelled() => return Err(DracoonClientError::Cancelled),
+                chunk = chunks.next() => chunk,
+            };
+
+            let Some(chunk) = chunk else {
+                break;
+            };
+            let chunk = chunk?;
+
+            tokio::select! {
+                biased;
+                () = cancellation_token.cancelled() => return Err(DracoonClientError::Cancelled),
+                () = gate.pass(chunk.len() as u64) => (),
+            }
+
+            writer
+                .write_all(&chunk)
+                .await
+                .or(Err(DracoonClientError::IoError))?;
+
+            inner.notify(
+                id,
+                ManagedTransferEvent::Progressed(progress.update(chunk.len() as u64, total)),
+            );
+        }
+
+        writer.flush().await.or(Err(DracoonClientError::IoError))?;
+
+        let file = writer.into_inner().into_std().await;
+        if let Some(timestamp) = node.timestamp_modification {
+            file.set_modified(SystemTime::from(timestamp))
+                .or(Err(DracoonClientError::IoError))?;
+        }
+        // the file is closed before it is moved to the target
+        drop(file);
+
+        tokio::fs::rename(&part_path, &target_path)
+            .await
+            .map_err(|err| {
+                error!("Error moving file to {}: {}", target_path.display(), err);
+                DracoonClientError::IoError
+            })
+    }
+    .await;
+
+    // remove partial file (the target is left untouched)
+    if let Err(err) = result {
+        let _ = tokio::fs::remove_file(&part_path).await;
+        return Err(err);
+    }
+
+    Ok(TransferOutcome::Downloaded(target_path))
+}
+
+/// path of the partial download next to the target (`<name>.part`)
+fn part_path(target_path: &Path) -> PathBuf {
+    let mut file_name = target_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".part");
+
+    target_path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{nodes::models::Node, tests::dracoon::get_connected_client};
+
+    /// waits until the mock received a request (max. 5 seconds)
+    async fn wait_for_request(mock: &mockito::Mock) {
+        for _ in 0..500 {
+            if mock.matched_async().await {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("request not received");
+    }
+
+    /// pauses all transfers of the manager (used as request matcher)
+    fn pause_all(manager: &TransferManager) -> bool {
+        for (id, _) in manager.transfers() {
+            manager.pause(id);
+        }
+        true
+    }
+
+    #[tokio::test]
+    async fn test_transfer_manager_cancel_queued() {
+        let (client, _mock_server) = get_connected_client().await;
+
+        let node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let manager = TransferManager::builder(client)
+            .with_max_concurrent_transfers(1)
+            .build();
+        let mut updates = manager.subscribe();
+
+        let id = manager.queue_download(DownloadRequest::new(node, std::env::temp_dir()));
+        assert_eq!(manager.state(id), Some(TransferState::Queued));
+
+        assert!(manager.pause(id));
+        assert!(manager.cancel(id));
+
+        let result = manager.wait(id).await.unwrap();
+        assert!(matches!(result, Err(DracoonClientError::Cancelled)));
+        assert_eq!(manager.state(id), Some(TransferState::Cancelled));
+
+        // finished transfers can't be paused or cancelled
+        assert!(!manager.pause(id));
+        assert!(!manager.cancel(id));
+        assert!(manager.wait(id).await.is_none());
+
+        let events = [
+            updates.next().await.unwrap().event,
+            updates.next().await.unwrap().event,
+            updates.next().await.unwrap().event,
+        ];
+        assert_eq!(
+            events,
+            [
+                ManagedTransferEvent::Queued,
+                ManagedTransferEvent::Paused,
+                ManagedTransferEvent::Cancelled
+            ]
+        );
+
+        manager.clear_finished();
+        assert!(manager.transfers().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_manager_pause_resume_download() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let mut node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+        node.name = "transfer_manager_pause_resume.bin".to_string();
+        node.size = Some(16);
+
+        let download_url_res = serde_json::json!({
+            "downloadUrl": format!("{}some/download/url", client.get_base_url())
+        });
+
+        let manager = TransferManager::builder(client).build();
+
+        // pause the running download before any content is requested
+        let pausing_manager = manager.clone();
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/2/downloads")
+            .match_request(move |_| pause_all(&pausing_manager))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(download_url_res.to_string())
+            .create();
+
+        let content_length_mock = mock_server
+            .mock("HEAD", "/some/download/url")
+            .with_status(200)
+            .with_header("content-length", "16")
+            .create();
+
+        let mock_bytes: [u8; 16] = [
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let download_mock = mock_server
+            .mock("GET", "/some/download/url")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(mock_bytes)
+            .create();
+
+        let id = manager.queue_download(DownloadRequest::new(node, std::env::temp_dir()));
+
+        wait_for_request(&content_length_mock).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // no chunk is requested while paused
+        assert_eq!(manager.state(id), Some(TransferState::Paused));
+        assert!(!download_mock.matched_async().await);
+
+        assert!(manager.resume(id));
+
+        let result = manager.wait(id).await.unwrap();
+        let Ok(TransferOutcome::Downloaded(path)) = result else {
+            panic!("download failed: {result:?}");
+        };
+        assert_eq!(manager.state(id), Some(TransferState::Completed));
+
+        download_url_mock.assert();
+        content_length_mock.assert();
+        download_mock.assert();
+
+        let content = tokio::fs::read(&path).await.unwrap();
+        assert_eq!(content, mock_bytes.to_vec());
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_transfer_manager_download_error_keeps_existing_file() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let mut node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+        node.name = "transfer_manager_existing.bin".to_string();
+
+        let target = std::env::temp_dir().join(&node.name);
+        tokio::fs::write(&target, b"existing").await.unwrap();
+
+        let download_url_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/2/downloads")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"code":404,"message":"Not Found","debugInfo":"Node not found","errorCode":-41000}"#)
+            .create();
+
+        let manager = TransferManager::builder(client).build();
+        let id = manager.queue_download(DownloadRequest::new(node, std::env::temp_dir()));
+
+        let result = manager.wait(id).await.unwrap();
+        assert!(result.unwrap_err().is_not_found());
+        assert_eq!(manager.state(id), Some(TransferState::Failed));
+
+        download_url_mock.assert();
+
+        let content = tokio::fs::read(&target).await.unwrap();
+        assert_eq!(content, b"existing".to_vec());
+
+        tokio::fs::remove_file(&target).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_transfer_manager_pause_resume_upload() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let source = std::env::temp_dir().join("transfer_manager_pause_resume_upload.bin");
+        tokio::fs::write(
+            &source,
+            [
+                0u8, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+            ],
+        )
+        .await
+        .unwrap();
+
+        let s3_urls_res =
+            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json")
+                .replace("$base_url/", client.get_base_url().as_str());
+
+        let manager = TransferManager::builder(client).build();
+
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!(
+                "../tests/responses/public/system_info_ok.json"
+            ))
+            .create();
+
+        // pause the running upload before any part is uploaded
+        let pausing_manager = manager.clone();
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .match_request(move |_| pause_all(&pausing_manager))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!(
+                "../tests/responses/upload/upload_channel_ok.json"
+            ))
+            .create();
+
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(s3_urls_res)
+            .create();
+
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .create();
+
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .with_status(202)
+            .create();
+
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!(
+                "../tests/responses/upload/upload_status_ok.json"
+            ))
+            .create();
+
+        let id = manager.queue_upload(UploadRequest::new(&source, parent_node));
+
+        wait_for_request(&upload_channel_mock).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // no part is requested or uploaded while paused
+        assert_eq!(manager.state(id), Some(TransferState::Paused));
+        assert!(!s3_urls_mock.matched_async().await);
+        assert!(!upload_mock.matched_async().await);
+
+        assert!(manager.resume(id));
+
+        let result = manager.wait(id).await.unwrap();
+        assert!(
+            matches!(result, Ok(TransferOutcome::Uploaded(_))),
+            "upload failed: {result:?}"
+        );
+        assert_eq!(manager.state(id), Some(TransferState::Completed));
+
+        system_info_mock.assert();
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+
+        tokio::fs::remove_file(&source).await.unwrap();
+    }
+}
@@ -1,20 +1,40 @@
-use std::time::Duration;
+use std::{
+    collections::{HashMap, HashSet},
+    future::{self, Future},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 use super::{
+    crypto::BlockingEncrypter,
     models::{
         CloneableUploadProgressCallback, CompleteS3FileUploadRequest, CreateFileUploadRequest,
         CreateFileUploadResponse, GeneratePresignedUrlsRequest, MissingKeysResponse, Node,
-        PresignedUrl, PresignedUrlList, S3FileUploadStatus, S3UploadStatus, UploadOptions,
-        UploadProgressCallback, UserFileKeySetBatchRequest,
+        PartProgress, PresignedUrl, PresignedUrlList, ReaderUploadRequest, S3FileUploadStatus,
+        S3UploadStatus, UploadChannelSlot, UploadEvent, UploadEventStream, UploadFuture,
+        UploadOptions, UploadProgressCallback, UploadReport, UploadRequest, UploadState,
+        UploadWriter, UserFileKeySetBatchRequest, VirusProtectionVerdict,
     },
-    CompleteUploadRequest, Upload,
+    CompleteUploadRequest, FileMeta, Nodes, OpenUpload, ProgressTracker, TransferProgress, Upload,
+    UploadMany,
 };
 use crate::{
-    client::{errors::DracoonClientError, Connected, GetClient},
+    client::{
+        errors::DracoonClientError,
+        retry::{is_transient, wait_for_retry},
+        throttle::{throttle_stream, PauseSignal, RateLimiter},
+        Connected, GetClient, UploadPolling,
+    },
     constants::{
-        DEFAULT_CHUNK_SIZE, DEFAULT_UPLOAD_GRANULARITY, DRACOON_API_PREFIX, FILES_BASE, FILES_KEYS,
-        FILES_S3_COMPLETE, FILES_S3_URLS, FILES_UPLOAD, MISSING_FILE_KEYS, MISSING_KEYS_BATCH,
-        NODES_BASE, POLLING_START_DELAY, UPLOADS_BASE,
+        DEFAULT_CHUNK_SIZE, DEFAULT_UPLOAD_GRANULARITY, DEFAULT_UPLOAD_PARALLELISM,
+        DRACOON_API_PREFIX, FILES_BASE, FILES_KEYS, FILES_S3_COMPLETE, FILES_S3_URLS, FILES_UPLOAD,
+        MISSING_FILE_KEYS, MISSING_KEYS_BATCH, NODES_BASE, POLLING_START_DELAY,
+        PRESIGNED_URLS_BATCH, S3_MAX_PARTS, S3_MAX_PART_SIZE, S3_MIN_PART_SIZE, UPLOADS_BASE,
+        UPLOAD_WRITER_BUFFER_SIZE,
     },
     nodes::models::{S3FileUploadPart, UserFileKeySetRequest},
     utils::{build_s3_error, FromResponse},
@@ -22,11 +42,18 @@ use crate::{
 };
 use async_trait::async_trait;
 
-use dco3_crypto::{ChunkedEncryption, DracoonCrypto, DracoonRSACrypto, Encrypter};
-use futures_util::Stream;
-use reqwest::{header, Body};
-use tokio::io::{AsyncRead, AsyncReadExt, BufReader};
-use tracing::error;
+use dco3_crypto::{DracoonCrypto, DracoonRSACrypto};
+use futures_util::{
+    stream::{self, FuturesUnordered},
+    Stream, StreamExt, TryStreamExt,
+};
+use reqwest::{header, Body, StatusCode};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, BufReader},
+    sync::mpsc::{self, UnboundedSender},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error};
 
 #[async_trait]
 impl<R: AsyncRead + Sync + Send + Unpin + 'static> Upload<R> for Dracoon<Connected> {
@@ -38,8 +65,51 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> Upload<R> for Dracoon<Connect
         callback: Option<UploadProgressCallback>,
         chunk_size: Option<usize>,
     ) -> Result<Node, DracoonClientError> {
+        self.upload_with_cancellation(
+            parent_node,
+            upload_options,
+            reader,
+            callback,
+            chunk_size,
+            CancellationToken::new(),
+        )
+        .await
+    }
+
+    async fn upload_with_cancellation<'r>(
+        &'r self,
+        parent_node: &Node,
+        mut upload_options: UploadOptions,
+        reader: BufReader<R>,
+        callback: Option<UploadProgressCallback>,
+        chunk_size: Option<usize>,
+        cancellation_token: CancellationToken,
+    ) -> Result<Node, DracoonClientError> {
+        // pre-flight check - fails before any content is uploaded
+        if upload_options.validate_policies {
+            upload_options.validate()?;
+        }
+
+        // wait for a free transfer slot (released when the upload is finished)
+        let permit = self.client.acquire_transfer_permit().await;
+
         let is_s3_upload = self.get_system_info().await?.use_s3_storage;
         let is_encrypted = parent_node.is_encrypted.unwrap_or(false);
+        let wait_for_virus_scan = upload_options.wait_for_virus_scan;
+
+        // the upload channel is recorded once created to clean it up on failure
+        let upload_channel = UploadChannelSlot::default();
+        upload_options.upload_channel = Some(upload_channel.clone());
+
+        // S3 part size and count limits do not apply to NFS uploads
+        let chunk_size = if is_s3_upload {
+            Some(resolve_chunk_size(
+                chunk_size,
+                Some(upload_options.file_meta.size),
+            )?)
+        } else {
+            chunk_size
+        };
 
         let upload_fn = match (is_encrypted, is_s3_upload) {
             (true, true) => Self::upload_to_s3_encrypted,
@@ -48,18 +118,452 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> Upload<R> for Dracoon<Connect
             (false, false) => Self::upload_to_nfs_unencrypted,
         };
 
-        upload_fn(
+        let upload = upload_fn(
             self,
             parent_node,
             upload_options,
             reader,
             callback,
             chunk_size,
+        );
+
+        let node = self
+            .run_upload::<R>(upload, &upload_channel, &cancellation_token)
+            .await?;
+        drop(permit);
+
+        if wait_for_virus_scan {
+            return self.wait_for_virus_scan(node).await;
+        }
+
+        Ok(node)
+    }
+
+    async fn upload_with_unknown_size<'r>(
+        &'r self,
+        parent_node: &Node,
+        mut upload_options: UploadOptions,
+        reader: BufReader<R>,
+        callback: Option<UploadProgressCallback>,
+        chunk_size: Option<usize>,
+    ) -> Result<Node, DracoonClientError> {
+        // pre-flight check - fails before any content is uploaded
+        if upload_options.validate_policies {
+            upload_options.validate()?;
+        }
+
+        // wait for a free transfer slot (released when the upload is finished)
+        let permit = self.client.acquire_transfer_permit().await;
+
+        if !self.get_system_info().await?.use_s3_storage {
+            return Err(DracoonClientError::UnknownSizeNotSupported);
+        }
+
+        let chunk_size = Some(resolve_chunk_size(chunk_size, None)?);
+
+        let wait_for_virus_scan = upload_options.wait_for_virus_scan;
+
+        // the upload channel is recorded once created to clean it up on failure
+        let upload_channel = UploadChannelSlot::default();
+        upload_options.upload_channel = Some(upload_channel.clone());
+
+        let upload = async {
+            if parent_node.is_encrypted.unwrap_or(false) {
+                self.upload_s3_encrypted(
+                    parent_node,
+                    upload_options,
+                    reader,
+                    callback,
+                    chunk_size,
+                    None,
+                )
+                .await
+            } else {
+                self.upload_s3_unencrypted(
+                    parent_node,
+                    upload_options,
+                    reader,
+                    callback,
+                    chunk_size,
+                    None,
+                )
+                .await
+            }
+        };
+
+        let node = self
+            .run_upload::<R>(upload, &upload_channel, &CancellationToken::new())
+            .await?;
+        drop(permit);
+
+        if wait_for_virus_scan {
+            return self.wait_for_virus_scan(node).await;
+        }
+
+        Ok(node)
+    }
+
+    async fn create_upload_state(
+        &self,
+        parent_node: &Node,
+        upload_options: UploadOptions,
+        chunk_size: Option<usize>,
+    ) -> Result<UploadState, DracoonClientError> {
+        let is_s3_upload = self.get_system_info().await?.use_s3_storage;
+        let is_encrypted = parent_node.is_encrypted.unwrap_or(false);
+
+        if !is_s3_upload || is_encrypted {
+            return Err(DracoonClientError::UploadNotResumable);
+        }
+
+        let chunk_size = resolve_chunk_size(chunk_size, Some(upload_options.file_meta.size))?;
+
+        let file_upload_req = CreateFileUploadRequest::from_upload_options(
+            parent_node.id,
+            &upload_options,
+            Some(true),
+        );
+
+        let upload_channel = <Dracoon<Connected> as UploadInternal<R>>::create_upload_channel::<
+            '_,
+            '_,
+        >(self, file_upload_req)
+        .await
+        .map_err(|err| {
+            error!("Error creating upload channel: {}", err);
+            err
+        })?;
+
+        Ok(UploadState::new(
+            upload_channel.upload_id,
+            &upload_options,
+            chunk_size,
+        ))
+    }
+
+    async fn resume_upload(
+        &self,
+        state: &mut UploadState,
+        mut reader: BufReader<R>,
+        callback: Option<UploadProgressCallback>,
+    ) -> Result<Node, DracoonClientError> {
+        // wait for a free transfer slot (released when the upload is finished)
+        let _permit = self.client.acquire_transfer_permit().await;
+
+        let upload_id = state.upload_id().to_string();
+        let (size, chunk_size, parallelism) =
+            (state.size(), state.chunk_size(), state.parallelism());
+
+        let rate_limit = state.max_rate().map(RateLimiter::new);
+
+        let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+
+        self.upload_s3_parts::<R, _>(
+            &upload_id,
+            state.parts_mut(),
+            &mut reader,
+            Some(size),
+            chunk_size,
+            parallelism,
+            None,
+            rate_limit.as_ref(),
+            None,
+            cloneable_callback,
+            |chunk, _| future::ready(Ok(chunk)),
+        )
+        .await?;
+
+        self.complete_s3_upload::<R>(upload_id, state.complete_request(), None)
+            .await
+    }
+
+    fn upload_with_events<'r>(
+        &'r self,
+        parent_node: &'r Node,
+        mut upload_options: UploadOptions,
+        reader: BufReader<R>,
+        chunk_size: Option<usize>,
+    ) -> (UploadFuture<'r>, UploadEventStream) {
+        let (events, mut receiver) = mpsc::unbounded_channel();
+
+        let progress_events = events.clone();
+        let callback: UploadProgressCallback = Box::new(move |progress| {
+            let _ = progress_events.send(UploadEvent::Progressed(progress));
+        });
+
+        // parts, retries and finalizing are reported from within the upload
+        upload_options.events = Some(events.clone());
+
+        let upload = async move {
+            let _ = events.send(UploadEvent::Started {
+                total: upload_options.file_meta.size,
+            });
+
+            let node = self
+                .upload(
+                    parent_node,
+                    upload_options,
+                    reader,
+                    Some(callback),
+                    chunk_size,
+                )
+                .await?;
+
+            let _ = events.send(UploadEvent::Finished);
+
+            Ok::<Node, DracoonClientError>(node)
+        };
+
+        // the stream ends when all senders are dropped (upload finished or failed)
+        let event_stream = async_stream::stream! {
+            while let Some(event) = receiver.recv().await {
+                yield event;
+            }
+        };
+
+        (Box::pin(upload), Box::pin(event_stream))
+    }
+}
+
+impl OpenUpload for Dracoon<Connected> {
+    fn open_upload(
+        &self,
+        parent_node: &Node,
+        upload_options: UploadOptions,
+        callback: Option<UploadProgressCallback>,
+        chunk_size: Option<usize>,
+    ) -> UploadWriter {
+        let (writer, reader) = tokio::io::duplex(UPLOAD_WRITER_BUFFER_SIZE);
+
+        let client = self.clone();
+        let parent_node = parent_node.clone();
+
+        let upload = tokio::spawn(async move {
+            client
+                .upload(
+                    &parent_node,
+                    upload_options,
+                    BufReader::new(reader),
+                    callback,
+                    chunk_size,
+                )
+                .await
+        });
+
+        UploadWriter::new(writer, upload)
+    }
+}
+
+#[async_trait]
+impl UploadMany for Dracoon<Connected> {
+    async fn upload_many(
+        &self,
+        requests: Vec<UploadRequest>,
+        concurrency: usize,
+        callback: Option<UploadProgressCallback>,
+    ) -> UploadReport {
+        let mut total_size = 0;
+        for req in &requests {
+            total_size += match &req.options {
+                Some(options) => options.file_meta.size,
+                None => tokio::fs::metadata(&req.source)
+                    .await
+                    .map_or(0, |metadata| metadata.len()),
+            };
+        }
+
+        let progress = BatchProgress::new(callback, total_size);
+
+        let results = stream::iter(requests)
+            .map(|req| {
+                let progress = progress.clone();
+
+                async move {
+                    let mut retries = 0;
+                    let result = loop {
+                        let reported = Arc::new(AtomicU64::new(0));
+                        let callback = progress.file_callback(reported.clone());
+
+                        match self.upload_from_path(&req, callback).await {
+                            Err(err) if is_transient(&err) => {
+                                // the retry reports the progress of the file again
+                                progress.withdraw(reported.load(Ordering::Relaxed));
+
+                                if let Err(err) =
+                                    wait_for_retry(self.client.retry_policy(), err, &mut retries)
+                                        .await
+                                {
+                                    break Err(err);
+                                }
+                            }
+                            result => break result,
+                        }
+                    };
+
+                    (req.source, result)
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+
+        build_upload_report(results)
+    }
+
+    async fn upload_many_readers<R: AsyncRead + Sync + Send + Unpin + 'static>(
+        &self,
+        requests: Vec<ReaderUploadRequest<R>>,
+        concurrency: usize,
+        callback: Option<UploadProgressCallback>,
+    ) -> UploadReport {
+        let total_size = requests.iter().map(|req| req.options.file_meta.size).sum();
+
+        let progress = BatchProgress::new(callback, total_size);
+
+        let results = stream::iter(requests)
+            .map(|req| {
+                let callback = progress.file_callback(Arc::new(AtomicU64::new(0)));
+
+                async move {
+                    let name = PathBuf::from(&req.options.file_meta.name);
+
+                    let result = <Dracoon<Connected> as Upload<R>>::upload(
+                        self,
+                        &req.parent_node,
+                        req.options,
+                        BufReader::new(req.reader),
+                        callback,
+                        None,
+                    )
+                    .await;
+
+                    (name, result)
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+
+        build_upload_report(results)
+    }
+}
+
+/// aggregated progress of a batch upload
+#[derive(Clone)]
+struct BatchProgress {
+    progress: Option<Arc<Mutex<(UploadProgressCallback, ProgressTracker)>>>,
+    total_size: u64,
+}
+
+impl BatchProgress {
+    fn new(callback: Option<UploadProgressCallback>, total_size: u64) -> Self {
+        Self {
+            progress: callback
+                .map(|callback| Arc::new(Mutex::new((callback, ProgressTracker::new())))),
+            total_size,
+        }
+    }
+
+    /// returns the callback of a single upload - reported bytes are added to `reported`
+    fn file_callback(&self, reported: Arc<AtomicU64>) -> Option<UploadProgressCallback> {
+        let progress = self.progress.clone()?;
+        let total_size = self.total_size;
+
+        Some(Box::new(move |file_progress: TransferProgress| {
+            reported.fetch_add(file_progress.bytes_transferred, Ordering::Relaxed);
+
+            let mut guard = progress.lock().expect("progress lock poisoned");
+            let (callback, tracker) = &mut *guard;
+            callback(tracker.update(file_progress.bytes_transferred, total_size));
+        }))
+    }
+
+    /// removes the progress of a failed upload attempt
+    fn withdraw(&self, bytes: u64) {
+        if let Some(progress) = &self.progress {
+            let mut guard = progress.lock().expect("progress lock poisoned");
+            guard.1.rewind(bytes);
+        }
+    }
+}
+
+/// sorts the results of a batch upload into uploaded files, conflicts and failures
+fn build_upload_report(results: Vec<(PathBuf, Result<Node, DracoonClientError>)>) -> UploadReport {
+    let mut report = UploadReport::default();
+    for (source, result) in results {
+        match result {
+            Ok(node) => report.uploaded.push((source, node)),
+            Err(err) if err.is_conflict() => report.conflicts.push((source, err)),
+            Err(err) => report.failed.push((source, err)),
+        }
+    }
+
+    report
+}
+
+impl Dracoon<Connected> {
+    /// uploads a local file of a batch upload
+    async fn upload_from_path(
+        &self,
+        req: &UploadRequest,
+        callback: Option<UploadProgressCallback>,
+    ) -> Result<Node, DracoonClientError> {
+        let (file, options) = open_upload_file(req).await?;
+
+        <Dracoon<Connected> as Upload<tokio::fs::File>>::upload(
+            self,
+            &req.parent_node,
+            options,
+            BufReader::new(file),
+            callback,
+            None,
         )
         .await
     }
 }
 
+/// opens the local file of an upload request - the upload options are read from the file if not set
+pub(crate) async fn open_upload_file(
+    req: &UploadRequest,
+) -> Result<(tokio::fs::File, UploadOptions), DracoonClientError> {
+    let file = tokio::fs::File::open(&req.source).await.map_err(|err| {
+        error!("Error opening file {}: {}", req.source.display(), err);
+        DracoonClientError::IoError
+    })?;
+
+    let options = match &req.options {
+        Some(options) => options.clone(),
+        None => {
+            let metadata = file.metadata().await.map_err(|err| {
+                error!("Error reading file metadata: {}", err);
+                DracoonClientError::IoError
+            })?;
+            let name = req
+                .source
+                .file_name()
+                .ok_or(DracoonClientError::IoError)?
+                .to_string_lossy();
+
+            let file_meta = FileMeta::builder(name, metadata.len());
+            let file_meta = match metadata.modified() {
+                Ok(modified) => file_meta.with_timestamp_modification(modified.into()),
+                Err(_) => file_meta,
+            };
+
+            UploadOptions::builder(file_meta.build()).build()
+        }
+    };
+
+    Ok((file, options))
+}
+
+/// reports an event of an upload started with [Upload::upload_with_events]
+fn send_upload_event(events: Option<&UnboundedSender<UploadEvent>>, event: UploadEvent) {
+    if let Some(events) = events {
+        let _ = events.send(event);
+    }
+}
+
 #[async_trait]
 trait UploadInternal<R: AsyncRead> {
     async fn create_upload_channel(
@@ -73,6 +577,8 @@ trait UploadInternal<R: AsyncRead> {
         generate_urls_req: GeneratePresignedUrlsRequest,
     ) -> Result<PresignedUrlList, DracoonClientError>;
 
+    async fn delete_upload_channel(&self, upload_id: String) -> Result<(), DracoonClientError>;
+
     async fn upload_to_s3_unencrypted(
         &self,
         parent_node: &Node,
@@ -115,6 +621,7 @@ trait UploadInternal<R: AsyncRead> {
         upload_channel: &CreateFileUploadResponse,
         chunk: bytes::Bytes,
         url_part: u32,
+        total_size: u64,
         callback: Option<CloneableUploadProgressCallback>,
     ) -> Result<S3FileUploadPart, DracoonClientError>;
 }
@@ -130,7 +637,13 @@ pub(crate) trait StreamUploadInternal<S>: GetClient<S> {
             + 'static,
         url: &PresignedUrl,
         chunk_size: u64,
-    ) -> Result<String, DracoonClientError> {
+        rate_limit: Option<&RateLimiter>,
+    ) -> Result<S3PartETag, DracoonClientError> {
+        let stream = throttle_stream(
+            stream,
+            self.get_client().upload_rate_limit().cloned(),
+            rate_limit.cloned(),
+        );
         let body = Body::wrap_stream(stream);
 
         let res = self
@@ -164,7 +677,10 @@ pub(crate) trait StreamUploadInternal<S>: GetClient<S> {
             .expect("ETag header invalid");
         let e_tag = e_tag_header.trim_start_matches('"').trim_end_matches('"');
 
-        Ok(e_tag.to_string())
+        Ok(S3PartETag {
+            value: e_tag.to_string(),
+            is_md5: !is_sse_kms_or_sse_c(res.headers()),
+        })
     }
 
     async fn upload_stream_to_nfs<'a>(
@@ -178,10 +694,16 @@ pub(crate) trait StreamUploadInternal<S>: GetClient<S> {
         file_size: u64,
         chunk_size: usize,
         curr_pos: Option<u64>,
+        rate_limit: Option<&RateLimiter>,
     ) -> Result<(), DracoonClientError> {
         // Initialize a variable to keep track of the number of bytes read
         let bytes_read = curr_pos.unwrap_or(0);
 
+        let stream = throttle_stream(
+            stream,
+            self.get_client().upload_rate_limit().cloned(),
+            rate_limit.cloned(),
+        );
         let body = Body::wrap_stream(stream);
 
         let start_range = bytes_read;
@@ -223,22 +745,24 @@ pub(crate) trait StreamUploadInternal<S>: GetClient<S> {
 
     fn create_stream(
         chunk: bytes::Bytes,
+        part_number: u32,
+        total_size: u64,
         callback: Option<CloneableUploadProgressCallback>,
     ) -> impl Stream<Item = Result<bytes::Bytes, std::io::Error>> {
         async_stream::stream! {
-            let mut buffer = Vec::new();
             let mut bytes_read = 0;
 
-            for byte in chunk.iter() {
-                buffer.push(*byte);
-                bytes_read += 1;
-                if buffer.len() == DEFAULT_UPLOAD_GRANULARITY || bytes_read == chunk.len() {
-                    if let Some(callback) = callback.clone() {
-                        callback.call(buffer.len() as u64, chunk.len() as u64);
-                    }
-                    yield Ok(bytes::Bytes::from(buffer.clone()));
-                    buffer.clear();
+            // slices share the chunk buffer (no copies)
+            while bytes_read < chunk.len() {
+                let end = (bytes_read + DEFAULT_UPLOAD_GRANULARITY).min(chunk.len());
+                let buffer = chunk.slice(bytes_read..end);
+                bytes_read = end;
+
+                if let Some(callback) = callback.clone() {
+                    let part = PartProgress::new(part_number, bytes_read as u64, chunk.len() as u64);
+                    callback.call_part(buffer.len() as u64, total_size, part);
                 }
+                yield Ok(buffer);
             }
         }
     }
@@ -295,6 +819,26 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternal<R> for Dracoon
         CreateFileUploadResponse::from_response(res).await
     }
 
+    async fn delete_upload_channel(&self, upload_id: String) -> Result<(), DracoonClientError> {
+        let url_part =
+            format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{FILES_BASE}/{FILES_UPLOAD}/{upload_id}");
+        let api_url = self.build_api_url(&url_part);
+
+        let res = self
+            .client
+            .http
+            .delete(api_url)
+            .header(header::AUTHORIZATION, self.get_auth_header().await?)
+            .send()
+            .await?;
+
+        if res.status().is_client_error() || res.status().is_server_error() {
+            return Err(DracoonClientError::from_response(res).await?);
+        }
+
+        Ok(())
+    }
+
     async fn create_s3_upload_urls(
         &self,
         upload_id: String,
@@ -363,28 +907,194 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternal<R> for Dracoon
         S3FileUploadStatus::from_response(res).await
     }
 
-    #[allow(clippy::single_match_else)]
-    #[allow(clippy::too_many_lines)]
     async fn upload_to_s3_unencrypted(
         &self,
         parent_node: &Node,
         upload_options: UploadOptions,
-        mut reader: BufReader<R>,
+        reader: BufReader<R>,
         callback: Option<UploadProgressCallback>,
         chunk_size: Option<usize>,
     ) -> Result<Node, DracoonClientError> {
-        let fm = upload_options.file_meta.clone();
+        let size = Some(upload_options.file_meta.size);
+        self.upload_s3_unencrypted(
+            parent_node,
+            upload_options,
+            reader,
+            callback,
+            chunk_size,
+            size,
+        )
+        .await
+    }
 
-        let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+    async fn upload_to_s3_encrypted(
+        &self,
+        parent_node: &Node,
+        upload_options: UploadOptions,
+        reader: BufReader<R>,
+        callback: Option<UploadProgressCallback>,
+        chunk_size: Option<usize>,
+    ) -> Result<Node, DracoonClientError> {
+        let size = Some(upload_options.file_meta.size);
+        self.upload_s3_encrypted(
+            parent_node,
+            upload_options,
+            reader,
+            callback,
+            chunk_size,
+            size,
+        )
+        .await
+    }
 
-        // create upload channel
-        let file_upload_req = CreateFileUploadRequest::from_upload_options(
-            parent_node.id,
-            &upload_options,
-            Some(true),
-        );
+    async fn get_missing_file_keys(
+        &self,
+        file_id: u64,
+    ) -> Result<MissingKeysResponse, DracoonClientError> {
+        let url_part = format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{MISSING_FILE_KEYS}");
 
-        let upload_channel = <Dracoon<Connected> as UploadInternal<R>>::create_upload_channel::<
+        let mut api_url = self.build_api_url(&url_part);
+
+        api_url
+            .query_pairs_mut()
+            .append_pair("file_id", file_id.to_string().as_str())
+            .append_pair("limit", MISSING_KEYS_BATCH.to_string().as_str())
+            .finish();
+
+        let response = self
+            .client
+            .http
+            .get(api_url)
+            .header(header::AUTHORIZATION, self.get_auth_header().await?)
+            .send()
+            .await?;
+
+        MissingKeysResponse::from_response(response).await
+    }
+
+    async fn set_file_keys(
+        &self,
+        keys_batch_req: UserFileKeySetBatchRequest,
+    ) -> Result<(), DracoonClientError> {
+        let url_part = format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{FILES_BASE}/{FILES_KEYS}");
+
+        let api_url = self.build_api_url(&url_part);
+
+        let response = self
+            .client
+            .http
+            .post(api_url)
+            .header(header::AUTHORIZATION, self.get_auth_header().await?)
+            .json(&keys_batch_req)
+            .send()
+            .await?;
+
+        if response.status().is_server_error() || response.status().is_client_error() {
+            return Err(DracoonClientError::from_response(response)
+                .await
+                .expect("Could not parse error response"));
+        }
+
+        Ok(())
+    }
+
+    async fn upload_chunk(
+        &self,
+        upload_channel: &CreateFileUploadResponse,
+        chunk: bytes::Bytes,
+        url_part: u32,
+        total_size: u64,
+        callback: Option<CloneableUploadProgressCallback>,
+    ) -> Result<S3FileUploadPart, DracoonClientError> {
+        let chunk_len: u64 = chunk
+            .len()
+            .try_into()
+            .map_err(|_| DracoonClientError::IoError)?;
+
+        let stream = Self::create_stream(chunk, url_part, total_size, callback);
+        let url_req = GeneratePresignedUrlsRequest::new(chunk_len, url_part, url_part);
+
+        let url = <Dracoon<Connected> as UploadInternal<R>>::create_s3_upload_urls(
+            self,
+            upload_channel.upload_id.clone(),
+            url_req,
+        )
+        .await?;
+
+        let url = url.urls.first().ok_or_else(|| {
+            error!("No presigned url returned for part {}", url_part);
+            DracoonClientError::MissingS3Url(url_part)
+        })?;
+
+        let e_tag = <Dracoon<Connected> as StreamUploadInternal<Connected>>::upload_stream_to_s3(
+            self,
+            Box::pin(stream),
+            url,
+            chunk_len,
+            None,
+        )
+        .await?;
+
+        Ok(S3FileUploadPart::new(url_part, e_tag.value))
+    }
+}
+
+impl Dracoon<Connected> {
+    /// runs an upload until it is done or cancelled - the upload channel
+    /// is removed from the server if the upload is cancelled or fails
+    async fn run_upload<R: AsyncRead + Sync + Send + Unpin + 'static>(
+        &self,
+        upload: impl Future<Output = Result<Node, DracoonClientError>> + Send,
+        upload_channel: &UploadChannelSlot,
+        cancellation_token: &CancellationToken,
+    ) -> Result<Node, DracoonClientError> {
+        let result = tokio::select! {
+            biased;
+            () = cancellation_token.cancelled() => Err(DracoonClientError::Cancelled),
+            result = upload => result,
+        };
+
+        if let Err(err) = &result {
+            if let Some(upload_id) = upload_channel.take() {
+                debug!("Upload failed ({}) - removing upload channel", err);
+                if let Err(err) = <Dracoon<Connected> as UploadInternal<R>>::delete_upload_channel(
+                    self, upload_id,
+                )
+                .await
+                {
+                    error!("Error removing upload channel: {}", err);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// uploads to S3 (unencrypted) - the size is optional (read until EOF if unknown)
+    async fn upload_s3_unencrypted<R: AsyncRead + Sync + Send + Unpin + 'static>(
+        &self,
+        parent_node: &Node,
+        upload_options: UploadOptions,
+        mut reader: BufReader<R>,
+        callback: Option<UploadProgressCallback>,
+        chunk_size: Option<usize>,
+        size: Option<u64>,
+    ) -> Result<Node, DracoonClientError> {
+        let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+
+        // create upload channel
+        let file_upload_req = CreateFileUploadRequest::from_upload_options(
+            parent_node.id,
+            &upload_options,
+            Some(true),
+        );
+        let file_upload_req = if size.is_some() {
+            file_upload_req
+        } else {
+            file_upload_req.without_size()
+        };
+
+        let upload_channel = <Dracoon<Connected> as UploadInternal<R>>::create_upload_channel::<
             '_,
             '_,
         >(self, file_upload_req)
@@ -394,81 +1104,123 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternal<R> for Dracoon
             err
         })?;
 
+        // recorded to remove the channel if the upload fails
+        if let Some(slot) = &upload_options.upload_channel {
+            slot.record(&upload_channel.upload_id);
+        }
+
+        let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+        let rate_limit = upload_options.max_rate.map(RateLimiter::new);
+
         let mut s3_parts = Vec::new();
 
-        let (count_urls, last_chunk_size) = calculate_s3_url_count(fm.size, chunk_size as u64);
-        let mut url_part: u32 = 1;
+        self.upload_s3_parts::<R, _>(
+            &upload_channel.upload_id,
+            &mut s3_parts,
+            &mut reader,
+            size,
+            chunk_size,
+            upload_options.parallelism,
+            upload_options.pause.as_ref(),
+            rate_limit.as_ref(),
+            upload_options.events.as_ref(),
+            cloneable_callback,
+            |chunk, _| future::ready(Ok(chunk)),
+        )
+        .await?;
 
-        let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+        // finalize upload
+        let complete_upload_req = CompleteS3FileUploadRequest::builder(s3_parts)
+            .with_resolution_strategy(upload_options.resolution_strategy.unwrap_or_default())
+            .with_keep_share_links(upload_options.keep_share_links.unwrap_or(false))
+            .build();
 
-        if count_urls > 1 {
-            while url_part < count_urls {
-                let mut buffer = vec![0; chunk_size];
-                let cb = cloneable_callback.clone();
-                let fm = fm.clone();
+        self.complete_s3_upload::<R>(
+            upload_channel.upload_id,
+            complete_upload_req,
+            upload_options.events.as_ref(),
+        )
+        .await
+    }
 
-                match reader.read_exact(&mut buffer).await {
-                    Ok(0) => break,
-                    Ok(n) => {
-                        buffer.truncate(n);
-                        let chunk = bytes::Bytes::from(buffer);
+    /// uploads to S3 (encrypted) - the size is optional (read until EOF if unknown)
+    #[allow(clippy::too_many_lines)]
+    async fn upload_s3_encrypted<R: AsyncRead + Sync + Send + Unpin + 'static>(
+        &self,
+        parent_node: &Node,
+        upload_options: UploadOptions,
+        mut reader: BufReader<R>,
+        callback: Option<UploadProgressCallback>,
+        chunk_size: Option<usize>,
+        size: Option<u64>,
+    ) -> Result<Node, DracoonClientError> {
+        let keypair = self.get_keypair(None).await?;
 
-                        let s3_part = <Dracoon<Connected> as UploadInternal<R>>::upload_chunk(
-                            self,
-                            &upload_channel,
-                            chunk,
-                            url_part,
-                            cb,
-                        )
-                        .await?;
+        let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
 
-                        s3_parts.push(s3_part);
-                        url_part += 1;
-                    }
-                    Err(err) => {
-                        error!("Error reading file: {}", err);
-                        return Err(DracoonClientError::IoError);
-                    }
-                }
-            }
-        }
+        // encrypt chunk by chunk while uploading (encrypted size equals plain size)
+        let encrypter = BlockingEncrypter::new(upload_options.file_key.as_ref())?;
 
-        // upload last chunk
-        let mut buffer = vec![
-            0;
-            last_chunk_size
-                .try_into()
-                .map_err(|_| DracoonClientError::IoError)?
-        ];
-        let cb = cloneable_callback.clone();
-        match reader.read_exact(&mut buffer).await {
-            Ok(n) => {
-                buffer.truncate(n);
-                let chunk = bytes::Bytes::from(buffer);
+        // create upload channel
+        let file_upload_req = CreateFileUploadRequest::from_upload_options(
+            parent_node.id,
+            &upload_options,
+            Some(true),
+        );
+        let file_upload_req = if size.is_some() {
+            file_upload_req
+        } else {
+            file_upload_req.without_size()
+        };
 
-                let s3_part = <Dracoon<Connected> as UploadInternal<R>>::upload_chunk(
-                    self,
-                    &upload_channel,
-                    chunk,
-                    url_part,
-                    cb,
-                )
-                .await?;
+        let upload_channel = <Dracoon<Connected> as UploadInternal<R>>::create_upload_channel::<
+            '_,
+            '_,
+        >(self, file_upload_req)
+        .await
+        .map_err(|err| {
+            error!("Error creating upload channel: {}", err);
+            err
+        })?;
 
-                s3_parts.push(s3_part);
-            }
-            Err(err) => {
-                error!("Error reading file: {}", err);
-                return Err(DracoonClientError::IoError);
-            }
+        // recorded to remove the channel if the upload fails
+        if let Some(slot) = &upload_options.upload_channel {
+            slot.record(&upload_channel.upload_id);
         }
 
+        let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+        let rate_limit = upload_options.max_rate.map(RateLimiter::new);
+
+        let mut s3_parts = Vec::new();
+
+        self.upload_s3_parts::<R, _>(
+            &upload_channel.upload_id,
+            &mut s3_parts,
+            &mut reader,
+            size,
+            chunk_size,
+            upload_options.parallelism,
+            upload_options.pause.as_ref(),
+            rate_limit.as_ref(),
+            upload_options.events.as_ref(),
+            cloneable_callback,
+            |chunk, is_last| encrypter.encrypt(chunk, is_last),
+        )
+        .await?;
+
+        // file key is available after encrypting the last chunk
+        let plain_file_key = encrypter.plain_file_key()?;
+        let file_key = DracoonCrypto::encrypt_file_key(plain_file_key.clone(), keypair)?;
+
         // finalize upload
         let complete_upload_req = CompleteS3FileUploadRequest::builder(s3_parts)
             .with_resolution_strategy(upload_options.resolution_strategy.unwrap_or_default())
             .with_keep_share_links(upload_options.keep_share_links.unwrap_or(false))
+            .with_file_key(file_key)
             .build();
 
+        send_upload_event(upload_options.events.as_ref(), UploadEvent::Finalizing);
+
         <Dracoon<Connected> as UploadInternal<R>>::finalize_upload::<'_, '_>(
             self,
             upload_channel.upload_id.clone(),
@@ -484,7 +1236,8 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternal<R> for Dracoon
         // return node if upload is done
         // return error if upload failed
         // polling with exponential backoff
-        let mut sleep_duration = POLLING_START_DELAY;
+        let started_at = Instant::now();
+        let mut sleep_duration = Duration::from_millis(POLLING_START_DELAY);
         loop {
             let status_response = <Dracoon<Connected> as UploadInternal<R>>::get_upload_status(
                 self,
@@ -498,174 +1251,354 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternal<R> for Dracoon
 
             match status_response.status {
                 S3UploadStatus::Done => {
+                    // given users or users missing the file key (limit 50)
+                    let recipients = match upload_options.public_keys.clone() {
+                        Some(public_keys) => public_keys,
+                        None => {
+                            <Dracoon<Connected> as UploadInternal<R>>::get_missing_file_keys(
+                                self,
+                                status_response
+                                    .node
+                                    .as_ref()
+                                    .expect("Node must be set if status is done")
+                                    .id,
+                            )
+                            .await
+                            .map_err(|err| {
+                                error!("Error getting missing file keys: {}", err);
+                                err
+                            })?
+                            .users
+                        }
+                    };
+
+                    // encrypt plain file key for each user
+                    let key_reqs = recipients
+                        .into_iter()
+                        .flat_map::<Result<UserFileKeySetRequest, DracoonClientError>, _>(|user| {
+                            let user_id = user.id;
+                            let file_id = status_response
+                                .node
+                                .as_ref()
+                                .expect("Node must be set if status is done")
+                                .id;
+                            let public_key = user.public_key_container;
+                            let file_key = DracoonCrypto::encrypt_file_key(
+                                plain_file_key.clone(),
+                                public_key,
+                            )?;
+                            let set_key_req =
+                                UserFileKeySetRequest::new(user_id, file_id, file_key);
+                            Ok(set_key_req)
+                        })
+                        .collect::<Vec<_>>();
+
+                    drop(plain_file_key);
+                    // set file keys
+                    if !key_reqs.is_empty() {
+                        <Dracoon<Connected> as UploadInternal<R>>::set_file_keys(
+                            self,
+                            key_reqs.into(),
+                        )
+                        .await
+                        .map_err(|err| {
+                            error!("Error setting file keys: {}", err);
+                            err
+                        })?;
+                    }
+
                     return Ok(status_response
                         .node
                         .expect("Node must be set if status is done"));
                 }
                 S3UploadStatus::Error => {
-                    let response = status_response
-                        .error_details
-                        .expect("Error message must be set if status is error");
-                    error!("Error uploading file: {}", response);
-                    return Err(DracoonClientError::Http(response));
+                    return Err(DracoonClientError::Http(
+                        status_response
+                            .error_details
+                            .expect("Error message must be set if status is error"),
+                    ));
                 }
                 _ => {
-                    tokio::time::sleep(Duration::from_millis(sleep_duration)).await;
-                    sleep_duration *= 2;
+                    wait_for_upload_status(
+                        self.client.upload_polling(),
+                        started_at,
+                        &mut sleep_duration,
+                    )
+                    .await?;
                 }
             }
         }
     }
 
-    #[allow(clippy::too_many_lines)]
-    async fn upload_to_s3_encrypted(
+    /// reads the content chunk by chunk and uploads up to `parallelism` S3 parts concurrently
+    /// - presigned urls are requested in batches (see `PRESIGNED_URLS_BATCH`)
+    /// - `transform` is applied to each chunk in order (flag is set for the last chunk) -
+    ///   CPU-heavy transforms (encryption) should run on blocking threads
+    /// - parts already contained in `s3_parts` are read but not uploaded again (resume)
+    /// - uploaded parts are added to `s3_parts` (parts in flight are kept on error)
+    /// - a paused upload finishes the parts in flight and waits before the next part
+    /// - `rate_limit` is shared by all parts (max. rate of the whole upload)
+    #[allow(clippy::too_many_arguments)]
+    async fn upload_s3_parts<R: AsyncRead + Sync + Send + Unpin + 'static, T>(
         &self,
-        parent_node: &Node,
-        upload_options: UploadOptions,
-        mut reader: BufReader<R>,
-        callback: Option<UploadProgressCallback>,
-        chunk_size: Option<usize>,
-    ) -> Result<Node, DracoonClientError> {
-        let keypair = self.get_keypair(None).await?;
-
-        let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
-
-        let mut crypto_buff = vec![
-            0u8;
-            upload_options
-                .file_meta
-                .size
-                .try_into()
-                .map_err(|_| DracoonClientError::IoError)?
-        ];
-        let mut read_buff = vec![
-            0u8;
-            upload_options
-                .file_meta
-                .size
-                .try_into()
-                .map_err(|_| DracoonClientError::IoError)?
-        ];
-        let mut crypter = DracoonCrypto::encrypter(&mut crypto_buff)?;
-
-        while let Ok(chunk) = reader.read(&mut read_buff).await {
-            if chunk == 0 {
+        upload_id: &str,
+        s3_parts: &mut Vec<S3FileUploadPart>,
+        reader: &mut BufReader<R>,
+        total_size: Option<u64>,
+        chunk_size: usize,
+        parallelism: Option<usize>,
+        pause: Option<&PauseSignal>,
+        rate_limit: Option<&RateLimiter>,
+        events: Option<&UnboundedSender<UploadEvent>>,
+        callback: Option<CloneableUploadProgressCallback>,
+        mut transform: impl FnMut(Vec<u8>, bool) -> T + Send,
+    ) -> Result<(), DracoonClientError>
+    where
+        T: Future<Output = Result<Vec<u8>, DracoonClientError>> + Send,
+    {
+        let parallelism = parallelism.unwrap_or(DEFAULT_UPLOAD_PARALLELISM).max(1);
+        let count_urls = total_size.map(|size| calculate_s3_url_count(size, chunk_size as u64).0);
+        let progress_total = total_size.unwrap_or(0);
+
+        let completed_parts = s3_parts
+            .iter()
+            .map(S3FileUploadPart::part_number)
+            .collect::<HashSet<_>>();
+
+        let mut urls: HashMap<u32, PresignedUrl> = HashMap::new();
+        let mut uploads = FuturesUnordered::new();
+        // chunks in flight and the chunk read ahead are reused for the next parts
+        let pool = BufferPool::new(parallelism + 1);
+        let mut result = Ok(());
+
+        // never read beyond the given size
+        let mut reader = reader.take(total_size.unwrap_or(u64::MAX));
+        let mut bytes_read: u64 = 0;
+        let mut url_part: u32 = 0;
+        let mut next_buffer = Some(read_chunk(&mut reader, chunk_size, &pool).await?);
+
+        while let Some(buffer) = next_buffer.take() {
+            url_part += 1;
+            bytes_read += buffer.len() as u64;
+
+            // read ahead to detect the last chunk (required if the size is unknown)
+            if buffer.len() == chunk_size {
+                let buffer = read_chunk(&mut reader, chunk_size, &pool).await?;
+                if buffer.is_empty() {
+                    pool.put(buffer);
+                } else {
+                    next_buffer = Some(buffer);
+                }
+            }
+            let is_last = next_buffer.is_none();
+
+            if is_last && total_size.is_some_and(|size| size != bytes_read) {
+                error!(
+                    "Error reading file: expected {:?} bytes, got {}",
+                    total_size, bytes_read
+                );
+                result = Err(DracoonClientError::IoError);
                 break;
             }
-            crypter.update(&read_buff[..chunk])?;
-        }
-        crypter.finalize()?;
-        // drop the read buffer after completing the encryption
-        drop(read_buff);
-
-        //TODO: rewrite without buffer clone
-        let enc_bytes = crypter.get_message().clone();
-
-        assert_eq!(enc_bytes.len() as u64, upload_options.file_meta.size);
-
-        let mut crypto_reader = BufReader::new(enc_bytes.as_slice());
-        let plain_file_key = crypter.get_plain_file_key();
-        let file_key = DracoonCrypto::encrypt_file_key(plain_file_key.clone(), keypair)?;
-        // drop the crypto buffer (enc bytes are still in the reader)
-        drop(crypto_buff);
-
-        let fm = upload_options.file_meta.clone();
 
-        // create upload channel
-        let file_upload_req = CreateFileUploadRequest::from_upload_options(
-            parent_node.id,
-            &upload_options,
-            Some(true),
-        );
-
-        let upload_channel = <Dracoon<Connected> as UploadInternal<R>>::create_upload_channel::<
-            '_,
-            '_,
-        >(self, file_upload_req)
-        .await
-        .map_err(|err| {
-            error!("Error creating upload channel: {}", err);
-            err
-        })?;
+            let chunk = bytes::Bytes::from(transform(buffer, is_last).await?);
+            let chunk_len: u64 = chunk
+                .len()
+                .try_into()
+                .map_err(|_| DracoonClientError::IoError)?;
 
-        let mut s3_parts = Vec::new();
+            if completed_parts.contains(&url_part) {
+                if let Some(callback) = callback.as_ref() {
+                    let part = PartProgress::new(url_part, chunk_len, chunk_len);
+                    callback.call_part(chunk_len, progress_total, part);
+                }
+                pool.recycle(chunk);
+                continue;
+            }
 
-        let (count_urls, last_chunk_size) = calculate_s3_url_count(fm.size, chunk_size as u64);
-        let mut url_part: u32 = 1;
+            // pause between parts - no request is in flight while paused
+            if let Some(pause) = pause.filter(|pause| pause.is_paused()) {
+                while let Some(s3_part) = uploads.next().await {
+                    match s3_part {
+                        Ok(s3_part) => s3_parts.push(s3_part),
+                        Err(err) => result = result.and(Err(err)),
+                    }
+                }
 
-        let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+                if result.is_err() {
+                    break;
+                }
 
-        if count_urls > 1 {
-            while url_part < count_urls {
-                let mut buffer = vec![0; chunk_size];
-                let cb = cloneable_callback.clone();
-                let fm = fm.clone();
+                pause.wait_while_paused().await;
+            }
 
-                match crypto_reader.read_exact(&mut buffer).await {
-                    Ok(0) => break,
-                    Ok(n) => {
-                        let chunk_len = n;
-                        buffer.truncate(chunk_len);
-                        let chunk = bytes::Bytes::from(buffer);
+            // all parts of a batch share the same size - last part is requested separately
+            if is_last || !urls.contains_key(&url_part) {
+                let last_part_number = if is_last {
+                    url_part
+                } else {
+                    let batch_end = url_part + PRESIGNED_URLS_BATCH - 1;
+                    count_urls.map_or(batch_end, |count| batch_end.min(count - 1))
+                };
+                let url_req =
+                    GeneratePresignedUrlsRequest::new(chunk_len, url_part, last_part_number);
+
+                let presigned_urls =
+                    match <Dracoon<Connected> as UploadInternal<R>>::create_s3_upload_urls(
+                        self,
+                        upload_id.to_string(),
+                        url_req,
+                    )
+                    .await
+                    {
+                        Ok(presigned_urls) => presigned_urls,
+                        Err(err) => {
+                            result = Err(err);
+                            break;
+                        }
+                    };
+
+                urls.extend((url_part..=last_part_number).zip(presigned_urls.urls));
+            }
 
-                        let s3_part = <Dracoon<Connected> as UploadInternal<R>>::upload_chunk(
-                            self,
-                            &upload_channel,
-                            chunk,
+            let Some(url) = urls.remove(&url_part) else {
+                error!("No presigned url returned for part {}", url_part);
+                result = Err(DracoonClientError::MissingS3Url(url_part));
+                break;
+            };
+            let callback = callback.clone();
+            let pool = pool.clone();
+
+            uploads.push(async move {
+                let upload_part = async {
+                    let mut url = url;
+                    let mut retries = 0;
+                    // progress of a failed attempt is not reported twice
+                    let reported = Arc::new(AtomicU64::new(0));
+
+                    loop {
+                        let counter = (retries == 0).then(|| reported.clone());
+                        let stream = Self::create_stream(
+                            chunk.clone(),
                             url_part,
-                            cb,
+                            progress_total,
+                            callback.clone().filter(|_| retries == 0),
                         )
-                        .await?;
-
-                        s3_parts.push(s3_part);
-                        url_part += 1;
+                        .inspect_ok(move |bytes| {
+                            if let Some(counter) = &counter {
+                                counter.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+                            }
+                        });
+
+                        let upload_res = self
+                            .upload_stream_to_s3(Box::pin(stream), &url, chunk_len, rate_limit)
+                            .await;
+
+                        let err = match upload_res {
+                            Ok(e_tag) => {
+                                verify_e_tag(&chunk, &e_tag, url_part)?;
+                                send_upload_event(
+                                    events,
+                                    UploadEvent::PartCompleted {
+                                        part_number: url_part,
+                                        size: chunk_len,
+                                    },
+                                );
+                                if let Some(callback) = callback.as_ref().filter(|_| retries > 0) {
+                                    let reported = reported.load(Ordering::Relaxed);
+                                    let part = PartProgress::new(url_part, chunk_len, chunk_len);
+                                    callback.call_part(
+                                        chunk_len.saturating_sub(reported),
+                                        progress_total,
+                                        part,
+                                    );
+                                }
+                                return Ok(S3FileUploadPart::new(url_part, e_tag.value));
+                            }
+                            Err(err) => err,
+                        };
+
+                        let is_expired = is_expired_url(&err);
+
+                        if !is_expired && !is_transient(&err) {
+                            return Err(err);
+                        }
+
+                        wait_for_retry(self.client.retry_policy(), err, &mut retries).await?;
+                        send_upload_event(
+                            events,
+                            UploadEvent::PartRetried {
+                                part_number: url_part,
+                                retry: retries,
+                            },
+                        );
+
+                        if is_expired {
+                            let url_req =
+                                GeneratePresignedUrlsRequest::new(chunk_len, url_part, url_part);
+                            url = <Dracoon<Connected> as UploadInternal<R>>::create_s3_upload_urls(
+                                self,
+                                upload_id.to_string(),
+                                url_req,
+                            )
+                            .await?
+                            .urls
+                            .into_iter()
+                            .next()
+                            .ok_or_else(|| {
+                                error!("No presigned url returned for part {}", url_part);
+                                DracoonClientError::MissingS3Url(url_part)
+                            })?;
+                        }
                     }
-                    Err(err) => return Err(DracoonClientError::IoError),
-                }
-            }
-        }
+                };
 
-        // upload last chunk
-        let mut buffer = vec![
-            0;
-            last_chunk_size
-                .try_into()
-                .map_err(|_| DracoonClientError::IoError)?
-        ];
-        let cb = cloneable_callback.clone();
-        match crypto_reader.read_exact(&mut buffer).await {
-            Ok(n) => {
-                buffer.truncate(n);
-                let chunk = bytes::Bytes::from(buffer);
+                let s3_part = upload_part.await;
+                pool.recycle(chunk);
 
-                let s3_part = <Dracoon<Connected> as UploadInternal<R>>::upload_chunk(
-                    self,
-                    &upload_channel,
-                    chunk,
-                    url_part,
-                    cb,
-                )
-                .await?;
+                s3_part
+            });
 
-                s3_parts.push(s3_part);
+            // wait for a free slot before reading the next chunk
+            while uploads.len() >= parallelism {
+                match uploads.next().await {
+                    Some(Ok(s3_part)) => s3_parts.push(s3_part),
+                    Some(Err(err)) => result = Err(err),
+                    None => break,
+                }
             }
 
-            Err(err) => {
-                error!("Error reading file: {}", err);
-                return Err(DracoonClientError::IoError);
+            if result.is_err() {
+                break;
             }
         }
 
-        // finalize upload
-        let complete_upload_req = CompleteS3FileUploadRequest::builder(s3_parts)
-            .with_resolution_strategy(upload_options.resolution_strategy.unwrap_or_default())
-            .with_keep_share_links(upload_options.keep_share_links.unwrap_or(false))
-            .with_file_key(file_key)
-            .build();
+        // keep parts in flight - they can be skipped when resuming
+        while let Some(s3_part) = uploads.next().await {
+            match s3_part {
+                Ok(s3_part) => s3_parts.push(s3_part),
+                Err(err) => result = result.and(Err(err)),
+            }
+        }
+
+        s3_parts.sort_by_key(S3FileUploadPart::part_number);
+
+        result
+    }
+
+    /// finalizes the S3 upload and polls the upload status (exponential backoff)
+    /// until the node is available
+    async fn complete_s3_upload<R: AsyncRead + Sync + Send + Unpin + 'static>(
+        &self,
+        upload_id: String,
+        complete_upload_req: CompleteS3FileUploadRequest,
+        events: Option<&UnboundedSender<UploadEvent>>,
+    ) -> Result<Node, DracoonClientError> {
+        send_upload_event(events, UploadEvent::Finalizing);
 
         <Dracoon<Connected> as UploadInternal<R>>::finalize_upload::<'_, '_>(
             self,
-            upload_channel.upload_id.clone(),
+            upload_id.clone(),
             complete_upload_req,
         )
         .await
@@ -674,15 +1607,12 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternal<R> for Dracoon
             err
         })?;
 
-        // get upload status
-        // return node if upload is done
-        // return error if upload failed
-        // polling with exponential backoff
-        let mut sleep_duration = POLLING_START_DELAY;
+        let started_at = Instant::now();
+        let mut sleep_duration = Duration::from_millis(POLLING_START_DELAY);
         loop {
             let status_response = <Dracoon<Connected> as UploadInternal<R>>::get_upload_status(
                 self,
-                upload_channel.upload_id.clone(),
+                upload_id.clone(),
             )
             .await
             .map_err(|err| {
@@ -692,177 +1622,284 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternal<R> for Dracoon
 
             match status_response.status {
                 S3UploadStatus::Done => {
-                    // fetch missing keys (limit 50)
-                    let missing_keys =
-                        <Dracoon<Connected> as UploadInternal<R>>::get_missing_file_keys(
-                            self,
-                            status_response
-                                .node
-                                .as_ref()
-                                .expect("Node must be set if status is done")
-                                .id,
-                        )
-                        .await
-                        .map_err(|err| {
-                            error!("Error getting missing file keys: {}", err);
-                            err
-                        })?;
-
-                    // encrypt plain file key for each user
-                    let key_reqs = missing_keys
-                        .users
-                        .into_iter()
-                        .flat_map::<Result<UserFileKeySetRequest, DracoonClientError>, _>(|user| {
-                            let user_id = user.id;
-                            let file_id = status_response
-                                .node
-                                .as_ref()
-                                .expect("Node must be set if status is done")
-                                .id;
-                            let public_key = user.public_key_container;
-                            let file_key = DracoonCrypto::encrypt_file_key(
-                                plain_file_key.clone(),
-                                public_key,
-                            )?;
-                            let set_key_req =
-                                UserFileKeySetRequest::new(user_id, file_id, file_key);
-                            Ok(set_key_req)
-                        })
-                        .collect::<Vec<_>>();
-
-                    drop(plain_file_key);
-                    // set file keys
-                    if !key_reqs.is_empty() {
-                        <Dracoon<Connected> as UploadInternal<R>>::set_file_keys(
-                            self,
-                            key_reqs.into(),
-                        )
-                        .await
-                        .map_err(|err| {
-                            error!("Error setting file keys: {}", err);
-                            err
-                        })?;
-                    }
-
                     return Ok(status_response
                         .node
                         .expect("Node must be set if status is done"));
                 }
                 S3UploadStatus::Error => {
-                    return Err(DracoonClientError::Http(
-                        status_response
-                            .error_details
-                            .expect("Error message must be set if status is error"),
-                    ));
+                    let response = status_response
+                        .error_details
+                        .expect("Error message must be set if status is error");
+                    error!("Error uploading file: {}", response);
+                    return Err(DracoonClientError::Http(response));
                 }
                 _ => {
-                    tokio::time::sleep(Duration::from_millis(sleep_duration)).await;
-                    sleep_duration *= 2;
+                    wait_for_upload_status(
+                        self.client.upload_polling(),
+                        started_at,
+                        &mut sleep_duration,
+                    )
+                    .await?;
                 }
             }
         }
     }
 
-    async fn get_missing_file_keys(
-        &self,
-        file_id: u64,
-    ) -> Result<MissingKeysResponse, DracoonClientError> {
-        let url_part = format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{MISSING_FILE_KEYS}");
+    /// polls an uploaded node until the virus scan verdict is available
+    /// (returned as is if virus protection is disabled for the room)
+    async fn wait_for_virus_scan(&self, node: Node) -> Result<Node, DracoonClientError> {
+        let mut node = match node.virus_protection_info {
+            Some(_) => node,
+            None => self.nodes().get_node(node.id).await?,
+        };
 
-        let mut api_url = self.build_api_url(&url_part);
+        let polling = self.client.upload_polling();
+        let started_at = Instant::now();
+        let mut sleep_duration = Duration::from_millis(POLLING_START_DELAY);
+
+        while node
+            .virus_protection_info
+            .as_ref()
+            .is_some_and(|info| info.verdict == VirusProtectionVerdict::InProgress)
+        {
+            if started_at.elapsed() + sleep_duration > polling.timeout {
+                error!(
+                    "Virus scan of node {} not completed within {:?}",
+                    node.id, polling.timeout
+                );
+                return Err(DracoonClientError::VirusScanTimedOut);
+            }
 
-        api_url
-            .query_pairs_mut()
-            .append_pair("file_id", file_id.to_string().as_str())
-            .append_pair("limit", MISSING_KEYS_BATCH.to_string().as_str())
-            .finish();
+            tokio::time::sleep(sleep_duration).await;
+            sleep_duration = (sleep_duration * 2).min(polling.max_delay);
 
-        let response = self
-            .client
-            .http
-            .get(api_url)
-            .header(header::AUTHORIZATION, self.get_auth_header().await?)
-            .send()
-            .await?;
+            node = self.nodes().get_node(node.id).await?;
+        }
 
-        MissingKeysResponse::from_response(response).await
+        Ok(node)
     }
+}
 
-    async fn set_file_keys(
-        &self,
-        keys_batch_req: UserFileKeySetBatchRequest,
-    ) -> Result<(), DracoonClientError> {
-        let url_part = format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{FILES_BASE}/{FILES_KEYS}");
+/// waits before polling the status of a finalized S3 upload again (exponential backoff)
+/// returns `DracoonClientError::S3UploadTimedOut` if the upload is not completed in time
+pub(crate) async fn wait_for_upload_status(
+    polling: &UploadPolling,
+    started_at: Instant,
+    sleep_duration: &mut Duration,
+) -> Result<(), DracoonClientError> {
+    if started_at.elapsed() + *sleep_duration > polling.timeout {
+        error!("S3 upload not completed within {:?}", polling.timeout);
+        return Err(DracoonClientError::S3UploadTimedOut);
+    }
 
-        let api_url = self.build_api_url(&url_part);
+    tokio::time::sleep(*sleep_duration).await;
+    *sleep_duration = (*sleep_duration * 2).min(polling.max_delay);
 
-        let response = self
-            .client
-            .http
-            .post(api_url)
-            .header(header::AUTHORIZATION, self.get_auth_header().await?)
-            .json(&keys_batch_req)
-            .send()
-            .await?;
+    Ok(())
+}
 
-        if response.status().is_server_error() || response.status().is_client_error() {
-            return Err(DracoonClientError::from_response(response)
-                .await
-                .expect("Could not parse error response"));
-        }
+/// checks if a presigned url expired (S3 responds with 403 Forbidden)
+fn is_expired_url(err: &DracoonClientError) -> bool {
+    matches!(err, DracoonClientError::S3Error(s3_err) if s3_err.status == StatusCode::FORBIDDEN)
+}
 
-        Ok(())
-    }
+/// ETag returned by S3 for an uploaded part
+pub(crate) struct S3PartETag {
+    pub value: String,
+    /// false if the object is encrypted with SSE-KMS or SSE-C (the ETag is no MD5 digest)
+    pub is_md5: bool,
+}
 
-    async fn upload_chunk(
-        &self,
-        upload_channel: &CreateFileUploadResponse,
-        chunk: bytes::Bytes,
-        url_part: u32,
-        callback: Option<CloneableUploadProgressCallback>,
-    ) -> Result<S3FileUploadPart, DracoonClientError> {
-        let chunk_len: u64 = chunk
-            .len()
-            .try_into()
-            .map_err(|_| DracoonClientError::IoError)?;
+/// checks if the response headers indicate SSE-KMS or SSE-C encryption
+fn is_sse_kms_or_sse_c(headers: &header::HeaderMap) -> bool {
+    let is_kms = headers
+        .get("x-amz-server-side-encryption")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("aws:kms"));
 
-        let stream = Self::create_stream(chunk, callback);
-        let url_req = GeneratePresignedUrlsRequest::new(chunk_len, url_part, url_part);
+    is_kms || headers.contains_key("x-amz-server-side-encryption-customer-algorithm")
+}
 
-        let url = <Dracoon<Connected> as UploadInternal<R>>::create_s3_upload_urls(
-            self,
-            upload_channel.upload_id.clone(),
-            url_req,
-        )
-        .await?;
+/// verifies an uploaded part against the returned ETag (MD5 of the part content)
+///
+/// ETags of multipart uploads and of SSE-KMS or SSE-C encrypted objects are no MD5 digests and are skipped
+pub(crate) fn verify_e_tag(
+    chunk: &[u8],
+    e_tag: &S3PartETag,
+    part_number: u32,
+) -> Result<(), DracoonClientError> {
+    let is_md5 = e_tag.is_md5;
+    let e_tag = e_tag.value.trim_matches('"');
+    if !is_md5 || e_tag.len() != 32 || !e_tag.chars().all(|c| c.is_ascii_hexdigit()) {
+        debug!("Skipping checksum verification of part {part_number} (ETag: {e_tag})");
+        return Ok(());
+    }
 
-        let url = url.urls.first().expect("Creating S3 url failed");
+    let digest =
+        openssl::hash::hash(openssl::hash::MessageDigest::md5(), chunk).map_err(|err| {
+            error!("Error calculating MD5 of part {}: {}", part_number, err);
+            DracoonClientError::Internal
+        })?;
+    let checksum = digest
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+
+    if !checksum.eq_ignore_ascii_case(e_tag) {
+        error!("Checksum mismatch of part {part_number} (expected: {checksum}, ETag: {e_tag})");
+        return Err(DracoonClientError::S3ChecksumMismatch(part_number));
+    }
 
-        let e_tag = <Dracoon<Connected> as StreamUploadInternal<Connected>>::upload_stream_to_s3(
-            self,
-            Box::pin(stream),
-            url,
-            chunk_len,
-        )
-        .await?;
+    Ok(())
+}
+
+/// reads a chunk (into a pooled buffer) until it is full or the reader is exhausted
+async fn read_chunk(
+    reader: &mut (impl AsyncRead + Unpin),
+    chunk_size: usize,
+    pool: &BufferPool,
+) -> Result<Vec<u8>, DracoonClientError> {
+    let mut buffer = pool.get(chunk_size);
+    buffer.resize(chunk_size, 0);
+    let mut filled = 0;
+
+    while filled < chunk_size {
+        let n = reader.read(&mut buffer[filled..]).await.map_err(|err| {
+            error!("Error reading file: {}", err);
+            DracoonClientError::IoError
+        })?;
 
-        Ok(S3FileUploadPart::new(url_part, e_tag))
+        if n == 0 {
+            break;
+        }
+        filled += n;
     }
+
+    buffer.truncate(filled);
+
+    Ok(buffer)
 }
 
-/// helper to calculate the number of S3 urls and the size of the last chunk
-pub fn calculate_s3_url_count(total_size: u64, chunk_size: u64) -> (u32, u64) {
-    // handle empty file first
-    if total_size == 0 {
-        return (1, 0);
+/// pool of chunk buffers reused for the parts of an upload
+/// (instead of allocating a new buffer per chunk)
+#[derive(Debug, Clone)]
+pub(crate) struct BufferPool {
+    buffers: Arc<Mutex<Vec<Vec<u8>>>>,
+    max_buffers: usize,
+}
+
+impl BufferPool {
+    pub fn new(max_buffers: usize) -> Self {
+        Self {
+            buffers: Arc::new(Mutex::new(Vec::new())),
+            max_buffers,
+        }
     }
 
-    let full_chunks = total_size / chunk_size;
-    let last_chunk_size = if total_size % chunk_size == 0 {
-        chunk_size // If it's an exact multiple, the last chunk is a full chunk
-    } else {
-        total_size % chunk_size
-    };
+    /// returns an empty buffer with a capacity of at least `capacity` bytes
+    pub fn get(&self, capacity: usize) -> Vec<u8> {
+        let buffer = self
+            .buffers
+            .lock()
+            .ok()
+            .and_then(|mut buffers| buffers.pop());
+
+        match buffer {
+            Some(mut buffer) => {
+                buffer.clear();
+                buffer.reserve(capacity);
+                buffer
+            }
+            None => Vec::with_capacity(capacity),
+        }
+    }
+
+    /// returns a buffer to the pool (dropped if the pool is full)
+    pub fn put(&self, buffer: Vec<u8>) {
+        if let Ok(mut buffers) = self.buffers.lock() {
+            if buffers.len() < self.max_buffers {
+                buffers.push(buffer);
+            }
+        }
+    }
+
+    /// returns the buffer of an uploaded chunk to the pool
+    /// (dropped if the chunk is still referenced e.g. by a running request)
+    pub fn recycle(&self, chunk: bytes::Bytes) {
+        if let Ok(buffer) = chunk.try_into_mut() {
+            self.put(buffer.into());
+        }
+    }
+}
+
+/// Validates the chunk size of an S3 upload against the S3 part size and part count limits.
+/// If no chunk size is passed, the smallest chunk size (in full MB, at least the default chunk size)
+/// within the part count limit is selected. The file size is None for uploads with unknown size.
+pub fn resolve_chunk_size(
+    chunk_size: Option<usize>,
+    file_size: Option<u64>,
+) -> Result<usize, DracoonClientError> {
+    let Some(chunk_size) = chunk_size else {
+        return Ok(file_size.map_or(DEFAULT_CHUNK_SIZE, auto_chunk_size));
+    };
+
+    // a single part may be smaller than the min. part size
+    let is_single_part = file_size.is_some_and(|size| size <= chunk_size as u64);
+
+    if chunk_size < S3_MIN_PART_SIZE && !is_single_part {
+        error!("Chunk size {} below min. part size", chunk_size);
+        return Err(DracoonClientError::InvalidChunkSize(format!(
+            "{chunk_size} bytes is below the min. part size of {S3_MIN_PART_SIZE} bytes"
+        )));
+    }
+
+    if chunk_size as u64 > S3_MAX_PART_SIZE {
+        error!("Chunk size {} exceeds max. part size", chunk_size);
+        return Err(DracoonClientError::InvalidChunkSize(format!(
+            "{chunk_size} bytes exceeds the max. part size of {S3_MAX_PART_SIZE} bytes"
+        )));
+    }
+
+    if let Some(file_size) = file_size {
+        let parts = file_size.div_ceil(chunk_size as u64);
+        if parts > S3_MAX_PARTS {
+            error!(
+                "Chunk size {} exceeds max. part count ({} parts)",
+                chunk_size, parts
+            );
+            return Err(DracoonClientError::InvalidChunkSize(format!(
+                "{chunk_size} bytes requires {parts} parts (max. {S3_MAX_PARTS})"
+            )));
+        }
+    }
+
+    Ok(chunk_size)
+}
+
+/// selects the smallest chunk size (in full MB) within the part count limit
+fn auto_chunk_size(file_size: u64) -> usize {
+    let min_chunk_size = file_size
+        .div_ceil(S3_MAX_PARTS)
+        .next_multiple_of(1024 * 1024)
+        .clamp(DEFAULT_CHUNK_SIZE as u64, S3_MAX_PART_SIZE);
+
+    // chunks are buffered in memory (limited by the address space on 32 bit targets)
+    usize::try_from(min_chunk_size).unwrap_or(usize::MAX)
+}
+
+/// helper to calculate the number of S3 urls and the size of the last chunk
+pub fn calculate_s3_url_count(total_size: u64, chunk_size: u64) -> (u32, u64) {
+    // handle empty file first
+    if total_size == 0 {
+        return (1, 0);
+    }
+
+    let full_chunks = total_size / chunk_size;
+    let last_chunk_size = if total_size % chunk_size == 0 {
+        chunk_size // If it's an exact multiple, the last chunk is a full chunk
+    } else {
+        total_size % chunk_size
+    };
 
     let url_count = if last_chunk_size == chunk_size {
         full_chunks
@@ -876,6 +1913,11 @@ pub fn calculate_s3_url_count(total_size: u64, chunk_size: u64) -> (u32, u64) {
     )
 }
 
+/// helper to calculate the offset of a chunk (part numbers start at 1)
+pub(crate) fn chunk_offset(chunk_part: u32, chunk_size: usize) -> u64 {
+    u64::from(chunk_part - 1) * chunk_size as u64
+}
+
 #[async_trait]
 impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connected>
     for Dracoon<Connected>
@@ -906,14 +1948,28 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
             err
         })?;
 
+        // recorded to remove the channel if the upload fails
+        if let Some(slot) = &upload_options.upload_channel {
+            slot.record(&upload_channel.upload_id);
+        }
+
         let (count_chunks, last_chunk_size) = calculate_s3_url_count(fm.size, chunk_size as u64);
         let mut chunk_part: u32 = 1;
 
         let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+        let rate_limit = upload_options.max_rate.map(RateLimiter::new);
+
+        let pool = BufferPool::new(1);
 
         if count_chunks > 1 {
             while chunk_part < count_chunks {
-                let mut buffer = vec![0; chunk_size];
+                // pause between chunks - no request is in flight while paused
+                if let Some(pause) = &upload_options.pause {
+                    pause.wait_while_paused().await;
+                }
+
+                let mut buffer = pool.get(chunk_size);
+                buffer.resize(chunk_size, 0);
                 let cb = cloneable_callback.clone();
                 let fm = fm.clone();
 
@@ -923,13 +1979,11 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
                         buffer.truncate(n);
                         let chunk = bytes::Bytes::from(buffer);
 
-                        let stream = Self::create_stream(chunk, cb);
+                        let stream = Self::create_stream(chunk.clone(), chunk_part, fm.size, cb);
 
                         let url = upload_channel.upload_url.clone();
 
-                        // truncation is safe because chunk_size is 32 MB
-                        #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
-                        let curr_pos: u64 = ((chunk_part - 1) * (chunk_size as u32)) as u64;
+                        let curr_pos = chunk_offset(chunk_part, chunk_size);
 
                         self.upload_stream_to_nfs(
                             Box::pin(stream),
@@ -937,10 +1991,12 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
                             upload_options.file_meta.size,
                             n,
                             Some(curr_pos),
+                            rate_limit.as_ref(),
                         )
                         .await?;
 
                         chunk_part += 1;
+                        pool.recycle(chunk);
                     }
                     Err(err) => {
                         error!("Error reading file: {}", err);
@@ -951,6 +2007,10 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
         }
 
         // upload last chunk
+        if let Some(pause) = &upload_options.pause {
+            pause.wait_while_paused().await;
+        }
+
         let mut buffer = vec![
             0;
             last_chunk_size
@@ -959,14 +2019,16 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
         ];
         let cb = cloneable_callback.clone();
         match reader.read_exact(&mut buffer).await {
+            // empty files are finalized without uploading any content
+            Ok(0) => debug!("Empty file - skipping content upload"),
             Ok(n) => {
                 buffer.truncate(n);
                 let chunk = bytes::Bytes::from(buffer);
-                let stream = Self::create_stream(chunk, cb);
+                let stream = Self::create_stream(chunk, chunk_part, fm.size, cb);
 
                 let url = upload_channel.upload_url.clone();
 
-                let curr_pos: u64 = (chunk_part - 1) as u64 * (DEFAULT_CHUNK_SIZE as u64);
+                let curr_pos = chunk_offset(chunk_part, chunk_size);
 
                 self.upload_stream_to_nfs(
                     Box::pin(stream),
@@ -974,6 +2036,7 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
                     upload_options.file_meta.size,
                     n,
                     Some(curr_pos),
+                    rate_limit.as_ref(),
                 )
                 .await?;
             }
@@ -989,6 +2052,8 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
             .with_keep_share_links(upload_options.keep_share_links.unwrap_or(false))
             .build();
 
+        send_upload_event(upload_options.events.as_ref(), UploadEvent::Finalizing);
+
         let node = <Dracoon<Connected> as UploadInternalNfs<R, Connected>>::finalize_nfs_upload::<
             '_,
             '_,
@@ -1014,44 +2079,8 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
 
         let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
 
-        let mut crypto_buff = vec![
-            0u8;
-            upload_options
-                .file_meta
-                .size
-                .try_into()
-                .map_err(|_| DracoonClientError::IoError)?
-        ];
-        let mut read_buff = vec![
-            0u8;
-            upload_options
-                .file_meta
-                .size
-                .try_into()
-                .map_err(|_| DracoonClientError::IoError)?
-        ];
-        let mut crypter = DracoonCrypto::encrypter(&mut crypto_buff)?;
-
-        while let Ok(chunk) = reader.read(&mut read_buff).await {
-            if chunk == 0 {
-                break;
-            }
-            crypter.update(&read_buff[..chunk])?;
-        }
-        crypter.finalize()?;
-        // drop the read buffer after completing the encryption
-        drop(read_buff);
-
-        //TODO: rewrite without buffer clone
-        let enc_bytes = crypter.get_message().clone();
-
-        assert_eq!(enc_bytes.len() as u64, upload_options.file_meta.size);
-
-        let mut crypto_reader = BufReader::new(enc_bytes.as_slice());
-        let plain_file_key = crypter.get_plain_file_key();
-        let file_key = DracoonCrypto::encrypt_file_key(plain_file_key.clone(), keypair)?;
-        // drop the crypto buffer (enc bytes are still in the reader)
-        drop(crypto_buff);
+        // encrypt chunk by chunk while uploading (encrypted size equals plain size)
+        let encrypter = BlockingEncrypter::new(upload_options.file_key.as_ref())?;
 
         let fm = upload_options.file_meta.clone();
 
@@ -1069,29 +2098,42 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
             err
         })?;
 
+        // recorded to remove the channel if the upload fails
+        if let Some(slot) = &upload_options.upload_channel {
+            slot.record(&upload_channel.upload_id);
+        }
+
         let (count_chunks, last_chunk_size) = calculate_s3_url_count(fm.size, chunk_size as u64);
         let mut chunk_part: u32 = 1;
 
         let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+        let rate_limit = upload_options.max_rate.map(RateLimiter::new);
+
+        let pool = BufferPool::new(1);
 
         if count_chunks > 1 {
             while chunk_part < count_chunks {
-                let mut buffer = vec![0; chunk_size];
+                // pause between chunks - no request is in flight while paused
+                if let Some(pause) = &upload_options.pause {
+                    pause.wait_while_paused().await;
+                }
+
+                let mut buffer = pool.get(chunk_size);
+                buffer.resize(chunk_size, 0);
                 let cb = cloneable_callback.clone();
                 let fm = fm.clone();
 
-                match crypto_reader.read_exact(&mut buffer).await {
+                match reader.read_exact(&mut buffer).await {
                     Ok(0) => break,
                     Ok(n) => {
-                        let chunk_len = n;
-                        buffer.truncate(chunk_len);
-                        let chunk = bytes::Bytes::from(buffer);
+                        buffer.truncate(n);
+                        let chunk = bytes::Bytes::from(encrypter.encrypt(buffer, false).await?);
 
-                        let stream = Self::create_stream(chunk, cb);
+                        let stream = Self::create_stream(chunk.clone(), chunk_part, fm.size, cb);
 
                         let url = upload_channel.upload_url.clone();
 
-                        let curr_pos: u64 = (chunk_part - 1) as u64 * (chunk_size as u64);
+                        let curr_pos = chunk_offset(chunk_part, chunk_size);
 
                         self.upload_stream_to_nfs(
                             Box::pin(stream),
@@ -1099,21 +2141,30 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
                             upload_options.file_meta.size,
                             n,
                             Some(curr_pos),
+                            rate_limit.as_ref(),
                         )
                         .await
                         .map_err(|err| {
-                            error!("Error uploading stream to S3: {}", err);
+                            error!("Error uploading stream to NFS: {}", err);
                             err
                         })?;
 
                         chunk_part += 1;
+                        pool.recycle(chunk);
+                    }
+                    Err(err) => {
+                        error!("Error reading file: {}", err);
+                        return Err(DracoonClientError::IoError);
                     }
-                    Err(err) => return Err(DracoonClientError::IoError),
                 }
             }
         }
 
         // upload last chunk
+        if let Some(pause) = &upload_options.pause {
+            pause.wait_while_paused().await;
+        }
+
         let mut buffer = vec![
             0;
             last_chunk_size
@@ -1121,30 +2172,36 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
                 .map_err(|_| DracoonClientError::IoError)?
         ];
         let cb = cloneable_callback.clone();
-        match crypto_reader.read_exact(&mut buffer).await {
+        match reader.read_exact(&mut buffer).await {
             Ok(n) => {
                 buffer.truncate(n);
-                let chunk = bytes::Bytes::from(buffer);
-                let stream = Self::create_stream(chunk, cb);
-
-                let url = upload_channel.upload_url.clone();
-
-                // truncation is safe because chunk_size is 32 MB
-                #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
-                let curr_pos: u64 = ((chunk_part - 1) * (DEFAULT_CHUNK_SIZE as u32)) as u64;
-
-                self.upload_stream_to_nfs(
-                    Box::pin(stream),
-                    &url,
-                    upload_options.file_meta.size,
-                    n,
-                    Some(curr_pos),
-                )
-                .await
-                .map_err(|err| {
-                    error!("Error uploading stream to NFS: {}", err);
-                    err
-                })?;
+                let encrypted = encrypter.encrypt(buffer, true).await?;
+
+                // empty files are finalized without uploading any content (file key is required)
+                if n == 0 {
+                    debug!("Empty file - skipping content upload");
+                } else {
+                    let chunk = bytes::Bytes::from(encrypted);
+                    let stream = Self::create_stream(chunk, chunk_part, fm.size, cb);
+
+                    let url = upload_channel.upload_url.clone();
+
+                    let curr_pos = chunk_offset(chunk_part, chunk_size);
+
+                    self.upload_stream_to_nfs(
+                        Box::pin(stream),
+                        &url,
+                        upload_options.file_meta.size,
+                        n,
+                        Some(curr_pos),
+                        rate_limit.as_ref(),
+                    )
+                    .await
+                    .map_err(|err| {
+                        error!("Error uploading stream to NFS: {}", err);
+                        err
+                    })?;
+                }
             }
 
             Err(err) => {
@@ -1153,6 +2210,10 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
             }
         }
 
+        // file key is available after encrypting the last chunk
+        let plain_file_key = encrypter.plain_file_key()?;
+        let file_key = DracoonCrypto::encrypt_file_key(plain_file_key.clone(), keypair)?;
+
         // finalize upload
         let complete_upload_req = CompleteUploadRequest::builder()
             .with_resolution_strategy(upload_options.resolution_strategy.unwrap_or_default())
@@ -1160,6 +2221,8 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
             .with_file_key(file_key)
             .build();
 
+        send_upload_event(upload_options.events.as_ref(), UploadEvent::Finalizing);
+
         let node = <Dracoon<Connected> as UploadInternalNfs<R, Connected>>::finalize_nfs_upload::<
             '_,
             '_,
@@ -1170,18 +2233,22 @@ impl<R: AsyncRead + Sync + Send + Unpin + 'static> UploadInternalNfs<R, Connecte
             err
         })?;
 
-        // fetch missing keys (limit 50)
-        let missing_keys =
-            <Dracoon<Connected> as UploadInternal<R>>::get_missing_file_keys(self, node.id)
-                .await
-                .map_err(|err| {
-                    error!("Error getting missing file keys: {}", err);
-                    err
-                })?;
+        // given users or users missing the file key (limit 50)
+        let recipients = match upload_options.public_keys.clone() {
+            Some(public_keys) => public_keys,
+            None => {
+                <Dracoon<Connected> as UploadInternal<R>>::get_missing_file_keys(self, node.id)
+                    .await
+                    .map_err(|err| {
+                        error!("Error getting missing file keys: {}", err);
+                        err
+                    })?
+                    .users
+            }
+        };
 
         // encrypt plain file key for each user
-        let key_reqs = missing_keys
-            .users
+        let key_reqs = recipients
             .into_iter()
             .flat_map::<Result<UserFileKeySetRequest, DracoonClientError>, _>(|user| {
                 let user_id = user.id;
@@ -1234,9 +2301,11 @@ mod tests {
     use std::sync::atomic::AtomicU64;
     use std::sync::Arc;
 
-    use dco3_crypto::FileKeyVersion;
+    use dco3_crypto::{Encrypt, FileKeyVersion};
+    use tokio::io::AsyncWriteExt;
 
-    use crate::nodes::FileMeta;
+    use crate::models::ObjectExpiration;
+    use crate::nodes::{FileMeta, TransferProgress, UserUserPublicKey};
     use crate::tests::dracoon::get_connected_client;
     use crate::tests::nodes::tests::assert_node;
 
@@ -1246,295 +2315,1597 @@ mod tests {
     async fn test_create_upload_channel() {
         let (client, mut mock_server) = get_connected_client().await;
 
-        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(200)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let channel_req = CreateFileUploadRequest::builder(123, "test".into()).build();
+
+        let upload_channel =
+            <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::create_upload_channel(
+                &client,
+                channel_req,
+            )
+            .await
+            .unwrap();
+
+        upload_channel_mock.assert();
+
+        assert_eq!(upload_channel.upload_id, "string");
+        assert_eq!(upload_channel.upload_url, "string");
+        assert_eq!(upload_channel.token, "string");
+    }
+
+    #[tokio::test]
+    async fn test_create_s3_upload_urls() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let s3_urls_res = include_str!("../tests/responses/upload/s3_urls_ok.json");
+
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/123/s3_urls")
+            .with_status(200)
+            .with_body(s3_urls_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let s3_urls_req = GeneratePresignedUrlsRequest::new(123456, 1, 1);
+
+        let s3_urls =
+            <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::create_s3_upload_urls(
+                &client,
+                "123".into(),
+                s3_urls_req,
+            )
+            .await
+            .unwrap();
+
+        s3_urls_mock.assert();
+
+        assert_eq!(s3_urls.urls.len(), 1);
+        assert_eq!(
+            s3_urls.urls.first().unwrap().url,
+            "https://test.dracoon.com/not/real/upload_url"
+        );
+        assert_eq!(s3_urls.urls.first().unwrap().part_number, 1);
+    }
+
+    #[tokio::test]
+    async fn test_finalize_upload() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/123/s3")
+            .with_status(202)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let req =
+            CompleteS3FileUploadRequest::builder(vec![S3FileUploadPart::new(1, "123".into())])
+                .build();
+
+        <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::finalize_upload(
+            &client,
+            "123".into(),
+            req,
+        )
+        .await
+        .unwrap();
+
+        finalize_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_get_upload_status() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/123")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_status =
+            <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::get_upload_status(
+                &client,
+                "123".into(),
+            )
+            .await
+            .unwrap();
+
+        status_mock.assert();
+
+        assert_eq!(upload_status.status, S3UploadStatus::Done);
+
+        assert_node(&upload_status.node.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_get_upload_status_pending() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let status_res = include_str!("../tests/responses/upload/upload_status_pending_ok.json");
+
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/123")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_status =
+            <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::get_upload_status(
+                &client,
+                "123".into(),
+            )
+            .await
+            .unwrap();
+
+        status_mock.assert();
+
+        assert_eq!(upload_status.status, S3UploadStatus::Finishing);
+    }
+
+    #[tokio::test]
+    async fn test_get_missing_file_keys() {
+        let (client, mut mock_server) = get_connected_client().await;
+        let missing_keys_res = include_str!("../tests/responses/nodes/missing_file_keys_ok.json");
+
+        let missing_keys_mock = mock_server
+            .mock("GET", "/api/v4/nodes/missingFileKeys?file_id=123&limit=50")
+            .with_status(200)
+            .with_body(missing_keys_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let missing_keys =
+            <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::get_missing_file_keys(
+                &client, 123,
+            )
+            .await
+            .unwrap();
+
+        missing_keys_mock.assert();
+
+        assert_eq!(missing_keys.items.len(), 1);
+        assert_eq!(missing_keys.users.len(), 1);
+        assert_eq!(missing_keys.files.len(), 1);
+        assert_eq!(missing_keys.items.first().unwrap().file_id, 3);
+        assert_eq!(missing_keys.items.first().unwrap().user_id, 2);
+        assert_eq!(missing_keys.users.first().unwrap().id, 2);
+        assert_eq!(missing_keys.files.first().unwrap().id, 3);
+        assert_eq!(
+            missing_keys.files.first().unwrap().file_key_container.key,
+            "string"
+        );
+        assert_eq!(
+            missing_keys.files.first().unwrap().file_key_container.iv,
+            "string"
+        );
+        assert_eq!(
+            missing_keys
+                .files
+                .first()
+                .unwrap()
+                .file_key_container
+                .version,
+            FileKeyVersion::RSA4096_AES256GCM
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_file_keys() {}
+
+    #[tokio::test]
+    async fn test_upload_stream_to_s3() {
+        let mock_bytes: [u8; 16] = [
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let chunk = bytes::Bytes::from(mock_bytes.to_vec());
+
+        let stream = async_stream::stream! {
+            yield Ok::<_, std::io::Error>(chunk);
+        };
+
+        let upload_mock = mock_server
+            .mock("PUT", "/some/upload/url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .create();
+
+        let upload_url = format!("{}some/upload/url", client.get_base_url());
+
+        let upload_url = PresignedUrl {
+            url: upload_url,
+            part_number: 1,
+        };
+
+        let file_meta = FileMeta::builder("test", 16).build();
+
+        let e_tag = <Dracoon<Connected> as StreamUploadInternal<Connected>>::upload_stream_to_s3(
+            &client,
+            Box::pin(stream),
+            &upload_url,
+            16,
+            None,
+        )
+        .await
+        .unwrap();
+
+        upload_mock.assert();
+        assert_eq!(e_tag.value, "string".to_string());
+        assert!(e_tag.is_md5);
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_s3_unencrypted() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let mock_bytes: Vec<u8> = vec![
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let reader = Cursor::new(mock_bytes);
+        let reader_clone = BufReader::new(reader);
+
+        let file_meta = FileMeta::builder("test", 16).build();
+
+        let upload_options = UploadOptions::builder(file_meta).build();
+
+        // mock upload channel
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock S3 urls
+        let s3_urls_response =
+            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
+        let s3_urls_response =
+            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(201)
+            .with_body(s3_urls_response.clone())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_res =
+            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+
+        // mock upload to S3
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .create();
+
+        // mock finalize upload
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .with_status(202)
+            .create();
+
+        // mock upload status
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let node =
+            <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_unencrypted(
+                &client,
+                &parent_node,
+                upload_options,
+                reader_clone,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+
+        assert_node(&node);
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_s3_unencrypted_parallel() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let mock_bytes: Vec<u8> = vec![
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let reader = BufReader::new(Cursor::new(mock_bytes));
+
+        let file_meta = FileMeta::builder("test", 16).build();
+
+        let upload_options = UploadOptions::builder(file_meta)
+            .with_parallelism(2)
+            .build();
+
+        // mock upload channel
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let base_url = client.get_base_url().to_string();
+        let urls = |parts: &[u32]| {
+            let urls = parts
+                .iter()
+                .map(|part| {
+                    serde_json::json!({ "url": format!("{base_url}upload_url"), "partNumber": part })
+                })
+                .collect::<Vec<_>>();
+            serde_json::json!({ "urls": urls }).to_string()
+        };
+
+        // mock S3 urls (one batch for full chunks, one request for the last chunk)
+        let s3_urls_batch_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "size": 4,
+                "firstPartNumber": 1,
+                "lastPartNumber": 3
+            })))
+            .with_status(201)
+            .with_body(urls(&[1, 2, 3]))
+            .with_header("content-type", "application/json")
+            .create();
+
+        let s3_urls_last_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "size": 4,
+                "firstPartNumber": 4,
+                "lastPartNumber": 4
+            })))
+            .with_status(201)
+            .with_body(urls(&[4]))
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock upload to S3
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .expect(4)
+            .create();
+
+        // mock finalize upload (parts must be sorted)
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "parts": [
+                    { "partNumber": 1, "partEtag": "string" },
+                    { "partNumber": 2, "partEtag": "string" },
+                    { "partNumber": 3, "partEtag": "string" },
+                    { "partNumber": 4, "partEtag": "string" }
+                ]
+            })))
+            .with_status(202)
+            .create();
+
+        // mock upload status
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let node =
+            <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_unencrypted(
+                &client,
+                &parent_node,
+                upload_options,
+                reader,
+                None,
+                Some(4),
+            )
+            .await
+            .unwrap();
+
+        upload_channel_mock.assert();
+        s3_urls_batch_mock.assert();
+        s3_urls_last_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+
+        assert_node(&node);
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_s3_unencrypted_no_content() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        // test 0KB file
+        let mock_bytes: Vec<u8> = vec![];
+
+        let reader = Cursor::new(mock_bytes);
+        let reader_clone = BufReader::new(reader);
+
+        let file_meta = FileMeta::builder("test", 0).build();
+
+        let upload_options = UploadOptions::builder(file_meta).build();
+
+        // mock upload channel
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock S3 urls
+        let s3_urls_response =
+            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
+        let s3_urls_response =
+            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(201)
+            .with_body(s3_urls_response.clone())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_res =
+            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+
+        // mock upload to S3
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .create();
+
+        // mock finalize upload
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .with_status(202)
+            .create();
+
+        // mock upload status
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let node =
+            <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_unencrypted(
+                &client,
+                &parent_node,
+                upload_options,
+                reader_clone,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+
+        assert_node(&node);
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_s3_encrypted() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let mock_bytes: Vec<u8> = vec![
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let reader = Cursor::new(mock_bytes);
+        let reader_clone = BufReader::new(reader);
+
+        let keypair =
+            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
+                .unwrap();
+        let enc_keypair =
+            DracoonCrypto::encrypt_private_key("TopSecret1234!", keypair.clone()).unwrap();
+        let enc_keypair_json = serde_json::to_string(&enc_keypair).unwrap();
+
+        let keypair_mock = mock_server
+            .mock("GET", "/api/v4/user/account/keypair")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(enc_keypair_json)
+            .create();
+
+        let _kp = client
+            .get_keypair(Some("TopSecret1234!".into()))
+            .await
+            .unwrap();
+
+        keypair_mock.assert();
+
+        let file_meta = FileMeta::builder("test", 16).build();
+
+        let upload_options = UploadOptions::builder(file_meta).build();
+
+        // mock upload channel
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock S3 urls
+        let s3_urls_response =
+            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
+        let s3_urls_response =
+            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(201)
+            .with_body(s3_urls_response.clone())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_res =
+            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+
+        // mock upload to S3
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .create();
+
+        // mock finalize upload
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .with_status(202)
+            .create();
+
+        // mock upload status
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock missing file keys
+        let missing_keys = include_str!("../tests/responses/nodes/missing_file_keys_empty_ok.json");
+        let keys_mock = mock_server
+            .mock("GET", "/api/v4/nodes/missingFileKeys?file_id=2&limit=50")
+            .with_status(200)
+            .with_body(missing_keys)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let node = <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_encrypted(
+            &client,
+            &parent_node,
+            upload_options,
+            reader_clone,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+        keys_mock.assert();
+
+        assert_node(&node);
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_s3_encrypted_no_content() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        // empty file
+        let mock_bytes: Vec<u8> = vec![];
+
+        let reader = Cursor::new(mock_bytes);
+        let reader_clone = BufReader::new(reader);
+
+        let keypair =
+            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
+                .unwrap();
+        let enc_keypair =
+            DracoonCrypto::encrypt_private_key("TopSecret1234!", keypair.clone()).unwrap();
+        let enc_keypair_json = serde_json::to_string(&enc_keypair).unwrap();
+
+        let keypair_mock = mock_server
+            .mock("GET", "/api/v4/user/account/keypair")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(enc_keypair_json)
+            .create();
+
+        let _kp = client
+            .get_keypair(Some("TopSecret1234!".into()))
+            .await
+            .unwrap();
+
+        keypair_mock.assert();
+
+        let file_meta = FileMeta::builder("test", 0).build();
+
+        let upload_options = UploadOptions::builder(file_meta).build();
+
+        // mock upload channel
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock S3 urls
+        let s3_urls_response =
+            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
+        let s3_urls_response =
+            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(201)
+            .with_body(s3_urls_response.clone())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_res =
+            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+
+        // mock upload to S3
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .create();
+
+        // mock finalize upload
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .with_status(202)
+            .create();
+
+        // mock upload status
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock missing file keys
+        let missing_keys = include_str!("../tests/responses/nodes/missing_file_keys_empty_ok.json");
+        let keys_mock = mock_server
+            .mock("GET", "/api/v4/nodes/missingFileKeys?file_id=2&limit=50")
+            .with_status(200)
+            .with_body(missing_keys)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let node = <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_encrypted(
+            &client,
+            &parent_node,
+            upload_options,
+            reader_clone,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+        keys_mock.assert();
+
+        assert_node(&node);
+    }
+
+    #[tokio::test]
+    async fn test_full_upload_unencrypted_s3() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let mock_bytes: Vec<u8> = vec![
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let reader = Cursor::new(mock_bytes);
+        let reader_clone = BufReader::new(reader);
+
+        let file_meta = FileMeta::builder("test", 16).build();
+
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
+            .with_status(200)
+            .with_body(sys_info_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_options = UploadOptions::builder(file_meta).build();
+
+        // mock upload channel
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock S3 urls
+        let s3_urls_response =
+            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
+        let s3_urls_response =
+            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(201)
+            .with_body(s3_urls_response.clone())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_res =
+            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+
+        // mock upload to S3
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .create();
+
+        // mock finalize upload
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .with_status(202)
+            .create();
+
+        // mock upload status
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let node = <Dracoon<Connected> as Upload<Cursor<Vec<u8>>>>::upload(
+            &client,
+            &parent_node,
+            upload_options,
+            reader_clone,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+
+        assert_node(&node);
+    }
+
+    #[tokio::test]
+    async fn test_full_upload_encrypted_s3() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node = serde_json::from_str(include_str!(
+            "../tests/responses/nodes/node_encrypted_ok.json"
+        ))
+        .unwrap();
+
+        let mock_bytes: Vec<u8> = vec![
+            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
+        ];
+
+        let reader = Cursor::new(mock_bytes);
+        let reader_clone = BufReader::new(reader);
+
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
+            .with_status(200)
+            .with_body(sys_info_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let keypair =
+            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
+                .unwrap();
+        let enc_keypair =
+            DracoonCrypto::encrypt_private_key("TopSecret1234!", keypair.clone()).unwrap();
+        let enc_keypair_json = serde_json::to_string(&enc_keypair).unwrap();
+
+        let keypair_mock = mock_server
+            .mock("GET", "/api/v4/user/account/keypair")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(enc_keypair_json)
+            .create();
+
+        let _kp = client
+            .get_keypair(Some("TopSecret1234!".into()))
+            .await
+            .unwrap();
+
+        keypair_mock.assert();
+
+        let file_meta = FileMeta::builder("test", 16).build();
+
+        let upload_options = UploadOptions::builder(file_meta).build();
+
+        // mock upload channel
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock S3 urls
+        let s3_urls_response =
+            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
+        let s3_urls_response =
+            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(201)
+            .with_body(s3_urls_response.clone())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_res =
+            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+
+        // mock upload to S3
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .create();
+
+        // mock finalize upload
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .with_status(202)
+            .create();
+
+        // mock upload status
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock missing file keys
+        let missing_keys = include_str!("../tests/responses/nodes/missing_file_keys_empty_ok.json");
+        let keys_mock = mock_server
+            .mock("GET", "/api/v4/nodes/missingFileKeys?file_id=2&limit=50")
+            .with_status(200)
+            .with_body(missing_keys)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let node = <Dracoon<Connected> as Upload<Cursor<Vec<u8>>>>::upload(
+            &client,
+            &parent_node,
+            upload_options,
+            reader_clone,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+        keys_mock.assert();
+
+        assert_node(&node);
+    }
+
+    #[test]
+    fn test_calculate_chunk_count_and_last_size() {
+        // exact multiple of chunk size
+        let (count, last_size) = calculate_s3_url_count(10 * 1024 * 1024, 5 * 1024 * 1024);
+        assert_eq!(count, 2);
+        assert_eq!(last_size, 5 * 1024 * 1024);
+
+        // exact value as chunk size
+        let (count, last_size) = calculate_s3_url_count(10 * 1024 * 1024, 10 * 1024 * 1024);
+        assert_eq!(count, 1);
+        assert_eq!(last_size, 10 * 1024 * 1024);
+
+        // not multiple of chunk size
+        let (count, last_size) = calculate_s3_url_count(10 * 1024 * 1024 + 13, 5 * 1024 * 1024);
+        assert_eq!(count, 3);
+        assert_eq!(last_size, 13);
+
+        // empty file
+        let (count, last_size) = calculate_s3_url_count(0, 5 * 1024 * 1024);
+        assert_eq!(count, 1);
+        assert_eq!(last_size, 0);
+
+        // file smaller than one chunk
+        let (count, last_size) = calculate_s3_url_count(13, 5 * 1024 * 1024);
+        assert_eq!(count, 1);
+        assert_eq!(last_size, 13);
+
+        // large file
+        let (count, last_size) = calculate_s3_url_count(1024 * 1024 * 1024, 5 * 1024 * 1024);
+        assert_eq!(count, 205);
+        assert_eq!(last_size, 4 * 1024 * 1024);
+    }
+
+    #[tokio::test]
+    async fn test_upload_callback_s3_unencrypted() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        // 10 MB file in mem
+        let mock_bytes: Vec<u8> = vec![12; 10 * 1024 * 1024];
+
+        let reader = Cursor::new(mock_bytes);
+        let reader_clone = BufReader::new(reader);
+
+        let file_meta = FileMeta::builder("test", 10 * 1024 * 1024).build();
+
+        let upload_options = UploadOptions::builder(file_meta).build();
+
+        // mock upload channel
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock S3 urls
+        let s3_urls_response =
+            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
+        let s3_urls_response =
+            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(201)
+            .with_body(s3_urls_response.clone())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_res =
+            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+
+        // mock upload to S3
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .create();
+
+        // mock finalize upload
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .with_status(202)
+            .create();
+
+        // mock upload status
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let arc_processed = Arc::new(AtomicU64::new(0));
+        let cloned_processed = arc_processed.clone();
+
+        let callback = move |progress: TransferProgress| {
+            cloned_processed.fetch_add(
+                progress.bytes_transferred,
+                std::sync::atomic::Ordering::Release,
+            );
+        };
+
+        let callback_boxed = Box::new(callback);
+
+        let node =
+            <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_unencrypted(
+                &client,
+                &parent_node,
+                upload_options,
+                reader_clone,
+                Some(callback_boxed),
+                None,
+            )
+            .await
+            .unwrap();
+
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+
+        assert_eq!(
+            arc_processed.load(std::sync::atomic::Ordering::Relaxed),
+            10 * 1024 * 1024
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_callback_s3_unencrypted_chunked() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        // 10 MB file in mem
+        let mock_bytes: Vec<u8> = vec![12; 10 * 1024 * 1024];
+
+        let reader = Cursor::new(mock_bytes);
+        let reader_clone = BufReader::new(reader);
+
+        let file_meta = FileMeta::builder("test", 10 * 1024 * 1024).build();
+
+        let upload_options = UploadOptions::builder(file_meta).build();
+
+        // mock upload channel
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock S3 urls
+        let s3_urls_response =
+            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
+        let s3_urls_response =
+            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(201)
+            .with_body(s3_urls_response.clone())
+            .with_header("content-type", "application/json")
+            .expect(2)
+            .create();
+
+        let upload_res =
+            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+
+        // mock upload to S3
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .expect(2)
+            .create();
+
+        // mock finalize upload
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .with_status(202)
+            .create();
+
+        // mock upload status
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let arc_processed = Arc::new(AtomicU64::new(0));
+        let cloned_processed = arc_processed.clone();
+
+        let callback = move |progress: TransferProgress| {
+            cloned_processed.fetch_add(
+                progress.bytes_transferred,
+                std::sync::atomic::Ordering::Release,
+            );
+        };
+
+        let callback_boxed = Box::new(callback);
+
+        let node =
+            <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_unencrypted(
+                &client,
+                &parent_node,
+                upload_options,
+                reader_clone,
+                Some(callback_boxed),
+                Some(5 * 1024 * 1024),
+            )
+            .await
+            .unwrap();
+
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+
+        assert_eq!(
+            arc_processed.load(std::sync::atomic::Ordering::Relaxed),
+            10 * 1024 * 1024
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_upload_skips_uploaded_parts() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let mock_bytes: Vec<u8> = vec![0, 12, 33, 44, 55, 66, 77, 88];
+        let reader = BufReader::new(Cursor::new(mock_bytes));
+
+        let file_meta = FileMeta::builder("test", 8).build();
+        let upload_options = UploadOptions::builder(file_meta).build();
+
+        let mut state = UploadState::new("string".into(), &upload_options, 4);
+        state
+            .parts_mut()
+            .push(S3FileUploadPart::new(1, "string".into()));
+
+        // only the missing (last) part is requested
+        let s3_urls_response = serde_json::json!({
+            "urls": [{ "url": format!("{}upload_url", client.get_base_url()), "partNumber": 2 }]
+        });
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "size": 4,
+                "firstPartNumber": 2,
+                "lastPartNumber": 2
+            })))
+            .with_status(201)
+            .with_body(s3_urls_response.to_string())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .expect(1)
+            .create();
 
-        let upload_channel_mock = mock_server
-            .mock("POST", "/api/v4/nodes/files/uploads")
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
+            .with_status(202)
+            .create();
+
+        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/uploads/string")
             .with_status(200)
-            .with_body(channel_res)
+            .with_body(status_res)
             .with_header("content-type", "application/json")
             .create();
 
-        let channel_req = CreateFileUploadRequest::builder(123, "test".into()).build();
-
-        let upload_channel =
-            <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::create_upload_channel(
-                &client,
-                channel_req,
-            )
+        let node = client
+            .resume_upload(&mut state, reader, None)
             .await
             .unwrap();
 
-        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
 
-        assert_eq!(upload_channel.upload_id, "string");
-        assert_eq!(upload_channel.upload_url, "string");
-        assert_eq!(upload_channel.token, "string");
+        assert_node(&node);
+        assert_eq!(state.parts().len(), 2);
     }
 
-    #[tokio::test]
-    async fn test_create_s3_upload_urls() {
-        let (client, mut mock_server) = get_connected_client().await;
+    #[test]
+    fn test_upload_state_roundtrip() {
+        let file_meta = FileMeta::builder("test", 8).build();
+        let upload_options = UploadOptions::builder(file_meta).build();
 
-        let s3_urls_res = include_str!("../tests/responses/upload/s3_urls_ok.json");
+        let mut state = UploadState::new("upload_id".into(), &upload_options, 4);
+        state
+            .parts_mut()
+            .push(S3FileUploadPart::new(1, "etag".into()));
 
-        let s3_urls_mock = mock_server
-            .mock("POST", "/api/v4/nodes/files/uploads/123/s3_urls")
-            .with_status(200)
-            .with_body(s3_urls_res)
-            .with_header("content-type", "application/json")
-            .create();
+        let json = serde_json::to_string(&state).unwrap();
+        let state: UploadState = serde_json::from_str(&json).unwrap();
 
-        let s3_urls_req = GeneratePresignedUrlsRequest::new(123456, 1, 1);
+        assert_eq!(state.upload_id(), "upload_id");
+        assert_eq!(state.size(), 8);
+        assert_eq!(state.chunk_size(), 4);
+        assert_eq!(state.parts(), &[S3FileUploadPart::new(1, "etag".into())]);
+    }
 
-        let s3_urls =
-            <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::create_s3_upload_urls(
-                &client,
-                "123".into(),
-                s3_urls_req,
-            )
-            .await
-            .unwrap();
+    #[tokio::test]
+    async fn test_read_chunk_reuses_pooled_buffers() {
+        let pool = BufferPool::new(1);
+        let mut reader = Cursor::new(vec![1u8; 6]);
+
+        let first = read_chunk(&mut reader, 4, &pool).await.unwrap();
+        assert_eq!(first, vec![1u8; 4]);
+        let ptr = first.as_ptr();
+
+        let chunk = bytes::Bytes::from(first);
+        let in_flight = chunk.clone();
+        pool.recycle(chunk);
+        // still referenced - not returned to the pool
+        assert!(pool.buffers.lock().unwrap().is_empty());
+
+        pool.recycle(in_flight);
+        let second = read_chunk(&mut reader, 4, &pool).await.unwrap();
+        assert_eq!(second, vec![1u8; 2]);
+        assert_eq!(second.as_ptr(), ptr);
+    }
 
-        s3_urls_mock.assert();
+    #[test]
+    fn test_verify_e_tag() {
+        let chunk = b"hello";
+        let e_tag = |value: &str| S3PartETag {
+            value: value.to_string(),
+            is_md5: true,
+        };
 
-        assert_eq!(s3_urls.urls.len(), 1);
-        assert_eq!(
-            s3_urls.urls.first().unwrap().url,
-            "https://test.dracoon.com/not/real/upload_url"
-        );
-        assert_eq!(s3_urls.urls.first().unwrap().part_number, 1);
+        assert!(verify_e_tag(chunk, &e_tag("\"5d41402abc4b2a76b9719d911017c592\""), 1).is_ok());
+        assert!(verify_e_tag(chunk, &e_tag("5D41402ABC4B2A76B9719D911017C592"), 1).is_ok());
+        // multipart ETags are not verified
+        assert!(verify_e_tag(chunk, &e_tag("\"5d41402abc4b2a76b9719d911017c592-2\""), 1).is_ok());
+
+        let err =
+            verify_e_tag(chunk, &e_tag("\"00000000000000000000000000000000\""), 3).unwrap_err();
+        assert!(err.is_s3_checksum_mismatch());
+        assert_eq!(err, DracoonClientError::S3ChecksumMismatch(3));
     }
 
     #[tokio::test]
-    async fn test_finalize_upload() {
+    async fn test_upload_stream_to_s3_sse_kms_skips_checksum() {
         let (client, mut mock_server) = get_connected_client().await;
 
-        let finalize_mock = mock_server
-            .mock("PUT", "/api/v4/nodes/files/uploads/123/s3")
-            .with_status(202)
-            .with_header("content-type", "application/json")
+        let chunk = bytes::Bytes::from_static(b"hello");
+        let stream = async_stream::stream! {
+            yield Ok::<_, std::io::Error>(chunk);
+        };
+
+        // SSE-KMS ETags are 32 hex characters but no MD5 of the content
+        let upload_mock = mock_server
+            .mock("PUT", "/some/upload/url")
+            .with_status(200)
+            .with_header("etag", "\"00000000000000000000000000000000\"")
+            .with_header("x-amz-server-side-encryption", "aws:kms")
             .create();
 
-        let req =
-            CompleteS3FileUploadRequest::builder(vec![S3FileUploadPart::new(1, "123".into())])
-                .build();
+        let upload_url = PresignedUrl {
+            url: format!("{}some/upload/url", client.get_base_url()),
+            part_number: 1,
+        };
 
-        <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::finalize_upload(
+        let e_tag = <Dracoon<Connected> as StreamUploadInternal<Connected>>::upload_stream_to_s3(
             &client,
-            "123".into(),
-            req,
+            Box::pin(stream),
+            &upload_url,
+            5,
+            None,
         )
         .await
         .unwrap();
 
-        finalize_mock.assert();
+        upload_mock.assert();
+
+        assert!(!e_tag.is_md5);
+        assert!(verify_e_tag(b"hello", &e_tag, 1).is_ok());
     }
 
     #[tokio::test]
-    async fn test_get_upload_status() {
+    async fn test_upload_failure_removes_upload_channel() {
         let (client, mut mock_server) = get_connected_client().await;
 
-        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
 
-        let status_mock = mock_server
-            .mock("GET", "/api/v4/nodes/files/uploads/123")
+        let reader = BufReader::new(Cursor::new(vec![0u8; 16]));
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 16).build()).build();
+
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
             .with_status(200)
-            .with_body(status_res)
+            .with_body(sys_info_res)
             .with_header("content-type", "application/json")
             .create();
 
-        let upload_status =
-            <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::get_upload_status(
-                &client,
-                "123".into(),
-            )
-            .await
-            .unwrap();
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
 
-        status_mock.assert();
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(400)
+            .with_body(r#"{"code":400,"message":"Bad Request","debugInfo":"Bad Request","errorCode":-80001}"#)
+            .with_header("content-type", "application/json")
+            .create();
 
-        assert_eq!(upload_status.status, S3UploadStatus::Done);
+        let delete_channel_mock = mock_server
+            .mock("DELETE", "/api/v4/nodes/files/uploads/string")
+            .with_status(204)
+            .create();
 
-        assert_node(&upload_status.node.unwrap());
+        let res = client
+            .upload(&parent_node, upload_options, reader, None, None)
+            .await;
+
+        system_info_mock.assert();
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        delete_channel_mock.assert();
+
+        assert!(res.unwrap_err().is_http_error());
     }
 
     #[tokio::test]
-    async fn test_get_upload_status_pending() {
+    async fn test_upload_to_s3_missing_presigned_url() {
         let (client, mut mock_server) = get_connected_client().await;
 
-        let status_res = include_str!("../tests/responses/upload/upload_status_pending_ok.json");
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
 
-        let status_mock = mock_server
-            .mock("GET", "/api/v4/nodes/files/uploads/123")
+        let reader = BufReader::new(Cursor::new(vec![0u8; 16]));
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 16).build()).build();
+
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
             .with_status(200)
-            .with_body(status_res)
+            .with_body(sys_info_res)
             .with_header("content-type", "application/json")
             .create();
 
-        let upload_status =
-            <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::get_upload_status(
-                &client,
-                "123".into(),
-            )
-            .await
-            .unwrap();
-
-        status_mock.assert();
-
-        assert_eq!(upload_status.status, S3UploadStatus::Finishing);
-    }
-
-    #[tokio::test]
-    async fn test_get_missing_file_keys() {
-        let (client, mut mock_server) = get_connected_client().await;
-        let missing_keys_res = include_str!("../tests/responses/nodes/missing_file_keys_ok.json");
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
 
-        let missing_keys_mock = mock_server
-            .mock("GET", "/api/v4/nodes/missingFileKeys?file_id=123&limit=50")
-            .with_status(200)
-            .with_body(missing_keys_res)
+        let s3_urls_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .with_status(201)
+            .with_body(r#"{"urls":[]}"#)
             .with_header("content-type", "application/json")
             .create();
 
-        let missing_keys =
-            <Dracoon<Connected> as UploadInternal<BufReader<&[u8]>>>::get_missing_file_keys(
-                &client, 123,
-            )
-            .await
-            .unwrap();
+        let delete_channel_mock = mock_server
+            .mock("DELETE", "/api/v4/nodes/files/uploads/string")
+            .with_status(204)
+            .create();
 
-        missing_keys_mock.assert();
+        let res = client
+            .upload(&parent_node, upload_options, reader, None, None)
+            .await;
 
-        assert_eq!(missing_keys.items.len(), 1);
-        assert_eq!(missing_keys.users.len(), 1);
-        assert_eq!(missing_keys.files.len(), 1);
-        assert_eq!(missing_keys.items.first().unwrap().file_id, 3);
-        assert_eq!(missing_keys.items.first().unwrap().user_id, 2);
-        assert_eq!(missing_keys.users.first().unwrap().id, 2);
-        assert_eq!(missing_keys.files.first().unwrap().id, 3);
-        assert_eq!(
-            missing_keys.files.first().unwrap().file_key_container.key,
-            "string"
-        );
-        assert_eq!(
-            missing_keys.files.first().unwrap().file_key_container.iv,
-            "string"
-        );
-        assert_eq!(
-            missing_keys
-                .files
-                .first()
-                .unwrap()
-                .file_key_container
-                .version,
-            FileKeyVersion::RSA4096_AES256GCM
-        );
-    }
+        system_info_mock.assert();
+        upload_channel_mock.assert();
+        s3_urls_mock.assert();
+        delete_channel_mock.assert();
 
-    #[tokio::test]
-    async fn test_set_file_keys() {}
+        assert_eq!(res.unwrap_err(), DracoonClientError::MissingS3Url(1));
+    }
 
     #[tokio::test]
-    async fn test_upload_stream_to_s3() {
-        let mock_bytes: [u8; 16] = [
-            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
-        ];
-
+    async fn test_upload_many_report() {
         let (client, mut mock_server) = get_connected_client().await;
 
-        let chunk = bytes::Bytes::from(mock_bytes.to_vec());
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
 
-        let stream = async_stream::stream! {
-            yield Ok::<_, std::io::Error>(chunk);
-        };
+        let source = std::env::temp_dir().join("dco3_upload_many_conflict.txt");
+        std::fs::write(&source, vec![0u8; 16]).unwrap();
+        let missing = std::env::temp_dir().join("dco3_upload_many_missing.txt");
 
-        let upload_mock = mock_server
-            .mock("PUT", "/some/upload/url")
-            .with_status(202)
-            .with_header("etag", "string")
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
+            .with_status(200)
+            .with_body(sys_info_res)
+            .with_header("content-type", "application/json")
             .create();
 
-        let upload_url = format!("{}some/upload/url", client.get_base_url());
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(409)
+            .with_body(r#"{"code":409,"message":"Conflict","debugInfo":"Node already exists","errorCode":-40001}"#)
+            .with_header("content-type", "application/json")
+            .create();
 
-        let upload_url = PresignedUrl {
-            url: upload_url,
-            part_number: 1,
-        };
+        let requests = vec![
+            UploadRequest::new(&source, parent_node.clone()),
+            UploadRequest::new(&missing, parent_node),
+        ];
 
-        let file_meta = FileMeta::builder("test", 16).build();
+        let report = client.upload_many(requests, 2, None).await;
 
-        let e_tag = <Dracoon<Connected> as StreamUploadInternal<Connected>>::upload_stream_to_s3(
-            &client,
-            Box::pin(stream),
-            &upload_url,
-            16,
-        )
-        .await
-        .unwrap();
+        system_info_mock.assert();
+        upload_channel_mock.assert();
 
-        upload_mock.assert();
-        assert_eq!(e_tag, "string".to_string());
+        assert!(!report.is_success());
+        assert!(report.uploaded.is_empty());
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].0, source);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, missing);
+
+        std::fs::remove_file(source).unwrap();
     }
 
     #[tokio::test]
-    async fn test_upload_to_s3_unencrypted() {
+    async fn test_upload_many_retry_resets_progress() {
         let (client, mut mock_server) = get_connected_client().await;
 
         let parent_node: Node =
             serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
 
-        let mock_bytes: Vec<u8> = vec![
-            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
-        ];
-
-        let reader = Cursor::new(mock_bytes);
-        let reader_clone = BufReader::new(reader);
-
-        let file_meta = FileMeta::builder("test", 16).build();
+        let source = std::env::temp_dir().join("dco3_upload_many_retry.txt");
+        std::fs::write(&source, vec![1u8; 16]).unwrap();
 
-        let upload_options = UploadOptions::builder(file_meta).build();
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
+            .with_status(200)
+            .with_body(sys_info_res)
+            .with_header("content-type", "application/json")
+            .create();
 
-        // mock upload channel
         let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
-
         let upload_channel_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads")
             .with_status(201)
             .with_body(channel_res)
             .with_header("content-type", "application/json")
+            .expect(2)
             .create();
 
-        // mock S3 urls
         let s3_urls_response =
             include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
         let s3_urls_response =
             s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
-
         let s3_urls_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
             .with_status(201)
-            .with_body(s3_urls_response.clone())
+            .with_body(s3_urls_response)
             .with_header("content-type", "application/json")
+            .expect(2)
             .create();
 
-        let upload_res =
-            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+        // first attempt fails (part upload and part retry)
+        let failed_upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(500)
+            .with_body("<Error><Code>InternalError</Code><Message>Internal error</Message></Error>")
+            .with_header("content-type", "application/xml")
+            .expect(2)
+            .create();
 
-        // mock upload to S3
         let upload_mock = mock_server
             .mock("PUT", "/upload_url")
             .with_status(202)
             .with_header("etag", "string")
+            .expect(1)
+            .create();
+
+        let delete_channel_mock = mock_server
+            .mock("DELETE", "/api/v4/nodes/files/uploads/string")
+            .with_status(204)
+            .expect(1)
             .create();
 
-        // mock finalize upload
         let finalize_mock = mock_server
             .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
             .with_status(202)
             .create();
 
-        // mock upload status
         let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
         let status_mock = mock_server
             .mock("GET", "/api/v4/nodes/files/uploads/string")
@@ -1543,155 +3914,154 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        let node =
-            <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_unencrypted(
-                &client,
-                &parent_node,
-                upload_options,
-                reader_clone,
-                None,
-                None,
+        let bytes_done = Arc::new(AtomicU64::new(0));
+        let cloned_bytes_done = bytes_done.clone();
+        let callback = move |progress: TransferProgress| {
+            cloned_bytes_done.store(progress.bytes_done, Ordering::Relaxed);
+        };
+
+        let report = client
+            .upload_many(
+                vec![UploadRequest::new(&source, parent_node)],
+                1,
+                Some(Box::new(callback)),
             )
-            .await
-            .unwrap();
+            .await;
 
+        system_info_mock.assert();
         upload_channel_mock.assert();
         s3_urls_mock.assert();
+        failed_upload_mock.assert();
         upload_mock.assert();
+        delete_channel_mock.assert();
         finalize_mock.assert();
         status_mock.assert();
 
-        assert_node(&node);
+        assert!(report.is_success());
+        assert_eq!(report.uploaded.len(), 1);
+
+        // progress of the failed attempt is not counted twice
+        assert_eq!(bytes_done.load(Ordering::Relaxed), 16);
+
+        std::fs::remove_file(source).unwrap();
     }
 
     #[tokio::test]
-    async fn test_upload_to_s3_unencrypted_no_content() {
+    async fn test_upload_many_readers_report() {
         let (client, mut mock_server) = get_connected_client().await;
 
         let parent_node: Node =
             serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
 
-        // test 0KB file
-        let mock_bytes: Vec<u8> = vec![];
-
-        let reader = Cursor::new(mock_bytes);
-        let reader_clone = BufReader::new(reader);
-
-        let file_meta = FileMeta::builder("test", 0).build();
-
-        let upload_options = UploadOptions::builder(file_meta).build();
-
-        // mock upload channel
-        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
+            .with_status(200)
+            .with_body(sys_info_res)
+            .with_header("content-type", "application/json")
+            .create();
 
         let upload_channel_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads")
-            .with_status(201)
-            .with_body(channel_res)
+            .with_status(409)
+            .with_body(r#"{"code":409,"message":"Conflict","debugInfo":"Node already exists","errorCode":-40001}"#)
             .with_header("content-type", "application/json")
+            .expect(2)
             .create();
 
-        // mock S3 urls
-        let s3_urls_response =
-            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
-        let s3_urls_response =
-            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+        let requests = ["first.txt", "second.txt"]
+            .into_iter()
+            .map(|name| {
+                ReaderUploadRequest::new(
+                    Cursor::new(vec![0u8; 16]),
+                    parent_node.clone(),
+                    UploadOptions::builder(FileMeta::builder(name, 16).build()).build(),
+                )
+            })
+            .collect::<Vec<_>>();
 
-        let s3_urls_mock = mock_server
-            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
-            .with_status(201)
-            .with_body(s3_urls_response.clone())
-            .with_header("content-type", "application/json")
-            .create();
+        let report = client.upload_many_readers(requests, 2, None).await;
 
-        let upload_res =
-            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+        system_info_mock.assert();
+        upload_channel_mock.assert();
 
-        // mock upload to S3
-        let upload_mock = mock_server
-            .mock("PUT", "/upload_url")
-            .with_status(202)
-            .with_header("etag", "string")
-            .create();
+        assert!(!report.is_success());
+        assert!(report.uploaded.is_empty());
+        assert!(report.failed.is_empty());
+        assert_eq!(
+            report
+                .conflicts
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>(),
+            vec![PathBuf::from("first.txt"), PathBuf::from("second.txt")]
+        );
+    }
 
-        // mock finalize upload
-        let finalize_mock = mock_server
-            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
-            .with_status(202)
-            .create();
+    #[tokio::test]
+    async fn test_upload_with_cancellation_cancelled() {
+        let (client, mut mock_server) = get_connected_client().await;
 
-        // mock upload status
-        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
-        let status_mock = mock_server
-            .mock("GET", "/api/v4/nodes/files/uploads/string")
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let reader = BufReader::new(Cursor::new(vec![0u8; 16]));
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 16).build()).build();
+
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
             .with_status(200)
-            .with_body(status_res)
+            .with_body(sys_info_res)
             .with_header("content-type", "application/json")
             .create();
 
-        let node =
-            <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_unencrypted(
-                &client,
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .expect(0)
+            .create();
+
+        let cancellation_token = CancellationToken::new();
+        cancellation_token.cancel();
+
+        let res = client
+            .upload_with_cancellation(
                 &parent_node,
                 upload_options,
-                reader_clone,
+                reader,
                 None,
                 None,
+                cancellation_token,
             )
-            .await
-            .unwrap();
+            .await;
 
+        system_info_mock.assert();
         upload_channel_mock.assert();
-        s3_urls_mock.assert();
-        upload_mock.assert();
-        finalize_mock.assert();
-        status_mock.assert();
 
-        assert_node(&node);
+        assert_eq!(res.unwrap_err(), DracoonClientError::Cancelled);
     }
 
     #[tokio::test]
-    async fn test_upload_to_s3_encrypted() {
+    async fn test_upload_with_unknown_size() {
         let (client, mut mock_server) = get_connected_client().await;
 
         let parent_node: Node =
             serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
 
-        let mock_bytes: Vec<u8> = vec![
-            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
-        ];
-
-        let reader = Cursor::new(mock_bytes);
-        let reader_clone = BufReader::new(reader);
+        let reader = BufReader::new(Cursor::new(vec![1u8; 2 * S3_MIN_PART_SIZE + 2]));
 
-        let keypair =
-            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
-                .unwrap();
-        let enc_keypair =
-            DracoonCrypto::encrypt_private_key("TopSecret1234!", keypair.clone()).unwrap();
-        let enc_keypair_json = serde_json::to_string(&enc_keypair).unwrap();
+        // size is ignored
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 0).build()).build();
 
-        let keypair_mock = mock_server
-            .mock("GET", "/api/v4/user/account/keypair")
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
             .with_status(200)
+            .with_body(sys_info_res)
             .with_header("content-type", "application/json")
-            .with_body(enc_keypair_json)
             .create();
 
-        let _kp = client
-            .get_keypair(Some("TopSecret1234!".into()))
-            .await
-            .unwrap();
-
-        keypair_mock.assert();
-
-        let file_meta = FileMeta::builder("test", 16).build();
-
-        let upload_options = UploadOptions::builder(file_meta).build();
-
-        // mock upload channel
         let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
-
         let upload_channel_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads")
             .with_status(201)
@@ -1699,36 +4069,51 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        // mock S3 urls
-        let s3_urls_response =
-            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
-        let s3_urls_response =
-            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+        let url = format!("{}upload_url", client.get_base_url());
 
-        let s3_urls_mock = mock_server
+        // full chunks are requested in a batch, the last chunk with its actual size
+        let s3_urls_batch_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "size": S3_MIN_PART_SIZE,
+                "firstPartNumber": 1,
+                "lastPartNumber": 20
+            })))
             .with_status(201)
-            .with_body(s3_urls_response.clone())
+            .with_body(
+                serde_json::json!({ "urls": [
+                    { "url": url, "partNumber": 1 },
+                    { "url": url, "partNumber": 2 }
+                ]})
+                .to_string(),
+            )
             .with_header("content-type", "application/json")
             .create();
 
-        let upload_res =
-            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+        let s3_urls_last_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "size": 2,
+                "firstPartNumber": 3,
+                "lastPartNumber": 3
+            })))
+            .with_status(201)
+            .with_body(serde_json::json!({ "urls": [{ "url": url, "partNumber": 3 }] }).to_string())
+            .with_header("content-type", "application/json")
+            .create();
 
-        // mock upload to S3
         let upload_mock = mock_server
             .mock("PUT", "/upload_url")
             .with_status(202)
             .with_header("etag", "string")
+            .expect(3)
             .create();
 
-        // mock finalize upload
         let finalize_mock = mock_server
             .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
             .with_status(202)
             .create();
 
-        // mock upload status
         let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
         let status_mock = mock_server
             .mock("GET", "/api/v4/nodes/files/uploads/string")
@@ -1737,77 +4122,87 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        // mock missing file keys
-        let missing_keys = include_str!("../tests/responses/nodes/missing_file_keys_empty_ok.json");
-        let keys_mock = mock_server
-            .mock("GET", "/api/v4/nodes/missingFileKeys?file_id=2&limit=50")
-            .with_status(200)
-            .with_body(missing_keys)
-            .with_header("content-type", "application/json")
-            .create();
-
-        let node = <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_encrypted(
-            &client,
-            &parent_node,
-            upload_options,
-            reader_clone,
-            None,
-            None,
-        )
-        .await
-        .unwrap();
+        let node = client
+            .upload_with_unknown_size(
+                &parent_node,
+                upload_options,
+                reader,
+                None,
+                Some(S3_MIN_PART_SIZE),
+            )
+            .await
+            .unwrap();
 
+        system_info_mock.assert();
         upload_channel_mock.assert();
-        s3_urls_mock.assert();
+        s3_urls_batch_mock.assert();
+        s3_urls_last_mock.assert();
         upload_mock.assert();
         finalize_mock.assert();
         status_mock.assert();
-        keys_mock.assert();
 
         assert_node(&node);
     }
 
+    #[test]
+    fn test_resolve_chunk_size() {
+        // default chunk size is used up to the part count limit
+        assert_eq!(
+            resolve_chunk_size(None, Some(0)).unwrap(),
+            DEFAULT_CHUNK_SIZE
+        );
+        assert_eq!(resolve_chunk_size(None, None).unwrap(), DEFAULT_CHUNK_SIZE);
+
+        // 1 TB requires at least 100 MB per part (in full MB)
+        let large_file = 1024 * 1024 * 1024 * 1024;
+        let chunk_size = resolve_chunk_size(None, Some(large_file)).unwrap();
+        assert_eq!(chunk_size, 1024 * 1024 * 105);
+        assert!(large_file.div_ceil(chunk_size as u64) <= S3_MAX_PARTS);
+
+        // small chunks are only valid for single part uploads
+        assert_eq!(resolve_chunk_size(Some(4), Some(4)).unwrap(), 4);
+        assert!(resolve_chunk_size(Some(4), Some(5))
+            .unwrap_err()
+            .is_invalid_chunk_size());
+        assert!(resolve_chunk_size(Some(4), None)
+            .unwrap_err()
+            .is_invalid_chunk_size());
+
+        let max_part_size = usize::try_from(S3_MAX_PART_SIZE).unwrap();
+        assert!(resolve_chunk_size(Some(max_part_size + 1), None)
+            .unwrap_err()
+            .is_invalid_chunk_size());
+        assert!(resolve_chunk_size(Some(S3_MIN_PART_SIZE), Some(large_file))
+            .unwrap_err()
+            .is_invalid_chunk_size());
+    }
+
+    #[test]
+    fn test_chunk_offset() {
+        assert_eq!(chunk_offset(1, DEFAULT_CHUNK_SIZE), 0);
+        assert_eq!(chunk_offset(2, DEFAULT_CHUNK_SIZE), 1024 * 1024 * 32);
+
+        // offsets past 4 GB (chunk 129 of 32 MB) must not overflow
+        let offset = chunk_offset(129, DEFAULT_CHUNK_SIZE);
+        assert_eq!(offset, 1024 * 1024 * 1024 * 4);
+        assert!(offset > u64::from(u32::MAX));
+
+        // chunk sizes above 4 GB are not truncated
+        let max_part_size = usize::try_from(S3_MAX_PART_SIZE).unwrap();
+        assert_eq!(chunk_offset(3, max_part_size), 2 * S3_MAX_PART_SIZE);
+    }
+
     #[tokio::test]
-    async fn test_upload_to_s3_encrypted_no_content() {
+    async fn test_upload_to_s3_retries_part_with_expired_url() {
         let (client, mut mock_server) = get_connected_client().await;
 
         let parent_node: Node =
             serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
 
-        // empty file
-        let mock_bytes: Vec<u8> = vec![];
-
-        let reader = Cursor::new(mock_bytes);
-        let reader_clone = BufReader::new(reader);
-
-        let keypair =
-            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
-                .unwrap();
-        let enc_keypair =
-            DracoonCrypto::encrypt_private_key("TopSecret1234!", keypair.clone()).unwrap();
-        let enc_keypair_json = serde_json::to_string(&enc_keypair).unwrap();
-
-        let keypair_mock = mock_server
-            .mock("GET", "/api/v4/user/account/keypair")
-            .with_status(200)
-            .with_header("content-type", "application/json")
-            .with_body(enc_keypair_json)
-            .create();
-
-        let _kp = client
-            .get_keypair(Some("TopSecret1234!".into()))
-            .await
-            .unwrap();
-
-        keypair_mock.assert();
-
-        let file_meta = FileMeta::builder("test", 0).build();
-
-        let upload_options = UploadOptions::builder(file_meta).build();
-
-        // mock upload channel
-        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+        let reader = BufReader::new(Cursor::new(vec![1u8; 16]));
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 16).build()).build();
 
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
         let upload_channel_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads")
             .with_status(201)
@@ -1815,36 +4210,41 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        // mock S3 urls
+        // initial url and a fresh url after the first one expired
         let s3_urls_response =
             include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
         let s3_urls_response =
             s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
-
         let s3_urls_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
             .with_status(201)
-            .with_body(s3_urls_response.clone())
+            .with_body(s3_urls_response)
             .with_header("content-type", "application/json")
+            .expect(2)
             .create();
 
-        let upload_res =
-            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+        let expired_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(403)
+            .with_body(
+                "<Error><Code>AccessDenied</Code><Message>Request has expired</Message></Error>",
+            )
+            .with_header("content-type", "application/xml")
+            .expect(1)
+            .create();
 
-        // mock upload to S3
         let upload_mock = mock_server
             .mock("PUT", "/upload_url")
             .with_status(202)
             .with_header("etag", "string")
+            .expect(1)
             .create();
 
-        // mock finalize upload
         let finalize_mock = mock_server
             .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
             .with_status(202)
             .create();
 
-        // mock upload status
         let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
         let status_mock = mock_server
             .mock("GET", "/api/v4/nodes/files/uploads/string")
@@ -1853,66 +4253,78 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        // mock missing file keys
-        let missing_keys = include_str!("../tests/responses/nodes/missing_file_keys_empty_ok.json");
-        let keys_mock = mock_server
-            .mock("GET", "/api/v4/nodes/missingFileKeys?file_id=2&limit=50")
-            .with_status(200)
-            .with_body(missing_keys)
-            .with_header("content-type", "application/json")
-            .create();
+        let arc_processed = Arc::new(AtomicU64::new(0));
+        let cloned_processed = arc_processed.clone();
+        let callback = move |progress: TransferProgress| {
+            cloned_processed.fetch_add(
+                progress.bytes_transferred,
+                std::sync::atomic::Ordering::Release,
+            );
+        };
 
-        let node = <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_encrypted(
-            &client,
-            &parent_node,
-            upload_options,
-            reader_clone,
-            None,
-            None,
-        )
-        .await
-        .unwrap();
+        let node =
+            <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_unencrypted(
+                &client,
+                &parent_node,
+                upload_options,
+                reader,
+                Some(Box::new(callback)),
+                None,
+            )
+            .await
+            .unwrap();
 
         upload_channel_mock.assert();
         s3_urls_mock.assert();
+        expired_mock.assert();
         upload_mock.assert();
         finalize_mock.assert();
         status_mock.assert();
-        keys_mock.assert();
 
         assert_node(&node);
+        // progress of the failed attempt is not reported twice
+        assert_eq!(arc_processed.load(std::sync::atomic::Ordering::Relaxed), 16);
     }
 
     #[tokio::test]
-    async fn test_full_upload_unencrypted_s3() {
-        let (client, mut mock_server) = get_connected_client().await;
+    async fn test_wait_for_upload_status_caps_delay() {
+        let polling = UploadPolling {
+            max_delay: Duration::from_millis(15),
+            timeout: Duration::from_secs(10),
+        };
+        let mut sleep_duration = Duration::from_millis(10);
 
-        let parent_node: Node =
-            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+        wait_for_upload_status(&polling, Instant::now(), &mut sleep_duration)
+            .await
+            .unwrap();
 
-        let mock_bytes: Vec<u8> = vec![
-            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
-        ];
+        assert_eq!(sleep_duration, Duration::from_millis(15));
+    }
 
-        let reader = Cursor::new(mock_bytes);
-        let reader_clone = BufReader::new(reader);
+    #[tokio::test]
+    async fn test_wait_for_upload_status_timed_out() {
+        let polling = UploadPolling {
+            max_delay: Duration::from_millis(100),
+            timeout: Duration::from_millis(50),
+        };
+        let mut sleep_duration = Duration::from_millis(100);
 
-        let file_meta = FileMeta::builder("test", 16).build();
+        let res = wait_for_upload_status(&polling, Instant::now(), &mut sleep_duration).await;
 
-        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        assert!(res.unwrap_err().is_s3_upload_timed_out());
+    }
 
-        let system_info_mock = mock_server
-            .mock("GET", "/api/v4/public/system/info")
-            .with_status(200)
-            .with_body(sys_info_res)
-            .with_header("content-type", "application/json")
-            .create();
+    #[tokio::test]
+    async fn test_upload_to_nfs_unencrypted_no_content() {
+        let (client, mut mock_server) = get_connected_client().await;
 
-        let upload_options = UploadOptions::builder(file_meta).build();
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
 
-        // mock upload channel
-        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+        let reader = BufReader::new(Cursor::new(Vec::<u8>::new()));
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 0).build()).build();
 
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
         let upload_channel_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads")
             .with_status(201)
@@ -1920,49 +4332,20 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        // mock S3 urls
-        let s3_urls_response =
-            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
-        let s3_urls_response =
-            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
-
-        let s3_urls_mock = mock_server
-            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
-            .with_status(201)
-            .with_body(s3_urls_response.clone())
-            .with_header("content-type", "application/json")
-            .create();
-
-        let upload_res =
-            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
-
-        // mock upload to S3
-        let upload_mock = mock_server
-            .mock("PUT", "/upload_url")
-            .with_status(202)
-            .with_header("etag", "string")
-            .create();
-
-        // mock finalize upload
+        // no content is uploaded - the upload is finalized directly
+        let node_res = include_str!("../tests/responses/nodes/node_ok.json");
         let finalize_mock = mock_server
-            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
-            .with_status(202)
-            .create();
-
-        // mock upload status
-        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
-        let status_mock = mock_server
-            .mock("GET", "/api/v4/nodes/files/uploads/string")
-            .with_status(200)
-            .with_body(status_res)
+            .mock("PUT", "/api/v4/uploads/string")
+            .with_status(201)
+            .with_body(node_res)
             .with_header("content-type", "application/json")
             .create();
 
-        let node = <Dracoon<Connected> as Upload<Cursor<Vec<u8>>>>::upload(
+        let node = <Dracoon<Connected> as UploadInternalNfs<Cursor<Vec<u8>>, Connected>>::upload_to_nfs_unencrypted(
             &client,
             &parent_node,
             upload_options,
-            reader_clone,
+            reader,
             None,
             None,
         )
@@ -1970,67 +4353,81 @@ mod tests {
         .unwrap();
 
         upload_channel_mock.assert();
-        s3_urls_mock.assert();
-        upload_mock.assert();
         finalize_mock.assert();
-        status_mock.assert();
 
         assert_node(&node);
     }
 
     #[tokio::test]
-    async fn test_full_upload_encrypted_s3() {
+    async fn test_wait_for_virus_scan() {
         let (client, mut mock_server) = get_connected_client().await;
 
-        let parent_node: Node = serde_json::from_str(include_str!(
-            "../tests/responses/nodes/node_encrypted_ok.json"
-        ))
-        .unwrap();
-
-        let mock_bytes: Vec<u8> = vec![
-            0, 12, 33, 44, 55, 66, 77, 88, 99, 111, 222, 255, 0, 12, 33, 44,
-        ];
-
-        let reader = Cursor::new(mock_bytes);
-        let reader_clone = BufReader::new(reader);
+        let with_verdict = |verdict: &str| {
+            let mut node: serde_json::Value =
+                serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json"))
+                    .unwrap();
+            node["virusProtectionInfo"] = serde_json::json!({
+                "verdict": verdict,
+                "lastCheckedAt": "2020-01-01T00:00:00.000Z",
+                "sha256": "string"
+            });
+            node
+        };
 
-        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        let uploaded: Node = serde_json::from_value(with_verdict("IN_PROGRESS")).unwrap();
 
-        let system_info_mock = mock_server
-            .mock("GET", "/api/v4/public/system/info")
+        let node_mock = mock_server
+            .mock("GET", "/api/v4/nodes/2")
             .with_status(200)
-            .with_body(sys_info_res)
+            .with_body(with_verdict("CLEAN").to_string())
             .with_header("content-type", "application/json")
             .create();
 
-        let keypair =
-            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
-                .unwrap();
-        let enc_keypair =
-            DracoonCrypto::encrypt_private_key("TopSecret1234!", keypair.clone()).unwrap();
-        let enc_keypair_json = serde_json::to_string(&enc_keypair).unwrap();
+        let node = client.wait_for_virus_scan(uploaded).await.unwrap();
 
-        let keypair_mock = mock_server
-            .mock("GET", "/api/v4/user/account/keypair")
-            .with_status(200)
-            .with_header("content-type", "application/json")
-            .with_body(enc_keypair_json)
-            .create();
+        node_mock.assert();
 
-        let _kp = client
-            .get_keypair(Some("TopSecret1234!".into()))
-            .await
-            .unwrap();
+        let info = node.virus_protection_info.unwrap();
+        assert_eq!(info.verdict, VirusProtectionVerdict::Clean);
+        assert_eq!(info.sha256, Some("string".to_string()));
+    }
 
-        keypair_mock.assert();
+    #[test]
+    fn test_unknown_virus_protection_verdict() {
+        let mut node: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+        node["virusProtectionInfo"] = serde_json::json!({
+            "verdict": "QUARANTINED",
+            "lastCheckedAt": "2020-01-01T00:00:00.000Z",
+            "sha256": "string"
+        });
 
-        let file_meta = FileMeta::builder("test", 16).build();
+        let node: Node = serde_json::from_value(node).unwrap();
 
-        let upload_options = UploadOptions::builder(file_meta).build();
+        assert_eq!(
+            node.virus_protection_info.unwrap().verdict,
+            VirusProtectionVerdict::Unknown
+        );
+    }
 
-        // mock upload channel
-        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+    #[tokio::test]
+    async fn test_open_upload() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 16).build()).build();
+
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
+            .with_status(200)
+            .with_body(sys_info_res)
+            .with_header("content-type", "application/json")
+            .create();
 
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
         let upload_channel_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads")
             .with_status(201)
@@ -2038,36 +4435,26 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        // mock S3 urls
-        let s3_urls_response =
-            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
-        let s3_urls_response =
-            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
-
+        let url = format!("{}upload_url", client.get_base_url());
         let s3_urls_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
             .with_status(201)
-            .with_body(s3_urls_response.clone())
+            .with_body(serde_json::json!({ "urls": [{ "url": url, "partNumber": 1 }] }).to_string())
             .with_header("content-type", "application/json")
             .create();
 
-        let upload_res =
-            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
-
-        // mock upload to S3
         let upload_mock = mock_server
             .mock("PUT", "/upload_url")
+            .match_body("aaaaaaaaaaaaaaaa")
             .with_status(202)
             .with_header("etag", "string")
             .create();
 
-        // mock finalize upload
         let finalize_mock = mock_server
             .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
             .with_status(202)
             .create();
 
-        // mock upload status
         let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
         let status_mock = mock_server
             .mock("GET", "/api/v4/nodes/files/uploads/string")
@@ -2076,84 +4463,42 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        // mock missing file keys
-        let missing_keys = include_str!("../tests/responses/nodes/missing_file_keys_empty_ok.json");
-        let keys_mock = mock_server
-            .mock("GET", "/api/v4/nodes/missingFileKeys?file_id=2&limit=50")
-            .with_status(200)
-            .with_body(missing_keys)
-            .with_header("content-type", "application/json")
-            .create();
+        let mut writer = client.open_upload(&parent_node, upload_options, None, None);
 
-        let node = <Dracoon<Connected> as Upload<Cursor<Vec<u8>>>>::upload(
-            &client,
-            &parent_node,
-            upload_options,
-            reader_clone,
-            None,
-            None,
-        )
-        .await
-        .unwrap();
+        writer.write_all(b"aaaaaaaa").await.unwrap();
+        writer.write_all(b"aaaaaaaa").await.unwrap();
 
+        let node = writer.finish().await.unwrap();
+
+        system_info_mock.assert();
         upload_channel_mock.assert();
         s3_urls_mock.assert();
         upload_mock.assert();
         finalize_mock.assert();
         status_mock.assert();
-        keys_mock.assert();
 
         assert_node(&node);
     }
 
-    #[test]
-    fn test_calculate_chunk_count_and_last_size() {
-        // exact multiple of chunk size
-        let (count, last_size) = calculate_s3_url_count(10 * 1024 * 1024, 5 * 1024 * 1024);
-        assert_eq!(count, 2);
-        assert_eq!(last_size, 5 * 1024 * 1024);
-
-        // exact value as chunk size
-        let (count, last_size) = calculate_s3_url_count(10 * 1024 * 1024, 10 * 1024 * 1024);
-        assert_eq!(count, 1);
-        assert_eq!(last_size, 10 * 1024 * 1024);
-
-        // not multiple of chunk size
-        let (count, last_size) = calculate_s3_url_count(10 * 1024 * 1024 + 13, 5 * 1024 * 1024);
-        assert_eq!(count, 3);
-        assert_eq!(last_size, 13);
-
-        // empty file
-        let (count, last_size) = calculate_s3_url_count(0, 5 * 1024 * 1024);
-        assert_eq!(count, 1);
-        assert_eq!(last_size, 0);
-
-        // large file
-        let (count, last_size) = calculate_s3_url_count(1024 * 1024 * 1024, 5 * 1024 * 1024);
-        assert_eq!(count, 205);
-        assert_eq!(last_size, 4 * 1024 * 1024);
-    }
-
     #[tokio::test]
-    async fn test_upload_callback_s3_unencrypted() {
+    async fn test_upload_with_events() {
         let (client, mut mock_server) = get_connected_client().await;
 
         let parent_node: Node =
             serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
 
-        // 10 MB file in mem
-        let mock_bytes: Vec<u8> = vec![12; 10 * 1024 * 1024];
-
-        let reader = Cursor::new(mock_bytes);
-        let reader_clone = BufReader::new(reader);
-
-        let file_meta = FileMeta::builder("test", 10 * 1024 * 1024).build();
+        let reader = BufReader::new(Cursor::new(vec![0u8; 16]));
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 16).build()).build();
 
-        let upload_options = UploadOptions::builder(file_meta).build();
+        let sys_info_res = include_str!("../tests/responses/public/system_info_ok.json");
+        let system_info_mock = mock_server
+            .mock("GET", "/api/v4/public/system/info")
+            .with_status(200)
+            .with_body(sys_info_res)
+            .with_header("content-type", "application/json")
+            .create();
 
-        // mock upload channel
         let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
-
         let upload_channel_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads")
             .with_status(201)
@@ -2161,36 +4506,25 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        // mock S3 urls
-        let s3_urls_response =
-            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
-        let s3_urls_response =
-            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
-
+        let url = format!("{}upload_url", client.get_base_url());
         let s3_urls_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
             .with_status(201)
-            .with_body(s3_urls_response.clone())
+            .with_body(serde_json::json!({ "urls": [{ "url": url, "partNumber": 1 }] }).to_string())
             .with_header("content-type", "application/json")
             .create();
 
-        let upload_res =
-            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
-
-        // mock upload to S3
         let upload_mock = mock_server
             .mock("PUT", "/upload_url")
             .with_status(202)
             .with_header("etag", "string")
             .create();
 
-        // mock finalize upload
         let finalize_mock = mock_server
             .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
             .with_status(202)
             .create();
 
-        // mock upload status
         let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
         let status_mock = mock_server
             .mock("GET", "/api/v4/nodes/files/uploads/string")
@@ -2199,60 +4533,115 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        let arc_processed = Arc::new(AtomicU64::new(0));
-        let cloned_processed = arc_processed.clone();
-
-        let callback = move |processed: u64, total: u64| {
-            let current = cloned_processed.load(std::sync::atomic::Ordering::Acquire);
+        let (upload, events) =
+            client.upload_with_events(&parent_node, upload_options, reader, None);
 
-            cloned_processed.fetch_add(processed, std::sync::atomic::Ordering::Release);
-        };
-
-        let callback_boxed = Box::new(callback);
-
-        let node =
-            <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_unencrypted(
-                &client,
-                &parent_node,
-                upload_options,
-                reader_clone,
-                Some(callback_boxed),
-                None,
-            )
-            .await
-            .unwrap();
+        let (node, events) = tokio::join!(upload, events.collect::<Vec<_>>());
 
+        system_info_mock.assert();
         upload_channel_mock.assert();
         s3_urls_mock.assert();
         upload_mock.assert();
         finalize_mock.assert();
         status_mock.assert();
 
-        assert_eq!(
-            arc_processed.load(std::sync::atomic::Ordering::Relaxed),
-            10 * 1024 * 1024
-        );
+        assert_node(&node.unwrap());
+
+        assert_eq!(events.first(), Some(&UploadEvent::Started { total: 16 }));
+        assert_eq!(events.last(), Some(&UploadEvent::Finished));
+        assert!(events.contains(&UploadEvent::PartCompleted {
+            part_number: 1,
+            size: 16
+        }));
+
+        let finalizing = events
+            .iter()
+            .position(|event| event == &UploadEvent::Finalizing)
+            .unwrap();
+        let last_progress = events
+            .iter()
+            .rposition(|event| matches!(event, UploadEvent::Progressed(_)))
+            .unwrap();
+        assert!(last_progress < finalizing);
     }
 
     #[tokio::test]
-    async fn test_upload_callback_s3_unencrypted_chunked() {
+    async fn test_upload_validate_policies() {
         let (client, mut mock_server) = get_connected_client().await;
 
         let parent_node: Node =
             serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
 
-        // 10 MB file in mem
-        let mock_bytes: Vec<u8> = vec![12; 10 * 1024 * 1024];
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .expect(0)
+            .create();
 
-        let reader = Cursor::new(mock_bytes);
-        let reader_clone = BufReader::new(reader);
+        let invalid_options = [
+            UploadOptions::builder(FileMeta::builder("test", 16).build())
+                .with_classification(5)
+                .with_validate_policies(true)
+                .build(),
+            UploadOptions::builder(FileMeta::builder("te:st", 16).build())
+                .with_validate_policies(true)
+                .build(),
+        ];
 
-        let file_meta = FileMeta::builder("test", 10 * 1024 * 1024).build();
+        for upload_options in invalid_options {
+            let reader = BufReader::new(Cursor::new(vec![0u8; 16]));
+            let err = client
+                .upload(&parent_node, upload_options, reader, None, None)
+                .await
+                .unwrap_err();
 
-        let upload_options = UploadOptions::builder(file_meta).build();
+            assert!(err.is_invalid_upload_options());
+        }
+
+        upload_channel_mock.assert();
+    }
+
+    #[test]
+    fn test_validate_upload_options_expiration() {
+        let options = |days: i64| {
+            UploadOptions::builder(FileMeta::builder("test", 16).build())
+                .with_expiration(ObjectExpiration::new(
+                    chrono::Utc::now() + chrono::Duration::days(days),
+                ))
+                .build()
+        };
+
+        // default expiration periods are no maximum - any future date is valid
+        assert!(options(3).validate().is_ok());
+        assert!(options(365).validate().is_ok());
+        assert!(options(-1).validate().unwrap_err().is_invalid_expiration());
+
+        let invalid = UploadOptions::builder(FileMeta::builder("test", 16).build())
+            .with_expiration(ObjectExpiration {
+                enable_expiration: true,
+                expire_at: Some("not a date".into()),
+            })
+            .build();
+        assert!(invalid.validate().unwrap_err().is_invalid_expiration());
+
+        let long_name = "a".repeat(151);
+        let options = UploadOptions::builder(FileMeta::builder(long_name, 16).build()).build();
+        assert!(options.validate().unwrap_err().is_invalid_upload_options());
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_nfs_unencrypted() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let reader = BufReader::new(Cursor::new(vec![0u8; 16]));
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 16).build()).build();
 
         // mock upload channel
-        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+        let channel_res =
+            include_str!("../tests/responses/upload/upload_channel_ok_with_placeholder.json");
+        let channel_res = channel_res.replace("$base_url/", client.get_base_url().as_str());
 
         let upload_channel_mock = mock_server
             .mock("POST", "/api/v4/nodes/files/uploads")
@@ -2261,80 +4650,236 @@ mod tests {
             .with_header("content-type", "application/json")
             .create();
 
-        // mock S3 urls
-        let s3_urls_response =
-            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
-        let s3_urls_response =
-            s3_urls_response.replace("$base_url/", client.get_base_url().as_str());
+        // content is uploaded in two chunks (chunk size 8)
+        let first_chunk_mock = mock_server
+            .mock("POST", "/upload_url")
+            .match_header("content-range", "bytes 0-8/16")
+            .with_status(201)
+            .create();
+        let last_chunk_mock = mock_server
+            .mock("POST", "/upload_url")
+            .match_header("content-range", "bytes 8-16/16")
+            .with_status(201)
+            .create();
 
-        let s3_urls_mock = mock_server
-            .mock("POST", "/api/v4/nodes/files/uploads/string/s3_urls")
+        let node_res = include_str!("../tests/responses/nodes/node_ok.json");
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/uploads/string")
             .with_status(201)
-            .with_body(s3_urls_response.clone())
+            .with_body(node_res)
             .with_header("content-type", "application/json")
-            .expect(2)
             .create();
 
-        let upload_res =
-            serde_json::from_str::<PresignedUrlList>(s3_urls_response.as_str()).unwrap();
+        let node = <Dracoon<Connected> as UploadInternalNfs<Cursor<Vec<u8>>, Connected>>::upload_to_nfs_unencrypted(
+            &client,
+            &parent_node,
+            upload_options,
+            reader,
+            None,
+            Some(8),
+        )
+        .await
+        .unwrap();
+
+        upload_channel_mock.assert();
+        first_chunk_mock.assert();
+        last_chunk_mock.assert();
+        finalize_mock.assert();
+
+        assert_node(&node);
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_nfs_encrypted() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let reader = BufReader::new(Cursor::new(vec![1u8; 16]));
+
+        let keypair =
+            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
+                .unwrap();
+        let enc_keypair =
+            DracoonCrypto::encrypt_private_key("TopSecret1234!", keypair.clone()).unwrap();
+
+        let keypair_mock = mock_server
+            .mock("GET", "/api/v4/user/account/keypair")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&enc_keypair).unwrap())
+            .create();
+
+        let _kp = client
+            .get_keypair(Some("TopSecret1234!".into()))
+            .await
+            .unwrap();
+
+        keypair_mock.assert();
+
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 16).build()).build();
+
+        // mock upload channel
+        let channel_res =
+            include_str!("../tests/responses/upload/upload_channel_ok_with_placeholder.json");
+        let channel_res = channel_res.replace("$base_url/", client.get_base_url().as_str());
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
 
-        // mock upload to S3
         let upload_mock = mock_server
-            .mock("PUT", "/upload_url")
-            .with_status(202)
-            .with_header("etag", "string")
+            .mock("POST", "/upload_url")
+            .with_status(201)
             .expect(2)
             .create();
 
-        // mock finalize upload
+        // file key is sent when finalizing the upload
+        let node_res = include_str!("../tests/responses/nodes/node_ok.json");
         let finalize_mock = mock_server
-            .mock("PUT", "/api/v4/nodes/files/uploads/string/s3")
-            .with_status(202)
+            .mock("PUT", "/api/v4/uploads/string")
+            .match_body(mockito::Matcher::Regex("fileKey".into()))
+            .with_status(201)
+            .with_body(node_res)
+            .with_header("content-type", "application/json")
             .create();
 
-        // mock upload status
-        let status_res = include_str!("../tests/responses/upload/upload_status_ok.json");
-        let status_mock = mock_server
-            .mock("GET", "/api/v4/nodes/files/uploads/string")
+        let missing_keys = include_str!("../tests/responses/nodes/missing_file_keys_empty_ok.json");
+        let keys_mock = mock_server
+            .mock("GET", "/api/v4/nodes/missingFileKeys?file_id=2&limit=50")
             .with_status(200)
-            .with_body(status_res)
+            .with_body(missing_keys)
             .with_header("content-type", "application/json")
             .create();
 
-        let arc_processed = Arc::new(AtomicU64::new(0));
-        let cloned_processed = arc_processed.clone();
+        let node = <Dracoon<Connected> as UploadInternalNfs<Cursor<Vec<u8>>, Connected>>::upload_to_nfs_encrypted(
+            &client,
+            &parent_node,
+            upload_options,
+            reader,
+            None,
+            Some(8),
+        )
+        .await
+        .unwrap();
 
-        let callback = move |processed: u64, total: u64| {
-            let current = cloned_processed.load(std::sync::atomic::Ordering::Acquire);
+        upload_channel_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        keys_mock.assert();
 
-            cloned_processed.fetch_add(processed, std::sync::atomic::Ordering::Release);
-        };
+        assert_node(&node);
+    }
 
-        let callback_boxed = Box::new(callback);
+    #[tokio::test]
+    async fn test_upload_to_nfs_encrypted_with_public_keys() {
+        let (client, mut mock_server) = get_connected_client().await;
 
-        let node =
-            <Dracoon<Connected> as UploadInternal<Cursor<Vec<u8>>>>::upload_to_s3_unencrypted(
-                &client,
-                &parent_node,
-                upload_options,
-                reader_clone,
-                Some(callback_boxed),
-                Some(5 * 1024 * 1024),
-            )
+        let parent_node: Node =
+            serde_json::from_str(include_str!("../tests/responses/nodes/node_ok.json")).unwrap();
+
+        let reader = BufReader::new(Cursor::new(vec![1u8; 16]));
+
+        let keypair =
+            DracoonCrypto::create_plain_user_keypair(dco3_crypto::UserKeyPairVersion::RSA4096)
+                .unwrap();
+        let enc_keypair =
+            DracoonCrypto::encrypt_private_key("TopSecret1234!", keypair.clone()).unwrap();
+
+        let keypair_mock = mock_server
+            .mock("GET", "/api/v4/user/account/keypair")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&enc_keypair).unwrap())
+            .create();
+
+        let _kp = client
+            .get_keypair(Some("TopSecret1234!".into()))
             .await
             .unwrap();
 
+        keypair_mock.assert();
+
+        let public_keys = vec![UserUserPublicKey::new(
+            42,
+            keypair.public_key_container.clone(),
+        )];
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 16).build())
+            .with_public_keys(public_keys)
+            .build();
+
+        // mock upload channel
+        let channel_res =
+            include_str!("../tests/responses/upload/upload_channel_ok_with_placeholder.json");
+        let channel_res = channel_res.replace("$base_url/", client.get_base_url().as_str());
+
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/uploads")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_mock = mock_server
+            .mock("POST", "/upload_url")
+            .with_status(201)
+            .create();
+
+        let node_res = include_str!("../tests/responses/nodes/node_ok.json");
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/uploads/string")
+            .with_status(201)
+            .with_body(node_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // missing file keys are not fetched - the file key is set for the given users
+        let missing_keys_mock = mock_server
+            .mock("GET", "/api/v4/nodes/missingFileKeys?file_id=2&limit=50")
+            .expect(0)
+            .create();
+        let set_keys_mock = mock_server
+            .mock("POST", "/api/v4/nodes/files/keys")
+            .match_body(mockito::Matcher::Regex(r#""userId":42"#.into()))
+            .with_status(204)
+            .create();
+
+        let node = <Dracoon<Connected> as UploadInternalNfs<Cursor<Vec<u8>>, Connected>>::upload_to_nfs_encrypted(
+            &client,
+            &parent_node,
+            upload_options,
+            reader,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
         upload_channel_mock.assert();
-        s3_urls_mock.assert();
         upload_mock.assert();
         finalize_mock.assert();
-        status_mock.assert();
+        missing_keys_mock.assert();
+        set_keys_mock.assert();
 
-        assert_eq!(
-            arc_processed.load(std::sync::atomic::Ordering::Relaxed),
-            10 * 1024 * 1024
-        );
+        assert_node(&node);
     }
 
-    //TODO: test NFS upload (unencrypted and encrypted)
+    #[test]
+    fn test_upload_options_debug_redacts_file_key() {
+        let (_, plain_key) = DracoonCrypto::encrypt(b"secret".to_vec()).unwrap();
+        let key = plain_key.key.clone();
+
+        let upload_options = UploadOptions::builder(FileMeta::builder("test", 16).build())
+            .with_file_key(plain_key)
+            .build();
+
+        let debug = format!("{upload_options:?}");
+
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains(&key));
+    }
 }
@@ -10,8 +10,8 @@ use crate::{
         DRACOON_API_PREFIX, PROVISIONING_BASE, PROVISIONING_CUSTOMERS,
         PROVISIONING_CUSTOMER_ATTRIBUTES, PROVISIONING_CUSTOMER_USERS, PROVISIONING_TOKEN_HEADER,
     },
-    users::UserList,
-    utils::FromResponse,
+    users::{UserItem, UserList, UsersFilter},
+    utils::{list_all, FromResponse},
     DracoonClientError, ListAllParams,
 };
 
@@ -54,6 +54,39 @@ pub trait CustomerProvisioning {
         &self,
         params: Option<ListAllParams>,
     ) -> Result<CustomerList, DracoonClientError>;
+    /// Returns all customers matching the filter (paginated)
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, CustomerProvisioning, provisioning::CustomersFilter};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #   .with_base_url("https://dracoon.team")
+    /// #   .with_provisioning_token("some_token")
+    /// #   .build_provisioning()
+    /// #   .unwrap();
+    /// let customers = dracoon.provisioning().get_all_customers(None).await.unwrap();
+    ///
+    /// // only locked customers
+    /// let filter = CustomersFilter::is_locked(true);
+    /// let customers = dracoon.provisioning().get_all_customers(Some(filter)).await.unwrap();
+    /// # }
+    async fn get_all_customers(
+        &self,
+        filter: Option<CustomersFilter>,
+    ) -> Result<Vec<Customer>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_offset(offset)
+                .with_limit(limit);
+            let params = match &filter {
+                Some(filter) => params.with_filter(filter.clone()),
+                None => params,
+            };
+
+            self.get_customers(Some(params.build()))
+        })
+        .await
+    }
     /// Creates a new customer
     /// ```no_run
     /// # use dco3::{Dracoon, OAuth2Flow, CustomerProvisioning, provisioning::{FirstAdminUser, NewCustomerRequest}};
@@ -132,7 +165,7 @@ pub trait CustomerProvisioning {
     async fn delete_customer(&self, id: u64) -> Result<(), DracoonClientError>;
     /// Returns a list of customer users
     /// ```no_run
-    /// # use dco3::{Dracoon, OAuth2Flow, CustomerProvisioning};
+    /// # use dco3::{Dracoon, OAuth2Flow, CustomerProvisioning, ListAllParams, users::UsersFilter};
     /// # #[tokio::main]
     /// # async fn main() {
     /// # let dracoon = Dracoon::builder()
@@ -141,12 +174,54 @@ pub trait CustomerProvisioning {
     /// #   .build_provisioning()
     /// #   .unwrap();
     /// let users = dracoon.provisioning().get_customer_users(123, None).await.unwrap();
+    ///
+    /// // count locked users of a customer
+    /// let params = ListAllParams::builder()
+    ///    .with_filter(UsersFilter::is_locked(true))
+    ///    .with_limit(1)
+    ///    .build();
+    /// let locked_users = dracoon.provisioning().get_customer_users(123, Some(params)).await.unwrap();
+    /// let count = locked_users.range.total;
     /// # }
     async fn get_customer_users(
         &self,
         id: u64,
         params: Option<ListAllParams>,
     ) -> Result<UserList, DracoonClientError>;
+    /// Returns all users of a customer matching the filter (paginated)
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, CustomerProvisioning, users::UsersFilter};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #   .with_base_url("https://dracoon.team")
+    /// #   .with_provisioning_token("some_token")
+    /// #   .build_provisioning()
+    /// #   .unwrap();
+    /// let users = dracoon.provisioning().get_all_customer_users(123, None).await.unwrap();
+    ///
+    /// // only users with a specific email domain
+    /// let filter = UsersFilter::email_contains("@dracoon.com");
+    /// let users = dracoon.provisioning().get_all_customer_users(123, Some(filter)).await.unwrap();
+    /// # }
+    async fn get_all_customer_users(
+        &self,
+        id: u64,
+        filter: Option<UsersFilter>,
+    ) -> Result<Vec<UserItem>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_offset(offset)
+                .with_limit(limit);
+            let params = match &filter {
+                Some(filter) => params.with_filter(filter.clone()),
+                None => params,
+            };
+
+            self.get_customer_users(id, Some(params.build()))
+        })
+        .await
+    }
     /// Returns a list of customer attributes
     /// ```no_run
     /// # use dco3::{Dracoon, OAuth2Flow, CustomerProvisioning};
@@ -174,8 +249,9 @@ pub trait CustomerProvisioning {
     /// #   .with_provisioning_token("some_token")
     /// #   .build_provisioning()
     /// #   .unwrap();
-    /// let mut attributes = CustomerAttributes::new();
-    /// attributes.add_attribute("foo", "bar");
+    /// let attributes = CustomerAttributes::new()
+    ///    .with_attribute("foo", "bar")
+    ///    .with_attribute("baz", "qux");
     /// let customer = dracoon.provisioning().update_customer_attributes(123, attributes).await.unwrap();
     /// # }
     async fn update_customer_attributes(
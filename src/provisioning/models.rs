@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use dco3_derive::FromResponse;
 use reqwest::Response;
 use serde::{Deserialize, Serialize};
@@ -10,7 +10,8 @@ use crate::{
     client::{DracoonClient, DracoonErrorResponse},
     user::UserAuthData,
     utils::{parse_body, FromResponse},
-    DracoonClientError, KeyValueEntry, RangedItems,
+    DracoonClientError, FilterOperator, FilterQuery, KeyValueEntry, RangedItems, SortOrder,
+    SortQuery,
 };
 
 #[derive(Clone)]
@@ -49,6 +50,11 @@ impl CustomerAttributes {
         };
         self.items.push(attrib);
     }
+
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.add_attribute(key, value);
+        self
+    }
 }
 
 pub type AttributesResponse = RangedItems<KeyValueEntry>;
@@ -83,6 +89,179 @@ impl FromResponse for CustomerList {
     }
 }
 
+#[derive(Debug, Clone)]
+pub enum CustomersFilter {
+    CompanyName(FilterOperator, String),
+    CustomerContractType(FilterOperator, String),
+    ProviderCustomerId(FilterOperator, String),
+    IsLocked(FilterOperator, bool),
+    TrialDaysLeft(FilterOperator, u64),
+    CreatedAt(FilterOperator, DateTime<Utc>),
+}
+
+impl FilterQuery for CustomersFilter {
+    fn to_filter_string(&self) -> String {
+        match self {
+            Self::CompanyName(op, value) => {
+                let op: String = op.into();
+                format!("companyName:{}:{}", op, value)
+            }
+            Self::CustomerContractType(op, value) => {
+                let op: String = op.into();
+                format!("customerContractType:{}:{}", op, value)
+            }
+            Self::ProviderCustomerId(op, value) => {
+                let op: String = op.into();
+                format!("providerCustomerId:{}:{}", op, value)
+            }
+            Self::IsLocked(op, value) => {
+                let op: String = op.into();
+                format!("isLocked:{}:{}", op, value)
+            }
+            Self::TrialDaysLeft(op, value) => {
+                let op: String = op.into();
+                format!("trialDaysLeft:{}:{}", op, value)
+            }
+            Self::CreatedAt(op, value) => {
+                let op: String = op.into();
+                let value = value.to_rfc3339_opts(SecondsFormat::Secs, true);
+                format!("createdAt:{}:{}", op, value)
+            }
+        }
+    }
+}
+
+impl CustomersFilter {
+    pub fn company_name_equals(value: impl Into<String>) -> Self {
+        Self::CompanyName(FilterOperator::Eq, value.into())
+    }
+
+    pub fn company_name_contains(value: impl Into<String>) -> Self {
+        Self::CompanyName(FilterOperator::Cn, value.into())
+    }
+
+    pub fn customer_contract_type_equals(value: impl Into<String>) -> Self {
+        Self::CustomerContractType(FilterOperator::Eq, value.into())
+    }
+
+    pub fn provider_customer_id_equals(value: impl Into<String>) -> Self {
+        Self::ProviderCustomerId(FilterOperator::Eq, value.into())
+    }
+
+    pub fn is_locked(value: bool) -> Self {
+        Self::IsLocked(FilterOperator::Eq, value)
+    }
+
+    pub fn trial_days_left_less_equals(value: u64) -> Self {
+        Self::TrialDaysLeft(FilterOperator::Le, value)
+    }
+
+    pub fn created_at_before(value: DateTime<Utc>) -> Self {
+        Self::CreatedAt(FilterOperator::Le, value)
+    }
+
+    pub fn created_at_after(value: DateTime<Utc>) -> Self {
+        Self::CreatedAt(FilterOperator::Ge, value)
+    }
+}
+
+impl From<CustomersFilter> for Box<dyn FilterQuery> {
+    fn from(filter: CustomersFilter) -> Self {
+        Box::new(filter)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum CustomersSortBy {
+    CompanyName(SortOrder),
+    CustomerContractType(SortOrder),
+    TrialDaysLeft(SortOrder),
+    QuotaMax(SortOrder),
+    QuotaUsed(SortOrder),
+    UserMax(SortOrder),
+    UserUsed(SortOrder),
+    CreatedAt(SortOrder),
+}
+
+impl SortQuery for CustomersSortBy {
+    fn to_sort_string(&self) -> String {
+        match self {
+            Self::CompanyName(order) => {
+                let order: String = order.into();
+                format!("companyName:{}", order)
+            }
+            Self::CustomerContractType(order) => {
+                let order: String = order.into();
+                format!("customerContractType:{}", order)
+            }
+            Self::TrialDaysLeft(order) => {
+                let order: String = order.into();
+                format!("trialDaysLeft:{}", order)
+            }
+            Self::QuotaMax(order) => {
+                let order: String = order.into();
+                format!("quotaMax:{}", order)
+            }
+            Self::QuotaUsed(order) => {
+                let order: String = order.into();
+                format!("quotaUsed:{}", order)
+            }
+            Self::UserMax(order) => {
+                let order: String = order.into();
+                format!("userMax:{}", order)
+            }
+            Self::UserUsed(order) => {
+                let order: String = order.into();
+                format!("userUsed:{}", order)
+            }
+            Self::CreatedAt(order) => {
+                let order: String = order.into();
+                format!("createdAt:{}", order)
+            }
+        }
+    }
+}
+
+impl CustomersSortBy {
+    pub fn company_name(order: SortOrder) -> Self {
+        Self::CompanyName(order)
+    }
+
+    pub fn customer_contract_type(order: SortOrder) -> Self {
+        Self::CustomerContractType(order)
+    }
+
+    pub fn trial_days_left(order: SortOrder) -> Self {
+        Self::TrialDaysLeft(order)
+    }
+
+    pub fn quota_max(order: SortOrder) -> Self {
+        Self::QuotaMax(order)
+    }
+
+    pub fn quota_used(order: SortOrder) -> Self {
+        Self::QuotaUsed(order)
+    }
+
+    pub fn user_max(order: SortOrder) -> Self {
+        Self::UserMax(order)
+    }
+
+    pub fn user_used(order: SortOrder) -> Self {
+        Self::UserUsed(order)
+    }
+
+    pub fn created_at(order: SortOrder) -> Self {
+        Self::CreatedAt(order)
+    }
+}
+
+impl From<CustomersSortBy> for Box<dyn SortQuery> {
+    fn from(sort_by: CustomersSortBy) -> Self {
+        Box::new(sort_by)
+    }
+}
+
 #[async_trait]
 impl FromResponse for AttributesResponse {
     async fn from_response(response: Response) -> Result<Self, DracoonClientError> {
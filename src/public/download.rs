@@ -1,7 +1,7 @@
 use std::cmp::min;
 
 use async_trait::async_trait;
-use dco3_crypto::{ChunkedEncryption, Decrypter, DracoonCrypto, DracoonRSACrypto};
+use dco3_crypto::{DracoonCrypto, DracoonRSACrypto};
 use futures_util::TryStreamExt;
 use reqwest::header::{self, RANGE};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
@@ -12,7 +12,7 @@ use crate::{
         DEFAULT_CHUNK_SIZE, DRACOON_API_PREFIX, PUBLIC_BASE, PUBLIC_DOWNLOAD_SHARES,
         PUBLIC_SHARES_BASE,
     },
-    nodes::DownloadProgressCallback,
+    nodes::{crypto::ChunkedDecrypter, DownloadProgressCallback, ProgressTracker},
     utils::{build_s3_error, FromResponse},
     DracoonClientError,
 };
@@ -37,22 +37,12 @@ impl<S: Send + Sync> PublicDownload for PublicEndpoint<S> {
             return Err(DracoonClientError::MissingArgument);
         }
 
-        let download_url = if share.is_protected {
-            let Some(ref password) = password else {
-                return Err(DracoonClientError::MissingArgument);
-            };
-            self.generate_download_url(
-                access_key.into(),
-                PublicDownloadTokenGenerateRequest::new(password),
-            )
-            .await?
-        } else {
-            self.generate_download_url(
-                access_key.into(),
-                PublicDownloadTokenGenerateRequest::default(),
-            )
-            .await?
-        };
+        // wait for a free transfer slot (released when the download is finished)
+        let _permit = self.client().acquire_transfer_permit().await;
+
+        let download_url = self
+            .get_download_url(access_key, &share, password.clone())
+            .await?;
 
         match share.is_encrypted.unwrap_or(false) {
             true => {
@@ -82,6 +72,25 @@ impl<S: Send + Sync> PublicDownload for PublicEndpoint<S> {
 
         Ok(())
     }
+
+    async fn get_download_url(
+        &self,
+        access_key: impl Into<String> + Send + Sync,
+        share: &PublicDownloadShare,
+        password: Option<String>,
+    ) -> Result<PublicDownloadTokenGenerateResponse, DracoonClientError> {
+        // password is only sent for protected shares
+        let req = if share.is_protected {
+            let Some(password) = password else {
+                return Err(DracoonClientError::MissingArgument);
+            };
+            PublicDownloadTokenGenerateRequest::new(password)
+        } else {
+            PublicDownloadTokenGenerateRequest::default()
+        };
+
+        self.generate_download_url(access_key.into(), req).await
+    }
 }
 
 #[async_trait]
@@ -154,6 +163,7 @@ impl<S: Send + Sync> PublicDownloadInternal for PublicEndpoint<S> {
     ) -> Result<(), DracoonClientError> {
         // offset (in bytes)
         let mut downloaded_bytes = 0u64;
+        let mut progress = ProgressTracker::new();
 
         // loop until all bytes are downloaded
         while downloaded_bytes < size {
@@ -194,7 +204,7 @@ impl<S: Send + Sync> PublicDownloadInternal for PublicEndpoint<S> {
 
                 // call progress callback if provided
                 if let Some(ref mut callback) = callback {
-                    callback(len, size);
+                    callback(progress.update(len, size));
                 }
                 if downloaded_bytes >= size {
                     break;
@@ -219,14 +229,12 @@ impl<S: Send + Sync> PublicDownloadInternal for PublicEndpoint<S> {
         let plain_key =
             DracoonCrypto::decrypt_file_key(encryption_info.file_key, plain_private_key)?;
 
-        // this is safe, because the maximum size of a file (encrypted) is 60 GB
-        #[allow(clippy::cast_possible_truncation)]
-        let mut buffer = vec![0u8; size as usize];
-
-        let mut crypter = DracoonCrypto::decrypter(plain_key, &mut buffer)?;
+        // decrypt chunk by chunk - the authentication tag is verified when finalizing
+        let mut decrypter = ChunkedDecrypter::new(&plain_key)?;
 
         // offset (in bytes)
         let mut downloaded_bytes = 0u64;
+        let mut progress = ProgressTracker::new();
 
         // loop until all bytes are downloaded
         while downloaded_bytes < size {
@@ -254,18 +262,22 @@ impl<S: Send + Sync> PublicDownloadInternal for PublicEndpoint<S> {
                 return Err(error);
             }
 
-            // write chunk to writer
+            // decrypt chunk and write to writer
             let mut stream = response.bytes_stream();
 
             while let Some(chunk) = stream.try_next().await? {
                 let len = chunk.len() as u64;
 
-                crypter.update(&chunk)?;
+                let plain = decrypter.update(&chunk)?;
+                writer
+                    .write_all(&plain)
+                    .await
+                    .or(Err(DracoonClientError::IoError))?;
                 downloaded_bytes += len;
 
                 // call progress callback if provided
                 if let Some(ref mut callback) = callback {
-                    callback(len, size);
+                    callback(progress.update(len, size));
                 }
                 if downloaded_bytes >= size {
                     break;
@@ -273,10 +285,10 @@ impl<S: Send + Sync> PublicDownloadInternal for PublicEndpoint<S> {
             }
         }
 
-        crypter.finalize()?;
+        let plain = decrypter.finalize()?;
 
         writer
-            .write_all(&buffer)
+            .write_all(&plain)
             .await
             .or(Err(DracoonClientError::IoError))?;
         Ok(())
@@ -104,12 +104,67 @@ pub trait Public {
     ///
     /// let public_upload_share = dracoon.public().get_public_upload_share(access_key.to_string()).await.unwrap();
     ///
+    /// // check remaining slots, size and expiration before uploading a file (size in bytes)
+    /// if let Err(err) = public_upload_share.validate_upload(1024) {
+    ///     println!("Cannot upload file: {}", err);
+    /// }
     /// # }
     /// ```
     async fn get_public_upload_share(
         &self,
         access_key: impl Into<String> + Send + Sync,
     ) -> Result<PublicUploadShare, DracoonClientError>;
+
+    /// Get the files uploaded to a public upload share (file request).
+    /// Files are only listed if `show_uploaded_files` is enabled for the share - otherwise
+    /// the list is empty.
+    ///
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Public};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap();
+    /// let uploaded_files = dracoon.public().get_public_uploaded_files("access_key").await.unwrap();
+    ///
+    /// for file in uploaded_files {
+    ///     println!("{} ({} bytes)", file.name, file.size);
+    /// }
+    /// # }
+    /// ```
+    async fn get_public_uploaded_files(
+        &self,
+        access_key: impl Into<String> + Send + Sync,
+    ) -> Result<Vec<PublicUploadedFileData>, DracoonClientError> {
+        let upload_share = self.get_public_upload_share(access_key).await?;
+
+        Ok(upload_share.uploaded_files.unwrap_or_default())
+    }
+
+    /// Cancels an upload to an upload share and removes its upload channel.
+    /// Uploaded content is discarded.
+    /// ```no_run
+    /// # use dco3::{Dracoon, Public};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap();
+    /// dracoon.public().cancel_upload("access_key", "upload_id").await.unwrap();
+    /// # }
+    /// ```
+    async fn cancel_upload(
+        &self,
+        access_key: impl Into<String> + Send + Sync,
+        upload_id: &str,
+    ) -> Result<(), DracoonClientError>;
 }
 
 #[async_trait]
@@ -140,8 +195,8 @@ pub trait PublicDownload {
     /// // or with a progress callback
     /// let share = dracoon.public().get_public_download_share(access_key.to_string()).await.unwrap();
     /// let password = Some("TopSecret123!".to_string());
-    /// dracoon.public().download(access_key.to_string(), share, password, &mut writer, Some(Box::new(|progress, total| {
-    ///    println!("Downloaded: {}%", progress);
+    /// dracoon.public().download(access_key.to_string(), share, password, &mut writer, Some(Box::new(|progress| {
+    ///    println!("Downloaded: {:.0}%", progress.fraction() * 100.0);
     /// })), None).await.unwrap();
     /// # }
     /// ```
@@ -154,6 +209,34 @@ pub trait PublicDownload {
         mut callback: Option<DownloadProgressCallback>,
         chunksize: Option<usize>,
     ) -> Result<(), DracoonClientError>;
+
+    /// Returns the (time-limited) download url of a public download share.
+    /// The password is required for protected shares (`DracoonClientError::MissingArgument` otherwise).
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Public, PublicDownload};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::password_flow("username", "password"))
+    /// #  .await
+    /// #  .unwrap();
+    /// let share = dracoon.public().get_public_download_share("access_key").await.unwrap();
+    /// let download_url = dracoon.public().get_download_url("access_key", &share, Some("TopSecret123!".to_string())).await.unwrap();
+    ///
+    /// println!("Download url: {}", download_url.download_url);
+    /// # }
+    /// ```
+    async fn get_download_url(
+        &self,
+        access_key: impl Into<String> + Send + Sync,
+        share: &PublicDownloadShare,
+        password: Option<String>,
+    ) -> Result<PublicDownloadTokenGenerateResponse, DracoonClientError>;
 }
 
 #[async_trait]
@@ -248,6 +331,28 @@ impl<S: Send + Sync> Public for PublicEndpoint<S> {
 
         Ok(PublicUploadShare::from_response(response).await?)
     }
+
+    async fn cancel_upload(
+        &self,
+        access_key: impl Into<String> + Send + Sync,
+        upload_id: &str,
+    ) -> Result<(), DracoonClientError> {
+        let url_part = format!(
+            "{DRACOON_API_PREFIX}/{PUBLIC_BASE}/{PUBLIC_SHARES_BASE}/{PUBLIC_UPLOAD_SHARES}/{}/{}",
+            access_key.into(),
+            upload_id
+        );
+
+        let url = self.client().build_api_url(&url_part);
+
+        let response = self.client().http.delete(url).send().await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(DracoonClientError::from_response(response).await?)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -259,10 +364,13 @@ mod tests {
     };
 
     use crate::{
-        nodes::{FileMeta, UploadOptions},
-        public::{PublicDownloadTokenGenerateRequest, PublicUpload},
+        nodes::{FileMeta, ResolutionStrategy, UploadOptions},
+        public::{
+            CreateShareUploadChannelRequest, PublicDownloadShare,
+            PublicDownloadTokenGenerateRequest, PublicUpload, PublicUploadShare,
+        },
         tests::dracoon::get_connected_client,
-        Dracoon, Public, PublicDownload,
+        Dracoon, DracoonClientError, Public, PublicDownload,
     };
 
     #[tokio::test]
@@ -500,6 +608,78 @@ mod tests {
         assert_eq!(private_key_container.created_by, Some(1));
     }
 
+    #[tokio::test]
+    async fn test_public_get_download_url() {
+        let mut mock_server = mockito::Server::new_async().await;
+
+        let client = Dracoon::builder()
+            .with_base_url(mock_server.url())
+            .with_client_id("client_id")
+            .with_client_secret("client_secret")
+            .build()
+            .unwrap();
+
+        let url_mock = mock_server
+            .mock("POST", "/api/v4/public/shares/downloads/test")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "password": "TopSecret1234!"
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"downloadUrl": "https://test.dracoon.com/some/download/url"}"#)
+            .create();
+
+        let share: PublicDownloadShare = serde_json::from_str(include_str!(
+            "../tests/responses/public/download_share_ok.json"
+        ))
+        .unwrap();
+
+        let download_url = client
+            .public()
+            .get_download_url("test", &share, Some("TopSecret1234!".to_string()))
+            .await
+            .unwrap();
+
+        url_mock.assert();
+
+        assert_eq!(
+            download_url.download_url,
+            "https://test.dracoon.com/some/download/url"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_public_get_download_url_protected_without_password() {
+        let mut mock_server = mockito::Server::new_async().await;
+
+        let client = Dracoon::builder()
+            .with_base_url(mock_server.url())
+            .with_client_id("client_id")
+            .with_client_secret("client_secret")
+            .build()
+            .unwrap();
+
+        let url_mock = mock_server
+            .mock("POST", "/api/v4/public/shares/downloads/test")
+            .expect(0)
+            .create();
+
+        let share: PublicDownloadShare = serde_json::from_str(include_str!(
+            "../tests/responses/public/download_share_ok.json"
+        ))
+        .unwrap();
+
+        let err = client
+            .public()
+            .get_download_url("test", &share, None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err, DracoonClientError::MissingArgument);
+
+        url_mock.assert();
+    }
+
     #[tokio::test]
     async fn test_public_download_unencrypted() {
         let mut mock_server = mockito::Server::new_async().await;
@@ -837,6 +1017,129 @@ mod tests {
         assert_eq!(uploaded_file.size, 16);
     }
 
+    #[tokio::test]
+    async fn test_get_public_uploaded_files() {
+        let mut mock_server = mockito::Server::new_async().await;
+
+        let client = Dracoon::builder()
+            .with_base_url(mock_server.url())
+            .with_client_id("client_id")
+            .with_client_secret("client_secret")
+            .build()
+            .unwrap();
+
+        let public_upload_share_res =
+            include_str!("../tests/responses/public/upload_share_ok.json");
+
+        let public_upload_share_mock = mock_server
+            .mock("GET", "/api/v4/public/shares/uploads/test")
+            .with_status(200)
+            .with_body(public_upload_share_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let uploaded_files = client
+            .public()
+            .get_public_uploaded_files("test")
+            .await
+            .unwrap();
+
+        public_upload_share_mock.assert();
+
+        assert_eq!(uploaded_files.len(), 1);
+        assert_eq!(uploaded_files[0].name, "string");
+        assert_eq!(uploaded_files[0].size, 16);
+    }
+
+    #[test]
+    fn test_create_share_upload_channel_request_serialization() {
+        let req = CreateShareUploadChannelRequest::builder("test.txt")
+            .with_size(16)
+            .with_resolution_strategy(ResolutionStrategy::Overwrite)
+            .with_keep_share_links(true)
+            .build();
+
+        let json = serde_json::to_value(&req).unwrap();
+
+        assert_eq!(json["name"], "test.txt");
+        assert_eq!(json["size"], 16);
+        assert_eq!(json["resolutionStrategy"], "overwrite");
+        assert_eq!(json["keepShareLinks"], true);
+
+        // unset options are not sent
+        let req = CreateShareUploadChannelRequest::builder("test.txt").build();
+        let json = serde_json::to_value(&req).unwrap();
+
+        assert!(json.get("resolutionStrategy").is_none());
+        assert!(json.get("keepShareLinks").is_none());
+
+        // options are taken over from the upload options
+        let upload_options = UploadOptions::builder(FileMeta::builder("test.txt", 16).build())
+            .with_resolution_strategy(ResolutionStrategy::Fail)
+            .with_keep_share_links(false)
+            .build();
+        let req = CreateShareUploadChannelRequest::from_upload_options(&upload_options, None, None);
+        let json = serde_json::to_value(&req).unwrap();
+
+        assert_eq!(json["resolutionStrategy"], "fail");
+        assert_eq!(json["keepShareLinks"], false);
+    }
+
+    #[test]
+    fn test_validate_public_upload_share() {
+        let mut share: PublicUploadShare = serde_json::from_str(include_str!(
+            "../tests/responses/public/upload_share_ok.json"
+        ))
+        .unwrap();
+
+        // share expired in 2021
+        assert!(share
+            .validate_upload(16)
+            .unwrap_err()
+            .is_invalid_upload_options());
+
+        share.expire_at = None;
+        assert!(share.validate_upload(16).is_ok());
+
+        share.remaining_size = Some(10);
+        assert!(share.validate_upload(10).is_ok());
+        assert!(share
+            .validate_upload(16)
+            .unwrap_err()
+            .is_invalid_upload_options());
+
+        share.remaining_slots = Some(0);
+        assert!(share
+            .validate_upload(1)
+            .unwrap_err()
+            .is_invalid_upload_options());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_upload() {
+        let mut mock_server = mockito::Server::new_async().await;
+
+        let client = Dracoon::builder()
+            .with_base_url(mock_server.url())
+            .with_client_id("client_id")
+            .with_client_secret("client_secret")
+            .build()
+            .unwrap();
+
+        let delete_mock = mock_server
+            .mock("DELETE", "/api/v4/public/shares/uploads/test/string")
+            .with_status(204)
+            .create();
+
+        client
+            .public()
+            .cancel_upload("test", "string")
+            .await
+            .unwrap();
+
+        delete_mock.assert();
+    }
+
     #[tokio::test]
     #[ignore = "not needed in CI (only for manual testing)"]
     async fn test_upload_unencrypted_staging() {
@@ -6,8 +6,11 @@ use dco3_derive::FromResponse;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    client::{DracoonClient, DracoonErrorResponse, GetClient},
-    nodes::{S3FileUploadPart, S3UploadStatus, UploadOptions, UserUserPublicKey},
+    client::{errors::DracoonClientError, DracoonClient, DracoonErrorResponse, GetClient},
+    nodes::{
+        ResolutionStrategy, S3FileUploadPart, S3UploadStatus, UploadOptions, UserUserPublicKey,
+        VirusProtectionInfo,
+    },
 };
 
 #[derive(Clone)]
@@ -82,7 +85,7 @@ pub struct PublicDownloadShare {
     pub is_encrypted: Option<bool>,
     pub file_key: Option<FileKey>,
     pub private_key_container: Option<PrivateKeyContainer>,
-    //virus_protection_info: VirusProtectionInfo TODO: add VirusProtectionInfo into Nodes
+    pub virus_protection_info: Option<VirusProtectionInfo>,
 }
 
 #[derive(Debug, Serialize, Clone, Default)]
@@ -138,6 +141,37 @@ pub struct PublicUploadShare {
     pub remaining_slots: Option<u32>,
 }
 
+impl PublicUploadShare {
+    /// Checks if a file with the given size can be uploaded (share not expired,
+    /// remaining slots and remaining size) - the password is not checked.
+    pub fn validate_upload(&self, size: u64) -> Result<(), DracoonClientError> {
+        if self
+            .expire_at
+            .is_some_and(|expire_at| expire_at <= Utc::now())
+        {
+            return Err(DracoonClientError::InvalidUploadOptions(
+                "upload share is expired".into(),
+            ));
+        }
+
+        if self.remaining_slots == Some(0) {
+            return Err(DracoonClientError::InvalidUploadOptions(
+                "no remaining upload slots".into(),
+            ));
+        }
+
+        if let Some(remaining_size) = self.remaining_size {
+            if size > remaining_size {
+                return Err(DracoonClientError::InvalidUploadOptions(format!(
+                    "file size exceeds remaining size of {remaining_size} bytes"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, FromResponse)]
 #[serde(rename_all = "camelCase")]
 pub struct PublicUploadedFileData {
@@ -167,6 +201,10 @@ pub struct CreateShareUploadChannelRequest {
     timestamp_creation: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     timestamp_modification: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolution_strategy: Option<ResolutionStrategy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keep_share_links: Option<bool>,
 }
 
 impl CreateShareUploadChannelRequest {
@@ -174,6 +212,8 @@ impl CreateShareUploadChannelRequest {
         CreateShareUploadChannelRequestBuilder::new(name)
     }
 
+    /// Creates the request from upload options (name, size, timestamps, resolution strategy
+    /// and keep share links) - classification and expiration are defined by the upload share
     pub fn from_upload_options(
         upload_options: &UploadOptions,
         is_s3_upload: Option<bool>,
@@ -195,6 +235,8 @@ impl CreateShareUploadChannelRequest {
             };
 
         req.direct_s3_upload = is_s3_upload;
+        req.resolution_strategy = upload_options.resolution_strategy.clone();
+        req.keep_share_links = upload_options.keep_share_links;
 
         if let Some(share_password) = share_password {
             req.password = Some(share_password);
@@ -211,6 +253,8 @@ pub struct CreateShareUploadChannelRequestBuilder {
     direct_s3_upload: Option<bool>,
     timestamp_creation: Option<String>,
     timestamp_modification: Option<String>,
+    resolution_strategy: Option<ResolutionStrategy>,
+    keep_share_links: Option<bool>,
 }
 
 impl CreateShareUploadChannelRequestBuilder {
@@ -222,6 +266,8 @@ impl CreateShareUploadChannelRequestBuilder {
             direct_s3_upload: None,
             timestamp_creation: None,
             timestamp_modification: None,
+            resolution_strategy: None,
+            keep_share_links: None,
         }
     }
 
@@ -250,6 +296,18 @@ impl CreateShareUploadChannelRequestBuilder {
         self
     }
 
+    /// sets the strategy if a file with the same name exists (default: autorename)
+    pub fn with_resolution_strategy(mut self, resolution_strategy: ResolutionStrategy) -> Self {
+        self.resolution_strategy = Some(resolution_strategy);
+        self
+    }
+
+    /// keeps share links of overwritten files (only with [ResolutionStrategy::Overwrite])
+    pub fn with_keep_share_links(mut self, keep_share_links: bool) -> Self {
+        self.keep_share_links = Some(keep_share_links);
+        self
+    }
+
     pub fn build(self) -> CreateShareUploadChannelRequest {
         CreateShareUploadChannelRequest {
             name: self.name,
@@ -258,6 +316,8 @@ impl CreateShareUploadChannelRequestBuilder {
             direct_S3_upload: self.direct_s3_upload,
             timestamp_creation: self.timestamp_creation,
             timestamp_modification: self.timestamp_modification,
+            resolution_strategy: self.resolution_strategy,
+            keep_share_links: self.keep_share_links,
         }
     }
 }
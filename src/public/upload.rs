@@ -1,17 +1,28 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
 use async_trait::async_trait;
-use dco3_crypto::{ChunkedEncryption, DracoonCrypto, DracoonRSACrypto, Encrypter};
+use dco3_crypto::{DracoonCrypto, DracoonRSACrypto};
 use tokio::io::{AsyncRead, AsyncReadExt, BufReader};
-use tracing::error;
+use tracing::{debug, error};
 
 use crate::{
+    client::throttle::RateLimiter,
     constants::{
         DEFAULT_CHUNK_SIZE, DRACOON_API_PREFIX, FILES_S3_COMPLETE, FILES_S3_URLS,
-        POLLING_START_DELAY, PUBLIC_BASE, PUBLIC_SHARES_BASE, PUBLIC_UPLOAD_SHARES,
+        POLLING_START_DELAY, PRESIGNED_URLS_BATCH, PUBLIC_BASE, PUBLIC_SHARES_BASE,
+        PUBLIC_UPLOAD_SHARES,
     },
     nodes::{
-        upload::{calculate_s3_url_count, StreamUploadInternal},
-        CloneableUploadProgressCallback, GeneratePresignedUrlsRequest, PresignedUrlList,
-        S3FileUploadPart, S3UploadStatus, UploadOptions, UploadProgressCallback,
+        crypto::BlockingEncrypter,
+        upload::{
+            calculate_s3_url_count, chunk_offset, resolve_chunk_size, verify_e_tag,
+            wait_for_upload_status, BufferPool, StreamUploadInternal,
+        },
+        CloneableUploadProgressCallback, GeneratePresignedUrlsRequest, PresignedUrl,
+        PresignedUrlList, S3FileUploadPart, S3UploadStatus, UploadOptions, UploadProgressCallback,
     },
     utils::FromResponse,
     DracoonClientError, Public,
@@ -36,9 +47,22 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUpload<
         callback: Option<UploadProgressCallback>,
         chunk_size: Option<usize>,
     ) -> Result<FileName, DracoonClientError> {
+        // wait for a free transfer slot (released when the upload is finished)
+        let _permit = self.client().acquire_transfer_permit().await;
+
         let use_s3_storage = self.get_system_info().await?.use_s3_storage;
         let is_encrypted = share.is_encrypted.unwrap_or(false);
 
+        // S3 part size and count limits do not apply to NFS uploads
+        let chunk_size = if use_s3_storage {
+            Some(resolve_chunk_size(
+                chunk_size,
+                Some(upload_options.file_meta.size),
+            )?)
+        } else {
+            chunk_size
+        };
+
         let upload_fn = match (use_s3_storage, is_encrypted) {
             (true, true) => PublicUploadInternal::upload_to_s3_encrypted,
             (true, false) => PublicUploadInternal::upload_to_s3_unencrypted,
@@ -143,10 +167,16 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
         let mut url_part: u32 = 1;
 
         let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+        let rate_limit = upload_options.max_rate.map(RateLimiter::new);
+
+        let mut urls: HashMap<u32, PresignedUrl> = HashMap::new();
+
+        let pool = BufferPool::new(1);
 
         if count_urls > 1 {
             while url_part < count_urls {
-                let mut buffer = vec![0; chunk_size];
+                let mut buffer = pool.get(chunk_size);
+                buffer.resize(chunk_size, 0);
                 let cb = cloneable_callback.clone();
                 let fm = fm.clone();
 
@@ -156,53 +186,53 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
                         buffer.truncate(n);
                         let chunk = bytes::Bytes::from(buffer);
 
-                        let stream: async_stream::__private::AsyncStream<
-                            Result<bytes::Bytes, std::io::Error>,
-                            _,
-                        > = async_stream::stream! {
-                            let mut buffer = Vec::new();
-                            let mut bytes_read = 0;
-
-                            for byte in chunk.iter() {
-                            buffer.push(*byte);
-                            bytes_read += 1;
-                            if buffer.len() == 1024 || bytes_read == chunk.len() {
-                            if let Some(callback) = cb.clone() {
-                                callback.call(buffer.len() as u64, fm.size);
-                                        }
-                                yield Ok(bytes::Bytes::from(buffer.clone()));
-                                buffer.clear();
-                                }
-                            }
-                        };
-
-                        let url_req = GeneratePresignedUrlsRequest::new(
-                            n.try_into().map_err(|_| DracoonClientError::IoError)?,
-                            url_part,
-                            url_part,
-                        );
-                        let url =
-                        <PublicEndpoint<S> as PublicUploadInternal<R, S>>::
-                            create_s3_upload_urls(self, access_key.clone(), upload_channel.upload_id.clone(), url_req)
-                            .await?;
-                        let url = url.urls.first().expect("Creating S3 url failed");
-
-                        // truncation is safe because chunk_size is 32 MB
-                        #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
-                        let curr_pos: u64 = ((url_part - 1) * (chunk_size as u32)) as u64;
+                        let stream = Self::create_stream(chunk.clone(), url_part, fm.size, cb);
+
+                        // request presigned urls for the next full chunks in one batch
+                        if !urls.contains_key(&url_part) {
+                            let last_part_number =
+                                (url_part + PRESIGNED_URLS_BATCH - 1).min(count_urls - 1);
+                            let url_req = GeneratePresignedUrlsRequest::new(
+                                n.try_into().map_err(|_| DracoonClientError::IoError)?,
+                                url_part,
+                                last_part_number,
+                            );
+                            let presigned_urls = <PublicEndpoint<S> as PublicUploadInternal<
+                                R,
+                                S,
+                            >>::create_s3_upload_urls(
+                                self,
+                                access_key.clone(),
+                                upload_channel.upload_id.clone(),
+                                url_req,
+                            )
+                            .await
+                            .map_err(|err| {
+                                error!("Error creating S3 upload urls: {}", err);
+                                err
+                            })?;
+                            urls.extend((url_part..=last_part_number).zip(presigned_urls.urls));
+                        }
+                        let url = urls.remove(&url_part).ok_or_else(|| {
+                            error!("No presigned url returned for part {}", url_part);
+                            DracoonClientError::MissingS3Url(url_part)
+                        })?;
 
                         let e_tag = self
                             .upload_stream_to_s3(
                                 Box::pin(stream),
-                                url,
+                                &url,
                                 chunk_size
                                     .try_into()
                                     .map_err(|_| DracoonClientError::IoError)?,
+                                rate_limit.as_ref(),
                             )
                             .await?;
 
-                        s3_parts.push(S3FileUploadPart::new(url_part, e_tag));
+                        verify_e_tag(&chunk, &e_tag, url_part)?;
+                        s3_parts.push(S3FileUploadPart::new(url_part, e_tag.value));
                         url_part += 1;
+                        pool.recycle(chunk);
                     }
                     Err(err) => {
                         error!("Error reading file: {}", err);
@@ -224,26 +254,7 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
             Ok(n) => {
                 buffer.truncate(n);
                 let chunk = bytes::Bytes::from(buffer);
-                let stream: async_stream::__private::AsyncStream<
-                    Result<bytes::Bytes, std::io::Error>,
-                    _,
-                > = async_stream::stream! {
-                    let mut buffer = Vec::new();
-                    let mut bytes_read = 0;
-
-                    for byte in chunk.iter() {
-                    buffer.push(*byte);
-                    bytes_read += 1;
-                    if buffer.len() == 1024 || bytes_read == chunk.len() {
-                    if let Some(callback) = cb.clone() {
-                        callback.call(buffer.len() as u64, fm.size);
-                                }
-                        yield Ok(bytes::Bytes::from(buffer.clone()));
-                        buffer.clear();
-                        }
-                    }
-
-                };
+                let stream = Self::create_stream(chunk.clone(), url_part, fm.size, cb);
 
                 let url_req = GeneratePresignedUrlsRequest::new(
                     n.try_into().map_err(|_| DracoonClientError::IoError)?,
@@ -258,19 +269,22 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
                 )
                 .await?;
 
-                let url = url.urls.first().expect("Creating S3 url failed");
-
-                let curr_pos: u64 = (url_part - 1) as u64 * (DEFAULT_CHUNK_SIZE as u64);
+                let url = url.urls.first().ok_or_else(|| {
+                    error!("No presigned url returned for part {}", url_part);
+                    DracoonClientError::MissingS3Url(url_part)
+                })?;
 
                 let e_tag = self
                     .upload_stream_to_s3(
                         Box::pin(stream),
                         url,
                         n.try_into().map_err(|_| DracoonClientError::IoError)?,
+                        rate_limit.as_ref(),
                     )
                     .await?;
 
-                s3_parts.push(S3FileUploadPart::new(url_part, e_tag));
+                verify_e_tag(&chunk, &e_tag, url_part)?;
+                s3_parts.push(S3FileUploadPart::new(url_part, e_tag.value));
             }
             Err(err) => {
                 error!("Error reading file: {}", err);
@@ -293,7 +307,8 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
         // return node if upload is done
         // return error if upload failed
         // polling with exponential backoff
-        let mut sleep_duration = POLLING_START_DELAY;
+        let started_at = Instant::now();
+        let mut sleep_duration = Duration::from_millis(POLLING_START_DELAY);
         loop {
             let status_response =
                 <PublicEndpoint<S> as PublicUploadInternal<R, S>>::get_upload_status(
@@ -315,8 +330,12 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
                     return Err(DracoonClientError::Http(response));
                 }
                 _ => {
-                    tokio::time::sleep(tokio::time::Duration::from_millis(sleep_duration)).await;
-                    sleep_duration *= 2;
+                    wait_for_upload_status(
+                        self.client().upload_polling(),
+                        started_at,
+                        &mut sleep_duration,
+                    )
+                    .await?;
                 }
             }
         }
@@ -332,58 +351,8 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
     ) -> Result<FileName, DracoonClientError> {
         let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
 
-        let mut crypto_buff = vec![
-            0u8;
-            upload_options
-                .file_meta
-                .size
-                .try_into()
-                .map_err(|_| DracoonClientError::IoError)?
-        ];
-        let mut read_buff = vec![
-            0u8;
-            upload_options
-                .file_meta
-                .size
-                .try_into()
-                .map_err(|_| DracoonClientError::IoError)?
-        ];
-        let mut crypter = DracoonCrypto::encrypter(&mut crypto_buff)?;
-
-        while let Ok(chunk) = reader.read(&mut read_buff).await {
-            if chunk == 0 {
-                break;
-            }
-            crypter.update(&read_buff[..chunk])?;
-        }
-        crypter.finalize()?;
-        // drop the read buffer after completing the encryption
-        drop(read_buff);
-
-        let enc_bytes = crypter.get_message().clone();
-
-        assert_eq!(enc_bytes.len() as u64, upload_options.file_meta.size);
-
-        let mut crypto_reader = BufReader::new(enc_bytes.as_slice());
-        let plain_file_key = crypter.get_plain_file_key();
-
-        // drop the crypto buffer (enc bytes are still in the reader)
-        drop(crypto_buff);
-
-        let public_keys = share.user_user_public_key_list.clone().unwrap_or_default();
-
-        let user_file_keys: Vec<_> = public_keys
-            .items
-            .iter()
-            .flat_map(|key| {
-                DracoonCrypto::encrypt_file_key(
-                    plain_file_key.clone(),
-                    key.public_key_container.clone(),
-                )
-                .map(|file_key| UserFileKey::new(key.id, file_key))
-                .into_iter()
-            })
-            .collect();
+        // encrypt chunk by chunk while uploading (encrypted size equals plain size)
+        let encrypter = BlockingEncrypter::new(upload_options.file_key.as_ref())?;
 
         let fm = upload_options.file_meta.clone();
 
@@ -409,70 +378,66 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
         let mut url_part: u32 = 1;
 
         let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+        let rate_limit = upload_options.max_rate.map(RateLimiter::new);
+
+        let mut urls: HashMap<u32, PresignedUrl> = HashMap::new();
+
+        let pool = BufferPool::new(1);
 
         if count_urls > 1 {
             while url_part < count_urls {
-                let mut buffer = vec![0; chunk_size];
+                let mut buffer = pool.get(chunk_size);
+                buffer.resize(chunk_size, 0);
                 let cb = cloneable_callback.clone();
                 let fm = fm.clone();
 
-                match crypto_reader.read_exact(&mut buffer).await {
+                match reader.read_exact(&mut buffer).await {
                     Ok(0) => break,
                     Ok(n) => {
                         let chunk_len = n;
                         buffer.truncate(chunk_len);
-                        let chunk = bytes::Bytes::from(buffer);
-
-                        let stream: async_stream::__private::AsyncStream<
-                            Result<bytes::Bytes, std::io::Error>,
-                            _,
-                        > = async_stream::stream! {
-                            let mut buffer = Vec::new();
-                            let mut bytes_read = 0;
-
-                            for byte in chunk.iter() {
-                            buffer.push(*byte);
-                            bytes_read += 1;
-                            if buffer.len() == 1024 || bytes_read == chunk.len() {
-                            if let Some(callback) = cb.clone() {
-                                callback.call(buffer.len() as u64, fm.size);
-                                        }
-                                yield Ok(bytes::Bytes::from(buffer.clone()));
-                                buffer.clear();
-                                }
-                            }
-                        };
-
-                        let url_req = GeneratePresignedUrlsRequest::new(
-                            chunk_len
-                                .try_into()
-                                .map_err(|_| DracoonClientError::IoError)?,
-                            url_part,
-                            url_part,
-                        );
-                        let url =
-                             <PublicEndpoint<S> as PublicUploadInternal<R, S>>::create_s3_upload_urls::<
-                                '_,
-                                '_,
-                            >(
-                                self, access_key.clone(), upload_channel.upload_id.clone(), url_req
+                        let chunk = bytes::Bytes::from(encrypter.encrypt(buffer, false).await?);
+
+                        let stream = Self::create_stream(chunk.clone(), url_part, fm.size, cb);
+
+                        // request presigned urls for the next full chunks in one batch
+                        if !urls.contains_key(&url_part) {
+                            let last_part_number =
+                                (url_part + PRESIGNED_URLS_BATCH - 1).min(count_urls - 1);
+                            let url_req = GeneratePresignedUrlsRequest::new(
+                                n.try_into().map_err(|_| DracoonClientError::IoError)?,
+                                url_part,
+                                last_part_number,
+                            );
+                            let presigned_urls = <PublicEndpoint<S> as PublicUploadInternal<
+                                R,
+                                S,
+                            >>::create_s3_upload_urls(
+                                self,
+                                access_key.clone(),
+                                upload_channel.upload_id.clone(),
+                                url_req,
                             )
                             .await
                             .map_err(|err| {
                                 error!("Error creating S3 upload urls: {}", err);
                                 err
                             })?;
-                        let url = url.urls.first().expect("Creating S3 url failed");
-
-                        let curr_pos: u64 = (url_part - 1) as u64 * (chunk_size as u64);
+                            urls.extend((url_part..=last_part_number).zip(presigned_urls.urls));
+                        }
+                        let url = urls.remove(&url_part).ok_or_else(|| {
+                            error!("No presigned url returned for part {}", url_part);
+                            DracoonClientError::MissingS3Url(url_part)
+                        })?;
 
                         let e_tag = self
                             .upload_stream_to_s3(
                                 Box::pin(stream),
-                                url,
+                                &url,
                                 chunk_len
                                     .try_into()
                                     .map_err(|_| DracoonClientError::IoError)?,
+                                rate_limit.as_ref(),
                             )
                             .await
                             .map_err(|err| {
@@ -480,8 +445,10 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
                                 err
                             })?;
 
-                        s3_parts.push(S3FileUploadPart::new(url_part, e_tag));
+                        verify_e_tag(&chunk, &e_tag, url_part)?;
+                        s3_parts.push(S3FileUploadPart::new(url_part, e_tag.value));
                         url_part += 1;
+                        pool.recycle(chunk);
                     }
                     Err(err) => return Err(DracoonClientError::IoError),
                 }
@@ -496,30 +463,12 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
                 .map_err(|_| DracoonClientError::IoError)?
         ];
         let cb = cloneable_callback.clone();
-        match crypto_reader.read_exact(&mut buffer).await {
+        match reader.read_exact(&mut buffer).await {
             Ok(n) => {
                 buffer.truncate(n);
-                let chunk = bytes::Bytes::from(buffer);
-                let stream: async_stream::__private::AsyncStream<
-                    Result<bytes::Bytes, std::io::Error>,
-                    _,
-                > = async_stream::stream! {
-                    let mut buffer = Vec::new();
-                    let mut bytes_read = 0;
-
-                    for byte in chunk.iter() {
-                    buffer.push(*byte);
-                    bytes_read += 1;
-                    if buffer.len() == 1024 || bytes_read == chunk.len() {
-                    if let Some(callback) = cb.clone() {
-                        callback.call(buffer.len() as u64, fm.size);
-                                }
-                        yield Ok(bytes::Bytes::from(buffer.clone()));
-                        buffer.clear();
-                        }
-                    }
-
-                };
+                let encrypted = encrypter.encrypt(buffer, true).await?;
+                let chunk = bytes::Bytes::from(encrypted);
+                let stream = Self::create_stream(chunk.clone(), url_part, fm.size, cb);
 
                 let url_req = GeneratePresignedUrlsRequest::new(
                     n.try_into().map_err(|_| DracoonClientError::IoError)?,
@@ -542,17 +491,17 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
                         err
                     })?;
 
-                let url = url.urls.first().expect("Creating S3 url failed");
-
-                // truncation is safe because chunk_size is 32 MB
-                #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
-                let curr_pos: u64 = ((url_part - 1) * (DEFAULT_CHUNK_SIZE as u32)) as u64;
+                let url = url.urls.first().ok_or_else(|| {
+                    error!("No presigned url returned for part {}", url_part);
+                    DracoonClientError::MissingS3Url(url_part)
+                })?;
 
                 let e_tag = self
                     .upload_stream_to_s3(
                         Box::pin(stream),
                         url,
                         n.try_into().map_err(|_| DracoonClientError::IoError)?,
+                        rate_limit.as_ref(),
                     )
                     .await
                     .map_err(|err| {
@@ -560,7 +509,8 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
                         err
                     })?;
 
-                s3_parts.push(S3FileUploadPart::new(url_part, e_tag));
+                verify_e_tag(&chunk, &e_tag, url_part)?;
+                s3_parts.push(S3FileUploadPart::new(url_part, e_tag.value));
             }
 
             Err(err) => {
@@ -569,6 +519,32 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
             }
         }
 
+        // file key is available after encrypting the last chunk
+        let plain_file_key = encrypter.plain_file_key()?;
+
+        let public_keys = match &upload_options.public_keys {
+            Some(public_keys) => public_keys.clone(),
+            None => {
+                share
+                    .user_user_public_key_list
+                    .clone()
+                    .unwrap_or_default()
+                    .items
+            }
+        };
+
+        let user_file_keys: Vec<_> = public_keys
+            .iter()
+            .flat_map(|key| {
+                DracoonCrypto::encrypt_file_key(
+                    plain_file_key.clone(),
+                    key.public_key_container.clone(),
+                )
+                .map(|file_key| UserFileKey::new(key.id, file_key))
+                .into_iter()
+            })
+            .collect();
+
         // finalize upload
         let complete_upload_req = CompleteS3ShareUploadRequest::new(s3_parts, Some(user_file_keys));
 
@@ -588,7 +564,8 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
         // return node if upload is done
         // return error if upload failed
         // polling with exponential backoff
-        let mut sleep_duration = POLLING_START_DELAY;
+        let started_at = Instant::now();
+        let mut sleep_duration = Duration::from_millis(POLLING_START_DELAY);
         loop {
             let status_response =
                 <PublicEndpoint<S> as PublicUploadInternal<R, S>>::get_upload_status(
@@ -614,8 +591,12 @@ impl<S: Send + Sync, R: AsyncRead + Send + Sync + Unpin + 'static> PublicUploadI
                     ));
                 }
                 _ => {
-                    tokio::time::sleep(tokio::time::Duration::from_millis(sleep_duration)).await;
-                    sleep_duration *= 2;
+                    wait_for_upload_status(
+                        self.client().upload_polling(),
+                        started_at,
+                        &mut sleep_duration,
+                    )
+                    .await?;
                 }
             }
         }
@@ -782,10 +763,14 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
         let mut chunk_part: u32 = 1;
 
         let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+        let rate_limit = upload_options.max_rate.map(RateLimiter::new);
+
+        let pool = BufferPool::new(1);
 
         if count_chunks > 1 {
             while chunk_part < count_chunks {
-                let mut buffer = vec![0; chunk_size];
+                let mut buffer = pool.get(chunk_size);
+                buffer.resize(chunk_size, 0);
                 let cb = cloneable_callback.clone();
                 let fm = fm.clone();
 
@@ -795,31 +780,11 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
                         buffer.truncate(n);
                         let chunk = bytes::Bytes::from(buffer);
 
-                        let stream: async_stream::__private::AsyncStream<
-                            Result<bytes::Bytes, std::io::Error>,
-                            _,
-                        > = async_stream::stream! {
-                            let mut buffer = Vec::new();
-                            let mut bytes_read = 0;
-
-                            for byte in chunk.iter() {
-                            buffer.push(*byte);
-                            bytes_read += 1;
-                            if buffer.len() == 1024 || bytes_read == chunk.len() {
-                            if let Some(callback) = cb.clone() {
-                                callback.call(buffer.len() as u64, fm.size);
-                                        }
-                                yield Ok(bytes::Bytes::from(buffer.clone()));
-                                buffer.clear();
-                                }
-                            }
-                        };
+                        let stream = Self::create_stream(chunk.clone(), chunk_part, fm.size, cb);
 
                         let url = upload_channel.upload_url.clone();
 
-                        // truncation is safe because chunk_size is 32 MB
-                        #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
-                        let curr_pos: u64 = ((chunk_part - 1) * (chunk_size as u32)) as u64;
+                        let curr_pos = chunk_offset(chunk_part, chunk_size);
 
                         self.upload_stream_to_nfs(
                             Box::pin(stream),
@@ -827,10 +792,12 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
                             upload_options.file_meta.size,
                             n,
                             Some(curr_pos),
+                            rate_limit.as_ref(),
                         )
                         .await?;
 
                         chunk_part += 1;
+                        pool.recycle(chunk);
                     }
                     Err(err) => {
                         error!("Error reading file: {}", err);
@@ -849,43 +816,26 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
         ];
         let cb = cloneable_callback.clone();
         match reader.read_exact(&mut buffer).await {
+            // empty files are finalized without uploading any content
+            Ok(0) => debug!("Empty file - skipping content upload"),
             Ok(n) => {
                 buffer.truncate(n);
                 let chunk = bytes::Bytes::from(buffer);
-                let stream: async_stream::__private::AsyncStream<
-                    Result<bytes::Bytes, std::io::Error>,
-                    _,
-                > = async_stream::stream! {
-                    let mut buffer = Vec::new();
-                    let mut bytes_read = 0;
-
-                    for byte in chunk.iter() {
-                    buffer.push(*byte);
-                    bytes_read += 1;
-                    if buffer.len() == 1024 || bytes_read == chunk.len() {
-                    if let Some(callback) = cb.clone() {
-                        callback.call(buffer.len() as u64, fm.size);
-                                }
-                        yield Ok(bytes::Bytes::from(buffer.clone()));
-                        buffer.clear();
-                        }
-                    }
-
-                };
+                let stream = Self::create_stream(chunk, chunk_part, fm.size, cb);
 
                 let url = upload_channel.upload_url.clone();
 
-                let curr_pos: u64 = (chunk_part - 1) as u64 * (DEFAULT_CHUNK_SIZE as u64);
+                let curr_pos = chunk_offset(chunk_part, chunk_size);
 
-                let e_tag = self
-                    .upload_stream_to_nfs(
-                        Box::pin(stream),
-                        &url,
-                        upload_options.file_meta.size,
-                        n,
-                        Some(curr_pos),
-                    )
-                    .await?;
+                self.upload_stream_to_nfs(
+                    Box::pin(stream),
+                    &url,
+                    upload_options.file_meta.size,
+                    n,
+                    Some(curr_pos),
+                    rate_limit.as_ref(),
+                )
+                .await?;
             }
             Err(err) => {
                 error!("Error reading file: {}", err);
@@ -919,61 +869,8 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
     ) -> Result<FileName, DracoonClientError> {
         let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
 
-        let mut crypto_buff = vec![
-            0u8;
-            upload_options
-                .file_meta
-                .size
-                .try_into()
-                .map_err(|_| DracoonClientError::IoError)?
-        ];
-        let mut read_buff = vec![
-            0u8;
-            upload_options
-                .file_meta
-                .size
-                .try_into()
-                .map_err(|_| DracoonClientError::IoError)?
-        ];
-        let mut crypter = DracoonCrypto::encrypter(&mut crypto_buff)?;
-
-        while let Ok(chunk) = reader.read(&mut read_buff).await {
-            if chunk == 0 {
-                break;
-            }
-            crypter.update(&read_buff[..chunk])?;
-        }
-        crypter.finalize()?;
-        // drop the read buffer after completing the encryption
-        drop(read_buff);
-
-        //TODO: rewrite without buffer clone
-        let enc_bytes = crypter.get_message().clone();
-
-        assert_eq!(enc_bytes.len() as u64, upload_options.file_meta.size);
-
-        let mut crypto_reader = BufReader::new(enc_bytes.as_slice());
-        let plain_file_key = crypter.get_plain_file_key();
-
-        // drop the crypto buffer (enc bytes are still in the reader)
-        drop(crypto_buff);
-
-        let public_keys = share.user_user_public_key_list.clone().unwrap_or_default();
-
-        let user_file_keys: Vec<_> = public_keys
-            .items
-            .iter()
-            .flat_map(|key| {
-                DracoonCrypto::encrypt_file_key(
-                    plain_file_key.clone(),
-                    key.public_key_container.clone(),
-                )
-                .map(|file_key| UserFileKey::new(key.id, file_key))
-                .into_iter()
-            })
-            .collect();
-
-        let user_file_keys = UserFileKeyList::from(user_file_keys);
+        // encrypt chunk by chunk while uploading (encrypted size equals plain size)
+        let encrypter = BlockingEncrypter::new(upload_options.file_key.as_ref())?;
 
         let fm = upload_options.file_meta.clone();
 
@@ -997,43 +894,29 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
         let mut chunk_part: u32 = 1;
 
         let cloneable_callback = callback.map(CloneableUploadProgressCallback::new);
+        let rate_limit = upload_options.max_rate.map(RateLimiter::new);
+
+        let pool = BufferPool::new(1);
 
         if count_chunks > 1 {
             while chunk_part < count_chunks {
-                let mut buffer = vec![0; chunk_size];
+                let mut buffer = pool.get(chunk_size);
+                buffer.resize(chunk_size, 0);
                 let cb = cloneable_callback.clone();
                 let fm = fm.clone();
 
-                match crypto_reader.read_exact(&mut buffer).await {
+                match reader.read_exact(&mut buffer).await {
                     Ok(0) => break,
                     Ok(n) => {
                         let chunk_len = n;
                         buffer.truncate(chunk_len);
-                        let chunk = bytes::Bytes::from(buffer);
+                        let chunk = bytes::Bytes::from(encrypter.encrypt(buffer, false).await?);
 
-                        let stream: async_stream::__private::AsyncStream<
-                            Result<bytes::Bytes, std::io::Error>,
-                            _,
-                        > = async_stream::stream! {
-                            let mut buffer = Vec::new();
-                            let mut bytes_read = 0;
-
-                            for byte in chunk.iter() {
-                            buffer.push(*byte);
-                            bytes_read += 1;
-                            if buffer.len() == 1024 || bytes_read == chunk.len() {
-                            if let Some(callback) = cb.clone() {
-                                callback.call(buffer.len() as u64, fm.size);
-                                        }
-                                yield Ok(bytes::Bytes::from(buffer.clone()));
-                                buffer.clear();
-                                }
-                            }
-                        };
+                        let stream = Self::create_stream(chunk.clone(), chunk_part, fm.size, cb);
 
                         let url = upload_channel.upload_url.clone();
 
-                        let curr_pos: u64 = (chunk_part - 1) as u64 * (chunk_size as u64);
+                        let curr_pos = chunk_offset(chunk_part, chunk_size);
 
                         self.upload_stream_to_nfs(
                             Box::pin(stream),
@@ -1041,6 +924,7 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
                             upload_options.file_meta.size,
                             chunk_len,
                             Some(curr_pos),
+                            rate_limit.as_ref(),
                         )
                         .await
                         .map_err(|err| {
@@ -1049,6 +933,7 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
                         })?;
 
                         chunk_part += 1;
+                        pool.recycle(chunk);
                     }
                     Err(err) => return Err(DracoonClientError::IoError),
                 }
@@ -1063,49 +948,36 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
                 .map_err(|_| DracoonClientError::IoError)?
         ];
         let cb = cloneable_callback.clone();
-        match crypto_reader.read_exact(&mut buffer).await {
+        match reader.read_exact(&mut buffer).await {
             Ok(n) => {
                 buffer.truncate(n);
-                let chunk = bytes::Bytes::from(buffer);
-                let stream: async_stream::__private::AsyncStream<
-                    Result<bytes::Bytes, std::io::Error>,
-                    _,
-                > = async_stream::stream! {
-                    let mut buffer = Vec::new();
-                    let mut bytes_read = 0;
-
-                    for byte in chunk.iter() {
-                    buffer.push(*byte);
-                    bytes_read += 1;
-                    if buffer.len() == 1024 || bytes_read == chunk.len() {
-                    if let Some(callback) = cb.clone() {
-                        callback.call(buffer.len() as u64, fm.size);
-                                }
-                        yield Ok(bytes::Bytes::from(buffer.clone()));
-                        buffer.clear();
-                        }
-                    }
+                let encrypted = encrypter.encrypt(buffer, true).await?;
 
-                };
+                // empty files are finalized without uploading any content (file key is required)
+                if n == 0 {
+                    debug!("Empty file - skipping content upload");
+                } else {
+                    let chunk = bytes::Bytes::from(encrypted);
+                    let stream = Self::create_stream(chunk, chunk_part, fm.size, cb);
 
-                let url = upload_channel.upload_url.clone();
+                    let url = upload_channel.upload_url.clone();
 
-                // truncation is safe because chunk_size is 32 MB
-                #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
-                let curr_pos: u64 = ((chunk_part - 1) * (DEFAULT_CHUNK_SIZE as u32)) as u64;
+                    let curr_pos = chunk_offset(chunk_part, chunk_size);
 
-                self.upload_stream_to_nfs(
-                    Box::pin(stream),
-                    &url,
-                    upload_options.file_meta.size,
-                    n,
-                    Some(curr_pos),
-                )
-                .await
-                .map_err(|err| {
-                    error!("Error uploading stream to NFS: {}", err);
-                    err
-                })?;
+                    self.upload_stream_to_nfs(
+                        Box::pin(stream),
+                        &url,
+                        upload_options.file_meta.size,
+                        n,
+                        Some(curr_pos),
+                        rate_limit.as_ref(),
+                    )
+                    .await
+                    .map_err(|err| {
+                        error!("Error uploading stream to NFS: {}", err);
+                        err
+                    })?;
+                }
             }
 
             Err(err) => {
@@ -1114,6 +986,34 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
             }
         }
 
+        // file key is available after encrypting the last chunk
+        let plain_file_key = encrypter.plain_file_key()?;
+
+        let public_keys = match &upload_options.public_keys {
+            Some(public_keys) => public_keys.clone(),
+            None => {
+                share
+                    .user_user_public_key_list
+                    .clone()
+                    .unwrap_or_default()
+                    .items
+            }
+        };
+
+        let user_file_keys: Vec<_> = public_keys
+            .iter()
+            .flat_map(|key| {
+                DracoonCrypto::encrypt_file_key(
+                    plain_file_key.clone(),
+                    key.public_key_container.clone(),
+                )
+                .map(|file_key| UserFileKey::new(key.id, file_key))
+                .into_iter()
+            })
+            .collect();
+
+        let user_file_keys = UserFileKeyList::from(user_file_keys);
+
         let public_upload =
             <PublicEndpoint<S> as PublicUploadInternalNfs<R, S>>::finalize_nfs_upload::<'_, '_>(
                 self,
@@ -1160,6 +1060,171 @@ impl<R: AsyncRead + Send + Sync + Unpin + 'static, S: Send + Sync> PublicUploadI
 
 #[cfg(test)]
 mod tests {
+    use std::io::Cursor;
+
+    use crate::{nodes::FileMeta, Dracoon};
+
+    use super::*;
 
     // TODO: write unit tests for public upload
+
+    #[tokio::test]
+    async fn test_get_upload_status() {
+        let mut mock_server = mockito::Server::new_async().await;
+
+        let client = Dracoon::builder()
+            .with_base_url(mock_server.url())
+            .with_client_id("client_id")
+            .with_client_secret("client_secret")
+            .build()
+            .unwrap();
+
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/public/shares/uploads/test/string")
+            .with_status(200)
+            .with_body(
+                serde_json::json!({
+                    "status": "done",
+                    "fileName": "test.txt",
+                    "size": 16
+                })
+                .to_string(),
+            )
+            .with_header("content-type", "application/json")
+            .create();
+
+        let status =
+            <PublicEndpoint<_> as PublicUploadInternal<Cursor<Vec<u8>>, _>>::get_upload_status(
+                client.public(),
+                "test".to_string(),
+                "string".to_string(),
+            )
+            .await
+            .unwrap();
+
+        status_mock.assert();
+
+        assert_eq!(status.status, S3UploadStatus::Done);
+        assert_eq!(status.file_name, "test.txt");
+        assert_eq!(status.size, Some(16));
+        assert!(status.error_details.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_s3_unencrypted_batched_urls() {
+        let mut mock_server = mockito::Server::new_async().await;
+
+        let client = Dracoon::builder()
+            .with_base_url(mock_server.url())
+            .with_client_id("client_id")
+            .with_client_secret("client_secret")
+            .build()
+            .unwrap();
+
+        let share: PublicUploadShare = serde_json::from_str(include_str!(
+            "../tests/responses/public/upload_share_ok.json"
+        ))
+        .unwrap();
+
+        // 22 parts of 1 byte: more full chunks than PRESIGNED_URLS_BATCH
+        let reader = BufReader::new(Cursor::new(vec![0u8; 22]));
+        let upload_options =
+            UploadOptions::builder(FileMeta::builder("test.txt", 22).build()).build();
+
+        let channel_res = include_str!("../tests/responses/upload/upload_channel_ok.json");
+        let upload_channel_mock = mock_server
+            .mock("POST", "/api/v4/public/shares/uploads/test")
+            .with_status(201)
+            .with_body(channel_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // mock S3 urls (parts 1-20, part 21 and the last chunk)
+        let base_url = format!("{}/", mock_server.url());
+        let s3_urls_batch_res =
+            include_str!("../tests/responses/upload/s3_urls_batch_ok_with_placeholder.json");
+        let s3_urls_batch_res = s3_urls_batch_res.replace("$base_url/", &base_url);
+        let s3_urls_res =
+            include_str!("../tests/responses/upload/s3_urls_ok_with_placeholder.json");
+        let s3_urls_res = s3_urls_res.replace("$base_url/", &base_url);
+
+        let s3_urls_batch_mock = mock_server
+            .mock("POST", "/api/v4/public/shares/uploads/test/string/s3_urls")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "size": 1,
+                "firstPartNumber": 1,
+                "lastPartNumber": PRESIGNED_URLS_BATCH
+            })))
+            .with_status(201)
+            .with_body(s3_urls_batch_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let s3_urls_next_batch_mock = mock_server
+            .mock("POST", "/api/v4/public/shares/uploads/test/string/s3_urls")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "size": 1,
+                "firstPartNumber": 21,
+                "lastPartNumber": 21
+            })))
+            .with_status(201)
+            .with_body(&s3_urls_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let s3_urls_last_mock = mock_server
+            .mock("POST", "/api/v4/public/shares/uploads/test/string/s3_urls")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "size": 1,
+                "firstPartNumber": 22,
+                "lastPartNumber": 22
+            })))
+            .with_status(201)
+            .with_body(&s3_urls_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_mock = mock_server
+            .mock("PUT", "/upload_url")
+            .with_status(202)
+            .with_header("etag", "string")
+            .expect(22)
+            .create();
+
+        let finalize_mock = mock_server
+            .mock("PUT", "/api/v4/public/shares/uploads/test/string/s3")
+            .with_status(202)
+            .create();
+
+        let status_res = include_str!("../tests/responses/public/upload_status_ok.json");
+        let status_mock = mock_server
+            .mock("GET", "/api/v4/public/shares/uploads/test/string")
+            .with_status(200)
+            .with_body(status_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let file_name =
+            <PublicEndpoint<_> as PublicUploadInternal<Cursor<Vec<u8>>, _>>::upload_to_s3_unencrypted(
+                client.public(),
+                "test".to_string(),
+                &share,
+                upload_options,
+                reader,
+                None,
+                Some(1),
+            )
+            .await
+            .unwrap();
+
+        upload_channel_mock.assert();
+        s3_urls_batch_mock.assert();
+        s3_urls_next_batch_mock.assert();
+        s3_urls_last_mock.assert();
+        upload_mock.assert();
+        finalize_mock.assert();
+        status_mock.assert();
+
+        assert_eq!(file_name, "test.txt");
+    }
 }
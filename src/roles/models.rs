@@ -50,7 +50,7 @@ pub struct RoleGroup {
 
 pub type RoleUserList = RangedItems<RoleUser>;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Right {
     pub id: u64,
@@ -58,7 +58,7 @@ pub struct Right {
     pub description: String,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Role {
     pub id: u64,
@@ -67,12 +67,67 @@ pub struct Role {
     pub items: Option<Vec<Right>>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl Role {
+    /// Returns the typed role - `None` for unknown roles
+    pub fn role_type(&self) -> Option<RoleType> {
+        RoleType::try_from(self.id).ok()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct RoleList {
     pub items: Vec<Role>,
 }
 
+impl RoleList {
+    /// Checks if the list contains the given role
+    pub fn contains(&self, role: RoleType) -> bool {
+        self.items.iter().any(|item| item.id == role.id())
+    }
+}
+
+/// Global roles of DRACOON (the id is fixed for every instance)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoleType {
+    ConfigManager,
+    UserManager,
+    GroupManager,
+    RoomManager,
+    LogAuditor,
+    NonMemberViewer,
+}
+
+impl RoleType {
+    pub fn id(&self) -> u64 {
+        match self {
+            RoleType::ConfigManager => 1,
+            RoleType::UserManager => 2,
+            RoleType::GroupManager => 3,
+            RoleType::RoomManager => 4,
+            RoleType::LogAuditor => 5,
+            RoleType::NonMemberViewer => 6,
+        }
+    }
+}
+
+/// Fails with the passed id for unknown roles
+impl TryFrom<u64> for RoleType {
+    type Error = u64;
+
+    fn try_from(id: u64) -> Result<Self, Self::Error> {
+        match id {
+            1 => Ok(RoleType::ConfigManager),
+            2 => Ok(RoleType::UserManager),
+            3 => Ok(RoleType::GroupManager),
+            4 => Ok(RoleType::RoomManager),
+            5 => Ok(RoleType::LogAuditor),
+            6 => Ok(RoleType::NonMemberViewer),
+            _ => Err(id),
+        }
+    }
+}
+
 #[async_trait]
 impl FromResponse for RoleList {
     async fn from_response(response: Response) -> Result<Self, DracoonClientError> {
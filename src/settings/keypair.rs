@@ -1,14 +1,15 @@
 use async_trait::async_trait;
 use dco3_crypto::{
-    DracoonCrypto, DracoonRSACrypto, PlainUserKeyPairContainer, UserKeyPairContainer,
+    DracoonCrypto, DracoonRSACrypto, FileKey, PlainUserKeyPairContainer, UserKeyPairContainer,
 };
 use reqwest::header;
+use tracing::error;
 
 use crate::{
     client::Connected,
     constants::{
-        DRACOON_API_PREFIX, FILES_BASE, FILES_KEYS, MISSING_FILE_KEYS, NODES_BASE, SETTINGS_BASE,
-        SETTINGS_KEYPAIR,
+        DRACOON_API_PREFIX, FILES_BASE, FILES_KEYS, MISSING_FILE_KEYS, NODES_BASE, ROOMS_BASE,
+        ROOMS_KEYPAIR, SETTINGS_BASE, SETTINGS_KEYPAIR,
     },
     nodes::{MissingKeysResponse, UseKey, UserFileKeySetBatchRequest},
     utils::FromResponse,
@@ -30,31 +31,106 @@ impl RescueKeyPair for SettingsEndpoint<Connected> {
     ) -> Result<u64, DracoonClientError> {
         let keypair = self.get_system_rescue_keypair(rescue_key_secret).await?;
 
-        let missing_keys = self
-            .get_missing_file_keys(room_id, file_id, user_id, None)
+        self.distribute_missing_keys_with_keypair(
+            &keypair,
+            UseKey::SystemRescueKey,
+            room_id,
+            file_id,
+            user_id,
+        )
+        .await
+    }
+
+    async fn distribute_missing_room_keys(
+        &self,
+        rescue_key_secret: &str,
+        room_id: u64,
+        file_id: Option<u64>,
+        user_id: Option<u64>,
+    ) -> Result<u64, DracoonClientError> {
+        let keypair = self
+            .get_room_rescue_keypair(room_id, rescue_key_secret)
             .await?;
 
-        let remaining_keys = if missing_keys.range.is_none() {
-            0
-        } else {
-            missing_keys.range.as_ref().unwrap().total
-        };
+        self.distribute_missing_keys_with_keypair(
+            &keypair,
+            UseKey::RoomRescueKey,
+            Some(room_id),
+            file_id,
+            user_id,
+        )
+        .await
+    }
 
-        let key_reqs =
-            UserFileKeySetBatchRequest::try_new_from_missing_keys(missing_keys, &keypair)?;
+    async fn get_system_rescue_keypair(
+        &self,
+        secret: &str,
+    ) -> Result<PlainUserKeyPairContainer, DracoonClientError> {
+        let url_part = format!("{DRACOON_API_PREFIX}/{SETTINGS_BASE}/{SETTINGS_KEYPAIR}",);
 
-        if !key_reqs.is_empty() {
-            self.set_file_keys(key_reqs).await?;
-        }
+        let api_url = self.client().build_api_url(&url_part);
 
-        Ok(remaining_keys)
+        let response = self
+            .client()
+            .http
+            .get(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .send()
+            .await?;
+
+        let keypair = UserKeyPairContainer::from_response(response).await?;
+
+        let keypair = DracoonCrypto::decrypt_keypair(secret, keypair)?;
+
+        Ok(keypair)
+    }
+
+    async fn get_room_rescue_keypair(
+        &self,
+        room_id: u64,
+        secret: &str,
+    ) -> Result<PlainUserKeyPairContainer, DracoonClientError> {
+        let url_part =
+            format!("{DRACOON_API_PREFIX}/{NODES_BASE}/{ROOMS_BASE}/{room_id}/{ROOMS_KEYPAIR}");
+
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .get(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .send()
+            .await?;
+
+        let keypair = UserKeyPairContainer::from_response(response).await?;
+
+        let keypair = DracoonCrypto::decrypt_keypair(secret, keypair)?;
+
+        Ok(keypair)
     }
 }
 
 #[async_trait]
-trait RescueKeypairInternal {
+pub(crate) trait RescueKeypairInternal {
+    async fn distribute_missing_keys_with_keypair(
+        &self,
+        keypair: &PlainUserKeyPairContainer,
+        use_key: UseKey,
+        room_id: Option<u64>,
+        file_id: Option<u64>,
+        user_id: Option<u64>,
+    ) -> Result<u64, DracoonClientError>;
+
     async fn get_missing_file_keys(
         &self,
+        use_key: UseKey,
         room_id: Option<u64>,
         file_id: Option<u64>,
         user_id: Option<u64>,
@@ -66,16 +142,48 @@ trait RescueKeypairInternal {
         req: UserFileKeySetBatchRequest,
     ) -> Result<(), DracoonClientError>;
 
-    async fn get_system_rescue_keypair(
+    /// Returns the file key of a file encrypted for the rescue key - only available
+    /// if the file key of any user is missing (e.g. the current user)
+    async fn get_rescue_file_key(
         &self,
-        secret: &str,
-    ) -> Result<PlainUserKeyPairContainer, DracoonClientError>;
+        use_key: UseKey,
+        file_id: u64,
+    ) -> Result<FileKey, DracoonClientError>;
 }
 
 #[async_trait]
 impl RescueKeypairInternal for SettingsEndpoint<Connected> {
+    async fn distribute_missing_keys_with_keypair(
+        &self,
+        keypair: &PlainUserKeyPairContainer,
+        use_key: UseKey,
+        room_id: Option<u64>,
+        file_id: Option<u64>,
+        user_id: Option<u64>,
+    ) -> Result<u64, DracoonClientError> {
+        let missing_keys = self
+            .get_missing_file_keys(use_key, room_id, file_id, user_id, None)
+            .await?;
+
+        let remaining_keys = if missing_keys.range.is_none() {
+            0
+        } else {
+            missing_keys.range.as_ref().unwrap().total
+        };
+
+        let key_reqs =
+            UserFileKeySetBatchRequest::try_new_from_missing_keys(missing_keys, keypair)?;
+
+        if !key_reqs.is_empty() {
+            self.set_file_keys(key_reqs).await?;
+        }
+
+        Ok(remaining_keys)
+    }
+
     async fn get_missing_file_keys(
         &self,
+        use_key: UseKey,
         room_id: Option<u64>,
         file_id: Option<u64>,
         user_id: Option<u64>,
@@ -90,7 +198,7 @@ impl RescueKeypairInternal for SettingsEndpoint<Connected> {
 
         let sorts = params.sort_to_string();
 
-        let rescue_key: String = UseKey::SystemRescueKey.into();
+        let rescue_key: String = use_key.into();
 
         api_url
             .query_pairs_mut()
@@ -146,30 +254,24 @@ impl RescueKeypairInternal for SettingsEndpoint<Connected> {
         Ok(())
     }
 
-    async fn get_system_rescue_keypair(
+    async fn get_rescue_file_key(
         &self,
-        secret: &str,
-    ) -> Result<PlainUserKeyPairContainer, DracoonClientError> {
-        let url_part = format!("{DRACOON_API_PREFIX}/{SETTINGS_BASE}/{SETTINGS_KEYPAIR}",);
-
-        let api_url = self.client().build_api_url(&url_part);
-
-        let response = self
-            .client()
-            .http
-            .get(api_url)
-            .header(
-                header::AUTHORIZATION,
-                self.client().get_auth_header().await?,
-            )
-            .send()
+        use_key: UseKey,
+        file_id: u64,
+    ) -> Result<FileKey, DracoonClientError> {
+        let missing_keys = self
+            .get_missing_file_keys(use_key, None, Some(file_id), None, None)
             .await?;
 
-        let keypair = UserKeyPairContainer::from_response(response).await?;
-
-        let keypair = DracoonCrypto::decrypt_keypair(secret, keypair)?;
-
-        Ok(keypair)
+        missing_keys
+            .files
+            .into_iter()
+            .find(|file| file.id == file_id)
+            .map(|file| file.file_key_container)
+            .ok_or_else(|| {
+                error!("No rescue file key available for file {}", file_id);
+                DracoonClientError::MissingEncryptionSecret
+            })
     }
 }
 
@@ -178,6 +280,7 @@ mod tests {
     use dco3_crypto::{DracoonCryptoError, FileKeyVersion, UserKeyPairVersion};
 
     use crate::{
+        nodes::UseKey,
         settings::{keypair::RescueKeypairInternal, RescueKeyPair},
         tests::dracoon::get_connected_client,
         DracoonClientError,
@@ -201,7 +304,7 @@ mod tests {
 
         let missing_keys = client
             .settings()
-            .get_missing_file_keys(None, None, None, None)
+            .get_missing_file_keys(UseKey::SystemRescueKey, None, None, None, None)
             .await
             .unwrap();
 
@@ -442,4 +545,61 @@ mod tests {
         missing_keys_mock.assert();
         keypair_mock.assert();
     }
+
+    #[tokio::test]
+    async fn test_get_room_rescue_keypair() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let response = include_str!("../tests/responses/keypair_ok.json");
+
+        let keypair_mock = mock_server
+            .mock("GET", "/api/v4/nodes/rooms/1/keypair")
+            .with_body(response)
+            .with_header("content-type", "application/json")
+            .with_status(200)
+            .create();
+
+        let keypair = client
+            .settings()
+            .get_room_rescue_keypair(1, "TopSecret1234!")
+            .await
+            .unwrap();
+
+        keypair_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_distribute_missing_room_keys() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let response = include_str!("../tests/responses/nodes/missing_file_keys_ok.json");
+        let keypair_response = include_str!("../tests/responses/keypair_ok.json");
+
+        let missing_keys_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/nodes/missingFileKeys?use_key=room_rescue_key&limit=100&offset=0&room_id=1",
+            )
+            .with_body(response)
+            .with_header("content-type", "application/json")
+            .with_status(200)
+            .create();
+
+        let keypair_mock = mock_server
+            .mock("GET", "/api/v4/nodes/rooms/1/keypair")
+            .with_body(keypair_response)
+            .with_header("content-type", "application/json")
+            .with_status(200)
+            .create();
+
+        let res = client
+            .settings()
+            .distribute_missing_room_keys("TopSecret1234!", 1, None, None)
+            .await;
+
+        assert!(res.is_ok());
+
+        missing_keys_mock.assert();
+        keypair_mock.assert();
+    }
 }
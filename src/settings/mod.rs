@@ -1,14 +1,17 @@
 use async_trait::async_trait;
+use dco3_crypto::PlainUserKeyPairContainer;
 
 use crate::DracoonClientError;
 
-mod keypair;
+pub(crate) mod keypair;
 mod models;
 
 pub use models::SettingsEndpoint;
 
 #[async_trait]
-/// This trait currently implements distributing missing keys uing the system rescue key.
+/// This trait implements distributing missing keys using the system or room rescue key
+/// and provides access to the (decrypted) rescue keypairs for recovery scenarios
+/// (e.g. to download files with [crate::Download::download_with_rescue_keypair]).
 pub trait RescueKeyPair {
     /// Distributes missing file keys using the rescue key.
     /// Returns the total amount missing keys.
@@ -52,4 +55,79 @@ pub trait RescueKeyPair {
         file_id: Option<u64>,
         user_id: Option<u64>,
     ) -> Result<u64, DracoonClientError>;
+    /// Distributes missing file keys of a room using the room rescue key.
+    /// Returns the total amount missing keys.
+    /// If the total amount is larger than 100, more keys need distribution
+    /// and the method should be called again.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, RescueKeyPair};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let mut missing_keys = dracoon.settings().distribute_missing_room_keys("room_rescue_key_secret", 123, None, None).await.unwrap();
+    ///
+    /// while missing_keys > 100 {
+    /// // loop until no more keys need distribution
+    /// missing_keys = dracoon.settings().distribute_missing_room_keys("room_rescue_key_secret", 123, None, None).await.unwrap();
+    /// }
+    /// # }
+    /// ```
+    async fn distribute_missing_room_keys(
+        &self,
+        rescue_key_secret: &str,
+        room_id: u64,
+        file_id: Option<u64>,
+        user_id: Option<u64>,
+    ) -> Result<u64, DracoonClientError>;
+    /// Gets the system rescue keypair and decrypts it with the given secret.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, RescueKeyPair};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let keypair = dracoon.settings().get_system_rescue_keypair("rescue_key_secret").await.unwrap();
+    /// # }
+    /// ```
+    async fn get_system_rescue_keypair(
+        &self,
+        secret: &str,
+    ) -> Result<PlainUserKeyPairContainer, DracoonClientError>;
+    /// Gets the rescue keypair of a room and decrypts it with the given secret.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, RescueKeyPair};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let keypair = dracoon.settings().get_room_rescue_keypair(123, "room_rescue_key_secret").await.unwrap();
+    /// # }
+    /// ```
+    async fn get_room_rescue_keypair(
+        &self,
+        room_id: u64,
+        secret: &str,
+    ) -> Result<PlainUserKeyPairContainer, DracoonClientError>;
 }
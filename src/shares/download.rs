@@ -1,13 +1,22 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
+use dco3_crypto::{DracoonCrypto, DracoonRSACrypto, UserKeyPairVersion};
+use futures_util::{stream, StreamExt};
 use reqwest::header;
+use tracing::error;
 
-use crate::constants::{DRACOON_API_PREFIX, SHARES_BASE, SHARES_DOWNLOAD, SHARES_EMAIL};
+use crate::constants::{
+    DEFAULT_SHARES_CONCURRENCY, DELETED_SHARES_BATCH, DRACOON_API_PREFIX, SHARES_BASE,
+    SHARES_DOWNLOAD, SHARES_EMAIL,
+};
 use crate::models::ListAllParams;
+use crate::nodes::download::DownloadInternal;
 use crate::utils::FromResponse;
-use crate::{client::Connected, DracoonClientError};
+use crate::{client::Connected, Dracoon, DracoonClientError};
 
 use super::models::*;
-use super::DownloadShares;
+use super::{DownloadShares, EncryptedDownloadShares};
 
 #[async_trait]
 impl DownloadShares for SharesEndpoint<Connected> {
@@ -84,23 +93,26 @@ impl DownloadShares for SharesEndpoint<Connected> {
 
         let api_url = self.client().build_api_url(&url_part);
 
-        let response = self
-            .client()
-            .http
-            .delete(api_url)
-            .header(
-                header::AUTHORIZATION,
-                self.client().get_auth_header().await?,
-            )
-            .header(header::CONTENT_TYPE, "application/json")
-            .json(&delete)
-            .send()
-            .await?;
-
-        if response.status().is_server_error() || response.status().is_client_error() {
-            return Err(DracoonClientError::from_response(response)
-                .await
-                .expect("Could not parse error response"));
+        // large lists are deleted in batches
+        for batch in delete.into_batches(DELETED_SHARES_BATCH) {
+            let response = self
+                .client()
+                .http
+                .delete(api_url.clone())
+                .header(
+                    header::AUTHORIZATION,
+                    self.client().get_auth_header().await?,
+                )
+                .header(header::CONTENT_TYPE, "application/json")
+                .json(&batch)
+                .send()
+                .await?;
+
+            if response.status().is_server_error() || response.status().is_client_error() {
+                return Err(DracoonClientError::from_response(response)
+                    .await
+                    .expect("Could not parse error response"));
+            }
         }
 
         Ok(())
@@ -110,6 +122,10 @@ impl DownloadShares for SharesEndpoint<Connected> {
         &self,
         create: CreateDownloadShareRequest,
     ) -> Result<DownloadShare, DracoonClientError> {
+        if let Some(expiration) = create.expiration() {
+            expiration.validate()?;
+        }
+
         let url_part = format!("{DRACOON_API_PREFIX}/{SHARES_BASE}/{SHARES_DOWNLOAD}");
 
         let api_url = self.client().build_api_url(&url_part);
@@ -130,6 +146,41 @@ impl DownloadShares for SharesEndpoint<Connected> {
         DownloadShare::from_response(response).await
     }
 
+    async fn create_download_shares(
+        &self,
+        node_ids: Vec<u64>,
+        options: CreateDownloadShareRequest,
+        max_concurrency: Option<usize>,
+    ) -> HashMap<u64, Result<DownloadShare, DracoonClientError>> {
+        let mut node_ids = node_ids;
+        node_ids.sort_unstable();
+        node_ids.dedup();
+
+        // a file key belongs to a single file and cannot be reused for other nodes
+        if options.is_encrypted() {
+            return node_ids
+                .into_iter()
+                .map(|node_id| {
+                    let err = DracoonClientError::InvalidShareOptions(
+                        "Keypair and file key cannot be shared by multiple nodes".into(),
+                    );
+                    (node_id, Err(err))
+                })
+                .collect();
+        }
+
+        let max_concurrency = max_concurrency.unwrap_or(DEFAULT_SHARES_CONCURRENCY).max(1);
+
+        stream::iter(node_ids)
+            .map(|node_id| {
+                let create = options.for_node(node_id);
+                async move { (node_id, self.create_download_share(create).await) }
+            })
+            .buffer_unordered(max_concurrency)
+            .collect()
+            .await
+    }
+
     async fn get_download_share(
         &self,
         download_share_id: u64,
@@ -250,3 +301,29 @@ impl DownloadShares for SharesEndpoint<Connected> {
         Ok(())
     }
 }
+
+#[async_trait]
+impl EncryptedDownloadShares for Dracoon<Connected> {
+    async fn create_encrypted_download_share(
+        &self,
+        create: CreateDownloadShareRequest,
+    ) -> Result<DownloadShare, DracoonClientError> {
+        let Some(password) = create.password() else {
+            error!("Share password is required for encrypted download shares");
+            return Err(DracoonClientError::MissingArgument);
+        };
+
+        let keypair = self.get_keypair(None).await?;
+        let file_key = self.get_file_key(create.node_id()).await?;
+        let plain_file_key = DracoonCrypto::decrypt_file_key(file_key, keypair)?;
+
+        let share_keypair = DracoonCrypto::create_plain_user_keypair(UserKeyPairVersion::RSA4096)?;
+        let share_file_key =
+            DracoonCrypto::encrypt_file_key(plain_file_key, share_keypair.clone())?;
+        let share_keypair = DracoonCrypto::encrypt_private_key(password, share_keypair)?;
+
+        let create = create.with_encryption(share_keypair, share_file_key);
+
+        self.shares().create_download_share(create).await
+    }
+}
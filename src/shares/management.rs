@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::{client::Connected, models::ListAllParams, utils::list_all, DracoonClientError};
+
+use super::{
+    models::*, DownloadShares, DownloadSharesFilter, ShareManagement, UploadShares,
+    UploadSharesFilter,
+};
+
+#[async_trait]
+impl ShareManagement for SharesEndpoint<Connected> {
+    async fn get_shares_for_node(&self, node_id: u64) -> Result<ShareSet, DracoonClientError> {
+        let (download_shares, upload_shares) = tokio::try_join!(
+            self.get_all_download_shares(Some(DownloadSharesFilter::node_id_equals(node_id))),
+            self.get_all_upload_shares(Some(UploadSharesFilter::target_id_equals(node_id)))
+        )?;
+
+        Ok(ShareSet {
+            download_shares,
+            upload_shares,
+        })
+    }
+
+    async fn prune_expired_shares(&self, dry_run: bool) -> Result<ShareSet, DracoonClientError> {
+        let (download_shares, upload_shares) = tokio::try_join!(
+            self.get_all_download_shares(None),
+            self.get_all_upload_shares(None)
+        )?;
+
+        let pruned = ShareSet {
+            download_shares: download_shares
+                .into_iter()
+                .filter(|share| share.is_expired() || share.is_exhausted())
+                .collect(),
+            upload_shares: upload_shares
+                .into_iter()
+                .filter(|share| share.is_expired() || share.is_exhausted())
+                .collect(),
+        };
+
+        if dry_run {
+            debug!("Dry run: {} shares would be removed", pruned.len());
+            return Ok(pruned);
+        }
+
+        if !pruned.download_shares.is_empty() {
+            let share_ids = pruned
+                .download_shares
+                .iter()
+                .map(|share| share.id)
+                .collect();
+            self.delete_download_shares(DeleteDownloadSharesRequest::new(share_ids))
+                .await?;
+        }
+
+        if !pruned.upload_shares.is_empty() {
+            let share_ids = pruned.upload_shares.iter().map(|share| share.id).collect();
+            self.delete_upload_shares(DeleteUploadSharesRequest::new(share_ids))
+                .await?;
+        }
+
+        debug!("Removed {} expired shares", pruned.len());
+
+        Ok(pruned)
+    }
+
+    async fn get_share_usage(&self) -> Result<ShareUsageReport, DracoonClientError> {
+        let (download_shares, upload_shares) = tokio::try_join!(
+            self.get_all_download_shares(None),
+            self.get_all_upload_shares(None)
+        )?;
+
+        Ok(ShareUsageReport::new(&download_shares, &upload_shares))
+    }
+}
+
+#[async_trait]
+pub(crate) trait SharesInternal {
+    /// Returns all download shares matching the filter (paginated)
+    async fn get_all_download_shares(
+        &self,
+        filter: Option<DownloadSharesFilter>,
+    ) -> Result<Vec<DownloadShare>, DracoonClientError>;
+
+    /// Returns all upload shares matching the filter (paginated)
+    async fn get_all_upload_shares(
+        &self,
+        filter: Option<UploadSharesFilter>,
+    ) -> Result<Vec<UploadShare>, DracoonClientError>;
+}
+
+#[async_trait]
+impl SharesInternal for SharesEndpoint<Connected> {
+    async fn get_all_download_shares(
+        &self,
+        filter: Option<DownloadSharesFilter>,
+    ) -> Result<Vec<DownloadShare>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_offset(offset)
+                .with_limit(limit);
+            let params = match &filter {
+                Some(filter) => params.with_filter(filter.clone()),
+                None => params,
+            };
+
+            self.get_download_shares(Some(params.build()))
+        })
+        .await
+    }
+
+    async fn get_all_upload_shares(
+        &self,
+        filter: Option<UploadSharesFilter>,
+    ) -> Result<Vec<UploadShare>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_offset(offset)
+                .with_limit(limit);
+            let params = match &filter {
+                Some(filter) => params.with_filter(filter.clone()),
+                None => params,
+            };
+
+            self.get_upload_shares(Some(params.build()))
+        })
+        .await
+    }
+}
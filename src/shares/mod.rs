@@ -1,14 +1,19 @@
 //! This module implements the shares DRACOON API.
 //! Documentation can be found here: <https://download.dracoon.com/api/swagger-ui/index.html?configUrl=/api/spec_v4/swagger-config#/shares>
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 pub use models::*;
 
-use crate::{models::ListAllParams, DracoonClientError};
+use crate::{models::ListAllParams, public::PublicUploadedFileData, DracoonClientError};
 
 mod download;
+mod management;
 mod models;
 mod upload;
 
+pub(crate) use management::SharesInternal;
+
 /// This trait provides all methods to manage download shares.
 #[async_trait]
 pub trait DownloadShares {
@@ -65,7 +70,7 @@ pub trait DownloadShares {
         &self,
         update: UpdateDownloadSharesBulkRequest,
     ) -> Result<(), DracoonClientError>;
-    /// Delete a list of shares (download shares).
+    /// Delete a list of shares (download shares) - large lists are deleted in batches of 100 shares.
     /// ```no_run
     /// # use dco3::{Dracoon, auth::OAuth2Flow, DownloadShares, shares::{DeleteDownloadSharesRequest}};
     /// # #[tokio::main]
@@ -115,6 +120,46 @@ pub trait DownloadShares {
         &self,
         create: CreateDownloadShareRequest,
     ) -> Result<DownloadShare, DracoonClientError>;
+    /// Creates download shares for multiple nodes with the same options (e.g. expiration, password).
+    /// The node id of the passed request is ignored - a share is created for each node id.
+    /// Duplicate node ids are removed (one share per node).
+    /// Shares are created with bounded concurrency (default: 5 parallel requests).
+    /// Returns the result (share or error) for each node id.
+    /// Options with a keypair or file key are rejected with [DracoonClientError::InvalidShareOptions]
+    /// for every node, since a file key belongs to a single file - use [EncryptedDownloadShares::create_encrypted_download_share] instead.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, DownloadShares, shares::{CreateDownloadShareRequest}};
+    /// # use chrono::{Duration, Utc};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let options = CreateDownloadShareRequest::builder(0)
+    ///     .with_expiration(Utc::now() + Duration::days(30))
+    ///     .with_password("VerySecret123!")
+    ///     .build();
+    /// let shares = dracoon.shares().create_download_shares(vec![1, 2, 3], options, None).await;
+    /// for (node_id, share) in shares {
+    ///     match share {
+    ///         Ok(share) => println!("Node {}: share {}", node_id, share.id),
+    ///         Err(err) => println!("Node {}: {}", node_id, err),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    async fn create_download_shares(
+        &self,
+        node_ids: Vec<u64>,
+        options: CreateDownloadShareRequest,
+        max_concurrency: Option<usize>,
+    ) -> HashMap<u64, Result<DownloadShare, DracoonClientError>>;
     /// Get download share
     /// ```no_run
     /// # use dco3::{Dracoon, auth::OAuth2Flow, DownloadShares};
@@ -190,8 +235,11 @@ pub trait DownloadShares {
     /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
     /// #  .await
     /// #  .unwrap();
-    /// let recipients = vec!["test@test.foo".to_string(), "test2@test.foo".to_string()];
-    /// let send_mail = DownloadShareLinkEmail::new("Test email", recipients, None);
+    /// let send_mail = DownloadShareLinkEmail::builder("Test email")
+    ///     .with_recipient("test@test.foo")
+    ///     .with_recipient("test2@test.foo")
+    ///     .with_receiver_language("en-US")
+    ///     .build();
     /// dracoon.shares().send_download_share_email(123, send_mail).await.unwrap();
     /// # }
     /// ```
@@ -202,6 +250,42 @@ pub trait DownloadShares {
     ) -> Result<(), DracoonClientError>;
 }
 
+/// This trait provides download shares for encrypted files.
+/// The file key is decrypted with the user keypair and encrypted for a share keypair
+/// protected by the share password - this requires the user keypair to be set
+/// (see [Dracoon::get_keypair](crate::Dracoon::get_keypair)).
+#[async_trait]
+pub trait EncryptedDownloadShares {
+    /// Create a download share for an encrypted file.
+    /// The share password is required and used to encrypt the share keypair.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, EncryptedDownloadShares, shares::{CreateDownloadShareRequest}};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// // the keypair needs to be set (once) with the encryption secret
+    /// let _ = dracoon.get_keypair(Some("secret".into())).await.unwrap();
+    /// let share = CreateDownloadShareRequest::builder(1)
+    ///     .with_name("test")
+    ///     .with_password("VerySecret123!")
+    ///     .build();
+    /// dracoon.create_encrypted_download_share(share).await.unwrap();
+    /// # }
+    /// ```
+    async fn create_encrypted_download_share(
+        &self,
+        create: CreateDownloadShareRequest,
+    ) -> Result<DownloadShare, DracoonClientError>;
+}
+
 /// This trait provides all methods to manage upload shares.
 #[async_trait]
 pub trait UploadShares {
@@ -259,7 +343,7 @@ pub trait UploadShares {
         &self,
         update: UpdateUploadSharesBulkRequest,
     ) -> Result<(), DracoonClientError>;
-    /// Delete a list of file requests (upload shares).
+    /// Delete a list of file requests (upload shares) - large lists are deleted in batches of 100 shares.
     /// ```no_run
     /// # use dco3::{Dracoon, auth::OAuth2Flow, UploadShares, shares::{DeleteUploadSharesRequest}};
     /// # #[tokio::main]
@@ -330,6 +414,29 @@ pub trait UploadShares {
         &self,
         upload_share_id: u64,
     ) -> Result<UploadShare, DracoonClientError>;
+    /// Get the files uploaded to an upload share (file request).
+    /// Files are only listed if `show_uploaded_files` is enabled for the share - otherwise
+    /// the list is empty.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, UploadShares};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let uploaded_files = dracoon.shares().get_upload_share_files(1).await.unwrap();
+    /// # }
+    /// ```
+    async fn get_upload_share_files(
+        &self,
+        upload_share_id: u64,
+    ) -> Result<Vec<PublicUploadedFileData>, DracoonClientError>;
     /// Update upload share
     /// ```no_run
     /// # use dco3::{Dracoon, auth::OAuth2Flow, UploadShares, shares::{UpdateUploadShareRequest}};
@@ -387,8 +494,11 @@ pub trait UploadShares {
     /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
     /// #  .await
     /// #  .unwrap();
-    /// let recipients = vec!["test@test.foo".to_string(), "test2@test.foo".to_string()];
-    /// let send_mail = UploadShareLinkEmail::new("Test email", recipients, None);
+    /// let send_mail = UploadShareLinkEmail::builder("Test email")
+    ///     .with_recipient("test@test.foo")
+    ///     .with_recipient("test2@test.foo")
+    ///     .with_receiver_language("en-US")
+    ///     .build();
     /// dracoon.shares().send_upload_share_email(123, send_mail).await.unwrap();
     /// # }
     /// ```
@@ -398,3 +508,81 @@ pub trait UploadShares {
         email: UploadShareLinkEmail,
     ) -> Result<(), DracoonClientError>;
 }
+
+/// This trait provides helpers to manage download and upload shares together.
+#[async_trait]
+pub trait ShareManagement {
+    /// Get all shares (download and upload shares) of a node.
+    /// Upload shares are only returned for rooms and folders (upload targets).
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, ShareManagement};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let shares = dracoon.shares().get_shares_for_node(123).await.unwrap();
+    /// for share in shares.download_shares {
+    ///     println!("Download share: {}", share.name);
+    /// }
+    /// # }
+    /// ```
+    async fn get_shares_for_node(&self, node_id: u64) -> Result<ShareSet, DracoonClientError>;
+
+    /// Deletes all shares past their expiration date and all shares which cannot be used anymore
+    /// (max. downloads or max. upload slots reached).
+    /// In dry-run mode nothing is deleted - the shares which would be removed are returned.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, ShareManagement};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// // check what would be removed
+    /// let expired = dracoon.shares().prune_expired_shares(true).await.unwrap();
+    /// println!("{} shares would be removed", expired.len());
+    ///
+    /// let removed = dracoon.shares().prune_expired_shares(false).await.unwrap();
+    /// # }
+    /// ```
+    async fn prune_expired_shares(&self, dry_run: bool) -> Result<ShareSet, DracoonClientError>;
+
+    /// Aggregates the usage (downloads, uploads and uploaded files) of all shares
+    /// in total, per top-level room and per creator.
+    /// All shares are fetched (pagination is handled internally).
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, ShareManagement};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let usage = dracoon.shares().get_share_usage().await.unwrap();
+    /// println!("{} downloads in total", usage.total.downloads);
+    ///
+    /// for (room, room_usage) in usage.by_room {
+    ///     println!("{}: {} downloads, {} uploads", room, room_usage.downloads, room_usage.uploads);
+    /// }
+    /// # }
+    /// ```
+    async fn get_share_usage(&self) -> Result<ShareUsageReport, DracoonClientError>;
+}
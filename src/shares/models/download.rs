@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use dco3_crypto::UserKeyPairContainer;
+use dco3_crypto::{FileKey, UserKeyPairContainer};
 use dco3_derive::FromResponse;
 use reqwest::Response;
 use serde::{Deserialize, Serialize};
@@ -13,7 +13,7 @@ use crate::{
     DracoonClientError,
 };
 
-#[derive(Debug, Deserialize, Clone, FromResponse)]
+#[derive(Debug, Deserialize, Serialize, Clone, FromResponse)]
 #[serde(rename_all = "camelCase")]
 pub struct DownloadShare {
     pub id: u64,
@@ -38,6 +38,20 @@ pub struct DownloadShare {
     pub expire_at: Option<DateTime<Utc>>,
 }
 
+impl DownloadShare {
+    /// Returns true if the share is past its expiration date
+    pub fn is_expired(&self) -> bool {
+        self.expire_at
+            .is_some_and(|expire_at| expire_at <= Utc::now())
+    }
+
+    /// Returns true if the max. number of downloads is reached
+    pub fn is_exhausted(&self) -> bool {
+        self.max_downloads
+            .is_some_and(|max_downloads| self.cnt_downloads >= max_downloads)
+    }
+}
+
 pub type DownloadSharesList = RangedItems<DownloadShare>;
 
 #[derive(Debug, Serialize, Clone)]
@@ -64,8 +78,12 @@ pub struct CreateDownloadShareRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     text_message_recipients: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    default_country: Option<String>,
+    #[serde(rename = "keyPair", skip_serializing_if = "Option::is_none")]
     keypair: Option<UserKeyPairContainer>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    file_key: Option<FileKey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     max_downloads: Option<u32>,
 }
 
@@ -73,6 +91,44 @@ impl CreateDownloadShareRequest {
     pub fn builder(node_id: u64) -> CreateDownloadShareRequestBuilder {
         CreateDownloadShareRequestBuilder::new(node_id)
     }
+
+    pub(crate) fn node_id(&self) -> u64 {
+        self.node_id
+    }
+
+    pub(crate) fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+
+    pub(crate) fn expiration(&self) -> Option<&ObjectExpiration> {
+        self.expiration.as_ref()
+    }
+
+    /// returns true if a share keypair or a file key is set (bound to a single file)
+    pub(crate) fn is_encrypted(&self) -> bool {
+        self.keypair.is_some() || self.file_key.is_some()
+    }
+
+    /// sets the (encrypted) share keypair and the file key encrypted for the share
+    /// the password is removed: it protects the share keypair and must never be sent to the server
+    pub(crate) fn with_encryption(
+        mut self,
+        keypair: UserKeyPairContainer,
+        file_key: FileKey,
+    ) -> Self {
+        self.keypair = Some(keypair);
+        self.file_key = Some(file_key);
+        self.password = None;
+        self
+    }
+
+    /// returns a copy of the request for another node (all other options are kept)
+    pub(crate) fn for_node(&self, node_id: u64) -> Self {
+        Self {
+            node_id,
+            ..self.clone()
+        }
+    }
 }
 
 pub struct CreateDownloadShareRequestBuilder {
@@ -86,7 +142,9 @@ pub struct CreateDownloadShareRequestBuilder {
     internal_notes: Option<String>,
     receiver_language: Option<String>,
     text_message_recipients: Option<Vec<String>>,
+    default_country: Option<String>,
     keypair: Option<UserKeyPairContainer>,
+    file_key: Option<FileKey>,
     max_downloads: Option<u32>,
 }
 
@@ -103,7 +161,9 @@ impl CreateDownloadShareRequestBuilder {
             internal_notes: None,
             receiver_language: None,
             text_message_recipients: None,
+            default_country: None,
             keypair: None,
+            file_key: None,
             max_downloads: None,
         }
     }
@@ -148,16 +208,29 @@ impl CreateDownloadShareRequestBuilder {
         self
     }
 
+    /// Sends the share password via SMS to the given phone numbers (requires a password
+    /// and SMS delivery to be enabled - see `share_password_sms_enabled` in the general settings).
     pub fn with_text_message_recipients(mut self, text_message_recipients: Vec<String>) -> Self {
         self.text_message_recipients = Some(text_message_recipients);
         self
     }
 
+    /// Sets the country (region code, e.g. "DE") used for phone numbers without country code
+    pub fn with_default_country(mut self, default_country: impl Into<String>) -> Self {
+        self.default_country = Some(default_country.into());
+        self
+    }
+
     pub fn with_keypair(mut self, keypair: UserKeyPairContainer) -> Self {
         self.keypair = Some(keypair);
         self
     }
 
+    pub fn with_file_key(mut self, file_key: FileKey) -> Self {
+        self.file_key = Some(file_key);
+        self
+    }
+
     pub fn with_max_downloads(mut self, max_downloads: u32) -> Self {
         self.max_downloads = Some(max_downloads);
         self
@@ -175,7 +248,9 @@ impl CreateDownloadShareRequestBuilder {
             internal_notes: self.internal_notes,
             receiver_language: self.receiver_language,
             text_message_recipients: self.text_message_recipients,
+            default_country: self.default_country,
             keypair: self.keypair,
+            file_key: self.file_key,
             max_downloads: self.max_downloads,
         }
     }
@@ -284,6 +359,14 @@ impl DeleteDownloadSharesRequest {
     pub fn new(share_ids: Vec<u64>) -> Self {
         share_ids.into()
     }
+
+    /// splits the request into requests with max. `batch_size` shares each
+    pub(crate) fn into_batches(self, batch_size: usize) -> Vec<Self> {
+        self.share_ids
+            .chunks(batch_size)
+            .map(|share_ids| share_ids.to_vec().into())
+            .collect()
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -355,6 +438,7 @@ impl UpdateDownloadShareRequestBuilder {
         self
     }
 
+    /// Sets the expiration - pass `ObjectExpiration::default()` to remove the expiration
     pub fn with_expiration(mut self, expiration: impl Into<ObjectExpiration>) -> Self {
         self.expiration = Some(expiration.into());
         self
@@ -620,9 +704,11 @@ impl DownloadSharesSortBy {
 }
 
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct DownloadShareLinkEmail {
     recipients: Vec<String>,
     body: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     receiver_language: Option<String>,
 }
 
@@ -638,4 +724,48 @@ impl DownloadShareLinkEmail {
             receiver_language,
         }
     }
+
+    pub fn builder(body: impl Into<String>) -> DownloadShareLinkEmailBuilder {
+        DownloadShareLinkEmailBuilder::new(body)
+    }
+}
+
+pub struct DownloadShareLinkEmailBuilder {
+    recipients: Vec<String>,
+    body: String,
+    receiver_language: Option<String>,
+}
+
+impl DownloadShareLinkEmailBuilder {
+    pub fn new(body: impl Into<String>) -> Self {
+        Self {
+            recipients: Vec::new(),
+            body: body.into(),
+            receiver_language: None,
+        }
+    }
+
+    pub fn with_recipient(mut self, recipient: impl Into<String>) -> Self {
+        self.recipients.push(recipient.into());
+        self
+    }
+
+    pub fn with_recipients(mut self, recipients: Vec<String>) -> Self {
+        self.recipients.extend(recipients);
+        self
+    }
+
+    /// Sets the language of the email (e.g. "de-DE" or "en-US")
+    pub fn with_receiver_language(mut self, receiver_language: impl Into<String>) -> Self {
+        self.receiver_language = Some(receiver_language.into());
+        self
+    }
+
+    pub fn build(self) -> DownloadShareLinkEmail {
+        DownloadShareLinkEmail {
+            recipients: self.recipients,
+            body: self.body,
+            receiver_language: self.receiver_language,
+        }
+    }
 }
@@ -1,12 +1,17 @@
 mod download;
 mod upload;
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 pub use download::*;
 pub use upload::*;
 
-use crate::client::DracoonClient;
+use url::Url;
+
+use crate::{
+    client::DracoonClient,
+    constants::{PUBLIC_BASE, PUBLIC_DOWNLOAD_SHARES_WEB, PUBLIC_UPLOAD_SHARES_WEB},
+};
 
 #[derive(Clone)]
 pub struct SharesEndpoint<S> {
@@ -25,4 +30,144 @@ impl<S> SharesEndpoint<S> {
     pub fn client(&self) -> &Arc<DracoonClient<S>> {
         &self.client
     }
+
+    /// Builds the public (web) link of a download share from its access key
+    pub fn download_share_url(&self, access_key: &str) -> Url {
+        share_url(
+            self.client.get_base_url(),
+            PUBLIC_DOWNLOAD_SHARES_WEB,
+            access_key,
+        )
+    }
+
+    /// Builds the public (web) link of an upload share (file request) from its access key
+    pub fn upload_share_url(&self, access_key: &str) -> Url {
+        share_url(
+            self.client.get_base_url(),
+            PUBLIC_UPLOAD_SHARES_WEB,
+            access_key,
+        )
+    }
+}
+
+impl DownloadShare {
+    /// Builds the public (web) link of the share for the given DRACOON base url
+    pub fn url(&self, base_url: &Url) -> Url {
+        share_url(base_url, PUBLIC_DOWNLOAD_SHARES_WEB, &self.access_key)
+    }
+}
+
+impl UploadShare {
+    /// Builds the public (web) link of the share for the given DRACOON base url
+    pub fn url(&self, base_url: &Url) -> Url {
+        share_url(base_url, PUBLIC_UPLOAD_SHARES_WEB, &self.access_key)
+    }
+}
+
+/// builds `{base_url}/public/{share_type}/{access_key}` (keeps a path of the base url)
+fn share_url(base_url: &Url, share_type: &str, access_key: &str) -> Url {
+    let mut url = base_url.clone();
+    url.path_segments_mut()
+        .expect("Base url cannot be a base")
+        .pop_if_empty()
+        .extend([PUBLIC_BASE, share_type, access_key]);
+    url.set_query(None);
+    url.set_fragment(None);
+    url
+}
+
+/// Download and upload shares (e.g. all shares of a node or the shares removed by pruning)
+#[derive(Debug, Clone, Default)]
+pub struct ShareSet {
+    pub download_shares: Vec<DownloadShare>,
+    pub upload_shares: Vec<UploadShare>,
+}
+
+impl ShareSet {
+    pub fn is_empty(&self) -> bool {
+        self.download_shares.is_empty() && self.upload_shares.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.download_shares.len() + self.upload_shares.len()
+    }
+}
+
+/// Aggregated usage (access counters) of shares
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ShareUsage {
+    pub download_shares: u64,
+    pub upload_shares: u64,
+    /// downloads of all download shares
+    pub downloads: u64,
+    /// uploads to all upload shares
+    pub uploads: u64,
+    /// files uploaded to all upload shares
+    pub uploaded_files: u64,
+}
+
+impl ShareUsage {
+    fn add_download_share(&mut self, share: &DownloadShare) {
+        self.download_shares += 1;
+        self.downloads += u64::from(share.cnt_downloads);
+    }
+
+    fn add_upload_share(&mut self, share: &UploadShare) {
+        self.upload_shares += 1;
+        self.uploads += u64::from(share.cnt_uploads.unwrap_or(0));
+        self.uploaded_files += u64::from(share.cnt_files.unwrap_or(0));
+    }
+}
+
+/// Usage of all shares - in total, per top-level room (by name) and per creator (by user id)
+#[derive(Debug, Clone, Default)]
+pub struct ShareUsageReport {
+    pub total: ShareUsage,
+    pub by_room: HashMap<String, ShareUsage>,
+    pub by_creator: HashMap<i64, ShareUsage>,
+}
+
+impl ShareUsageReport {
+    pub fn new(download_shares: &[DownloadShare], upload_shares: &[UploadShare]) -> Self {
+        let mut report = Self::default();
+
+        for share in download_shares {
+            report.total.add_download_share(share);
+            report
+                .by_creator
+                .entry(share.created_by.id)
+                .or_default()
+                .add_download_share(share);
+            if let Some(room) = share.node_path.as_deref().and_then(top_level_room) {
+                report
+                    .by_room
+                    .entry(room.to_string())
+                    .or_default()
+                    .add_download_share(share);
+            }
+        }
+
+        for share in upload_shares {
+            report.total.add_upload_share(share);
+            report
+                .by_creator
+                .entry(share.created_by.id)
+                .or_default()
+                .add_upload_share(share);
+            if let Some(room) = share.target_path.as_deref().and_then(top_level_room) {
+                report
+                    .by_room
+                    .entry(room.to_string())
+                    .or_default()
+                    .add_upload_share(share);
+            }
+        }
+
+        report
+    }
+}
+
+/// returns the top-level room of a node path (e.g. "room" for "/room/folder/file.txt")
+fn top_level_room(path: &str) -> Option<&str> {
+    path.split('/').find(|segment| !segment.is_empty())
 }
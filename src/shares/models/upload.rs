@@ -12,7 +12,7 @@ use crate::{
     DracoonClientError,
 };
 
-#[derive(Debug, Deserialize, Clone, FromResponse)]
+#[derive(Debug, Deserialize, Serialize, Clone, FromResponse)]
 #[serde(rename_all = "camelCase")]
 pub struct UploadShare {
     pub id: u64,
@@ -48,6 +48,21 @@ impl FromResponse for UploadSharesList {
     }
 }
 
+impl UploadShare {
+    /// Returns true if the share is past its expiration date
+    pub fn is_expired(&self) -> bool {
+        self.expire_at
+            .is_some_and(|expire_at| expire_at <= Utc::now())
+    }
+
+    /// Returns true if the max. number of uploaded files (slots) is reached
+    pub fn is_exhausted(&self) -> bool {
+        self.max_slots
+            .zip(self.cnt_files)
+            .is_some_and(|(max_slots, cnt_files)| cnt_files >= max_slots)
+    }
+}
+
 pub type UploadSharesList = RangedItems<UploadShare>;
 
 #[derive(Debug, Serialize, Clone)]
@@ -55,6 +70,7 @@ pub type UploadSharesList = RangedItems<UploadShare>;
 pub struct UploadShareLinkEmail {
     body: String,
     recipients: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     receiver_language: Option<String>,
 }
 
@@ -70,9 +86,54 @@ impl UploadShareLinkEmail {
             receiver_language,
         }
     }
+
+    pub fn builder(body: impl Into<String>) -> UploadShareLinkEmailBuilder {
+        UploadShareLinkEmailBuilder::new(body)
+    }
+}
+
+pub struct UploadShareLinkEmailBuilder {
+    body: String,
+    recipients: Vec<String>,
+    receiver_language: Option<String>,
+}
+
+impl UploadShareLinkEmailBuilder {
+    pub fn new(body: impl Into<String>) -> Self {
+        Self {
+            body: body.into(),
+            recipients: Vec::new(),
+            receiver_language: None,
+        }
+    }
+
+    pub fn with_recipient(mut self, recipient: impl Into<String>) -> Self {
+        self.recipients.push(recipient.into());
+        self
+    }
+
+    pub fn with_recipients(mut self, recipients: Vec<String>) -> Self {
+        self.recipients.extend(recipients);
+        self
+    }
+
+    /// Sets the language of the email (e.g. "de-DE" or "en-US")
+    pub fn with_receiver_language(mut self, receiver_language: impl Into<String>) -> Self {
+        self.receiver_language = Some(receiver_language.into());
+        self
+    }
+
+    pub fn build(self) -> UploadShareLinkEmail {
+        UploadShareLinkEmail {
+            body: self.body,
+            recipients: self.recipients,
+            receiver_language: self.receiver_language,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateUploadShareRequest {
     target_id: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -99,12 +160,20 @@ pub struct CreateUploadShareRequest {
     show_creator_username: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     text_message_recipients: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receiver_language: Option<String>,
 }
 
 impl CreateUploadShareRequest {
     pub fn builder(target_id: u64) -> CreateUploadShareRequestBuilder {
         CreateUploadShareRequestBuilder::new(target_id)
     }
+
+    pub(crate) fn expiration(&self) -> Option<&ObjectExpiration> {
+        self.expiration.as_ref()
+    }
 }
 
 #[derive(Debug, Default)]
@@ -122,6 +191,8 @@ pub struct CreateUploadShareRequestBuilder {
     show_creator_name: Option<bool>,
     show_creator_username: Option<bool>,
     text_message_recipients: Option<Vec<String>>,
+    default_country: Option<String>,
+    receiver_language: Option<String>,
 }
 
 impl CreateUploadShareRequestBuilder {
@@ -187,11 +258,24 @@ impl CreateUploadShareRequestBuilder {
         self
     }
 
+    /// Sends the share password via SMS to the given phone numbers (requires a password
+    /// and SMS delivery to be enabled - see `share_password_sms_enabled` in the general settings).
     pub fn with_text_message_recipients(mut self, text_message_recipients: Vec<String>) -> Self {
         self.text_message_recipients = Some(text_message_recipients);
         self
     }
 
+    /// Sets the country (region code, e.g. "DE") used for phone numbers without country code
+    pub fn with_default_country(mut self, default_country: impl Into<String>) -> Self {
+        self.default_country = Some(default_country.into());
+        self
+    }
+
+    pub fn with_receiver_language(mut self, receiver_language: impl Into<String>) -> Self {
+        self.receiver_language = Some(receiver_language.into());
+        self
+    }
+
     pub fn build(self) -> CreateUploadShareRequest {
         CreateUploadShareRequest {
             target_id: self.target_id,
@@ -207,6 +291,8 @@ impl CreateUploadShareRequestBuilder {
             show_creator_name: self.show_creator_name,
             show_creator_username: self.show_creator_username,
             text_message_recipients: self.text_message_recipients,
+            default_country: self.default_country,
+            receiver_language: self.receiver_language,
         }
     }
 }
@@ -292,6 +378,7 @@ impl UpdateUploadShareRequestBuilder {
         self
     }
 
+    /// Sets the expiration - pass `ObjectExpiration::default()` to remove the expiration
     pub fn with_expiration(mut self, expiration: impl Into<ObjectExpiration>) -> Self {
         self.expiration = Some(expiration.into());
         self
@@ -406,6 +493,14 @@ impl DeleteUploadSharesRequest {
     pub fn new(share_ids: Vec<u64>) -> Self {
         share_ids.into()
     }
+
+    /// splits the request into requests with max. `batch_size` shares each
+    pub(crate) fn into_batches(self, batch_size: usize) -> Vec<Self> {
+        self.share_ids
+            .chunks(batch_size)
+            .map(|share_ids| share_ids.to_vec().into())
+            .collect()
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
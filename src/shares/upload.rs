@@ -1,8 +1,12 @@
 use async_trait::async_trait;
 use reqwest::header;
 
-use crate::constants::{DRACOON_API_PREFIX, SHARES_BASE, SHARES_EMAIL, SHARES_UPLOAD};
+use crate::constants::{
+    DELETED_SHARES_BATCH, DRACOON_API_PREFIX, PUBLIC_BASE, PUBLIC_SHARES_BASE,
+    PUBLIC_UPLOAD_SHARES, SHARES_BASE, SHARES_EMAIL, SHARES_UPLOAD,
+};
 use crate::models::ListAllParams;
+use crate::public::{PublicUploadShare, PublicUploadedFileData};
 use crate::utils::FromResponse;
 use crate::{client::Connected, DracoonClientError};
 
@@ -84,23 +88,26 @@ impl UploadShares for SharesEndpoint<Connected> {
 
         let api_url = self.client().build_api_url(&url_part);
 
-        let response = self
-            .client()
-            .http
-            .delete(api_url)
-            .header(
-                header::AUTHORIZATION,
-                self.client().get_auth_header().await?,
-            )
-            .header(header::CONTENT_TYPE, "application/json")
-            .json(&delete)
-            .send()
-            .await?;
-
-        if response.status().is_server_error() || response.status().is_client_error() {
-            return Err(DracoonClientError::from_response(response)
-                .await
-                .expect("Could not parse error response"));
+        // large lists are deleted in batches
+        for batch in delete.into_batches(DELETED_SHARES_BATCH) {
+            let response = self
+                .client()
+                .http
+                .delete(api_url.clone())
+                .header(
+                    header::AUTHORIZATION,
+                    self.client().get_auth_header().await?,
+                )
+                .header(header::CONTENT_TYPE, "application/json")
+                .json(&batch)
+                .send()
+                .await?;
+
+            if response.status().is_server_error() || response.status().is_client_error() {
+                return Err(DracoonClientError::from_response(response)
+                    .await
+                    .expect("Could not parse error response"));
+            }
         }
 
         Ok(())
@@ -110,6 +117,10 @@ impl UploadShares for SharesEndpoint<Connected> {
         &self,
         create: CreateUploadShareRequest,
     ) -> Result<UploadShare, DracoonClientError> {
+        if let Some(expiration) = create.expiration() {
+            expiration.validate()?;
+        }
+
         let url_part = format!("{DRACOON_API_PREFIX}/{SHARES_BASE}/{SHARES_UPLOAD}");
 
         let api_url = self.client().build_api_url(&url_part);
@@ -156,6 +167,37 @@ impl UploadShares for SharesEndpoint<Connected> {
         UploadShare::from_response(response).await
     }
 
+    async fn get_upload_share_files(
+        &self,
+        upload_share_id: u64,
+    ) -> Result<Vec<PublicUploadedFileData>, DracoonClientError> {
+        let upload_share = self.get_upload_share(upload_share_id).await?;
+
+        if !upload_share.show_uploaded_files.unwrap_or(false) {
+            return Ok(Vec::new());
+        }
+
+        // uploaded files are only available via the public upload share
+        let url_part = format!(
+            "{DRACOON_API_PREFIX}/{PUBLIC_BASE}/{PUBLIC_SHARES_BASE}/{PUBLIC_UPLOAD_SHARES}/{}",
+            upload_share.access_key
+        );
+
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .get(api_url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        let public_upload_share = PublicUploadShare::from_response(response).await?;
+
+        Ok(public_upload_share.uploaded_files.unwrap_or_default())
+    }
+
     async fn update_upload_share(
         &self,
         upload_share_id: u64,
@@ -1,12 +1,13 @@
 #[cfg(test)]
 mod tests {
 
-    use chrono::DateTime;
+    use chrono::{DateTime, Utc};
 
     use crate::*;
 
     use crate::groups::{CreateGroupRequest, GroupsFilter, GroupsSortBy, UpdateGroupRequest};
     use crate::nodes::UserType;
+    use crate::roles::RoleType;
     use crate::tests::dracoon::get_connected_client;
 
     #[tokio::test]
@@ -127,6 +128,47 @@ mod tests {
         groups_mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_get_groups_with_typed_filters() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let groups_res = include_str!("./responses/groups/groups_ok.json");
+
+        let groups_mock = mock_server
+            .mock("GET", "/api/v4/groups")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded(
+                    "filter".into(),
+                    "name:cn:sales|createdAt:ge:2023-01-01T00:00:00Z|createdAt:le:2023-12-31T23:59:59Z"
+                        .into(),
+                ),
+                mockito::Matcher::UrlEncoded("sort".into(), "cntUsers:desc".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(groups_res)
+            .create();
+
+        let params = ListAllParams::builder()
+            .with_filter(GroupsFilter::name_contains("sales"))
+            .with_filter(GroupsFilter::created_at_after(
+                DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            ))
+            .with_filter(GroupsFilter::created_at_before(
+                DateTime::parse_from_rfc3339("2023-12-31T23:59:59Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            ))
+            .with_sort(GroupsSortBy::cnt_users(SortOrder::Desc))
+            .build();
+
+        dracoon.groups().get_groups(Some(params)).await.unwrap();
+
+        groups_mock.assert();
+    }
+
     #[tokio::test]
     async fn test_get_groups_with_sort() {
         let (dracoon, mock_server) = get_connected_client().await;
@@ -345,4 +387,265 @@ mod tests {
 
         groups_mock.assert();
     }
+
+    #[tokio::test]
+    async fn test_add_group_users_in_batches() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+        let group_res = include_str!("./responses/groups/group_ok.json");
+
+        let user_ids = (1..=250).collect::<Vec<u64>>();
+
+        let batch_mocks = user_ids
+            .chunks(100)
+            .map(|batch| {
+                mock_server
+                    .mock("POST", "/api/v4/groups/123/users")
+                    .match_body(mockito::Matcher::Json(serde_json::json!({ "ids": batch })))
+                    .with_status(200)
+                    .with_body(group_res)
+                    .with_header("content-type", "application/json")
+                    .create()
+            })
+            .collect::<Vec<_>>();
+
+        let group = dracoon
+            .groups()
+            .add_group_users(123, user_ids.into())
+            .await
+            .unwrap();
+
+        assert_eq!(batch_mocks.len(), 3);
+        for mock in batch_mocks {
+            mock.assert();
+        }
+
+        assert_eq!(group.id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_remove_group_users() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+        let group_res = include_str!("./responses/groups/group_ok.json");
+
+        let group_mock = mock_server
+            .mock("DELETE", "/api/v4/groups/123/users")
+            .match_body(mockito::Matcher::Json(serde_json::json!({ "ids": [1, 2] })))
+            .with_status(200)
+            .with_body(group_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        dracoon
+            .groups()
+            .remove_group_users(123, vec![1, 2].into())
+            .await
+            .unwrap();
+
+        group_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_set_group_members() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+        let group_users_res = include_str!("./responses/groups/group_users_ok.json");
+        let group_res = include_str!("./responses/groups/group_ok.json");
+
+        let members_mock = mock_server
+            .mock("GET", "/api/v4/groups/123/users")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "isMember:eq:true".into(),
+            ))
+            .with_status(200)
+            .with_body(group_users_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let add_mock = mock_server
+            .mock("POST", "/api/v4/groups/123/users")
+            .match_body(mockito::Matcher::Json(serde_json::json!({ "ids": [3, 4] })))
+            .with_status(200)
+            .with_body(group_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let remove_mock = mock_server
+            .mock("DELETE", "/api/v4/groups/123/users")
+            .match_body(mockito::Matcher::Json(serde_json::json!({ "ids": [1] })))
+            .with_status(200)
+            .with_body(group_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let changes = dracoon
+            .groups()
+            .set_group_members(123, vec![4, 2, 3])
+            .await
+            .unwrap();
+
+        members_mock.assert();
+        add_mock.assert();
+        remove_mock.assert();
+
+        assert_eq!(changes.added, vec![3, 4]);
+        assert_eq!(changes.removed, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_set_group_members_unchanged() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+        let group_users_res = include_str!("./responses/groups/group_users_ok.json");
+
+        let members_mock = mock_server
+            .mock("GET", "/api/v4/groups/123/users")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(group_users_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let changes = dracoon
+            .groups()
+            .set_group_members(123, vec![1, 2])
+            .await
+            .unwrap();
+
+        members_mock.assert();
+        assert!(changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_group_last_admin_rooms() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+        let rooms_res = include_str!("./responses/groups/last_admin_rooms_ok.json");
+
+        let rooms_mock = mock_server
+            .mock("GET", "/api/v4/groups/123/last_admin_rooms")
+            .with_status(200)
+            .with_body(rooms_res)
+            .create();
+
+        let rooms = dracoon
+            .groups()
+            .get_group_last_admin_rooms(123)
+            .await
+            .unwrap();
+
+        rooms_mock.assert();
+
+        assert_eq!(rooms.items.len(), 1);
+        let room = rooms.items.first().unwrap();
+        assert_eq!(room.id, 2);
+        assert_eq!(room.name, "string");
+        assert_eq!(room.parent_path, "/parent");
+        assert_eq!(room.parent_id, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_validate_group_deletion_last_admin() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+        let rooms_res = include_str!("./responses/groups/last_admin_rooms_ok.json");
+
+        let rooms_mock = mock_server
+            .mock("GET", "/api/v4/groups/123/last_admin_rooms")
+            .with_status(200)
+            .with_body(rooms_res)
+            .create();
+
+        let err = dracoon
+            .groups()
+            .validate_group_deletion(123)
+            .await
+            .unwrap_err();
+
+        rooms_mock.assert();
+
+        assert_eq!(
+            err,
+            DracoonClientError::WouldOrphanRooms(vec![OrphanedRoom {
+                id: 2,
+                name: "string".into(),
+                parent_path: "/parent".into(),
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_group_deletion_ok() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let rooms_mock = mock_server
+            .mock("GET", "/api/v4/groups/123/last_admin_rooms")
+            .with_status(200)
+            .with_body(r#"{"items": []}"#)
+            .create();
+
+        dracoon.groups().validate_group_deletion(123).await.unwrap();
+
+        rooms_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_get_group_roles() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+        let roles_res = include_str!("./responses/roles/roles_ok.json");
+
+        let roles_mock = mock_server
+            .mock("GET", "/api/v4/groups/123/roles")
+            .with_status(200)
+            .with_body(roles_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let roles = dracoon.groups().get_group_roles(123).await.unwrap();
+
+        roles_mock.assert();
+
+        assert_eq!(roles.items.len(), 1);
+        assert!(roles.contains(RoleType::ConfigManager));
+    }
+
+    #[tokio::test]
+    async fn test_assign_group_role() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+        let role_groups_res = include_str!("./responses/roles/role_group_list_ok.json");
+
+        let role_mock = mock_server
+            .mock("POST", "/api/v4/roles/4/groups")
+            .match_body(mockito::Matcher::Json(serde_json::json!({ "ids": [123] })))
+            .with_status(200)
+            .with_body(role_groups_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        dracoon
+            .groups()
+            .assign_group_role(123, RoleType::RoomManager)
+            .await
+            .unwrap();
+
+        role_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_revoke_group_role() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+        let role_groups_res = include_str!("./responses/roles/role_group_list_ok.json");
+
+        let role_mock = mock_server
+            .mock("DELETE", "/api/v4/roles/5/groups")
+            .match_body(mockito::Matcher::Json(serde_json::json!({ "ids": [123] })))
+            .with_status(200)
+            .with_body(role_groups_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        dracoon
+            .groups()
+            .revoke_group_role(123, RoleType::LogAuditor)
+            .await
+            .unwrap();
+
+        role_mock.assert();
+    }
 }
@@ -237,10 +237,10 @@ pub mod tests {
 
         let nodes_res = include_str!("./responses/nodes/nodes_ok.json");
 
-        let path = "/some/path/test";
+        let path = "/some/path/string";
 
         let nodes_mock = mock_server
-            .mock("GET", "/api/v4/nodes/search?search_string=test&depth_level=2&filter=parentPath%3Aeq%3A%2Fsome%2Fpath%2F")
+            .mock("GET", "/api/v4/nodes/search?search_string=string&depth_level=2&limit=500&offset=0&filter=parentPath%3Aeq%3A%2Fsome%2Fpath%2F")
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(nodes_res)
@@ -257,6 +257,44 @@ pub mod tests {
         assert_node(&node);
     }
 
+    #[tokio::test]
+    async fn test_get_node_from_path_paginated() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        // first page only contains a partial match ("contains" search)
+        let mut first_page: serde_json::Value =
+            serde_json::from_str(include_str!("./responses/nodes/nodes_ok.json")).unwrap();
+        first_page["range"]["total"] = serde_json::json!(501);
+        first_page["items"][0]["name"] = serde_json::json!("string_copy");
+
+        let mut second_page: serde_json::Value =
+            serde_json::from_str(include_str!("./responses/nodes/nodes_ok.json")).unwrap();
+        second_page["range"]["total"] = serde_json::json!(501);
+
+        let path = "/some/path/string";
+
+        let first_page_mock = mock_server
+            .mock("GET", "/api/v4/nodes/search?search_string=string&depth_level=2&limit=500&offset=0&filter=parentPath%3Aeq%3A%2Fsome%2Fpath%2F")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(first_page.to_string())
+            .create();
+
+        let second_page_mock = mock_server
+            .mock("GET", "/api/v4/nodes/search?search_string=string&depth_level=2&limit=500&offset=500&filter=parentPath%3Aeq%3A%2Fsome%2Fpath%2F")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(second_page.to_string())
+            .create();
+
+        let node = dracoon.nodes().get_node_from_path(path).await.unwrap();
+
+        first_page_mock.assert();
+        second_page_mock.assert();
+
+        assert_node(&node.unwrap());
+    }
+
     #[tokio::test]
     async fn test_get_node_from_path_no_result() {
         let (dracoon, mock_server) = get_connected_client().await;
@@ -265,10 +303,10 @@ pub mod tests {
 
         let nodes_res = include_str!("./responses/nodes/nodes_search_no_result.json");
 
-        let path = "/some/path/test";
+        let path = "/some/path/string";
 
         let nodes_mock = mock_server
-            .mock("GET", "/api/v4/nodes/search?search_string=test&depth_level=2&filter=parentPath%3Aeq%3A%2Fsome%2Fpath%2F")
+            .mock("GET", "/api/v4/nodes/search?search_string=string&depth_level=2&limit=500&offset=0&filter=parentPath%3Aeq%3A%2Fsome%2Fpath%2F")
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(nodes_res)
@@ -318,6 +356,20 @@ pub mod tests {
         node_mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_cancel_upload() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let delete_mock = mock_server
+            .mock("DELETE", "/api/v4/nodes/files/uploads/string")
+            .with_status(204)
+            .create();
+
+        dracoon.nodes().cancel_upload("string").await.unwrap();
+
+        delete_mock.assert();
+    }
+
     #[tokio::test]
     async fn test_delete_nodes() {
         let (dracoon, mock_server) = get_connected_client().await;
@@ -337,6 +389,69 @@ pub mod tests {
         node_mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_delete_deleted_nodes_chunked() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let deleted_node_ids: Vec<u64> = (1..=150).collect();
+
+        let first_batch_mock = mock_server
+            .mock("DELETE", "/api/v4/nodes/deleted_nodes")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "deletedNodeIds": (1..=100).collect::<Vec<u64>>()
+            })))
+            .with_status(204)
+            .create();
+
+        let second_batch_mock = mock_server
+            .mock("DELETE", "/api/v4/nodes/deleted_nodes")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "deletedNodeIds": (101..=150).collect::<Vec<u64>>()
+            })))
+            .with_status(204)
+            .create();
+
+        dracoon
+            .nodes()
+            .delete_deleted_nodes(deleted_node_ids.into())
+            .await
+            .unwrap();
+
+        first_batch_mock.assert();
+        second_batch_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_get_file_versions() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let versions_res = include_str!("./responses/nodes/file_versions_ok.json");
+
+        let versions_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/nodes/123/deleted_nodes/versions?type=file&name=test.txt&offset=0",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(versions_res)
+            .create();
+
+        let versions = dracoon
+            .nodes()
+            .get_file_versions(123, "test.txt", None)
+            .await
+            .unwrap();
+
+        versions_mock.assert();
+
+        assert_eq!(versions.items.len(), 1);
+        assert_eq!(versions.items[0].id, 42);
+        assert_eq!(versions.items[0].name, "test.txt");
+        assert_eq!(versions.items[0].size, Some(16));
+        assert!(versions.items[0].deleted_at.is_some());
+    }
+
     #[tokio::test]
     async fn test_copy_nodes() {
         let (dracoon, mock_server) = get_connected_client().await;
@@ -629,4 +744,162 @@ pub mod tests {
 
         assert_node(node);
     }
+
+    #[tokio::test]
+    async fn test_get_node_size_summaries() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        // room 1 contains file 10 (100 bytes), folder 11 and folder 12
+        // folder 11 contains files 20 (50 bytes) and 21 (25 bytes), folder 12 is empty
+        let room_mock = mock_server
+            .mock("GET", "/api/v4/nodes?limit=500&offset=0&parent_id=1")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!(
+                "./responses/nodes/size_summaries_room_ok.json"
+            ))
+            .create();
+
+        let folder_mock = mock_server
+            .mock("GET", "/api/v4/nodes?limit=500&offset=0&parent_id=11")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!(
+                "./responses/nodes/size_summaries_folder_ok.json"
+            ))
+            .create();
+
+        let empty_folder_mock = mock_server
+            .mock("GET", "/api/v4/nodes?limit=500&offset=0&parent_id=12")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!("./responses/nodes/nodes_empty_ok.json"))
+            .create();
+
+        let summaries = dracoon
+            .nodes()
+            .get_node_size_summaries(1, Some(2))
+            .await
+            .unwrap();
+
+        room_mock.assert();
+        folder_mock.assert();
+        empty_folder_mock.assert();
+
+        assert_eq!(summaries.len(), 3);
+
+        let room = summaries.first().unwrap();
+        assert_eq!(room.node_id, 1);
+        assert_eq!(room.parent_id, None);
+        assert_eq!(room.file_count, 3);
+        assert_eq!(room.size, 175);
+
+        let folder = summaries.iter().find(|s| s.node_id == 11).unwrap();
+        assert_eq!(folder.parent_id, Some(1));
+        assert_eq!(folder.file_count, 2);
+        assert_eq!(folder.size, 75);
+
+        let empty_folder = summaries.iter().find(|s| s.node_id == 12).unwrap();
+        assert_eq!(empty_folder.file_count, 0);
+        assert_eq!(empty_folder.size, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_node_tree() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        let node_res = include_str!("./responses/nodes/node_ok.json");
+
+        // room 2 contains file 10 and folder 11, folder 11 contains folder 20
+        // folder 20 is not listed due to depth 2
+        let node_mock = mock_server
+            .mock("GET", "/api/v4/nodes/2")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(node_res)
+            .create();
+
+        let room_mock = mock_server
+            .mock("GET", "/api/v4/nodes?limit=500&offset=0&parent_id=2")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!("./responses/nodes/node_tree_room_ok.json"))
+            .create();
+
+        let folder_mock = mock_server
+            .mock("GET", "/api/v4/nodes?limit=500&offset=0&parent_id=11")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!("./responses/nodes/node_tree_folder_ok.json"))
+            .create();
+
+        let sub_folder_mock = mock_server
+            .mock("GET", "/api/v4/nodes?limit=500&offset=0&parent_id=20")
+            .expect(0)
+            .create();
+
+        let tree = dracoon.nodes().get_node_tree(2, 2).await.unwrap();
+
+        node_mock.assert();
+        room_mock.assert();
+        folder_mock.assert();
+        sub_folder_mock.assert();
+
+        assert_eq!(tree.node.id, 2);
+        assert_eq!(tree.children.len(), 2);
+        assert_eq!(tree.node_count(), 4);
+
+        let file = tree.children.iter().find(|c| c.node.id == 10).unwrap();
+        assert!(file.children.is_empty());
+
+        let folder = tree.children.iter().find(|c| c.node.id == 11).unwrap();
+        assert_eq!(folder.children.len(), 1);
+        assert_eq!(folder.children.first().unwrap().node.id, 20);
+        assert!(folder.children.first().unwrap().children.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reclassify_files() {
+        let (dracoon, mut mock_server) = get_connected_client().await;
+
+        // files 10 and 11 are reclassified, file 12 already has classification 2
+        let files = include_str!("./responses/nodes/reclassify_files_ok.json");
+
+        let search_mock = mock_server
+            .mock("GET", "/api/v4/nodes/search?search_string=*&depth_level=-1&limit=500&offset=0&filter=type%3Aeq%3Afile&parent_id=1")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(files)
+            .create();
+
+        let update_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/files")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "objectIds": [10, 11],
+                "classification": 2
+            })))
+            .with_status(204)
+            .create();
+
+        let progress = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let progress_clone = progress.clone();
+
+        let updated = dracoon
+            .nodes()
+            .reclassify_files(
+                1,
+                2,
+                Some(Box::new(move |updated, total| {
+                    progress_clone.lock().unwrap().push((updated, total));
+                })),
+            )
+            .await
+            .unwrap();
+
+        search_mock.assert();
+        update_mock.assert();
+
+        assert_eq!(updated, 2);
+        assert_eq!(*progress.lock().unwrap(), vec![(2, 2)]);
+    }
 }
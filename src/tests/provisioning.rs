@@ -1,12 +1,16 @@
 #[cfg(test)]
 mod tests {
-    use chrono::DateTime;
+    use chrono::{DateTime, Utc};
 
     use crate::{
         client::Provisioning,
-        provisioning::{Customer, FirstAdminUser, NewCustomerRequest, UpdateCustomerRequest},
+        provisioning::{
+            Customer, CustomerAttributes, CustomersFilter, CustomersSortBy, FirstAdminUser,
+            NewCustomerRequest, UpdateCustomerRequest,
+        },
         tests::users::tests::assert_user_item,
-        CustomerProvisioning, Dracoon, ListAllParams,
+        users::UsersFilter,
+        CustomerProvisioning, Dracoon, ListAllParams, SortOrder,
     };
 
     async fn get_provisioning_client() -> (Dracoon<Provisioning>, mockito::ServerGuard) {
@@ -153,21 +157,23 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "missing models for filter query"]
     async fn test_get_customers_with_filter() {
         let (dracoon, mut mock_server) = get_provisioning_client().await;
         let customers_res = include_str!("./responses/provisioning/customers_ok.json");
 
         let customers_mock = mock_server
-            // TODO: add filter query
-            .mock("GET", "/api/v4/provisioning/customers?offset=0")
+            .mock(
+                "GET",
+                "/api/v4/provisioning/customers?offset=0&filter=companyName%3Acn%3Astring",
+            )
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(customers_res)
             .create();
 
-        // TODO: add filter query
-        let params = ListAllParams::builder().build();
+        let params = ListAllParams::builder()
+            .with_filter(CustomersFilter::company_name_contains("string"))
+            .build();
 
         let customers = dracoon
             .provisioning()
@@ -186,21 +192,70 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "missing models for sort query"]
+    async fn test_get_customers_with_created_at_filter() {
+        let (dracoon, mut mock_server) = get_provisioning_client().await;
+        let customers_res = include_str!("./responses/provisioning/customers_ok.json");
+
+        let customers_mock = mock_server
+            .mock("GET", "/api/v4/provisioning/customers")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("offset".into(), "0".into()),
+                mockito::Matcher::UrlEncoded(
+                    "filter".into(),
+                    "createdAt:ge:2023-01-01T00:00:00Z|createdAt:le:2023-12-31T23:59:59Z".into(),
+                ),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(customers_res)
+            .create();
+
+        let params = ListAllParams::builder()
+            .with_filter(CustomersFilter::created_at_after(
+                DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            ))
+            .with_filter(CustomersFilter::created_at_before(
+                DateTime::parse_from_rfc3339("2023-12-31T23:59:59Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            ))
+            .build();
+
+        dracoon
+            .provisioning()
+            .get_customers(Some(params))
+            .await
+            .unwrap();
+
+        customers_mock.assert();
+    }
+
+    #[tokio::test]
     async fn test_get_customers_with_sort() {
         let (dracoon, mut mock_server) = get_provisioning_client().await;
         let customers_res = include_str!("./responses/provisioning/customers_ok.json");
 
         let customers_mock = mock_server
-            // TODO: add sort query
-            .mock("GET", "/api/v4/provisioning/customers?offset=0")
+            .mock(
+                "GET",
+                "/api/v4/provisioning/customers?offset=0&sort=companyName%3Adesc",
+            )
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(customers_res)
             .create();
 
-        // TODO: add sort query
-        let customers = dracoon.provisioning().get_customers(None).await.unwrap();
+        let params = ListAllParams::builder()
+            .with_sort(CustomersSortBy::company_name(SortOrder::Desc))
+            .build();
+
+        let customers = dracoon
+            .provisioning()
+            .get_customers(Some(params))
+            .await
+            .unwrap();
         assert_eq!(customers.range.total, 1);
         assert_eq!(customers.range.offset, 0);
         assert_eq!(customers.range.limit, 0);
@@ -212,6 +267,51 @@ mod tests {
         customers_mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_get_all_customers() {
+        let (dracoon, mut mock_server) = get_provisioning_client().await;
+
+        let mut customers: serde_json::Value =
+            serde_json::from_str(include_str!("./responses/provisioning/customers_ok.json"))
+                .unwrap();
+        customers["range"]["total"] = serde_json::json!(501);
+
+        let first_page_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/provisioning/customers?limit=500&offset=0&filter=isLocked%3Aeq%3Afalse",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(customers.to_string())
+            .create();
+
+        let second_page_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/provisioning/customers?limit=500&offset=500&filter=isLocked%3Aeq%3Afalse",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(customers.to_string())
+            .create();
+
+        let customers = dracoon
+            .provisioning()
+            .get_all_customers(Some(CustomersFilter::is_locked(false)))
+            .await
+            .unwrap();
+
+        first_page_mock.assert();
+        second_page_mock.assert();
+
+        assert_eq!(customers.len(), 2);
+
+        for customer in &customers {
+            assert_customer(customer).await;
+        }
+    }
+
     #[tokio::test]
     async fn test_create_customer() {
         let (dracoon, mut mock_server) = get_provisioning_client().await;
@@ -371,6 +471,124 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_update_customer_user_max() {
+        let (dracoon, mut mock_server) = get_provisioning_client().await;
+        let res = include_str!("./responses/provisioning/update_customer_ok.json");
+
+        let customer_mock = mock_server
+            .mock("PUT", "/api/v4/provisioning/customers/1")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "quotaMax": 10000000,
+                "userMax": 100
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(res)
+            .create();
+
+        let update = UpdateCustomerRequest::builder()
+            .with_quota_max(10000000)
+            .with_user_max(100)
+            .build();
+
+        let customer = dracoon
+            .provisioning()
+            .update_customer(1, update)
+            .await
+            .unwrap();
+
+        customer_mock.assert();
+
+        assert_eq!(customer.quota_max, 10000000);
+        assert_eq!(customer.user_max, 100);
+    }
+
+    #[tokio::test]
+    async fn test_get_customer_attributes() {
+        let (dracoon, mut mock_server) = get_provisioning_client().await;
+        let res = include_str!("./responses/provisioning/customer_attributes_ok.json");
+
+        let attributes_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/provisioning/customers/1/customerAttributes?offset=0",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(res)
+            .create();
+
+        let attributes = dracoon
+            .provisioning()
+            .get_customer_attributes(1, None)
+            .await
+            .unwrap();
+
+        attributes_mock.assert();
+
+        assert_eq!(attributes.range.total, 1);
+        assert_eq!(attributes.items.len(), 1);
+
+        let kv = attributes.items.first().unwrap();
+        assert_eq!(kv.key, "string");
+        assert_eq!(kv.value, "string");
+    }
+
+    #[tokio::test]
+    async fn test_update_customer_attributes() {
+        let (dracoon, mut mock_server) = get_provisioning_client().await;
+        let res = include_str!("./responses/provisioning/customer_ok.json");
+
+        let attributes_mock = mock_server
+            .mock("PUT", "/api/v4/provisioning/customers/1/customerAttributes")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "items": [
+                    { "key": "foo", "value": "bar" },
+                    { "key": "baz", "value": "qux" }
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(res)
+            .create();
+
+        let attributes = CustomerAttributes::new()
+            .with_attribute("foo", "bar")
+            .with_attribute("baz", "qux");
+
+        let customer = dracoon
+            .provisioning()
+            .update_customer_attributes(1, attributes)
+            .await
+            .unwrap();
+
+        attributes_mock.assert();
+
+        assert_customer(&customer).await;
+    }
+
+    #[tokio::test]
+    async fn test_delete_customer_attribute() {
+        let (dracoon, mut mock_server) = get_provisioning_client().await;
+
+        let attribute_mock = mock_server
+            .mock(
+                "DELETE",
+                "/api/v4/provisioning/customers/1/customerAttributes/foo",
+            )
+            .with_status(204)
+            .create();
+
+        dracoon
+            .provisioning()
+            .delete_customer_attribute(1, "foo".to_string())
+            .await
+            .unwrap();
+
+        attribute_mock.assert();
+    }
+
     #[tokio::test]
     async fn test_get_customer_users() {
         let (dracoon, mut mock_server) = get_provisioning_client().await;
@@ -452,4 +670,63 @@ mod tests {
 
         assert_user_item(user);
     }
+
+    #[tokio::test]
+    async fn test_get_customer_users_with_filter() {
+        let (dracoon, mut mock_server) = get_provisioning_client().await;
+        let res = include_str!("./responses/users/users_ok.json");
+
+        let customer_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/provisioning/customers/1/users?offset=0&filter=isLocked%3Aeq%3Afalse%7Cemail%3Acn%3A%40dracoon.com",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(res)
+            .create();
+
+        let params = ListAllParams::builder()
+            .with_filter(UsersFilter::is_locked(false))
+            .with_filter(UsersFilter::email_contains("@dracoon.com"))
+            .build();
+
+        let users = dracoon
+            .provisioning()
+            .get_customer_users(1, Some(params))
+            .await
+            .unwrap();
+
+        customer_mock.assert();
+
+        assert_eq!(users.range.total, 1);
+        assert_user_item(users.items.first().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_get_all_customer_users() {
+        let (dracoon, mut mock_server) = get_provisioning_client().await;
+        let res = include_str!("./responses/users/users_ok.json");
+
+        let customer_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/provisioning/customers/1/users?limit=500&offset=0&filter=isLocked%3Aeq%3Atrue",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(res)
+            .create();
+
+        let users = dracoon
+            .provisioning()
+            .get_all_customer_users(1, Some(UsersFilter::is_locked(true)))
+            .await
+            .unwrap();
+
+        customer_mock.assert();
+
+        assert_eq!(users.len(), 1);
+        assert_user_item(users.first().unwrap());
+    }
 }
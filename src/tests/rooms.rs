@@ -5,11 +5,12 @@ mod tests {
     use crate::{
         nodes::{
             ConfigRoomRequest, CreateRoomRequest, EncryptRoomRequest, GroupMemberAcceptance,
-            NodePermissions, RoomGroup, RoomGroupsAddBatchRequestItem, RoomPoliciesRequest,
-            RoomUser, RoomUsersAddBatchRequestItem, UpdateRoomRequest, UserType,
+            NodePermissions, RoomGroup, RoomGroupsAddBatchRequestItem, RoomInvitation,
+            RoomPoliciesRequest, RoomTemplate, RoomUser, RoomUsersAddBatchRequestItem,
+            RoomWebhookAssignment, UpdateRoomRequest, UserType,
         },
         tests::{dracoon::get_connected_client, nodes::tests::assert_node},
-        ListAllParams, Rooms,
+        ListAllParams, RoomInvitations, Rooms,
     };
 
     fn assert_room_user(room_user: &RoomUser) {
@@ -110,6 +111,81 @@ mod tests {
         assert_node(&room);
     }
 
+    #[tokio::test]
+    async fn test_create_room_with_s3_tags() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let room_res = include_str!("../tests/responses/nodes/node_ok.json");
+        let s3_tags_res = include_str!("../tests/responses/config/s3_tags_ok.json");
+
+        let room_mock = mock_server
+            .mock("POST", "/api/v4/nodes/rooms")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"name": "test"}),
+            ))
+            .with_status(201)
+            .with_body(room_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let s3_tags_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/2/s3_tags")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"ids": [1, 2]})))
+            .with_status(200)
+            .with_body(s3_tags_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let room_req = CreateRoomRequest::builder("test")
+            .with_s3_tag_ids(vec![1, 2])
+            .build();
+
+        let room = client.nodes().create_room(room_req).await.unwrap();
+
+        room_mock.assert();
+        s3_tags_mock.assert();
+
+        assert_node(&room);
+    }
+
+    #[tokio::test]
+    async fn test_create_room_with_s3_tags_rollback() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let room_res = include_str!("../tests/responses/nodes/node_ok.json");
+
+        let room_mock = mock_server
+            .mock("POST", "/api/v4/nodes/rooms")
+            .with_status(201)
+            .with_body(room_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let s3_tags_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/2/s3_tags")
+            .with_status(404)
+            .with_body(r#"{"code":404,"message":"Not Found","debugInfo":"S3 tag not found","errorCode":-90034}"#)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let delete_mock = mock_server
+            .mock("DELETE", "/api/v4/nodes/2")
+            .with_status(204)
+            .create();
+
+        let room_req = CreateRoomRequest::builder("test")
+            .with_s3_tag_ids(vec![1, 2])
+            .build();
+
+        let err = client.nodes().create_room(room_req).await.unwrap_err();
+
+        room_mock.assert();
+        s3_tags_mock.assert();
+        delete_mock.assert();
+
+        assert!(err.is_not_found());
+    }
+
     #[tokio::test]
     async fn test_update_room() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -338,7 +414,7 @@ mod tests {
 
         let user_updates = vec![RoomUsersAddBatchRequestItem::new(
             1,
-            NodePermissions::new_with_read_permissions(),
+            NodePermissions::read_only(),
         )];
 
         client
@@ -466,7 +542,7 @@ mod tests {
 
         let group_updates = vec![RoomGroupsAddBatchRequestItem::new(
             1,
-            NodePermissions::new_with_edit_permissions(),
+            NodePermissions::edit(),
             Some(GroupMemberAcceptance::Pending),
         )];
 
@@ -498,4 +574,623 @@ mod tests {
 
         room_groups_mock.assert();
     }
+
+    #[tokio::test]
+    async fn test_get_room_webhooks() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let room_webhooks_res = include_str!("../tests/responses/nodes/room_webhooks_ok.json");
+
+        let room_webhooks_mock = mock_server
+            .mock("GET", "/api/v4/nodes/rooms/123/webhooks?offset=0")
+            .with_status(200)
+            .with_body(room_webhooks_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let room_webhooks = client.nodes().get_room_webhooks(123, None).await.unwrap();
+
+        room_webhooks_mock.assert();
+
+        assert_eq!(room_webhooks.range.total, 1);
+        assert_eq!(room_webhooks.items.len(), 1);
+
+        let room_webhook = room_webhooks.items.first().unwrap();
+
+        assert!(room_webhook.is_assigned);
+        assert_eq!(room_webhook.webhook.id, 1);
+        assert_eq!(room_webhook.webhook.name, "string");
+        assert_eq!(room_webhook.webhook.url, "https://example.com/webhook");
+        assert!(room_webhook.webhook.is_enabled);
+        assert_eq!(room_webhook.webhook.event_type_names, vec!["node.created"]);
+        assert_eq!(room_webhook.webhook.fail_status, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_update_room_webhooks() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let room_webhooks_res = include_str!("../tests/responses/nodes/room_webhooks_ok.json");
+
+        let room_webhooks_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/123/webhooks")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "items": [{"webhookId": 1, "isAssigned": true}]
+            })))
+            .with_status(200)
+            .with_body(room_webhooks_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let assignments = vec![RoomWebhookAssignment::assign(1)];
+
+        let room_webhooks = client
+            .nodes()
+            .update_room_webhooks(123, assignments.into())
+            .await
+            .unwrap();
+
+        room_webhooks_mock.assert();
+
+        assert_eq!(room_webhooks.items.len(), 1);
+        assert!(room_webhooks.items.first().unwrap().is_assigned);
+    }
+
+    #[tokio::test]
+    async fn test_get_room_s3_tags() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let s3_tags_res = include_str!("../tests/responses/config/s3_tags_ok.json");
+
+        let s3_tags_mock = mock_server
+            .mock("GET", "/api/v4/nodes/rooms/123/s3_tags")
+            .with_status(200)
+            .with_body(s3_tags_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let s3_tags = client.nodes().get_room_s3_tags(123).await.unwrap();
+
+        s3_tags_mock.assert();
+
+        let s3_tags = s3_tags.items.unwrap();
+        assert_eq!(s3_tags.len(), 1);
+
+        let s3_tag = s3_tags.first().unwrap();
+        assert_eq!(s3_tag.id, Some(0));
+        assert_eq!(s3_tag.key.as_ref().unwrap(), "string");
+        assert_eq!(s3_tag.value.as_ref().unwrap(), "string");
+        assert_eq!(s3_tag.is_mandatory, Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_update_room_s3_tags() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let s3_tags_res = include_str!("../tests/responses/config/s3_tags_ok.json");
+
+        let s3_tags_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/123/s3_tags")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"ids": [0]})))
+            .with_status(200)
+            .with_body(s3_tags_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let s3_tags = client
+            .nodes()
+            .update_room_s3_tags(123, vec![0].into())
+            .await
+            .unwrap();
+
+        s3_tags_mock.assert();
+
+        assert_eq!(s3_tags.items.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_set_room_admins() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let room_users_res = include_str!("../tests/responses/nodes/room_users_ok.json");
+        let room_groups_res = include_str!("../tests/responses/nodes/room_groups_ok.json");
+
+        let room_users_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/nodes/rooms/123/users?limit=500&offset=0&filter=isGranted%3Aeq%3Atrue",
+            )
+            .with_status(200)
+            .with_body(room_users_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let room_groups_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/nodes/rooms/123/groups?limit=500&offset=0&filter=isGranted%3Aeq%3Atrue",
+            )
+            .with_status(200)
+            .with_body(room_groups_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // user 1 is granted admin permissions
+        let grant_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/123/users")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "items": [{
+                    "id": 1,
+                    "permissions": {
+                        "manage": true,
+                        "read": true,
+                        "create": true,
+                        "change": true,
+                        "delete": true,
+                        "manageDownloadShare": true,
+                        "manageUploadShare": true,
+                        "readRecycleBin": true,
+                        "restoreRecycleBin": true,
+                        "deleteRecycleBin": true
+                    }
+                }]
+            })))
+            .with_status(204)
+            .create();
+
+        // user 3 loses admin permissions but keeps all others
+        let revoke_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/123/users")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "items": [{
+                    "id": 3,
+                    "permissions": {
+                        "manage": false,
+                        "read": true,
+                        "create": true,
+                        "change": true,
+                        "delete": true,
+                        "manageDownloadShare": true,
+                        "manageUploadShare": true,
+                        "readRecycleBin": true,
+                        "restoreRecycleBin": true,
+                        "deleteRecycleBin": true
+                    }
+                }]
+            })))
+            .with_status(204)
+            .create();
+
+        // group 3 already is an admin group - no update expected
+        let room_groups_update_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/123/groups")
+            .expect(0)
+            .create();
+
+        client
+            .nodes()
+            .set_room_admins(123, vec![1], vec![3])
+            .await
+            .unwrap();
+
+        room_users_mock.assert();
+        room_groups_mock.assert();
+        grant_mock.assert();
+        revoke_mock.assert();
+        room_groups_update_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_invite_to_room_existing_user() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let users_res = include_str!("./responses/users/users_ok.json");
+
+        let users_mock = mock_server
+            .mock("GET", "/api/v4/users?offset=0&filter=email%3Aeq%3Astring")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(users_res)
+            .create();
+
+        let create_user_mock = mock_server.mock("POST", "/api/v4/users").expect(0).create();
+
+        let room_users_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/123/users")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "items": [{
+                    "id": 1,
+                    "permissions": {
+                        "manage": false,
+                        "read": true,
+                        "create": false,
+                        "change": false,
+                        "delete": false,
+                        "manageDownloadShare": false,
+                        "manageUploadShare": false,
+                        "readRecycleBin": false,
+                        "restoreRecycleBin": false,
+                        "deleteRecycleBin": false
+                    }
+                }]
+            })))
+            .with_status(204)
+            .create();
+
+        let invitation = RoomInvitation::builder("string", "string", "string").build();
+
+        let result = client.invite_to_room(123, invitation).await.unwrap();
+
+        users_mock.assert();
+        create_user_mock.assert();
+        room_users_mock.assert();
+
+        assert_eq!(result.user_id, 1);
+        assert!(!result.created);
+    }
+
+    #[tokio::test]
+    async fn test_invite_to_room_new_user() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let user_res = include_str!("./responses/users/User_ok.json");
+
+        let users_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/users?offset=0&filter=email%3Aeq%3Aguest%40example.com",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"range":{"offset":0,"limit":0,"total":0},"items":[]}"#)
+            .create();
+
+        let create_user_mock = mock_server
+            .mock("POST", "/api/v4/users")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "firstName": "Jane",
+                "lastName": "Doe",
+                "userName": "guest@example.com",
+                "email": "guest@example.com",
+                "notifyUser": true
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(user_res)
+            .create();
+
+        let room_users_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/123/users")
+            .with_status(204)
+            .create();
+
+        let invitation = RoomInvitation::builder("guest@example.com", "Jane", "Doe")
+            .with_edit_permissions()
+            .with_notify_user(true)
+            .build();
+
+        let result = client.invite_to_room(123, invitation).await.unwrap();
+
+        users_mock.assert();
+        create_user_mock.assert();
+        room_users_mock.assert();
+
+        assert_eq!(result.user_id, 1);
+        assert!(result.created);
+    }
+
+    #[test]
+    fn test_node_permissions_presets() {
+        assert_eq!(NodePermissions::read_only().to_string(), "-r---------");
+        assert_eq!(NodePermissions::edit().to_string(), "-rwcd-mmrr-");
+        assert_eq!(NodePermissions::room_admin().to_string(), "mrwcd-mmrrd");
+
+        let permissions = NodePermissions::builder()
+            .with_read(true)
+            .with_create(true)
+            .build();
+
+        assert_eq!(permissions.to_string(), "-rw--------");
+    }
+
+    #[tokio::test]
+    async fn test_clone_room_without_files() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let mut source: serde_json::Value =
+            serde_json::from_str(include_str!("./responses/nodes/node_ok.json")).unwrap();
+        source["id"] = serde_json::json!(123);
+        source["inheritPermissions"] = serde_json::json!(false);
+
+        let room_res = include_str!("./responses/nodes/node_ok.json");
+        let folder_res = include_str!("./responses/nodes/folder_ok.json");
+        let policies_res = include_str!("./responses/nodes/room_policies_ok.json");
+        let room_users_res = include_str!("./responses/nodes/room_users_ok.json");
+        let room_groups_res = include_str!("./responses/nodes/room_groups_ok.json");
+
+        let mut children: serde_json::Value =
+            serde_json::from_str(include_str!("./responses/nodes/nodes_ok.json")).unwrap();
+        let template = children["items"][0].clone();
+        let mut folder = template.clone();
+        folder["id"] = serde_json::json!(10);
+        folder["type"] = serde_json::json!("folder");
+        folder["name"] = serde_json::json!("folder");
+        let mut file = template;
+        file["id"] = serde_json::json!(11);
+        file["type"] = serde_json::json!("file");
+        children["items"] = serde_json::json!([folder, file]);
+        children["range"]["total"] = serde_json::json!(2);
+
+        let source_mock = mock_server
+            .mock("GET", "/api/v4/nodes/123")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(source.to_string())
+            .create();
+
+        let policies_mock = mock_server
+            .mock("GET", "/api/v4/nodes/rooms/123/policies")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(policies_res)
+            .create();
+
+        let room_users_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/nodes/rooms/123/users?limit=500&offset=0&filter=isGranted%3Aeq%3Atrue",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(room_users_res)
+            .create();
+
+        let room_groups_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/nodes/rooms/123/groups?limit=500&offset=0&filter=isGranted%3Aeq%3Atrue",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(room_groups_res)
+            .create();
+
+        // user 3 and group 3 are admins of the source room
+        let create_room_mock = mock_server
+            .mock("POST", "/api/v4/nodes/rooms")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "name": "string",
+                "parentId": 456,
+                "recycleBinRetentionPeriod": 9999,
+                "inheritPermissions": false,
+                "adminIds": [3],
+                "adminGroupIds": [3],
+                "notes": "string",
+                "hasActivitiesLog": true,
+                "classification": 4
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(room_res)
+            .create();
+
+        let update_policies_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/2/policies")
+            .with_status(204)
+            .create();
+
+        let children_mock = mock_server
+            .mock("GET", "/api/v4/nodes?limit=500&offset=0&parent_id=123")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(children.to_string())
+            .create();
+
+        let folder_children_mock = mock_server
+            .mock("GET", "/api/v4/nodes?limit=500&offset=0&parent_id=10")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"range":{"offset":0,"limit":500,"total":0},"items":[]}"#)
+            .create();
+
+        let create_folder_mock = mock_server
+            .mock("POST", "/api/v4/nodes/folders")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "name": "folder",
+                "parentId": 2
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(folder_res)
+            .create();
+
+        let copy_mock = mock_server
+            .mock("POST", "/api/v4/nodes/2/copy_to")
+            .expect(0)
+            .create();
+
+        let room = client
+            .nodes()
+            .clone_room(123, Some(456), false)
+            .await
+            .unwrap();
+
+        source_mock.assert();
+        policies_mock.assert();
+        room_users_mock.assert();
+        room_groups_mock.assert();
+        create_room_mock.assert();
+        update_policies_mock.assert();
+        children_mock.assert();
+        folder_children_mock.assert();
+        create_folder_mock.assert();
+        copy_mock.assert();
+
+        assert_eq!(room.id, 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_all_rooms() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        // rooms 1 and 2 on root level, room 3 is nested in room 1
+        let root_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/nodes?limit=500&offset=0&filter=type%3Aeq%3Aroom",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!("./responses/nodes/rooms_root_ok.json"))
+            .create();
+
+        let room_1_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/nodes?limit=500&offset=0&filter=type%3Aeq%3Aroom&parent_id=1",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!("./responses/nodes/rooms_nested_ok.json"))
+            .create();
+
+        let empty_mocks = [2, 3]
+            .iter()
+            .map(|id| {
+                mock_server
+                    .mock(
+                        "GET",
+                        format!(
+                            "/api/v4/nodes?limit=500&offset=0&filter=type%3Aeq%3Aroom&parent_id={id}"
+                        )
+                        .as_str(),
+                    )
+                    .with_status(200)
+                    .with_header("content-type", "application/json")
+                    .with_body(include_str!("./responses/nodes/nodes_empty_ok.json"))
+                    .create()
+            })
+            .collect::<Vec<_>>();
+
+        let rooms = client.nodes().get_all_rooms(None).await.unwrap();
+
+        root_mock.assert();
+        room_1_mock.assert();
+        empty_mocks.iter().for_each(|mock| mock.assert());
+
+        let ids = rooms.iter().map(|room| room.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_apply_template() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let room_res = include_str!("./responses/nodes/node_ok.json");
+        let folder_res = include_str!("./responses/nodes/folder_ok.json");
+
+        let create_room_mock = mock_server
+            .mock("POST", "/api/v4/nodes/rooms")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "name": "Project",
+                "parentId": 123,
+                "inheritPermissions": false,
+                "adminIds": [1],
+                "classification": 3
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(room_res)
+            .create();
+
+        let room_users_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/2/users")
+            .with_status(204)
+            .create();
+
+        let room_folder_mock = mock_server
+            .mock("POST", "/api/v4/nodes/folders")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "name": "Documents",
+                "parentId": 2
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(folder_res)
+            .expect(1)
+            .create();
+
+        // folder_ok.json has id 2
+        let sub_folder_mock = mock_server
+            .mock("POST", "/api/v4/nodes/folders")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "name": "Internal",
+                "parentId": 2
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(folder_res)
+            .expect(1)
+            .create();
+
+        let template = RoomTemplate::builder("Project")
+            .with_classification(3)
+            .with_inherit_permissions(false)
+            .with_admin_ids(vec![1])
+            .with_user_permissions(4, NodePermissions::read_only())
+            .with_folder("Documents")
+            .with_folder("/Documents/Internal/")
+            .build();
+
+        let room = client
+            .nodes()
+            .apply_template(Some(123), &template)
+            .await
+            .unwrap();
+
+        create_room_mock.assert();
+        room_users_mock.assert();
+        room_folder_mock.assert();
+        sub_folder_mock.assert();
+
+        assert_eq!(room.id, 2);
+    }
+
+    #[tokio::test]
+    async fn test_apply_template_rollback() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let room_res = include_str!("./responses/nodes/node_ok.json");
+
+        let create_room_mock = mock_server
+            .mock("POST", "/api/v4/nodes/rooms")
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(room_res)
+            .create();
+
+        let folder_mock = mock_server
+            .mock("POST", "/api/v4/nodes/folders")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"code":400,"message":"Bad Request","debugInfo":"Bad Request","errorCode":-80001}"#)
+            .create();
+
+        let delete_room_mock = mock_server
+            .mock("DELETE", "/api/v4/nodes/2")
+            .with_status(204)
+            .create();
+
+        let template = RoomTemplate::builder("Project")
+            .with_folder("Documents")
+            .build();
+
+        let res = client.nodes().apply_template(None, &template).await;
+
+        create_room_mock.assert();
+        folder_mock.assert();
+        delete_room_mock.assert();
+
+        assert!(res.is_err());
+    }
 }
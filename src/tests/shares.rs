@@ -10,7 +10,11 @@ mod download_share_tests {
             UpdateDownloadShareRequest, UpdateDownloadSharesBulkRequest,
         },
         tests::dracoon::get_connected_client,
-        DownloadShares, ListAllParams, SortOrder,
+        DownloadShares, DracoonClientError, EncryptedDownloadShares, ListAllParams,
+        ObjectExpiration, SortOrder,
+    };
+    use dco3_crypto::{
+        DracoonCrypto, DracoonRSACrypto, Encrypt, FileKey, UserKeyPairContainer, UserKeyPairVersion,
     };
 
     fn assert_download_share(share: &DownloadShare) {
@@ -281,6 +285,37 @@ mod download_share_tests {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_delete_download_shares_in_batches() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let first_batch_mock = mock_server
+            .mock("DELETE", "/api/v4/shares/downloads")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "shareIds": (1..=100).collect::<Vec<u64>>()
+            })))
+            .with_status(204)
+            .create();
+        let last_batch_mock = mock_server
+            .mock("DELETE", "/api/v4/shares/downloads")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "shareIds": (101..=150).collect::<Vec<u64>>()
+            })))
+            .with_status(204)
+            .create();
+
+        let share_ids = (1..=150).collect::<Vec<u64>>();
+
+        let res = client
+            .shares()
+            .delete_download_shares(share_ids.into())
+            .await;
+
+        first_batch_mock.assert();
+        last_batch_mock.assert();
+        assert!(res.is_ok());
+    }
+
     #[tokio::test]
     async fn test_create_download_share() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -306,6 +341,208 @@ mod download_share_tests {
         assert_download_share(&share);
     }
 
+    #[tokio::test]
+    async fn test_create_download_share_with_sms_recipients() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let share_res = include_str!("./responses/shares/download_share_ok.json");
+
+        let share_mock = mock_server
+            .mock("POST", "/api/v4/shares/downloads")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "nodeId": 1,
+                "password": "VerySecret123!",
+                "textMessageRecipients": ["+491701234567", "01701234567"],
+                "defaultCountry": "DE"
+            })))
+            .with_status(201)
+            .with_body(share_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let share = CreateDownloadShareRequest::builder(1)
+            .with_password("VerySecret123!")
+            .with_text_message_recipients(vec!["+491701234567".into(), "01701234567".into()])
+            .with_default_country("DE")
+            .build();
+
+        let share = client.shares().create_download_share(share).await.unwrap();
+
+        share_mock.assert();
+
+        assert_download_share(&share);
+    }
+
+    #[tokio::test]
+    async fn test_create_download_shares() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let share_res = include_str!("./responses/shares/download_share_ok.json");
+
+        let share_mock = mock_server
+            .mock("POST", "/api/v4/shares/downloads")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "nodeId": 1,
+                "password": "VerySecret123!",
+                "maxDownloads": 2
+            })))
+            .with_status(201)
+            .with_body(share_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let not_found_mock = mock_server
+            .mock("POST", "/api/v4/shares/downloads")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "nodeId": 2,
+                "password": "VerySecret123!",
+                "maxDownloads": 2
+            })))
+            .with_status(404)
+            .with_body(r#"{"code":404,"message":"Not Found","debugInfo":"Node not found","errorCode":-41000}"#)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let options = CreateDownloadShareRequest::builder(0)
+            .with_password("VerySecret123!")
+            .with_max_downloads(2)
+            .build();
+
+        let shares = client
+            .shares()
+            .create_download_shares(vec![1, 2, 1], options, Some(2))
+            .await;
+
+        share_mock.assert();
+        not_found_mock.assert();
+
+        assert_eq!(shares.len(), 2);
+        assert_download_share(shares[&1].as_ref().unwrap());
+        assert!(shares[&2].as_ref().unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_create_download_shares_encrypted_options() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let share_mock = mock_server
+            .mock("POST", "/api/v4/shares/downloads")
+            .expect(0)
+            .create();
+
+        let keypair =
+            DracoonCrypto::create_plain_user_keypair(UserKeyPairVersion::RSA4096).unwrap();
+        let enc_keypair = DracoonCrypto::encrypt_private_key("VerySecret123!", keypair).unwrap();
+
+        let options = CreateDownloadShareRequest::builder(0)
+            .with_keypair(enc_keypair)
+            .build();
+
+        let shares = client
+            .shares()
+            .create_download_shares(vec![1, 2], options, None)
+            .await;
+
+        share_mock.assert();
+
+        assert_eq!(shares.len(), 2);
+        assert!(shares[&1].as_ref().unwrap_err().is_invalid_share_options());
+        assert!(shares[&2].as_ref().unwrap_err().is_invalid_share_options());
+    }
+
+    #[tokio::test]
+    async fn test_create_encrypted_download_share() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let share_res = include_str!("./responses/shares/download_share_ok.json");
+
+        let (_, plain_key) = DracoonCrypto::encrypt(b"secret".to_vec()).unwrap();
+        let plain_key_value = plain_key.key.clone();
+        let keypair =
+            DracoonCrypto::create_plain_user_keypair(UserKeyPairVersion::RSA4096).unwrap();
+        let enc_keypair =
+            DracoonCrypto::encrypt_private_key("TopSecret1234!", keypair.clone()).unwrap();
+        let file_key = DracoonCrypto::encrypt_file_key(plain_key, keypair).unwrap();
+
+        let keypair_mock = mock_server
+            .mock("GET", "/api/v4/user/account/keypair")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&enc_keypair).unwrap())
+            .create();
+
+        let file_key_mock = mock_server
+            .mock("GET", "/api/v4/nodes/files/1/user_file_key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&file_key).unwrap())
+            .create();
+
+        let sent_body = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let captured_body = sent_body.clone();
+
+        let share_mock = mock_server
+            .mock("POST", "/api/v4/shares/downloads")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({ "nodeId": 1 }),
+            ))
+            .match_request(move |req| {
+                *captured_body.lock().unwrap() = req.body().unwrap().clone();
+                true
+            })
+            .with_status(201)
+            .with_body(share_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let _kp = client
+            .get_keypair(Some("TopSecret1234!".into()))
+            .await
+            .unwrap();
+
+        let share = CreateDownloadShareRequest::builder(1)
+            .with_password("VerySecret123!")
+            .build();
+
+        let share = client.create_encrypted_download_share(share).await.unwrap();
+
+        keypair_mock.assert();
+        file_key_mock.assert();
+        share_mock.assert();
+
+        assert_download_share(&share);
+
+        let body: serde_json::Value = serde_json::from_slice(&sent_body.lock().unwrap()).unwrap();
+
+        // the share password protects the share keypair and must never be sent
+        assert!(body.get("password").is_none());
+
+        // the file key must be decryptable with the share keypair (and the share password)
+        let share_keypair: UserKeyPairContainer =
+            serde_json::from_value(body["keyPair"].clone()).unwrap();
+        let share_file_key: FileKey = serde_json::from_value(body["fileKey"].clone()).unwrap();
+        let share_keypair =
+            DracoonCrypto::decrypt_private_key("VerySecret123!", &share_keypair).unwrap();
+        let share_plain_key =
+            DracoonCrypto::decrypt_file_key(share_file_key, share_keypair).unwrap();
+
+        assert_eq!(share_plain_key.key, plain_key_value);
+    }
+
+    #[tokio::test]
+    async fn test_create_encrypted_download_share_without_password() {
+        let (client, _mock_server) = get_connected_client().await;
+
+        let share = CreateDownloadShareRequest::builder(1).build();
+
+        let err = client
+            .create_encrypted_download_share(share)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err, DracoonClientError::MissingArgument);
+    }
+
     #[tokio::test]
     async fn test_get_download_share() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -355,6 +592,45 @@ mod download_share_tests {
         assert_download_share(&share);
     }
 
+    #[tokio::test]
+    async fn test_update_download_share_request_body() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let share_res = include_str!("./responses/shares/download_share_ok.json");
+
+        let share_mock = mock_server
+            .mock("PUT", "/api/v4/shares/downloads/123")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "expiration": { "enableExpiration": false, "expireAt": null },
+                "notes": "updated",
+                "showCreatorName": true,
+                "resetPassword": true,
+                "resetMaxDownloads": true
+            })))
+            .with_status(200)
+            .with_body(share_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let update = UpdateDownloadShareRequest::builder()
+            .with_expiration(ObjectExpiration::default())
+            .with_notes("updated")
+            .with_show_creator_name(true)
+            .with_reset_password(true)
+            .with_reset_max_downloads(true)
+            .build();
+
+        let share = client
+            .shares()
+            .update_download_share(123, update)
+            .await
+            .unwrap();
+
+        share_mock.assert();
+
+        assert_download_share(&share);
+    }
+
     #[tokio::test]
     async fn test_delete_download_share() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -392,6 +668,33 @@ mod download_share_tests {
 
         assert!(res.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_send_download_share_email_with_builder() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let share_mock = mock_server
+            .mock("POST", "/api/v4/shares/downloads/123/email")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "recipients": ["foo@localhost", "bar@localhost"],
+                "body": "test",
+                "receiverLanguage": "de-DE"
+            })))
+            .with_status(204)
+            .create();
+
+        let email = DownloadShareLinkEmail::builder("test")
+            .with_recipient("foo@localhost")
+            .with_recipients(vec!["bar@localhost".into()])
+            .with_receiver_language("de-DE")
+            .build();
+
+        let res = client.shares().send_download_share_email(123, email).await;
+
+        share_mock.assert();
+
+        assert!(res.is_ok());
+    }
 }
 
 #[cfg(test)]
@@ -404,7 +707,7 @@ mod upload_share_tests {
             UploadShare, UploadShareLinkEmail, UploadSharesFilter, UploadSharesSortBy,
         },
         tests::dracoon::get_connected_client,
-        ListAllParams, SortOrder, UploadShares,
+        ListAllParams, ObjectExpiration, SortOrder, UploadShares,
     };
 
     fn assert_upload_share(share: &UploadShare) {
@@ -692,6 +995,34 @@ mod upload_share_tests {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_delete_upload_shares_in_batches() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let first_batch_mock = mock_server
+            .mock("DELETE", "/api/v4/shares/uploads")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "shareIds": (1..=100).collect::<Vec<u64>>()
+            })))
+            .with_status(204)
+            .create();
+        let last_batch_mock = mock_server
+            .mock("DELETE", "/api/v4/shares/uploads")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "shareIds": (101..=150).collect::<Vec<u64>>()
+            })))
+            .with_status(204)
+            .create();
+
+        let share_ids = (1..=150).collect::<Vec<u64>>();
+
+        let res = client.shares().delete_upload_shares(share_ids.into()).await;
+
+        first_batch_mock.assert();
+        last_batch_mock.assert();
+        assert!(res.is_ok());
+    }
+
     #[tokio::test]
     async fn test_create_upload_share() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -720,6 +1051,42 @@ mod upload_share_tests {
         assert_upload_share(&share);
     }
 
+    #[tokio::test]
+    async fn test_create_upload_share_with_sms_recipients() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let share_res = include_str!("./responses/shares/upload_share_ok.json");
+
+        let share_mock = mock_server
+            .mock("POST", "/api/v4/shares/uploads")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "targetId": 1,
+                "password": "VerySecret123!",
+                "showUploadedFiles": true,
+                "textMessageRecipients": ["+491701234567"],
+                "defaultCountry": "DE",
+                "receiverLanguage": "de-DE"
+            })))
+            .with_status(201)
+            .with_body(share_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let share = CreateUploadShareRequest::builder(1)
+            .with_password("VerySecret123!")
+            .with_show_uploaded_files(true)
+            .with_text_message_recipients(vec!["+491701234567".into()])
+            .with_default_country("DE")
+            .with_receiver_language("de-DE")
+            .build();
+
+        let share = client.shares().create_upload_share(share).await.unwrap();
+
+        share_mock.assert();
+
+        assert_upload_share(&share);
+    }
+
     #[tokio::test]
     async fn test_get_upload_share() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -740,6 +1107,37 @@ mod upload_share_tests {
         assert_upload_share(&share);
     }
 
+    #[tokio::test]
+    async fn test_get_upload_share_files() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let share_res = include_str!("./responses/shares/upload_share_ok.json");
+        let public_share_res = include_str!("./responses/public/upload_share_ok.json");
+
+        let share_mock = mock_server
+            .mock("GET", "/api/v4/shares/uploads/123")
+            .with_status(200)
+            .with_body(share_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let public_share_mock = mock_server
+            .mock("GET", "/api/v4/public/shares/uploads/string")
+            .with_status(200)
+            .with_body(public_share_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let files = client.shares().get_upload_share_files(123).await.unwrap();
+
+        share_mock.assert();
+        public_share_mock.assert();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "string");
+        assert_eq!(files[0].size, 16);
+    }
+
     #[tokio::test]
     async fn test_update_upload_share() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -769,6 +1167,45 @@ mod upload_share_tests {
         assert_upload_share(&share);
     }
 
+    #[tokio::test]
+    async fn test_update_upload_share_request_body() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let share_res = include_str!("./responses/shares/upload_share_ok.json");
+
+        let share_mock = mock_server
+            .mock("PUT", "/api/v4/shares/uploads/123")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "expiration": { "enableExpiration": false, "expireAt": null },
+                "maxSize": 1024,
+                "showUploadedFiles": true,
+                "notes": "updated",
+                "resetMaxSlots": true
+            })))
+            .with_status(200)
+            .with_body(share_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let update = UpdateUploadShareRequest::builder()
+            .with_expiration(ObjectExpiration::default())
+            .with_max_size(1024)
+            .with_show_uploaded_files(true)
+            .with_notes("updated")
+            .with_reset_max_slots(true)
+            .build();
+
+        let share = client
+            .shares()
+            .update_upload_share(123, update)
+            .await
+            .unwrap();
+
+        share_mock.assert();
+
+        assert_upload_share(&share);
+    }
+
     #[tokio::test]
     async fn test_delete_upload_share() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -802,4 +1239,303 @@ mod upload_share_tests {
 
         assert!(res.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_send_upload_share_email_with_builder() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let shares_mock = mock_server
+            .mock("POST", "/api/v4/shares/uploads/123/email")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "body": "test",
+                "recipients": ["foo@localhost", "bar@localhost"],
+                "receiverLanguage": "en-US"
+            })))
+            .with_status(204)
+            .create();
+
+        let email = UploadShareLinkEmail::builder("test")
+            .with_recipients(vec!["foo@localhost".into(), "bar@localhost".into()])
+            .with_receiver_language("en-US")
+            .build();
+
+        let res = client.shares().send_upload_share_email(123, email).await;
+
+        shares_mock.assert();
+
+        assert!(res.is_ok());
+    }
+}
+
+#[cfg(test)]
+mod share_management_tests {
+    use crate::{
+        shares::{DownloadShare, UploadShare},
+        tests::dracoon::get_connected_client,
+        ShareManagement,
+    };
+
+    /// download shares list with an expired (id 1) and an active share (id 3)
+    fn download_shares_with_active_share() -> String {
+        let mut shares: serde_json::Value =
+            serde_json::from_str(include_str!("./responses/shares/download_shares_ok.json"))
+                .unwrap();
+
+        let mut active = shares["items"][0].clone();
+        active["id"] = serde_json::json!(3);
+        active["expireAt"] = serde_json::json!("2099-01-01T00:00:00.000Z");
+        active["maxDownloads"] = serde_json::Value::Null;
+
+        shares["items"].as_array_mut().unwrap().push(active);
+        shares["range"]["total"] = serde_json::json!(2);
+
+        shares.to_string()
+    }
+
+    #[tokio::test]
+    async fn test_get_shares_for_node() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let download_shares_res = include_str!("./responses/shares/download_shares_ok.json");
+        let upload_shares_res = include_str!("./responses/shares/upload_shares_ok.json");
+
+        let download_shares_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/shares/downloads?limit=500&offset=0&filter=nodeId%3Aeq%3A2",
+            )
+            .with_status(200)
+            .with_body(download_shares_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_shares_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/shares/uploads?limit=500&offset=0&filter=targetId%3Aeq%3A2",
+            )
+            .with_status(200)
+            .with_body(upload_shares_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let shares = client.shares().get_shares_for_node(2).await.unwrap();
+
+        download_shares_mock.assert();
+        upload_shares_mock.assert();
+
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares.download_shares[0].node_id, 2);
+        assert_eq!(shares.upload_shares[0].target_id, 2);
+    }
+
+    #[tokio::test]
+    async fn test_prune_expired_shares_dry_run() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let upload_shares_res = include_str!("./responses/shares/upload_shares_ok.json");
+
+        let download_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/downloads?limit=500&offset=0")
+            .with_status(200)
+            .with_body(download_shares_with_active_share())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/uploads?limit=500&offset=0")
+            .with_status(200)
+            .with_body(upload_shares_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let delete_mock = mock_server
+            .mock("DELETE", mockito::Matcher::Any)
+            .expect(0)
+            .create();
+
+        let pruned = client.shares().prune_expired_shares(true).await.unwrap();
+
+        download_shares_mock.assert();
+        upload_shares_mock.assert();
+        delete_mock.assert();
+
+        assert_eq!(pruned.len(), 2);
+        assert_eq!(pruned.download_shares[0].id, 1);
+        assert_eq!(pruned.upload_shares[0].id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_prune_expired_shares() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let upload_shares_res = include_str!("./responses/shares/upload_shares_ok.json");
+
+        let download_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/downloads?limit=500&offset=0")
+            .with_status(200)
+            .with_body(download_shares_with_active_share())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/uploads?limit=500&offset=0")
+            .with_status(200)
+            .with_body(upload_shares_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let delete_download_shares_mock = mock_server
+            .mock("DELETE", "/api/v4/shares/downloads")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "shareIds": [1]
+            })))
+            .with_status(204)
+            .create();
+
+        let delete_upload_shares_mock = mock_server
+            .mock("DELETE", "/api/v4/shares/uploads")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "shareIds": [1]
+            })))
+            .with_status(204)
+            .create();
+
+        let pruned = client.shares().prune_expired_shares(false).await.unwrap();
+
+        download_shares_mock.assert();
+        upload_shares_mock.assert();
+        delete_download_shares_mock.assert();
+        delete_upload_shares_mock.assert();
+
+        assert_eq!(pruned.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_share_urls() {
+        let (client, mock_server) = get_connected_client().await;
+
+        assert_eq!(
+            client.shares().download_share_url("abc").as_str(),
+            format!("{}/public/download-shares/abc", mock_server.url())
+        );
+        assert_eq!(
+            client.shares().upload_share_url("abc").as_str(),
+            format!("{}/public/upload-shares/abc", mock_server.url())
+        );
+
+        let download_share: DownloadShare =
+            serde_json::from_str(include_str!("./responses/shares/download_share_ok.json"))
+                .unwrap();
+        let upload_share: UploadShare =
+            serde_json::from_str(include_str!("./responses/shares/upload_share_ok.json")).unwrap();
+
+        let base_url = url::Url::parse("https://dracoon.team/tenant/").unwrap();
+
+        assert_eq!(
+            download_share.url(&base_url).as_str(),
+            "https://dracoon.team/tenant/public/download-shares/string"
+        );
+        assert_eq!(
+            upload_share.url(&base_url).as_str(),
+            "https://dracoon.team/tenant/public/upload-shares/string"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_share_usage() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let mut download_shares: serde_json::Value =
+            serde_json::from_str(&download_shares_with_active_share()).unwrap();
+        download_shares["items"][0]["nodePath"] = serde_json::json!("/Room A/folder/file.txt");
+        download_shares["items"][1]["nodePath"] = serde_json::json!("/Room B/file.txt");
+        download_shares["items"][1]["createdBy"]["id"] = serde_json::json!(4);
+
+        let mut upload_shares: serde_json::Value =
+            serde_json::from_str(include_str!("./responses/shares/upload_shares_ok.json")).unwrap();
+        upload_shares["items"][0]["targetPath"] = serde_json::json!("/Room A/uploads");
+
+        let download_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/downloads?limit=500&offset=0")
+            .with_status(200)
+            .with_body(download_shares.to_string())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/uploads?limit=500&offset=0")
+            .with_status(200)
+            .with_body(upload_shares.to_string())
+            .with_header("content-type", "application/json")
+            .create();
+
+        let usage = client.shares().get_share_usage().await.unwrap();
+
+        download_shares_mock.assert();
+        upload_shares_mock.assert();
+
+        assert_eq!(usage.total.download_shares, 2);
+        assert_eq!(usage.total.upload_shares, 1);
+        assert_eq!(usage.total.downloads, 20);
+        assert_eq!(usage.total.uploads, 15);
+        assert_eq!(usage.total.uploaded_files, 10);
+
+        let room_a = usage.by_room["Room A"];
+        assert_eq!(room_a.download_shares, 1);
+        assert_eq!(room_a.upload_shares, 1);
+        assert_eq!(room_a.downloads, 10);
+        assert_eq!(usage.by_room["Room B"].downloads, 10);
+
+        assert_eq!(usage.by_creator[&3].download_shares, 1);
+        assert_eq!(usage.by_creator[&3].upload_shares, 1);
+        assert_eq!(usage.by_creator[&4].download_shares, 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_shares_for_node_paginated() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let download_shares_res = include_str!("./responses/shares/download_shares_paged_ok.json");
+        let upload_shares_res = include_str!("./responses/shares/upload_shares_ok.json");
+
+        let download_shares_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/shares/downloads?limit=500&offset=0&filter=nodeId%3Aeq%3A2",
+            )
+            .with_status(200)
+            .with_body(download_shares_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let next_download_shares_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/shares/downloads?limit=500&offset=500&filter=nodeId%3Aeq%3A2",
+            )
+            .with_status(200)
+            .with_body(download_shares_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let upload_shares_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/shares/uploads?limit=500&offset=0&filter=targetId%3Aeq%3A2",
+            )
+            .with_status(200)
+            .with_body(upload_shares_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let shares = client.shares().get_shares_for_node(2).await.unwrap();
+
+        download_shares_mock.assert();
+        next_download_shares_mock.assert();
+        upload_shares_mock.assert();
+
+        assert_eq!(shares.download_shares.len(), 2);
+        assert_eq!(shares.upload_shares.len(), 1);
+    }
 }
@@ -1,11 +1,13 @@
 #[cfg(test)]
 mod tests {
     use dco3_crypto::{DracoonCrypto, DracoonRSACrypto, UserKeyPairContainer};
+    use futures_util::TryStreamExt;
 
     use crate::{
+        nodes::NodeType,
         tests::dracoon::{assert_user_account, get_connected_client},
-        user::UpdateUserAccountRequest,
-        User, UserAccountKeyPairs,
+        user::{UpdateSubscriptionsBulkRequest, UpdateUserAccountRequest},
+        NodeSubscriptions, User, UserAccountKeyPairs, UserAvatar,
     };
 
     #[tokio::test]
@@ -160,4 +162,173 @@ mod tests {
         assert_eq!(customer.cnt_internal_user.unwrap(), 9);
         assert_eq!(customer.customer_encryption_enabled, true);
     }
+
+    #[tokio::test]
+    async fn test_get_node_subscriptions() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let subscriptions_res = include_str!("../tests/responses/subscribed_nodes_ok.json");
+
+        let subscriptions_mock = mock_server
+            .mock("GET", "/api/v4/user/subscriptions/nodes?offset=0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(subscriptions_res)
+            .create();
+
+        let subscriptions = client.user().get_node_subscriptions(None).await.unwrap();
+
+        subscriptions_mock.assert();
+
+        assert_eq!(subscriptions.range.total, 1);
+        let subscription = subscriptions.items.first().unwrap();
+        assert_eq!(subscription.id, 2);
+        assert_eq!(subscription.node_type, NodeType::Room);
+        assert_eq!(subscription.auth_parent_id, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_node() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let subscription_res = include_str!("../tests/responses/subscribed_node_ok.json");
+
+        let subscription_mock = mock_server
+            .mock("POST", "/api/v4/user/subscriptions/nodes/2")
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(subscription_res)
+            .create();
+
+        let subscription = client.user().subscribe_node(2).await.unwrap();
+
+        subscription_mock.assert();
+
+        assert_eq!(subscription.id, 2);
+        assert_eq!(subscription.node_type, NodeType::Room);
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_node() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let subscription_mock = mock_server
+            .mock("DELETE", "/api/v4/user/subscriptions/nodes/2")
+            .with_status(204)
+            .create();
+
+        client.user().unsubscribe_node(2).await.unwrap();
+
+        subscription_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_update_node_subscriptions() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let subscriptions_mock = mock_server
+            .mock("PUT", "/api/v4/user/subscriptions/nodes")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "isSubscribed": false,
+                "objectIds": [1, 2]
+            })))
+            .with_status(204)
+            .create();
+
+        let req = UpdateSubscriptionsBulkRequest::unsubscribe(vec![1, 2]);
+
+        client.user().update_node_subscriptions(req).await.unwrap();
+
+        subscriptions_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_get_user_avatar() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let avatar_res = include_str!("../tests/responses/avatar_ok.json");
+
+        let avatar_mock = mock_server
+            .mock("GET", "/api/v4/user/account/avatar")
+            .with_status(200)
+            .with_body(avatar_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let avatar = client.user().get_user_avatar().await.unwrap();
+
+        avatar_mock.assert();
+
+        assert_eq!(avatar.avatar_uuid, "string");
+        assert_eq!(avatar.avatar_uri, "string");
+        assert!(avatar.is_custom_avatar);
+    }
+
+    #[tokio::test]
+    async fn test_download_avatar() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let avatar_bytes = vec![137, 80, 78, 71, 13, 10, 26, 10];
+
+        let avatar_mock = mock_server
+            .mock("GET", "/api/v4/downloads/avatar/123/some-uuid")
+            .with_status(200)
+            .with_body(avatar_bytes.clone())
+            .with_header("content-type", "image/png")
+            .create();
+
+        let avatar = client
+            .user()
+            .download_avatar(123, "some-uuid")
+            .await
+            .unwrap();
+
+        avatar_mock.assert();
+
+        assert_eq!(avatar.to_vec(), avatar_bytes);
+    }
+
+    #[tokio::test]
+    async fn test_download_avatar_stream() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let avatar_bytes = vec![137, 80, 78, 71, 13, 10, 26, 10];
+
+        let avatar_mock = mock_server
+            .mock("GET", "/api/v4/downloads/avatar/123/some-uuid")
+            .with_status(200)
+            .with_body(avatar_bytes.clone())
+            .with_header("content-type", "image/png")
+            .create();
+
+        let stream = client
+            .user()
+            .download_avatar_stream(123, "some-uuid")
+            .await
+            .unwrap();
+
+        let chunks = stream.try_collect::<Vec<_>>().await.unwrap();
+
+        avatar_mock.assert();
+
+        assert_eq!(chunks.concat(), avatar_bytes);
+    }
+
+    #[tokio::test]
+    async fn test_download_avatar_not_found() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let avatar_mock = mock_server
+            .mock("GET", "/api/v4/downloads/avatar/123/some-uuid")
+            .with_status(404)
+            .with_body(r#"{"code":404,"message":"Not Found","debugInfo":"Avatar not found","errorCode":-70501}"#)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let res = client.user().download_avatar(123, "some-uuid").await;
+
+        avatar_mock.assert();
+
+        assert!(res.unwrap_err().is_not_found());
+    }
 }
@@ -1,12 +1,15 @@
 #[cfg(test)]
 pub mod tests {
     use crate::{
+        roles::RoleType,
         tests::dracoon::get_connected_client,
         user::UserAuthData,
         users::{
-            CreateUserRequest, UpdateUserRequest, UserData, UserItem, UsersFilter, UsersSortBy,
+            AuthMethod, CreateUserRequest, UpdateUserRequest, UserAuthDataUpdateRequest, UserData,
+            UserDeletionStrategy, UserGroupsFilter, UserItem, UsersFilter, UsersSortBy,
         },
-        ListAllParams, SortOrder, Users,
+        DracoonClientError, ListAllParams, OrphanedRoom, SortOrder, UserDataExport, UserDeletion,
+        UserRoles, Users,
     };
 
     pub fn assert_user_item(user: &UserItem) {
@@ -173,6 +176,39 @@ pub mod tests {
         assert_user_item(user);
     }
 
+    #[tokio::test]
+    async fn test_get_users_with_multiple_filters() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let users_res = include_str!("./responses/users/users_ok.json");
+
+        let users_mock = mock_server
+            .mock("GET", "/api/v4/users")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "userName:cn:jdoe|firstName:eq:John|lastName:cn:Doe|isLocked:eq:false".into(),
+            ))
+            .with_status(200)
+            .with_body(users_res)
+            .create();
+
+        let params = ListAllParams::builder()
+            .with_filter(UsersFilter::username_contains("jdoe"))
+            .with_filter(UsersFilter::first_name_equals("John"))
+            .with_filter(UsersFilter::last_name_contains("Doe"))
+            .with_filter(UsersFilter::is_locked(false))
+            .build();
+
+        let users = client
+            .users()
+            .get_users(Some(params), None, None)
+            .await
+            .unwrap();
+
+        users_mock.assert();
+        assert_eq!(users.range.total, 1);
+    }
+
     #[tokio::test]
     async fn test_get_users_with_roles() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -225,6 +261,38 @@ pub mod tests {
         assert_user_item(user);
     }
 
+    #[tokio::test]
+    async fn test_get_users_by_attribute() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let users_res = include_str!("./responses/users/users_with_attributes_ok.json");
+
+        let users_mock = mock_server
+            .mock("GET", "/api/v4/users?offset=0&filter=userAttributes%3Aeq%3AemployeeId%3A4711&include_attributes=true")
+            .with_status(200)
+            .with_body(users_res)
+            .create();
+
+        let params = ListAllParams::builder()
+            .with_filter(UsersFilter::attribute_equals("employeeId", "4711"))
+            .build();
+
+        let users = client
+            .users()
+            .get_users(Some(params), None, Some(true))
+            .await
+            .unwrap();
+
+        users_mock.assert();
+        assert_eq!(users.range.total, 1);
+        let user = users.items.first().unwrap();
+        assert_user_item(user);
+
+        let attributes = user.user_attributes.as_ref().unwrap();
+        assert_eq!(attributes.get("employeeId"), Some("4711"));
+        assert_eq!(attributes.get("unknown"), None);
+    }
+
     #[tokio::test]
     async fn test_create_user() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -250,6 +318,69 @@ pub mod tests {
         assert_user_data(&user);
     }
 
+    #[tokio::test]
+    async fn test_create_users_bulk() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let user_res = include_str!("./responses/users/user_ok.json");
+
+        let created_mock = mock_server
+            .mock("POST", "/api/v4/users")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"userName": "new"}),
+            ))
+            .with_status(201)
+            .with_body(user_res)
+            .create();
+
+        let conflict_mock = mock_server
+            .mock("POST", "/api/v4/users")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"userName": "existing"}),
+            ))
+            .with_status(409)
+            .with_body(r#"{"code":409,"message":"Conflict","debugInfo":"User already exists","errorCode":-70117}"#)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let failed_mock = mock_server
+            .mock("POST", "/api/v4/users")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"userName": "invalid"}),
+            ))
+            .with_status(400)
+            .with_body(r#"{"code":400,"message":"Bad Request","debugInfo":"Invalid email","errorCode":-80009}"#)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let requests = ["new", "existing", "invalid"]
+            .into_iter()
+            .map(|user_name| {
+                CreateUserRequest::builder("test", "test")
+                    .with_user_name(user_name)
+                    .build()
+            })
+            .collect::<Vec<_>>();
+
+        let report = client.users().create_users_bulk(requests, 2).await;
+
+        created_mock.assert();
+        conflict_mock.assert();
+        failed_mock.assert();
+
+        assert!(!report.is_success());
+        assert_eq!(report.created.len(), 1);
+        assert_user_data(&report.created[0]);
+
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].0.user_name(), Some("existing"));
+        assert!(report.skipped[0].1.is_conflict());
+
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0.user_name(), Some("invalid"));
+        assert!(!report.failed[0].1.is_conflict());
+    }
+
     #[tokio::test]
     async fn test_get_user() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -269,6 +400,31 @@ pub mod tests {
         assert_user_data(&user);
     }
 
+    #[tokio::test]
+    async fn test_get_user_with_effective_roles() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let user_res = include_str!("./responses/users/user_with_roles_ok.json");
+
+        let user_mock = mock_server
+            .mock("GET", "/api/v4/users/123?effective_roles=true")
+            .with_status(200)
+            .with_body(user_res)
+            .create();
+
+        let user = client.users().get_user(123, Some(true)).await.unwrap();
+
+        user_mock.assert();
+
+        assert_user_data(&user);
+        let roles = user.user_roles.unwrap();
+        assert!(roles.contains(RoleType::RoomManager));
+        assert_eq!(roles.items[0].role_type(), Some(RoleType::RoomManager));
+        let rights = roles.items[0].items.as_ref().unwrap();
+        assert_eq!(rights.len(), 1);
+        assert_eq!(rights[0].id, 1);
+    }
+
     #[tokio::test]
     async fn test_update_user() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -291,6 +447,71 @@ pub mod tests {
         assert_user_data(&user);
     }
 
+    #[tokio::test]
+    async fn test_update_user_auth_method() {
+        let (client, mut mock_server) = get_connected_client().await;
+        let user_res = include_str!("./responses/users/user_ok.json");
+
+        let user_mock = mock_server
+            .mock("PUT", "/api/v4/users/123")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "authData": {
+                    "method": "openid",
+                    "login": "jane.doe@example.com",
+                    "oidConfigId": 2
+                }
+            })))
+            .with_status(200)
+            .with_body(user_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let auth_method = AuthMethod::new_open_id_connect(2, "jane.doe@example.com");
+        let user = client
+            .users()
+            .update_user_auth_method(123, auth_method)
+            .await
+            .unwrap();
+
+        user_mock.assert();
+
+        assert_user_data(&user);
+    }
+
+    #[tokio::test]
+    async fn test_update_user_auth_data_active_directory() {
+        let (client, mut mock_server) = get_connected_client().await;
+        let user_res = include_str!("./responses/users/user_ok.json");
+
+        let user_mock = mock_server
+            .mock("PUT", "/api/v4/users/123")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "authData": {
+                    "method": "active_directory",
+                    "login": "jdoe",
+                    "adConfigId": 1
+                }
+            })))
+            .with_status(200)
+            .with_body(user_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let auth_data = UserAuthDataUpdateRequest::new(
+            Some("jdoe".into()),
+            Some(AuthMethod::new_active_directory(1, "jdoe")),
+            Some(1),
+            None,
+        );
+        let update = UpdateUserRequest::builder()
+            .with_auth_data(auth_data)
+            .build();
+
+        client.users().update_user(123, update).await.unwrap();
+
+        user_mock.assert();
+    }
+
     #[tokio::test]
     async fn test_delete_user() {
         let (client, mut mock_server) = get_connected_client().await;
@@ -308,4 +529,475 @@ pub mod tests {
 
         user_mock.assert();
     }
+
+    #[tokio::test]
+    async fn test_get_user_last_admin_rooms() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let rooms_res = include_str!("./responses/users/last_admin_rooms_ok.json");
+
+        let rooms_mock = mock_server
+            .mock("GET", "/api/v4/users/123/last_admin_rooms")
+            .with_status(200)
+            .with_body(rooms_res)
+            .create();
+
+        let rooms = client.users().get_user_last_admin_rooms(123).await.unwrap();
+
+        rooms_mock.assert();
+
+        assert_eq!(rooms.items.len(), 1);
+        let room = rooms.items.first().unwrap();
+        assert_eq!(room.id, 2);
+        assert_eq!(room.name, "string");
+        assert_eq!(room.parent_path, "/parent");
+        assert_eq!(room.parent_id, Some(1));
+        assert!(!room.last_admin_in_group);
+    }
+
+    #[tokio::test]
+    async fn test_validate_user_deletion_last_admin() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let rooms_res = include_str!("./responses/users/last_admin_rooms_ok.json");
+
+        let rooms_mock = mock_server
+            .mock("GET", "/api/v4/users/123/last_admin_rooms")
+            .with_status(200)
+            .with_body(rooms_res)
+            .create();
+
+        let err = client
+            .users()
+            .validate_user_deletion(123)
+            .await
+            .unwrap_err();
+
+        rooms_mock.assert();
+
+        assert!(err.is_would_orphan_rooms());
+        assert_eq!(
+            err,
+            DracoonClientError::WouldOrphanRooms(vec![OrphanedRoom {
+                id: 2,
+                name: "string".into(),
+                parent_path: "/parent".into(),
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_user_deletion_ok() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let rooms_mock = mock_server
+            .mock("GET", "/api/v4/users/123/last_admin_rooms")
+            .with_status(200)
+            .with_body(r#"{"items": []}"#)
+            .create();
+
+        client.users().validate_user_deletion(123).await.unwrap();
+
+        rooms_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_checked() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let rooms_mock = mock_server
+            .mock("GET", "/api/v4/users/123/last_admin_rooms")
+            .with_status(200)
+            .with_body(r#"{"items": []}"#)
+            .create();
+
+        let download_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/downloads")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "createdById:eq:123".into(),
+            ))
+            .with_status(200)
+            .with_body(include_str!(
+                "./responses/users/user_download_shares_ok.json"
+            ))
+            .create();
+
+        let upload_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/uploads")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "createdById:eq:123".into(),
+            ))
+            .with_status(200)
+            .with_body(include_str!("./responses/users/user_upload_shares_ok.json"))
+            .create();
+
+        let delete_mock = mock_server
+            .mock("DELETE", "/api/v4/users/123")
+            .with_status(204)
+            .create();
+
+        let report = client
+            .delete_user_checked(123, UserDeletionStrategy::Safe)
+            .await
+            .unwrap();
+
+        rooms_mock.assert();
+        download_shares_mock.assert();
+        upload_shares_mock.assert();
+        delete_mock.assert();
+
+        assert!(report.reassigned_rooms.is_empty());
+        // expired and exhausted shares are not reported
+        assert_eq!(report.download_shares.len(), 1);
+        assert_eq!(report.download_shares[0].id, 1);
+        assert_eq!(report.upload_shares.len(), 1);
+        assert_eq!(report.upload_shares[0].id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_checked_last_admin() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let rooms_mock = mock_server
+            .mock("GET", "/api/v4/users/123/last_admin_rooms")
+            .with_status(200)
+            .with_body(include_str!("./responses/users/last_admin_rooms_ok.json"))
+            .create();
+
+        let download_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/downloads")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "createdById:eq:123".into(),
+            ))
+            .with_status(200)
+            .with_body(include_str!(
+                "./responses/users/user_download_shares_ok.json"
+            ))
+            .expect(0)
+            .create();
+
+        let upload_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/uploads")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "createdById:eq:123".into(),
+            ))
+            .with_status(200)
+            .with_body(include_str!("./responses/users/user_upload_shares_ok.json"))
+            .expect(0)
+            .create();
+
+        let delete_mock = mock_server
+            .mock("DELETE", "/api/v4/users/123")
+            .with_status(204)
+            .expect(0)
+            .create();
+
+        let err = client
+            .delete_user_checked(123, UserDeletionStrategy::Safe)
+            .await
+            .unwrap_err();
+
+        rooms_mock.assert();
+        download_shares_mock.assert();
+        upload_shares_mock.assert();
+        delete_mock.assert();
+
+        assert_eq!(
+            err,
+            DracoonClientError::WouldOrphanRooms(vec![OrphanedRoom {
+                id: 2,
+                name: "string".into(),
+                parent_path: "/parent".into(),
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_checked_reassign_rooms() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let rooms_mock = mock_server
+            .mock("GET", "/api/v4/users/123/last_admin_rooms")
+            .with_status(200)
+            .with_body(include_str!("./responses/users/last_admin_rooms_ok.json"))
+            .create();
+
+        let _download_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/downloads")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "createdById:eq:123".into(),
+            ))
+            .with_status(200)
+            .with_body(include_str!(
+                "./responses/users/user_download_shares_ok.json"
+            ))
+            .create();
+
+        let _upload_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/uploads")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "createdById:eq:123".into(),
+            ))
+            .with_status(200)
+            .with_body(include_str!("./responses/users/user_upload_shares_ok.json"))
+            .create();
+
+        let room_users_mock = mock_server
+            .mock("PUT", "/api/v4/nodes/rooms/2/users")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "items": [{ "id": 456, "permissions": { "manage": true } }]
+            })))
+            .with_status(204)
+            .create();
+
+        let delete_mock = mock_server
+            .mock("DELETE", "/api/v4/users/123")
+            .with_status(204)
+            .create();
+
+        let report = client
+            .delete_user_checked(123, UserDeletionStrategy::ReassignRoomsTo(456))
+            .await
+            .unwrap();
+
+        rooms_mock.assert();
+        room_users_mock.assert();
+        delete_mock.assert();
+
+        assert_eq!(
+            report.reassigned_rooms,
+            vec![OrphanedRoom {
+                id: 2,
+                name: "string".into(),
+                parent_path: "/parent".into(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_user_groups() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let groups_res = include_str!("./responses/users/user_groups_ok.json");
+
+        let groups_mock = mock_server
+            .mock(
+                "GET",
+                "/api/v4/users/123/groups?offset=0&filter=isMember%3Aeq%3Afalse",
+            )
+            .with_status(200)
+            .with_body(groups_res)
+            .create();
+
+        let params = ListAllParams::builder()
+            .with_filter(UserGroupsFilter::is_member(false))
+            .build();
+
+        let groups = client
+            .users()
+            .get_user_groups(123, Some(params))
+            .await
+            .unwrap();
+
+        groups_mock.assert();
+
+        assert_eq!(groups.range.total, 2);
+        assert_eq!(groups.items.len(), 2);
+        assert_eq!(groups.items[0].id, 1);
+        assert_eq!(groups.items[0].name, "string");
+        assert!(groups.items[0].is_member);
+        assert!(!groups.items[1].is_member);
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_avatar() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let avatar_mock = mock_server
+            .mock("DELETE", "/api/v4/users/123/avatar")
+            .with_status(200)
+            .with_body(
+                r#"{"avatarUri": "string", "avatarUuid": "string", "isCustomAvatar": false}"#,
+            )
+            .with_header("content-type", "application/json")
+            .create();
+
+        let avatar = client.users().delete_user_avatar(123).await.unwrap();
+
+        avatar_mock.assert();
+
+        assert_eq!(avatar.avatar_uuid, "string");
+        assert!(!avatar.is_custom_avatar);
+    }
+
+    #[tokio::test]
+    async fn test_get_user_attributes() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let attributes_res = include_str!("./responses/users/user_attributes_ok.json");
+
+        let attributes_mock = mock_server
+            .mock("GET", "/api/v4/users/123/userAttributes?offset=0")
+            .with_status(200)
+            .with_body(attributes_res)
+            .create();
+
+        let attributes = client.users().get_user_attributes(123, None).await.unwrap();
+
+        attributes_mock.assert();
+
+        assert_eq!(attributes.range.total, 1);
+        let attribute = attributes.items.first().unwrap();
+        assert_eq!(attribute.key, "employeeId");
+        assert_eq!(attribute.value, "4711");
+    }
+
+    #[tokio::test]
+    async fn test_export_user_data() {
+        let (client, mut mock_server) = get_connected_client().await;
+
+        let user_mock = mock_server
+            .mock("GET", "/api/v4/users/123")
+            .with_status(200)
+            .with_body(include_str!("./responses/users/user_ok.json"))
+            .create();
+
+        let attributes_mock = mock_server
+            .mock("GET", "/api/v4/users/123/userAttributes")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(include_str!("./responses/users/user_attributes_ok.json"))
+            .create();
+
+        let groups_mock = mock_server
+            .mock("GET", "/api/v4/users/123/groups")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "isMember:eq:true".into(),
+            ))
+            .with_status(200)
+            .with_body(include_str!("./responses/users/user_groups_ok.json"))
+            .create();
+
+        let download_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/downloads")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "createdById:eq:123".into(),
+            ))
+            .with_status(200)
+            .with_body(include_str!("./responses/shares/download_shares_ok.json"))
+            .create();
+
+        let upload_shares_mock = mock_server
+            .mock("GET", "/api/v4/shares/uploads")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "filter".into(),
+                "createdById:eq:123".into(),
+            ))
+            .with_status(200)
+            .with_body(include_str!("./responses/shares/upload_shares_ok.json"))
+            .create();
+
+        let events_mock = mock_server
+            .mock("GET", "/api/v4/eventlog/events")
+            .match_query(mockito::Matcher::UrlEncoded("user_id".into(), "123".into()))
+            .with_status(200)
+            .with_body(include_str!("./responses/eventlog/events_ok.json"))
+            .create();
+
+        let report = client.export_user_data(123).await.unwrap();
+
+        user_mock.assert();
+        attributes_mock.assert();
+        groups_mock.assert();
+        download_shares_mock.assert();
+        upload_shares_mock.assert();
+        events_mock.assert();
+
+        assert_user_data(&report.user);
+        assert_eq!(report.attributes.len(), 1);
+        assert_eq!(report.groups.len(), 2);
+        assert_eq!(report.download_shares.len(), 1);
+        assert_eq!(report.upload_shares.len(), 1);
+        assert_eq!(report.events.len(), 1);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["user"]["userName"], "string");
+        assert_eq!(json["attributes"][0]["key"], "employeeId");
+        assert!(json["downloadShares"].is_array());
+        assert!(json["exportedAt"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_get_user_roles() {
+        let (client, mut mock_server) = get_connected_client().await;
+        let roles_res = include_str!("./responses/roles/roles_ok.json");
+
+        let roles_mock = mock_server
+            .mock("GET", "/api/v4/users/123/roles")
+            .with_status(200)
+            .with_body(roles_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let roles = client.users().get_user_roles(123).await.unwrap();
+
+        roles_mock.assert();
+
+        assert_eq!(roles.items.len(), 1);
+        assert_eq!(roles.items[0].role_type(), Some(RoleType::ConfigManager));
+        assert!(roles.contains(RoleType::ConfigManager));
+        assert!(!roles.contains(RoleType::RoomManager));
+    }
+
+    #[tokio::test]
+    async fn test_assign_user_role() {
+        let (client, mut mock_server) = get_connected_client().await;
+        let role_users_res = include_str!("./responses/roles/role_user_list_ok.json");
+
+        let role_mock = mock_server
+            .mock("POST", "/api/v4/roles/4/users")
+            .match_body(mockito::Matcher::Json(serde_json::json!({ "ids": [123] })))
+            .with_status(200)
+            .with_body(role_users_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        client
+            .users()
+            .assign_user_role(123, RoleType::RoomManager)
+            .await
+            .unwrap();
+
+        role_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_revoke_user_role() {
+        let (client, mut mock_server) = get_connected_client().await;
+        let role_users_res = include_str!("./responses/roles/role_user_list_ok.json");
+
+        let role_mock = mock_server
+            .mock("DELETE", "/api/v4/roles/5/users")
+            .match_body(mockito::Matcher::Json(serde_json::json!({ "ids": [123] })))
+            .with_status(200)
+            .with_body(role_users_res)
+            .with_header("content-type", "application/json")
+            .create();
+
+        client
+            .users()
+            .revoke_user_role(123, RoleType::LogAuditor)
+            .await
+            .unwrap();
+
+        role_mock.assert();
+    }
 }
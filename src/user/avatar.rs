@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use reqwest::{header, Response};
+
+use crate::{
+    client::{errors::DracoonClientError, Connected},
+    constants::{
+        DOWNLOADS_AVATAR, DOWNLOADS_BASE, DRACOON_API_PREFIX, USER_ACCOUNT, USER_ACCOUNT_AVATAR,
+        USER_BASE,
+    },
+    nodes::DownloadStream,
+    utils::FromResponse,
+};
+
+use super::{models::Avatar, UserAvatar, UserEndpoint};
+
+#[async_trait]
+impl UserAvatar for UserEndpoint<Connected> {
+    async fn get_user_avatar(&self) -> Result<Avatar, DracoonClientError> {
+        let url_part =
+            format!("{DRACOON_API_PREFIX}/{USER_BASE}/{USER_ACCOUNT}/{USER_ACCOUNT_AVATAR}");
+
+        let url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .get(url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        Avatar::from_response(response).await
+    }
+
+    async fn download_user_avatar(&self) -> Result<Bytes, DracoonClientError> {
+        let avatar = self.get_user_avatar().await?;
+
+        let response = self.client().http.get(avatar.avatar_uri).send().await?;
+
+        avatar_bytes(response).await
+    }
+
+    async fn download_avatar(
+        &self,
+        user_id: u64,
+        avatar_uuid: &str,
+    ) -> Result<Bytes, DracoonClientError> {
+        let response = self.get_avatar_response(user_id, avatar_uuid).await?;
+
+        avatar_bytes(response).await
+    }
+
+    async fn download_avatar_stream(
+        &self,
+        user_id: u64,
+        avatar_uuid: &str,
+    ) -> Result<DownloadStream, DracoonClientError> {
+        let response = self.get_avatar_response(user_id, avatar_uuid).await?;
+
+        if response.status().is_client_error() || response.status().is_server_error() {
+            return Err(DracoonClientError::from_response(response).await?);
+        }
+
+        Ok(Box::pin(response.bytes_stream().map_err(Into::into)))
+    }
+}
+
+impl UserEndpoint<Connected> {
+    /// Requests the avatar of a user (public endpoint - no authorization required)
+    async fn get_avatar_response(
+        &self,
+        user_id: u64,
+        avatar_uuid: &str,
+    ) -> Result<Response, DracoonClientError> {
+        let url_part = format!(
+            "{DRACOON_API_PREFIX}/{DOWNLOADS_BASE}/{DOWNLOADS_AVATAR}/{user_id}/{avatar_uuid}"
+        );
+
+        let url = self.client().build_api_url(&url_part);
+
+        Ok(self.client().http.get(url).send().await?)
+    }
+}
+
+async fn avatar_bytes(response: Response) -> Result<Bytes, DracoonClientError> {
+    if response.status().is_client_error() || response.status().is_server_error() {
+        return Err(DracoonClientError::from_response(response).await?);
+    }
+
+    Ok(response.bytes().await?)
+}
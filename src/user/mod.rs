@@ -2,14 +2,17 @@
 //! Documentation can be found here: <https://download.dracoon.com/api/swagger-ui/index.html?configUrl=/api/spec_v4/swagger-config#/user>
 
 use async_trait::async_trait;
+use bytes::Bytes;
 use dco3_crypto::PlainUserKeyPairContainer;
 
 pub use self::models::*;
-use super::client::errors::DracoonClientError;
+use super::{client::errors::DracoonClientError, models::ListAllParams, nodes::DownloadStream};
 
 pub mod account;
+pub mod avatar;
 pub mod keypairs;
 pub mod models;
+pub mod subscriptions;
 
 #[async_trait]
 pub trait User {
@@ -144,3 +147,178 @@ pub trait UserAccountKeyPairs {
     /// ```
     async fn delete_user_keypair(&self) -> Result<(), DracoonClientError>;
 }
+
+/// This trait provides methods to manage node subscriptions ("notify me on change") of the user.
+#[async_trait]
+pub trait NodeSubscriptions {
+    /// Get a list of all nodes the user is subscribed to.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, NodeSubscriptions};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let subscriptions = dracoon.user().get_node_subscriptions(None).await.unwrap();
+    /// # }
+    /// ```
+    async fn get_node_subscriptions(
+        &self,
+        params: Option<ListAllParams>,
+    ) -> Result<SubscribedNodeList, DracoonClientError>;
+    /// Subscribe to a node by id.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, NodeSubscriptions};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let subscription = dracoon.user().subscribe_node(123).await.unwrap();
+    /// # }
+    /// ```
+    async fn subscribe_node(&self, node_id: u64) -> Result<SubscribedNode, DracoonClientError>;
+    /// Unsubscribe from a node by id.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, NodeSubscriptions};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// dracoon.user().unsubscribe_node(123).await.unwrap();
+    /// # }
+    /// ```
+    async fn unsubscribe_node(&self, node_id: u64) -> Result<(), DracoonClientError>;
+    /// Subscribe to or unsubscribe from multiple nodes.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, NodeSubscriptions, user::UpdateSubscriptionsBulkRequest};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let req = UpdateSubscriptionsBulkRequest::subscribe(vec![1, 2, 3]);
+    /// dracoon.user().update_node_subscriptions(req).await.unwrap();
+    /// # }
+    /// ```
+    async fn update_node_subscriptions(
+        &self,
+        req: UpdateSubscriptionsBulkRequest,
+    ) -> Result<(), DracoonClientError>;
+}
+
+/// This trait provides methods to retrieve user avatars (e.g. to render user lists).
+#[async_trait]
+pub trait UserAvatar {
+    /// Get the avatar information of the user.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, UserAvatar};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let avatar = dracoon.user().get_user_avatar().await.unwrap();
+    /// # }
+    /// ```
+    async fn get_user_avatar(&self) -> Result<Avatar, DracoonClientError>;
+    /// Download the avatar of the user as bytes.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, UserAvatar};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let avatar = dracoon.user().download_user_avatar().await.unwrap();
+    /// # }
+    /// ```
+    async fn download_user_avatar(&self) -> Result<Bytes, DracoonClientError>;
+    /// Download the avatar of any user by user id and avatar UUID as bytes.
+    /// The avatar UUID is part of user information (e.g. `UserInfo`).
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, UserAvatar};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let avatar = dracoon.user().download_avatar(123, "avatar-uuid").await.unwrap();
+    /// # }
+    /// ```
+    async fn download_avatar(
+        &self,
+        user_id: u64,
+        avatar_uuid: &str,
+    ) -> Result<Bytes, DracoonClientError>;
+    /// Download the avatar of any user by user id and avatar UUID as stream of bytes.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, UserAvatar};
+    /// # use futures_util::TryStreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let mut stream = dracoon.user().download_avatar_stream(123, "avatar-uuid").await.unwrap();
+    ///
+    /// while let Some(chunk) = stream.try_next().await.unwrap() {
+    ///     println!("Received {} bytes", chunk.len());
+    /// }
+    /// # }
+    /// ```
+    async fn download_avatar_stream(
+        &self,
+        user_id: u64,
+        avatar_uuid: &str,
+    ) -> Result<DownloadStream, DracoonClientError>;
+}
@@ -9,6 +9,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     client::{errors::DracoonClientError, models::DracoonErrorResponse, DracoonClient},
+    models::RangedItems,
+    nodes::NodeType,
     roles::RoleList,
     utils::{parse_body, FromResponse},
 };
@@ -68,7 +70,7 @@ pub struct UserAuthData {
     pub oid_config_id: Option<u64>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UserGroup {
     pub id: u64,
@@ -189,3 +191,53 @@ pub struct CustomerData {
     pub cnt_guest_user: Option<u64>,
     pub customer_encryption_enabled: bool,
 }
+
+#[derive(Debug, Deserialize, Clone, FromResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscribedNode {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub node_type: NodeType,
+    pub auth_parent_id: Option<u64>,
+}
+
+pub type SubscribedNodeList = RangedItems<SubscribedNode>;
+
+#[async_trait]
+impl FromResponse for SubscribedNodeList {
+    async fn from_response(response: Response) -> Result<Self, DracoonClientError> {
+        parse_body::<Self, DracoonErrorResponse>(response).await
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSubscriptionsBulkRequest {
+    is_subscribed: bool,
+    object_ids: Vec<u64>,
+}
+
+impl UpdateSubscriptionsBulkRequest {
+    pub fn subscribe(object_ids: Vec<u64>) -> Self {
+        Self {
+            is_subscribed: true,
+            object_ids,
+        }
+    }
+
+    pub fn unsubscribe(object_ids: Vec<u64>) -> Self {
+        Self {
+            is_subscribed: false,
+            object_ids,
+        }
+    }
+}
+
+/// Avatar of a user - GET /user/account/avatar
+#[derive(Debug, Deserialize, Clone, FromResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct Avatar {
+    pub avatar_uri: String,
+    pub avatar_uuid: String,
+    pub is_custom_avatar: bool,
+}
@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use reqwest::header;
+
+use super::{
+    models::{SubscribedNode, SubscribedNodeList, UpdateSubscriptionsBulkRequest},
+    NodeSubscriptions, UserEndpoint,
+};
+use crate::{
+    client::{errors::DracoonClientError, Connected},
+    constants::{DRACOON_API_PREFIX, USER_BASE, USER_SUBSCRIPTIONS, USER_SUBSCRIPTIONS_NODES},
+    models::ListAllParams,
+    utils::FromResponse,
+};
+
+#[async_trait]
+impl NodeSubscriptions for UserEndpoint<Connected> {
+    async fn get_node_subscriptions(
+        &self,
+        params: Option<ListAllParams>,
+    ) -> Result<SubscribedNodeList, DracoonClientError> {
+        let params = params.unwrap_or_default();
+        let url_part = format!(
+            "{DRACOON_API_PREFIX}/{USER_BASE}/{USER_SUBSCRIPTIONS}/{USER_SUBSCRIPTIONS_NODES}"
+        );
+
+        let mut url = self.client().build_api_url(&url_part);
+
+        let filters = params.filter_to_string();
+        let sorts = params.sort_to_string();
+
+        url.query_pairs_mut()
+            .extend_pairs(params.limit.map(|v| ("limit", v.to_string())))
+            .extend_pairs(params.offset.map(|v| ("offset", v.to_string())))
+            .extend_pairs(params.sort.map(|_| ("sort", sorts)))
+            .extend_pairs(params.filter.map(|_| ("filter", filters)))
+            .finish();
+
+        let response = self
+            .client()
+            .http
+            .get(url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        SubscribedNodeList::from_response(response).await
+    }
+
+    async fn subscribe_node(&self, node_id: u64) -> Result<SubscribedNode, DracoonClientError> {
+        let url_part = format!(
+            "{DRACOON_API_PREFIX}/{USER_BASE}/{USER_SUBSCRIPTIONS}/{USER_SUBSCRIPTIONS_NODES}/{node_id}"
+        );
+
+        let url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .post(url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        SubscribedNode::from_response(response).await
+    }
+
+    async fn unsubscribe_node(&self, node_id: u64) -> Result<(), DracoonClientError> {
+        let url_part = format!(
+            "{DRACOON_API_PREFIX}/{USER_BASE}/{USER_SUBSCRIPTIONS}/{USER_SUBSCRIPTIONS_NODES}/{node_id}"
+        );
+
+        let url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .delete(url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .send()
+            .await?;
+
+        if response.status().is_client_error() || response.status().is_server_error() {
+            return Err(DracoonClientError::from_response(response).await?);
+        }
+
+        Ok(())
+    }
+
+    async fn update_node_subscriptions(
+        &self,
+        req: UpdateSubscriptionsBulkRequest,
+    ) -> Result<(), DracoonClientError> {
+        let url_part = format!(
+            "{DRACOON_API_PREFIX}/{USER_BASE}/{USER_SUBSCRIPTIONS}/{USER_SUBSCRIPTIONS_NODES}"
+        );
+
+        let url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .put(url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&req)
+            .send()
+            .await?;
+
+        if response.status().is_client_error() || response.status().is_server_error() {
+            return Err(DracoonClientError::from_response(response).await?);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::{
+    client::Connected,
+    nodes::{NodePermissions, RoomUsersAddBatchRequestItem},
+    shares::{DownloadSharesFilter, SharesInternal, UploadSharesFilter},
+    Dracoon, DracoonClientError, Rooms, Users,
+};
+
+use super::{UserDeletion, UserDeletionReport, UserDeletionStrategy};
+
+#[async_trait]
+impl UserDeletion for Dracoon<Connected> {
+    async fn delete_user_checked(
+        &self,
+        user_id: u64,
+        strategy: UserDeletionStrategy,
+    ) -> Result<UserDeletionReport, DracoonClientError> {
+        let rooms = self.users().get_user_last_admin_rooms(user_id).await?;
+
+        // fail early: shares are only listed if the user can be deleted
+        let admin_id = match strategy {
+            UserDeletionStrategy::ReassignRoomsTo(admin_id) => Some(admin_id),
+            UserDeletionStrategy::Safe if rooms.is_empty() => None,
+            UserDeletionStrategy::Safe => return Err(rooms.into()),
+        };
+
+        let (download_shares, upload_shares) = tokio::try_join!(
+            self.shares()
+                .get_all_download_shares(Some(DownloadSharesFilter::created_by_id_equals(user_id))),
+            self.shares()
+                .get_all_upload_shares(Some(UploadSharesFilter::created_by_id_equals(user_id)))
+        )?;
+
+        let download_shares = download_shares
+            .into_iter()
+            .filter(|share| !share.is_expired() && !share.is_exhausted())
+            .collect();
+        let upload_shares = upload_shares
+            .into_iter()
+            .filter(|share| !share.is_expired() && !share.is_exhausted())
+            .collect();
+
+        let reassigned_rooms = match admin_id {
+            Some(admin_id) => {
+                for room in &rooms.items {
+                    debug!(
+                        "Granting room admin in room {} to user {}",
+                        room.id, admin_id
+                    );
+                    let admin =
+                        RoomUsersAddBatchRequestItem::new(admin_id, NodePermissions::room_admin());
+                    self.nodes()
+                        .update_room_users(room.id, vec![admin].into())
+                        .await?;
+                }
+
+                rooms.items.into_iter().map(Into::into).collect()
+            }
+            _ => Vec::new(),
+        };
+
+        self.users().delete_user(user_id).await?;
+
+        Ok(UserDeletionReport {
+            reassigned_rooms,
+            download_shares,
+            upload_shares,
+        })
+    }
+}
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    client::Connected,
+    eventlog::{EventlogParams, LogEvent},
+    models::{KeyValueEntry, ListAllParams},
+    shares::{DownloadSharesFilter, SharesInternal, UploadSharesFilter},
+    user::UserGroup,
+    utils::list_all,
+    Dracoon, DracoonClientError, Eventlog, Users,
+};
+
+use super::{UserDataExport, UserDataReport, UserGroupsFilter};
+
+#[async_trait]
+impl UserDataExport for Dracoon<Connected> {
+    async fn export_user_data(&self, user_id: u64) -> Result<UserDataReport, DracoonClientError> {
+        let (user, attributes, groups, download_shares, upload_shares, events) = tokio::try_join!(
+            self.users().get_user(user_id, None),
+            self.get_all_user_attributes(user_id),
+            self.get_all_user_groups(user_id),
+            self.shares()
+                .get_all_download_shares(Some(DownloadSharesFilter::created_by_id_equals(user_id))),
+            self.shares()
+                .get_all_upload_shares(Some(UploadSharesFilter::created_by_id_equals(user_id))),
+            self.get_all_user_events(user_id)
+        )?;
+
+        Ok(UserDataReport {
+            user,
+            attributes,
+            groups,
+            download_shares,
+            upload_shares,
+            events,
+            exported_at: Utc::now(),
+        })
+    }
+}
+
+impl Dracoon<Connected> {
+    /// lists all custom attributes of a user
+    async fn get_all_user_attributes(
+        &self,
+        user_id: u64,
+    ) -> Result<Vec<KeyValueEntry>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_offset(offset)
+                .with_limit(limit)
+                .build();
+
+            self.users().get_user_attributes(user_id, Some(params))
+        })
+        .await
+    }
+
+    /// lists all groups a user is a member of
+    async fn get_all_user_groups(
+        &self,
+        user_id: u64,
+    ) -> Result<Vec<UserGroup>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = ListAllParams::builder()
+                .with_offset(offset)
+                .with_limit(limit)
+                .with_filter(UserGroupsFilter::is_member(true))
+                .build();
+
+            self.users().get_user_groups(user_id, Some(params))
+        })
+        .await
+    }
+
+    /// lists all eventlog entries of a user
+    async fn get_all_user_events(&self, user_id: u64) -> Result<Vec<LogEvent>, DracoonClientError> {
+        list_all(|offset, limit| {
+            let params = EventlogParams::builder()
+                .with_offset(offset)
+                .with_limit(limit)
+                .with_user_id(user_id as i64)
+                .build();
+
+            self.eventlog().get_events(params)
+        })
+        .await
+    }
+}
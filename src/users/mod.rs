@@ -1,12 +1,19 @@
 use async_trait::async_trait;
 
+mod deletion;
+mod export;
 mod models;
 #[allow(clippy::module_inception)]
 mod users;
 
 pub use models::*;
 
-use crate::{models::ListAllParams, DracoonClientError};
+use crate::{
+    models::ListAllParams,
+    roles::{RoleList, RoleType, RoleUserList},
+    user::Avatar,
+    DracoonClientError,
+};
 
 #[async_trait]
 pub trait Users {
@@ -32,6 +39,11 @@ pub trait Users {
     /// // optionally include roles and attributes
     /// let users = dracoon.users().get_users(Some(params), None, None).await.unwrap();
     ///
+    /// // include roles inherited via groups (effective roles)
+    /// let params = ListAllParams::builder()
+    ///     .with_filter(UsersFilter::effective_roles(true))
+    ///     .build();
+    /// let users = dracoon.users().get_users(Some(params), Some(true), None).await.unwrap();
     /// # }
     /// ```
     async fn get_users(
@@ -74,6 +86,7 @@ pub trait Users {
     /// ```
     async fn create_user(&self, req: CreateUserRequest) -> Result<UserData, DracoonClientError>;
     /// Get a user by id.
+    /// If `effective_roles` is set, the roles of the user include roles inherited via groups.
     /// ```no_run
     /// # use dco3::{Dracoon, OAuth2Flow, Users};
     /// # #[tokio::main]
@@ -88,7 +101,8 @@ pub trait Users {
     /// #  .await
     /// #  .unwrap();
     /// // optionally you can include effective roles
-    /// let user = dracoon.users().get_user(123, None).await.unwrap();
+    /// let user = dracoon.users().get_user(123, Some(true)).await.unwrap();
+    /// let roles = user.user_roles.unwrap_or_default();
     /// # }
     /// ```
     async fn get_user(
@@ -122,6 +136,38 @@ pub trait Users {
         user_id: u64,
         req: UpdateUserRequest,
     ) -> Result<UserData, DracoonClientError>;
+    /// Changes the authentication method of a user (local, Active Directory or OpenID Connect).
+    /// For Active Directory and OpenID Connect the login (e.g. the OIDC subject / user name)
+    /// and the config id are set.
+    /// ```no_run
+    /// # use dco3::{Dracoon, OAuth2Flow, Users, users::AuthMethod};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// // migrate a local user to OpenID Connect (config id 2)
+    /// let auth_method = AuthMethod::new_open_id_connect(2, "jane.doe@example.com");
+    /// let user = dracoon.users().update_user_auth_method(123, auth_method).await.unwrap();
+    /// # }
+    /// ```
+    async fn update_user_auth_method(
+        &self,
+        user_id: u64,
+        auth_method: AuthMethod,
+    ) -> Result<UserData, DracoonClientError> {
+        let req = UpdateUserRequest::builder()
+            .with_auth_method(auth_method)
+            .build();
+
+        self.update_user(user_id, req).await
+    }
     /// Deletes a user by id.
     /// ```no_run
     /// # use dco3::{Dracoon, Users, OAuth2Flow};
@@ -162,4 +208,278 @@ pub trait Users {
         &self,
         user_id: u64,
     ) -> Result<LastAdminUserRoomList, DracoonClientError>;
+    /// Checks if a user can be deleted without leaving rooms without an administrator.
+    /// Returns a [DracoonClientError::WouldOrphanRooms] error with the affected rooms if
+    /// the user is the last admin of any room.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Users};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// match dracoon.users().validate_user_deletion(123).await {
+    ///     Ok(()) => dracoon.users().delete_user(123).await.unwrap(),
+    ///     Err(err) if err.is_would_orphan_rooms() => println!("User is last admin: {}", err),
+    ///     Err(err) => panic!("{}", err),
+    /// }
+    /// # }
+    /// ```
+    async fn validate_user_deletion(&self, user_id: u64) -> Result<(), DracoonClientError> {
+        let rooms = self.get_user_last_admin_rooms(user_id).await?;
+
+        if rooms.is_empty() {
+            return Ok(());
+        }
+
+        Err(rooms.into())
+    }
+    /// Get a list of the groups of a user.
+    /// By default, only groups the user is a member of are returned.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Users, users::UserGroupsFilter, ListAllParams};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// // Params are optional
+    /// let params = ListAllParams::builder()
+    ///     .with_filter(UserGroupsFilter::name_contains("test"))
+    ///     .build();
+    /// // pass None if you don't want to use any params
+    /// let groups = dracoon.users().get_user_groups(123, Some(params)).await.unwrap();
+    /// # }
+    /// ```
+    async fn get_user_groups(
+        &self,
+        user_id: u64,
+        params: Option<ListAllParams>,
+    ) -> Result<UserGroupList, DracoonClientError>;
+    /// Deletes the custom avatar of a user (resets the avatar to the default avatar).
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Users};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let avatar = dracoon.users().delete_user_avatar(123).await.unwrap();
+    /// assert!(!avatar.is_custom_avatar);
+    /// # }
+    /// ```
+    async fn delete_user_avatar(&self, user_id: u64) -> Result<Avatar, DracoonClientError>;
+    /// Get a list of the custom attributes of a user.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Users};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let attributes = dracoon.users().get_user_attributes(123, None).await.unwrap();
+    /// # }
+    /// ```
+    async fn get_user_attributes(
+        &self,
+        user_id: u64,
+        params: Option<ListAllParams>,
+    ) -> Result<UserAttributeList, DracoonClientError>;
+    /// Creates multiple users with a limited number of concurrent requests.
+    /// Users that already exist (409 Conflict) are skipped - all other errors are reported as failed.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, Users, users::CreateUserRequest};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let requests = vec![
+    ///     CreateUserRequest::builder("Jane", "Doe")
+    ///         .with_user_name("jane.doe")
+    ///         .with_email("jane.doe@localhost")
+    ///         .build(),
+    ///     CreateUserRequest::builder("John", "Doe")
+    ///         .with_user_name("john.doe")
+    ///         .with_email("john.doe@localhost")
+    ///         .build(),
+    /// ];
+    ///
+    /// let report = dracoon.users().create_users_bulk(requests, 5).await;
+    ///
+    /// for (req, err) in &report.failed {
+    ///     println!("Creating user {:?} failed: {}", req.user_name(), err);
+    /// }
+    /// # }
+    /// ```
+    async fn create_users_bulk(
+        &self,
+        requests: Vec<CreateUserRequest>,
+        concurrency: usize,
+    ) -> UserImportReport;
+}
+
+/// This trait provides the management of global roles of a user.
+#[async_trait]
+pub trait UserRoles: Users {
+    /// Get the roles of a user.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, UserRoles, roles::RoleType};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let roles = dracoon.users().get_user_roles(123).await.unwrap();
+    /// let is_room_manager = roles.contains(RoleType::RoomManager);
+    /// # }
+    /// ```
+    async fn get_user_roles(&self, user_id: u64) -> Result<RoleList, DracoonClientError>;
+    /// Assign a role to a user.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, UserRoles, roles::RoleType};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// dracoon.users().assign_user_role(123, RoleType::UserManager).await.unwrap();
+    /// # }
+    /// ```
+    async fn assign_user_role(
+        &self,
+        user_id: u64,
+        role: RoleType,
+    ) -> Result<RoleUserList, DracoonClientError>;
+    /// Revoke a role from a user.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, UserRoles, roles::RoleType};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// dracoon.users().revoke_user_role(123, RoleType::LogAuditor).await.unwrap();
+    /// # }
+    /// ```
+    async fn revoke_user_role(
+        &self,
+        user_id: u64,
+        role: RoleType,
+    ) -> Result<RoleUserList, DracoonClientError>;
+}
+
+/// This trait provides the export of all data stored about a user.
+#[async_trait]
+pub trait UserDataExport {
+    /// Collects the account data, attributes, group memberships, shares and eventlog entries
+    /// of a user into a single (serializable) report - e.g. for a data subject access request.
+    /// Requires the permissions to manage users and shares and to read the eventlog.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, UserDataExport};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// let report = dracoon.export_user_data(123).await.unwrap();
+    ///
+    /// let json = serde_json::to_string_pretty(&report).unwrap();
+    /// # }
+    /// ```
+    async fn export_user_data(&self, user_id: u64) -> Result<UserDataReport, DracoonClientError>;
+}
+
+/// This trait provides a checked deletion of users which never leaves rooms without an administrator.
+#[async_trait]
+pub trait UserDeletion {
+    /// Deletes a user after checking the rooms the user is the last administrator of
+    /// and the open (not expired or exhausted) shares created by the user.
+    /// With [UserDeletionStrategy::Safe], a [DracoonClientError::WouldOrphanRooms] error
+    /// with the affected rooms is returned before any shares are listed and the user is *not* deleted.
+    /// With [UserDeletionStrategy::ReassignRoomsTo], the given user is granted room administrator
+    /// permissions in these rooms first.
+    /// ```no_run
+    /// # use dco3::{Dracoon, auth::OAuth2Flow, UserDeletion, users::UserDeletionStrategy};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dracoon = Dracoon::builder()
+    /// #  .with_base_url("https://dracoon.team")
+    /// #  .with_client_id("client_id")
+    /// #  .with_client_secret("client_secret")
+    /// #  .build()
+    /// #  .unwrap()
+    /// #  .connect(OAuth2Flow::PasswordFlow("username".into(), "password".into()))
+    /// #  .await
+    /// #  .unwrap();
+    /// match dracoon.delete_user_checked(123, UserDeletionStrategy::Safe).await {
+    ///     Ok(report) => println!("Deleted user with {} open download shares", report.download_shares.len()),
+    ///     Err(err) if err.is_would_orphan_rooms() => {
+    ///         // hand over the rooms to another admin (user 456)
+    ///         let strategy = UserDeletionStrategy::ReassignRoomsTo(456);
+    ///         dracoon.delete_user_checked(123, strategy).await.unwrap();
+    ///     }
+    ///     Err(err) => panic!("{}", err),
+    /// }
+    /// # }
+    /// ```
+    async fn delete_user_checked(
+        &self,
+        user_id: u64,
+        strategy: UserDeletionStrategy,
+    ) -> Result<UserDeletionReport, DracoonClientError>;
 }
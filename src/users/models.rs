@@ -9,8 +9,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     client::{DracoonClient, DracoonErrorResponse},
-    models::{ObjectExpiration, RangedItems},
+    eventlog::LogEvent,
+    models::{KeyValueEntry, ObjectExpiration, OrphanedRoom, RangedItems},
     roles::RoleList,
+    shares::{DownloadShare, UploadShare},
+    user::UserGroup,
     utils::{parse_body, FromResponse},
     DracoonClientError, FilterOperator, FilterQuery, SortOrder, SortQuery,
 };
@@ -36,7 +39,7 @@ impl<S> UsersEndpoint<S> {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, FromResponse)]
+#[derive(Debug, Clone, Deserialize, Serialize, FromResponse)]
 #[serde(rename_all = "camelCase")]
 pub struct UserData {
     pub id: u64,
@@ -77,10 +80,27 @@ pub struct UserItem {
     pub home_room_id: Option<u64>,
     pub public_key_container: Option<PublicKeyContainer>,
     pub user_roles: Option<RoleList>,
+    pub user_attributes: Option<UserAttributes>,
 }
 
 pub type UserList = RangedItems<UserItem>;
 
+/// Custom attributes of a user (only included in user lists if requested)
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct UserAttributes {
+    pub items: Vec<KeyValueEntry>,
+}
+
+impl UserAttributes {
+    /// Returns the value of an attribute (None if the attribute is not set)
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value.as_str())
+    }
+}
+
 #[async_trait]
 impl FromResponse for UserList {
     async fn from_response(response: Response) -> Result<Self, DracoonClientError> {
@@ -88,6 +108,58 @@ impl FromResponse for UserList {
     }
 }
 
+pub type UserGroupList = RangedItems<UserGroup>;
+
+// FromResponse is implemented for all attribute lists in the provisioning module
+pub type UserAttributeList = RangedItems<KeyValueEntry>;
+
+/// Data stored about a user - e.g. for a data subject access request
+/// (see [crate::users::UserDataExport::export_user_data])
+/// - user: account data
+/// - attributes: custom user attributes
+/// - groups: groups the user is a member of
+/// - download_shares / upload_shares: shares created by the user
+/// - events: eventlog entries of the user
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserDataReport {
+    pub user: UserData,
+    pub attributes: Vec<KeyValueEntry>,
+    pub groups: Vec<UserGroup>,
+    pub download_shares: Vec<DownloadShare>,
+    pub upload_shares: Vec<UploadShare>,
+    pub events: Vec<LogEvent>,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// Strategy for rooms a user is the last administrator of when deleting the user
+/// (see [crate::UserDeletion])
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum UserDeletionStrategy {
+    /// Only delete the user if no room would be left without an administrator
+    #[default]
+    Safe,
+    /// Grant room administrator permissions to the given user before deleting
+    ReassignRoomsTo(u64),
+}
+
+/// Result of a checked user deletion:
+/// - reassigned_rooms: rooms the user was the last administrator of (now managed by the new admin)
+/// - download_shares / upload_shares: shares created by the user that were still open
+#[derive(Debug, Clone, Default)]
+pub struct UserDeletionReport {
+    pub reassigned_rooms: Vec<OrphanedRoom>,
+    pub download_shares: Vec<DownloadShare>,
+    pub upload_shares: Vec<UploadShare>,
+}
+
+#[async_trait]
+impl FromResponse for UserGroupList {
+    async fn from_response(response: Response) -> Result<Self, DracoonClientError> {
+        parse_body::<Self, DracoonErrorResponse>(response).await
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LastAdminUserRoom {
@@ -101,7 +173,30 @@ pub struct LastAdminUserRoom {
 
 #[derive(Debug, Clone, Deserialize, FromResponse)]
 pub struct LastAdminUserRoomList {
-    items: Vec<LastAdminUserRoom>,
+    pub items: Vec<LastAdminUserRoom>,
+}
+
+impl LastAdminUserRoomList {
+    /// Returns true if the user is not the last admin of any room
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl From<LastAdminUserRoom> for OrphanedRoom {
+    fn from(room: LastAdminUserRoom) -> Self {
+        Self {
+            id: room.id,
+            name: room.name,
+            parent_path: room.parent_path,
+        }
+    }
+}
+
+impl From<LastAdminUserRoomList> for DracoonClientError {
+    fn from(rooms: LastAdminUserRoomList) -> Self {
+        DracoonClientError::WouldOrphanRooms(rooms.items.into_iter().map(Into::into).collect())
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -148,6 +243,40 @@ impl CreateUserRequest {
     ) -> CreateUserRequestBuilder {
         CreateUserRequestBuilder::new(first_name, last_name)
     }
+
+    pub fn first_name(&self) -> &str {
+        &self.first_name
+    }
+
+    pub fn last_name(&self) -> &str {
+        &self.last_name
+    }
+
+    pub fn user_name(&self) -> Option<&str> {
+        self.user_name.as_deref()
+    }
+
+    pub fn email(&self) -> Option<&str> {
+        self.email.as_deref()
+    }
+}
+
+/// Result of a bulk user creation (see [crate::users::Users::create_users_bulk])
+/// - created: created users
+/// - skipped: users not created because they already exist (409 Conflict)
+/// - failed: users not created because of any other error
+#[derive(Debug, Default)]
+pub struct UserImportReport {
+    pub created: Vec<UserData>,
+    pub skipped: Vec<(CreateUserRequest, DracoonClientError)>,
+    pub failed: Vec<(CreateUserRequest, DracoonClientError)>,
+}
+
+impl UserImportReport {
+    /// Returns true if no user failed (skipped users are not considered a failure)
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
 }
 
 pub struct CreateUserRequestBuilder {
@@ -336,6 +465,12 @@ impl UpdateUserRequestBuilder {
         self
     }
 
+    /// Changes the authentication method (e.g. migration from local users to OpenID Connect)
+    pub fn with_auth_method(mut self, auth_method: AuthMethod) -> Self {
+        self.auth_data = Some(UserAuthDataUpdateRequest::auth_method(auth_method));
+        self
+    }
+
     pub fn with_email(mut self, email: impl Into<String>) -> Self {
         self.email = Some(email.into());
         self
@@ -433,10 +568,10 @@ impl UserAuthDataUpdateRequest {
         oid_config_id: Option<u64>,
     ) -> Self {
         Self {
-            method: None,
+            method: method.map(Into::into),
             login,
-            ad_config_id: None,
-            oid_config_id: None,
+            ad_config_id,
+            oid_config_id,
         }
     }
 }
@@ -626,7 +761,7 @@ impl UserAuthDataBuilder {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum UsersFilter {
     Email(FilterOperator, String),
     UserName(FilterOperator, String),
@@ -638,6 +773,7 @@ pub enum UsersFilter {
     Phone(FilterOperator, String),
     IsEncryptionEnabled(FilterOperator, bool),
     HasRole(FilterOperator, String),
+    Attribute(FilterOperator, String, String),
 }
 
 impl FilterQuery for UsersFilter {
@@ -683,6 +819,10 @@ impl FilterQuery for UsersFilter {
                 let op: String = op.into();
                 format!("hasRole:{}:{}", op, value)
             }
+            Self::Attribute(op, key, value) => {
+                let op: String = op.into();
+                format!("userAttributes:{}:{}:{}", op, key, value)
+            }
         }
     }
 }
@@ -704,10 +844,18 @@ impl UsersFilter {
         Self::UserName(FilterOperator::Cn, value.into())
     }
 
+    pub fn first_name_equals(value: impl Into<String>) -> Self {
+        Self::FirstName(FilterOperator::Eq, value.into())
+    }
+
     pub fn first_name_contains(value: impl Into<String>) -> Self {
         Self::FirstName(FilterOperator::Cn, value.into())
     }
 
+    pub fn last_name_equals(value: impl Into<String>) -> Self {
+        Self::LastName(FilterOperator::Eq, value.into())
+    }
+
     pub fn last_name_contains(value: impl Into<String>) -> Self {
         Self::LastName(FilterOperator::Cn, value.into())
     }
@@ -739,6 +887,53 @@ impl UsersFilter {
     pub fn has_role(value: impl Into<String>) -> Self {
         Self::HasRole(FilterOperator::Eq, value.into())
     }
+
+    /// Filters users by a custom attribute (key and value)
+    pub fn attribute_equals(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::Attribute(FilterOperator::Eq, key.into(), value.into())
+    }
+
+    /// Filters users by a custom attribute (key and part of the value)
+    pub fn attribute_contains(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::Attribute(FilterOperator::Cn, key.into(), value.into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum UserGroupsFilter {
+    IsMember(FilterOperator, bool),
+    DisplayName(FilterOperator, String),
+}
+
+impl FilterQuery for UserGroupsFilter {
+    fn to_filter_string(&self) -> String {
+        match self {
+            Self::IsMember(op, value) => {
+                let op: String = op.into();
+                format!("isMember:{}:{}", op, value)
+            }
+            Self::DisplayName(op, value) => {
+                let op: String = op.into();
+                format!("displayName:{}:{}", op, value)
+            }
+        }
+    }
+}
+
+impl UserGroupsFilter {
+    pub fn is_member(value: bool) -> Self {
+        Self::IsMember(FilterOperator::Eq, value)
+    }
+
+    pub fn name_contains(value: impl Into<String>) -> Self {
+        Self::DisplayName(FilterOperator::Cn, value.into())
+    }
+}
+
+impl From<UserGroupsFilter> for Box<dyn FilterQuery> {
+    fn from(filter: UserGroupsFilter) -> Self {
+        Box::new(filter)
+    }
 }
 
 #[derive(Debug)]
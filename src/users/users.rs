@@ -1,15 +1,22 @@
 use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
 use reqwest::header;
 
 use crate::{
     client::Connected,
-    constants::{DRACOON_API_PREFIX, USERS_BASE, USERS_LAST_ADMIN_ROOMS},
+    constants::{
+        DRACOON_API_PREFIX, ROLES_BASE, ROLES_USERS, USERS_ATTRIBUTES, USERS_AVATAR, USERS_BASE,
+        USERS_GROUPS, USERS_LAST_ADMIN_ROOMS, USERS_ROLES,
+    },
+    roles::{AssignRoleBatchRequest, RevokeRoleBatchRequest, RoleList, RoleType, RoleUserList},
+    user::Avatar,
     utils::FromResponse,
     DracoonClientError, ListAllParams, Users,
 };
 
 use super::{
-    CreateUserRequest, LastAdminUserRoomList, UpdateUserRequest, UserData, UserList, UsersEndpoint,
+    CreateUserRequest, LastAdminUserRoomList, UpdateUserRequest, UserAttributeList, UserData,
+    UserGroupList, UserImportReport, UserList, UserRoles, UsersEndpoint,
 };
 
 #[async_trait]
@@ -77,7 +84,13 @@ impl Users for UsersEndpoint<Connected> {
         effective_roles: Option<bool>,
     ) -> Result<UserData, DracoonClientError> {
         let url_part = format!("/{DRACOON_API_PREFIX}/{USERS_BASE}/{user_id}");
-        let api_url = self.client().build_api_url(&url_part);
+        let mut api_url = self.client().build_api_url(&url_part);
+
+        if let Some(effective_roles) = effective_roles {
+            api_url
+                .query_pairs_mut()
+                .append_pair("effective_roles", &effective_roles.to_string());
+        }
 
         let response = self
             .client()
@@ -162,4 +175,187 @@ impl Users for UsersEndpoint<Connected> {
 
         LastAdminUserRoomList::from_response(response).await
     }
+
+    async fn get_user_groups(
+        &self,
+        user_id: u64,
+        params: Option<ListAllParams>,
+    ) -> Result<UserGroupList, DracoonClientError> {
+        let params = params.unwrap_or_default();
+        let url_part = format!("/{DRACOON_API_PREFIX}/{USERS_BASE}/{user_id}/{USERS_GROUPS}");
+        let mut api_url = self.client().build_api_url(&url_part);
+
+        let filters = params.filter_to_string();
+        let sorts = params.sort_to_string();
+
+        api_url
+            .query_pairs_mut()
+            .extend_pairs(params.limit.map(|v| ("limit", v.to_string())))
+            .extend_pairs(params.offset.map(|v| ("offset", v.to_string())))
+            .extend_pairs(params.sort.map(|_| ("sort", sorts)))
+            .extend_pairs(params.filter.map(|_| ("filter", filters)))
+            .finish();
+
+        let response = self
+            .client()
+            .http
+            .get(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .send()
+            .await?;
+
+        UserGroupList::from_response(response).await
+    }
+
+    async fn delete_user_avatar(&self, user_id: u64) -> Result<Avatar, DracoonClientError> {
+        let url_part = format!("/{DRACOON_API_PREFIX}/{USERS_BASE}/{user_id}/{USERS_AVATAR}");
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .delete(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .send()
+            .await?;
+
+        Avatar::from_response(response).await
+    }
+
+    async fn get_user_attributes(
+        &self,
+        user_id: u64,
+        params: Option<ListAllParams>,
+    ) -> Result<UserAttributeList, DracoonClientError> {
+        let params = params.unwrap_or_default();
+        let url_part = format!("/{DRACOON_API_PREFIX}/{USERS_BASE}/{user_id}/{USERS_ATTRIBUTES}");
+        let mut api_url = self.client().build_api_url(&url_part);
+
+        let filters = params.filter_to_string();
+        let sorts = params.sort_to_string();
+
+        api_url
+            .query_pairs_mut()
+            .extend_pairs(params.limit.map(|v| ("limit", v.to_string())))
+            .extend_pairs(params.offset.map(|v| ("offset", v.to_string())))
+            .extend_pairs(params.sort.map(|_| ("sort", sorts)))
+            .extend_pairs(params.filter.map(|_| ("filter", filters)))
+            .finish();
+
+        let response = self
+            .client()
+            .http
+            .get(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .send()
+            .await?;
+
+        UserAttributeList::from_response(response).await
+    }
+
+    async fn create_users_bulk(
+        &self,
+        requests: Vec<CreateUserRequest>,
+        concurrency: usize,
+    ) -> UserImportReport {
+        let results = stream::iter(requests)
+            .map(|req| async move {
+                let result = self.create_user(req.clone()).await;
+                (req, result)
+            })
+            .buffered(concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut report = UserImportReport::default();
+        for (req, result) in results {
+            match result {
+                Ok(user) => report.created.push(user),
+                Err(err) if err.is_conflict() => report.skipped.push((req, err)),
+                Err(err) => report.failed.push((req, err)),
+            }
+        }
+
+        report
+    }
+}
+
+#[async_trait]
+impl UserRoles for UsersEndpoint<Connected> {
+    async fn get_user_roles(&self, user_id: u64) -> Result<RoleList, DracoonClientError> {
+        let url_part = format!("/{DRACOON_API_PREFIX}/{USERS_BASE}/{user_id}/{USERS_ROLES}");
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .get(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .send()
+            .await?;
+
+        RoleList::from_response(response).await
+    }
+
+    async fn assign_user_role(
+        &self,
+        user_id: u64,
+        role: RoleType,
+    ) -> Result<RoleUserList, DracoonClientError> {
+        let role_id = role.id();
+        let url_part = format!("/{DRACOON_API_PREFIX}/{ROLES_BASE}/{role_id}/{ROLES_USERS}");
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .post(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&AssignRoleBatchRequest::from(vec![user_id]))
+            .send()
+            .await?;
+
+        RoleUserList::from_response(response).await
+    }
+
+    async fn revoke_user_role(
+        &self,
+        user_id: u64,
+        role: RoleType,
+    ) -> Result<RoleUserList, DracoonClientError> {
+        let role_id = role.id();
+        let url_part = format!("/{DRACOON_API_PREFIX}/{ROLES_BASE}/{role_id}/{ROLES_USERS}");
+        let api_url = self.client().build_api_url(&url_part);
+
+        let response = self
+            .client()
+            .http
+            .delete(api_url)
+            .header(
+                header::AUTHORIZATION,
+                self.client().get_auth_header().await?,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&RevokeRoleBatchRequest::from(vec![user_id]))
+            .send()
+            .await?;
+
+        RoleUserList::from_response(response).await
+    }
 }
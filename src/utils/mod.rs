@@ -1,3 +1,5 @@
+use std::future::Future;
+
 use async_trait::async_trait;
 use reqwest::Response;
 use serde::de::DeserializeOwned;
@@ -6,6 +8,8 @@ use tracing::error;
 
 use super::{
     client::{errors::DracoonClientError, models::StatusCodeState},
+    constants::LIST_ALL_PAGE_LIMIT,
+    models::RangedItems,
     nodes::models::S3ErrorResponse,
 };
 
@@ -60,3 +64,110 @@ pub trait FromResponse {
     where
         Self: Sized;
 }
+
+/// Lists all items of a paginated endpoint - `get_page` is called with the offset and limit
+/// of each page until the total is reached
+pub(crate) async fn list_all<T, F, Fut>(mut get_page: F) -> Result<Vec<T>, DracoonClientError>
+where
+    F: FnMut(u64, u64) -> Fut,
+    Fut: Future<Output = Result<RangedItems<T>, DracoonClientError>>,
+{
+    let mut items = Vec::new();
+    let mut offset = 0;
+
+    loop {
+        let page = get_page(offset, LIST_ALL_PAGE_LIMIT).await?;
+        let total = page.range.total;
+        items.extend(page.items);
+        offset += LIST_ALL_PAGE_LIMIT;
+
+        if offset >= total {
+            break;
+        }
+    }
+
+    Ok(items)
+}
+
+/// Fills the buffer with cryptographically secure random bytes
+pub(crate) fn random_bytes(buf: &mut [u8]) -> Result<(), DracoonClientError> {
+    openssl::rand::rand_bytes(buf).map_err(|err| {
+        error!("Error generating random bytes: {}", err);
+        DracoonClientError::Internal
+    })
+}
+
+/// Returns a uniformly distributed random index below `upper` (rejection sampling)
+/// - `upper` must be greater than 0 and fit into a `u32`
+pub(crate) fn random_index(upper: usize) -> Result<usize, DracoonClientError> {
+    let upper = match u32::try_from(upper) {
+        Ok(upper) if upper > 0 => upper,
+        _ => {
+            error!("Invalid upper bound for random index: {}", upper);
+            return Err(DracoonClientError::Internal);
+        }
+    };
+    let zone = u32::MAX - (u32::MAX % upper);
+
+    loop {
+        let mut buf = [0u8; 4];
+        random_bytes(&mut buf)?;
+
+        let value = u32::from_le_bytes(buf);
+        if value < zone {
+            return Ok((value % upper) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Range;
+
+    #[test]
+    fn test_random_index() {
+        for upper in [1, 2, 7, 62] {
+            let index = random_index(upper).unwrap();
+            assert!(index < upper);
+        }
+    }
+
+    #[test]
+    fn test_random_index_invalid_upper() {
+        assert!(matches!(random_index(0), Err(DracoonClientError::Internal)));
+        assert!(matches!(
+            random_index(u32::MAX as usize + 1),
+            Err(DracoonClientError::Internal)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_all_requests_all_pages() {
+        let total = 2 * LIST_ALL_PAGE_LIMIT + 1;
+        let mut offsets = Vec::new();
+
+        let items = list_all(|offset, limit| {
+            offsets.push(offset);
+            let items = (offset..total.min(offset + limit)).collect::<Vec<_>>();
+            async move {
+                Ok(RangedItems {
+                    range: Range {
+                        offset,
+                        limit,
+                        total,
+                    },
+                    items,
+                })
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(items, (0..total).collect::<Vec<_>>());
+        assert_eq!(
+            offsets,
+            vec![0, LIST_ALL_PAGE_LIMIT, 2 * LIST_ALL_PAGE_LIMIT]
+        );
+    }
+}